@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use crate::errors::AsmValidationError;
+use crate::token_types::{located, AliasTokens, FileTokens, SourcePos};
+use crate::validation::{validate_label, validate_operand_label};
+
+
+/// Takes a line of assembly and returns true if it defines an alias for a label, such as
+/// `.set print, @uart_write`
+pub fn is_alias_directive(line:&str) -> bool {
+    line.starts_with(".set ") || line == ".set"
+}
+
+
+/// Takes a line of assembly defining an alias in the form `.set <alias>, @<label>`, the section it is in, the
+/// namespace it is in, and its position in the source, and returns its tokens. The alias is qualified by the
+/// namespace like any other label defined there, while the label must already be written with its qualified
+/// name. Will return an `AsmValidationError` if the alias or label is missing or invalid.
+pub fn parse_alias_directive(line:&str, mode:char, namespace:&Option<String>, pos:Option<SourcePos>)
+        -> Result<AliasTokens, AsmValidationError> {
+    let operands:Vec<&str> = line[".set".len()..].split(',').map(|operand| operand.trim()).collect();
+    let (alias, target) = match operands[..] {
+        [alias, target] if !alias.is_empty() && !target.is_empty() => (alias, target),
+        _ => {
+            return Err(AsmValidationError(format!("Incorrect format for alias on line {}, which should be .set <alias>, @<label>", line)));
+        }
+    };
+
+    validate_label(line, alias)?;
+    validate_operand_label(line, target)?;
+    let alias = match namespace {
+        Some(namespace) => format!("{}.{}", namespace, alias),
+        None => alias.to_owned()
+    };
+
+    Ok(AliasTokens::new(mode, alias, target[1..].to_owned(), pos))
+}
+
+
+/// Takes a stream of tokens and the label table built from their labels, and adds every alias defined with
+/// `.set` to the table at the address of the label it names. An alias may name another alias, in any order.
+/// Returns the problems found, with one for each alias which has the same name as a label or another alias,
+/// or which names a label that does not exist or an alias which is never resolved because it names itself.
+pub fn resolve_aliases(tokens_stream:&[FileTokens], label_table:&mut HashMap<String, i64>) -> Vec<String> {
+    let mut errors:Vec<String> = Vec::new();
+    let mut unresolved:Vec<&AliasTokens> = Vec::new();
+    for tokens in tokens_stream {
+        if let FileTokens::AliasTokens(t) = tokens {
+            if label_table.contains_key(&t.alias) || unresolved.iter().any(|other| other.alias == t.alias) {
+                errors.push(format!("{}Duplicate label \"{}\" detected!", located(t.pos.as_ref()), t.alias));
+            } else {
+                unresolved.push(t);
+            }
+        }
+    }
+
+    // each pass resolves every alias whose label is known, so a chain of aliases takes one pass per link
+    loop {
+        let (resolved, remaining):(Vec<&AliasTokens>, Vec<&AliasTokens>) = unresolved.into_iter()
+            .partition(|t| label_table.contains_key(&t.target));
+        unresolved = remaining;
+        if resolved.is_empty() {
+            break;
+        }
+
+        for t in resolved {
+            label_table.insert(t.alias.to_owned(), label_table[&t.target]);
+        }
+    }
+
+    for t in unresolved {
+        errors.push(format!("{}The alias {} names the label {}, which was not found", located(t.pos.as_ref()), t.alias, t.target));
+    }
+
+    errors
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::aliases::*;
+    use crate::label_table::generate_label_table;
+    use crate::process_source_into_tokens;
+
+
+    #[test]
+    fn test_parse_alias_directive() {
+        assert!(is_alias_directive(".set print, @uart_write"));
+        assert!(!is_alias_directive("settings: .int 5"));
+
+        let tokens = parse_alias_directive(".set print, @uart.write", 'c', &None, None).unwrap();
+        assert_eq!((tokens.alias.as_str(), tokens.target.as_str()), ("print", "uart.write"));
+
+        let tokens = parse_alias_directive(".set print, @write", 'c', &Some("uart".to_owned()), None).unwrap();
+        assert_eq!(tokens.alias, "uart.print");
+
+        assert!(parse_alias_directive(".set print", 'c', &None, None).is_err());
+        assert!(parse_alias_directive(".set print, uart_write", 'c', &None, None).is_err());
+        assert!(parse_alias_directive(".set 2print, @uart_write", 'c', &None, None).is_err());
+    }
+
+
+    #[test]
+    fn test_aliases_in_label_table() {
+        let source = "init:\n    JUMP $g8, $g9, @print\n.set print, @put\n.set put, @uart_write\nuart_write:\n    HALT\n\
+            data:\n.set greeting_length, @length\nlength: .int 5\n";
+        let label_table = generate_label_table(&process_source_into_tokens(source, None).unwrap()).unwrap();
+        assert_eq!(label_table["print"], label_table["uart_write"]);
+        assert_eq!(label_table["put"], label_table["uart_write"]);
+        assert_eq!(label_table["greeting_length"], 0x1000);
+    }
+
+
+    #[test]
+    fn test_invalid_aliases() {
+        let source = "init:\n    HALT\n.set init, @init\n.set print, @missing\n.set loop, @loop\n";
+        let error = generate_label_table(&process_source_into_tokens(source, None).unwrap()).unwrap_err().0;
+        assert_eq!(error.lines().collect::<Vec<&str>>(), vec![
+            "<source>:3:1: Duplicate label \"init\" detected!",
+            "<source>:4:1: The alias print names the label missing, which was not found",
+            "<source>:5:1: The alias loop names the label loop, which was not found"
+        ]);
+    }
+}
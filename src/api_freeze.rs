@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use crate::errors::{AsmValidationError, FrozenApiError};
+
+
+/// Represents a single label of a lock file given to `--freeze-api`, being the label and the address frozen
+/// for it, which is `None` if it is listed without one and has not been recorded yet
+pub type FrozenLabel = (String, Option<i64>);
+
+
+/// The comments at the start of every lock file written by `write_lock_file`
+const LOCK_FILE_HEADER:&str = "; the addresses of the labels frozen with --freeze-api, which the program must keep\n\
+    ; add a label without an address to freeze it, or assemble with --refreeze to record new addresses\n";
+
+
+/// Takes the contents of a lock file and returns every label frozen in it, in the order they are listed.
+/// Each line holds a label and, once it has been recorded, its address in hex, in the same form as a map
+/// file, such as `putc 00000010`. Everything after a ';' is a comment, blank lines are ignored, and any other
+/// malformed line gives an `AsmValidationError`.
+pub fn parse_lock_file(contents:&str) -> Result<Vec<FrozenLabel>, AsmValidationError> {
+    let mut frozen:Vec<FrozenLabel> = Vec::new();
+    for line in contents.lines() {
+        let line = line[..line.find(';').unwrap_or(line.len())].trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let tokens:Vec<&str> = line.split_whitespace().collect();
+        let address = match tokens[1..] {
+            [] => None,
+            [address] => match i64::from_str_radix(address, 16) {
+                Ok(address) => Some(address),
+                Err(_) => return Err(AsmValidationError(format!("{} is not a valid address in the lock file", address)))
+            },
+            _ => return Err(AsmValidationError(format!("{} is not a valid lock file entry, which should be a label and its address", line)))
+        };
+
+        let label = tokens[0].trim_start_matches('@');
+        if frozen.iter().any(|(frozen_label, _)| frozen_label == label) {
+            return Err(AsmValidationError(format!("{} is frozen more than once in the lock file", label)));
+        }
+
+        frozen.push((label.to_owned(), address));
+    }
+
+    Ok(frozen)
+}
+
+
+/// Takes the filename of a lock file given to `--freeze-api` and returns every label frozen in it, as
+/// described for `parse_lock_file`.
+pub fn read_lock_file(filename:&str) -> Result<Vec<FrozenLabel>, Box<dyn Error>> {
+    Ok(parse_lock_file(&fs::read_to_string(filename)?)?)
+}
+
+
+/// Takes the labels frozen in a lock file and the label table of the program, and returns each label with
+/// its address in the program, which is what the lock file should now hold. A label listed without an address
+/// is given its address in the program, as is every label if `refreeze` is true, so that a release which
+/// must move its entry points can record their new addresses.
+///
+/// Will return a `FrozenApiError` listing every frozen label which is not in the program, and, unless
+/// `refreeze` is true, every one which is not at the address frozen for it.
+pub fn freeze_addresses(frozen:&[FrozenLabel], label_table:&HashMap<String, i64>, refreeze:bool) -> Result<Vec<(String, i64)>, FrozenApiError> {
+    let mut errors:Vec<String> = Vec::new();
+    let mut moved = false;
+    let mut addresses:Vec<(String, i64)> = Vec::new();
+    for (label, frozen_address) in frozen {
+        match (label_table.get(label), frozen_address) {
+            (None, _) => errors.push(format!("{} is frozen but is not in the program", label)),
+            (Some(address), Some(frozen_address)) if address != frozen_address && !refreeze => {
+                errors.push(format!("{} is frozen at {:08X} but is now at {:08X}", label, frozen_address, address));
+                moved = true;
+            },
+            (Some(address), _) => addresses.push((label.clone(), *address))
+        }
+    }
+
+    if moved {
+        errors.push("assemble with --refreeze if the move is intended".to_owned());
+    }
+
+    if !errors.is_empty() {
+        return Err(FrozenApiError(errors.join("; ")));
+    }
+
+    Ok(addresses)
+}
+
+
+/// Takes the frozen labels and their addresses, and returns the contents of the lock file which holds them,
+/// in the order they are given.
+pub fn render_lock_file(addresses:&[(String, i64)]) -> String {
+    let entries:String = addresses.iter().map(|(label, address)| format!("{:<16} {:08X}\n", label, address)).collect();
+    format!("{}{}", LOCK_FILE_HEADER, entries)
+}
+
+
+/// Takes the filename of a lock file, the labels frozen in it, and their addresses in the program, and
+/// rewrites the file with the addresses if any of them differ from what it holds, so that a build which
+/// changes nothing leaves the file untouched. Returns true if the file was written.
+pub fn write_lock_file(filename:&str, frozen:&[FrozenLabel], addresses:&[(String, i64)]) -> Result<bool, Box<dyn Error>> {
+    let unchanged = frozen.iter().zip(addresses).all(|((_, frozen_address), (_, address))| *frozen_address == Some(*address));
+    if unchanged {
+        return Ok(false);
+    }
+
+    fs::write(filename, render_lock_file(addresses))?;
+    Ok(true)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::api_freeze::*;
+
+
+    #[test]
+    fn test_read_lock_file() {
+        let frozen = read_lock_file("test_files/test_freeze_api.lock").unwrap();
+        assert_eq!(frozen, vec![
+            ("init".to_owned(), Some(0x0000)),
+            ("double".to_owned(), Some(0x0003)),
+            ("value".to_owned(), None)
+        ]);
+
+        assert!(parse_lock_file("putc 0010 extra\n").is_err());
+        assert!(parse_lock_file("putc 00G0\n").is_err());
+        assert!(parse_lock_file("putc\n@putc 0010\n").unwrap_err().0.contains("frozen more than once"));
+    }
+
+
+    #[test]
+    fn test_freeze_addresses() {
+        let frozen = read_lock_file("test_files/test_freeze_api.lock").unwrap();
+        let label_table = HashMap::from([("init".to_owned(), 0x0000), ("double".to_owned(), 0x0005), ("value".to_owned(), 0x1000)]);
+        let err = freeze_addresses(&frozen, &label_table, false).unwrap_err();
+        assert_eq!(err.0, "double is frozen at 00000003 but is now at 00000005; assemble with --refreeze if the move is intended");
+
+        let addresses = freeze_addresses(&frozen, &label_table, true).unwrap();
+        assert_eq!(addresses, vec![("init".to_owned(), 0x0000), ("double".to_owned(), 0x0005), ("value".to_owned(), 0x1000)]);
+        assert!(render_lock_file(&addresses).ends_with("init             00000000\ndouble           00000005\nvalue            00001000\n"));
+        assert_eq!(parse_lock_file(&render_lock_file(&addresses)).unwrap()[1], ("double".to_owned(), Some(0x0005)));
+
+        // a label listed without an address is recorded rather than checked, but must still be in the program
+        let label_table = HashMap::from([("init".to_owned(), 0x0000), ("double".to_owned(), 0x0003), ("value".to_owned(), 0x1004)]);
+        assert_eq!(freeze_addresses(&frozen, &label_table, false).unwrap()[2], ("value".to_owned(), 0x1004));
+        assert_eq!(freeze_addresses(&frozen, &HashMap::new(), true).unwrap_err().0,
+            "init is frozen but is not in the program; double is frozen but is not in the program; value is frozen but is not in the program");
+    }
+}
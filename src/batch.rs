@@ -0,0 +1,193 @@
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::panic;
+use flate2::read::GzDecoder;
+use crate::diagnostics::{error_kind, json_string, render_json_diagnostics, Severity};
+use crate::errors::{ArchiveError, AsmValidationError};
+use crate::assemble_source_with_limits;
+use crate::limits::ResourceLimits;
+
+
+/// Represents the result of assembling a single source file from an archive, with the path of the file in
+/// the archive, and the JSON diagnostics of the errors which stopped it assembling if it failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchResult {
+    pub file: String,
+    pub passed: bool,
+    pub diagnostics: Vec<String>
+}
+
+
+/// The path of a source file in an archive and its contents
+type ArchiveSource = (String, Vec<u8>);
+
+
+/// Takes a reader of a tar archive and returns the path and contents of every `.asm` file in it
+fn read_tar_sources<R:Read>(reader:R) -> io::Result<Vec<ArchiveSource>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut sources:Vec<ArchiveSource> = Vec::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+        if entry.header().entry_type().is_file() && path.ends_with(".asm") {
+            let mut contents:Vec<u8> = Vec::new();
+            entry.read_to_end(&mut contents)?;
+            sources.push((path, contents));
+        }
+    }
+
+    Ok(sources)
+}
+
+
+/// Takes a zip archive and returns the path and contents of every `.asm` file in it
+fn read_zip_sources(file:File) -> Result<Vec<ArchiveSource>, Box<dyn Error>> {
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut sources:Vec<ArchiveSource> = Vec::new();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if entry.is_file() && entry.name().ends_with(".asm") {
+            let mut contents:Vec<u8> = Vec::new();
+            entry.read_to_end(&mut contents)?;
+            sources.push((entry.name().to_owned(), contents));
+        }
+    }
+
+    Ok(sources)
+}
+
+
+/// Takes the filename of a `.zip`, `.tar`, `.tar.gz`, or `.tgz` archive and returns the path and contents of
+/// every `.asm` file in it, in order of their paths. Files with any other extension are left out. Will
+/// return an `ArchiveError` if the archive cannot be read or its extension is not one of these.
+pub fn read_archive_sources(filename:&str) -> Result<Vec<ArchiveSource>, ArchiveError> {
+    let to_archive_error = |e:&dyn Error| ArchiveError(format!("{}: {}", filename, e));
+    let file = File::open(filename).map_err(|e| to_archive_error(&e))?;
+    let mut sources = if filename.ends_with(".zip") {
+        read_zip_sources(file).map_err(|e| to_archive_error(e.as_ref()))?
+    } else if filename.ends_with(".tar") {
+        read_tar_sources(file).map_err(|e| to_archive_error(&e))?
+    } else if filename.ends_with(".tar.gz") || filename.ends_with(".tgz") {
+        read_tar_sources(GzDecoder::new(file)).map_err(|e| to_archive_error(&e))?
+    } else {
+        return Err(ArchiveError(format!("{} should be a .zip, .tar, .tar.gz, or .tgz archive", filename)));
+    };
+
+    sources.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(sources)
+}
+
+
+/// Takes the path of a source file in an archive and its contents, and assembles it in the same way as
+/// `assemble_source`, returning whether it passed and the JSON diagnostics of every error found if not.
+/// Diagnostics name the file by its path in the archive. A panic while assembling is caught and reported
+/// as an error, and a submission using more than the default `ResourceLimits` fails, so one bad submission
+/// cannot stop the rest from being graded.
+pub fn assemble_submission(file:&str, contents:&[u8]) -> BatchResult {
+    let result = panic::catch_unwind(|| -> Result<Vec<u8>, Box<dyn Error>> {
+        let source = std::str::from_utf8(contents).map_err(|_| AsmValidationError(format!("{} is not UTF-8", file)))?;
+        assemble_source_with_limits(source, file, ResourceLimits::default())
+    });
+
+    let (message, kind) = match result {
+        Ok(Ok(_)) => return BatchResult { file: file.to_owned(), passed: true, diagnostics: Vec::new() },
+        Ok(Err(e)) => (e.to_string(), error_kind(e.as_ref())),
+        Err(_) => ("the assembler stopped unexpectedly".to_owned(), "error")
+    };
+
+    let diagnostics = render_json_diagnostics(&message, Severity::Error, kind).lines().map(|line| line.to_owned()).collect();
+    BatchResult { file: file.to_owned(), passed: false, diagnostics }
+}
+
+
+/// Takes the filename of an archive and assembles every `.asm` file in it independently using
+/// `assemble_submission`, returning the result of each in order of their paths. Will return an
+/// `ArchiveError` if the archive cannot be read.
+pub fn assemble_archive(filename:&str) -> Result<Vec<BatchResult>, ArchiveError> {
+    Ok(read_archive_sources(filename)?.iter().map(|(file, contents)| assemble_submission(file, contents)).collect())
+}
+
+
+/// Takes the filename of an archive and the results of assembling its files, and returns a JSON report of
+/// them giving the number which passed and failed, and then whether each file passed along with its
+/// diagnostics, such as:
+///
+/// ```json
+/// {
+///   "archive": "submissions.zip",
+///   "passed": 1,
+///   "failed": 1,
+///   "files": [
+///     {"file": "alice/prog.asm", "passed": true, "diagnostics": []},
+///     {"file": "bob/prog.asm", "passed": false, "diagnostics": [{"file": "bob/prog.asm", "line": 3, ...}]}
+///   ]
+/// }
+/// ```
+pub fn render_report(archive:&str, results:&[BatchResult]) -> String {
+    let passed = results.iter().filter(|result| result.passed).count();
+    let files:Vec<String> = results.iter().map(|result| format!(
+        "    {{\"file\": {}, \"passed\": {}, \"diagnostics\": [{}]}}",
+        json_string(&result.file), result.passed, result.diagnostics.join(", ")
+    )).collect();
+
+    format!("{{\n  \"archive\": {},\n  \"passed\": {},\n  \"failed\": {},\n  \"files\": [\n{}\n  ]\n}}\n",
+        json_string(archive), passed, results.len() - passed, files.join(",\n"))
+}
+
+
+/// Takes the filename of the report, the filename of the archive, and the results of assembling its files,
+/// and writes the report of `render_report` to the file, or to stdout if the filename is `-`.
+pub fn write_report(report_filename:&str, archive:&str, results:&[BatchResult]) -> Result<(), Box<dyn Error>> {
+    let report = render_report(archive, results);
+    if report_filename == "-" {
+        print!("{}", report);
+    } else {
+        fs::write(report_filename, report)?;
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::batch::*;
+
+
+    #[test]
+    fn test_assemble_archive() {
+        for archive in ["test_files/test_batch.zip", "test_files/test_batch.tar", "test_files/test_batch.tar.gz"] {
+            let results = assemble_archive(archive).unwrap();
+            let files:Vec<&str> = results.iter().map(|result| result.file.as_str()).collect();
+            assert_eq!(files, vec!["alice/prog.asm", "bob/prog.asm", "carol/prog.asm"]);
+            assert_eq!(results.iter().map(|result| result.passed).collect::<Vec<bool>>(), vec![true, false, false]);
+
+            assert_eq!(results[1].diagnostics.len(), 2);
+            assert!(results[1].diagnostics[0].starts_with("{\"file\": \"bob/prog.asm\", \"line\": 2, \"column\": 9, \"severity\": \"error\", \
+                \"code\": \"E0201\", \"kind\": \"invalid-instruction\", \"message\": \"$g12 is not a valid register"));
+            assert!(results[2].diagnostics[0].contains("\"code\": \"E0404\", \"kind\": \"assemble\", \"message\": \"The label missing was not found!\""));
+        }
+    }
+
+
+    #[test]
+    fn test_render_report() {
+        let results = assemble_archive("test_files/test_batch.zip").unwrap();
+        let report = render_report("test_files/test_batch.zip", &results);
+        assert!(report.starts_with("{\n  \"archive\": \"test_files/test_batch.zip\",\n  \"passed\": 1,\n  \"failed\": 2,\n  \"files\": [\n"));
+        assert!(report.contains("    {\"file\": \"alice/prog.asm\", \"passed\": true, \"diagnostics\": []},\n"));
+        assert!(report.ends_with("}]}\n  ]\n}\n"));
+    }
+
+
+    #[test]
+    fn test_invalid_submissions() {
+        let result = assemble_submission("dave/prog.asm", &[0xFF, 0xFE]);
+        assert!(!result.passed);
+        assert!(result.diagnostics[0].contains("dave/prog.asm is not UTF-8"));
+
+        assert!(read_archive_sources("test_files/does_not_exist.zip").is_err());
+        assert!(read_archive_sources("test_files/test_label_table_gen.asm").unwrap_err().0.contains("should be a .zip"));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::errors::AsmValidationError;
+use crate::hardening::{split_instr, split_label, strip_comment, SCRATCH_REGISTERS};
+use crate::limits::ResourceBudget;
+use crate::preprocessor::SourceLine;
+use crate::validation::validate_int_immediate;
+
+
+/// The opcodes of the byte access pseudo-instructions
+pub const BYTE_ACCESS_OPCODES:[&str; 2] = ["LOADB", "STOREB"];
+
+
+/// Takes a line of assembly and returns true if it is one of the byte access pseudo-instructions, `LOADB`
+/// or `STOREB`
+pub fn is_byte_access(line:&str) -> bool {
+    BYTE_ACCESS_OPCODES.contains(&split_instr(split_label(strip_comment(line)).1).0)
+}
+
+
+/// Takes the parity operand of a byte access and returns it, which is 0 for the lower byte of the word and
+/// 1 for the upper byte, or an `AsmValidationError` if it is neither.
+fn parse_parity(operand:&str, instr:&str) -> Result<i64, AsmValidationError> {
+    match validate_int_immediate(operand, 16, false) {
+        Ok(parity @ 0..=1) => Ok(parity),
+        _ => Err(AsmValidationError(format!(
+            "The parity {} of {} must be 0 for the lower byte of the word or 1 for the upper byte", operand, instr
+        )))
+    }
+}
+
+
+/// Returns the lines which load the given byte of the word at `$ua + rs + rt` into `rd`, with its upper
+/// byte cleared
+fn load_byte(rd:&str, rs:&str, rt:&str, parity:i64) -> Vec<String> {
+    let mut lines = vec![format!("LOAD {}, {}, {}", rd, rs, rt), "ADDI $g9, $zero, 8".to_owned()];
+    if parity == 0 {
+        lines.push(format!("SLL {}, {}, $g9", rd, rd));
+    }
+
+    lines.push(format!("SRL {}, {}, $g9", rd, rd));
+    lines
+}
+
+
+/// Returns the lines which replace the given byte of the word at `$ua + rs + rt` with the lower byte of
+/// `rd`, keeping the other byte of the word. As there is no AND, each mask is applied with two NANDs.
+fn store_byte(rd:&str, rs:&str, rt:&str, parity:i64) -> Vec<String> {
+    let mut lines = match parity {
+        0 => vec!["MOVLI $g9, 0x00".to_owned(), "MOVUI $g9, 0xFF".to_owned()],
+        _ => vec!["MOVLI $g9, 0xFF".to_owned(), "MOVUI $g9, 0x00".to_owned()]
+    };
+
+    lines.push(format!("LOAD $g8, {}, {}", rs, rt));
+    lines.push("NAND $g8, $g8, $g9".to_owned());
+    lines.push("NAND $g8, $g8, $g8".to_owned());
+    if parity == 0 {
+        lines.push("NAND $g9, $g9, $g9".to_owned());
+        lines.push(format!("NAND $g9, {}, $g9", rd));
+        lines.push("NAND $g9, $g9, $g9".to_owned());
+    } else {
+        lines.push("ADDI $g9, $zero, 8".to_owned());
+        lines.push(format!("SLL $g9, {}, $g9", rd));
+    }
+
+    lines.push("OR $g8, $g8, $g9".to_owned());
+    lines.push(format!("STORE $g8, {}, {}", rs, rt));
+    lines
+}
+
+
+/// Takes the logical lines of a source file and the budget it is assembled under, and expands the byte
+/// access pseudo-instructions, which read and write one byte of a word in the same form as `LOAD` and
+/// `STORE` with a parity after the registers, which is 0 for the lower byte and 1 for the upper byte, the
+/// same order `.byte` packs bytes in:
+///  - `LOADB rd, rs, rt, parity` loads the word at `$ua + rs + rt` and shifts it so that `rd` holds the
+///    byte in its lower half and 0 in its upper half.
+///  - `STOREB rd, rs, rt, parity` loads the word at `$ua + rs + rt`, masks out the byte, merges in the
+///    lower byte of `rd`, and stores the word back, so the other byte is kept.
+///
+/// The expansions use `$g8` and `$g9` as scratch registers, so their values are not kept, and none of the
+/// registers of a byte access can be one of them. The label of a byte access marks the first line of its
+/// expansion, and each line keeps the number of the line it was expanded from so that diagnostics point at
+/// the right place. Every byte access is counted as an expansion of the budget.
+///
+/// Will return an `AsmValidationError` if a byte access does not have three registers and a parity of 0 or
+/// 1, or uses one of the scratch registers.
+pub fn expand_byte_accesses(source_lines:Vec<SourceLine>, budget:&mut ResourceBudget) -> Result<Vec<SourceLine>, AsmValidationError> {
+    let mut expanded:Vec<SourceLine> = Vec::with_capacity(source_lines.len());
+    for source_line in source_lines {
+        if !is_byte_access(&source_line.text) {
+            expanded.push(source_line);
+            continue;
+        }
+
+        let line = strip_comment(&source_line.text).to_owned();
+        let (label, instr) = split_label(&line);
+        let (opcode, operands) = split_instr(instr);
+        let (rd, rs, rt, parity) = match operands[..] {
+            [rd, rs, rt, parity] => (rd, rs, rt, parse_parity(parity, instr).map_err(|e| source_line.locate(e))?),
+            _ => return Err(source_line.locate(AsmValidationError(format!(
+                "Incorrect format for {} on line {}, which should be {} <rd>, <rs>, <rt>, <parity>", opcode, line, opcode
+            ))))
+        };
+
+        if [rd, rs, rt].iter().any(|register| SCRATCH_REGISTERS.contains(register)) {
+            return Err(AsmValidationError(format!(
+                "{}: the byte access {} cannot use $g8 or $g9, which its expansion uses", source_line.location(), instr
+            )));
+        }
+
+        let mut lines = match opcode {
+            "LOADB" => load_byte(rd, rs, rt, parity),
+            _ => store_byte(rd, rs, rt, parity)
+        };
+
+        if let Some(label) = label {
+            lines[0] = format!("{}: {}", label, lines[0]);
+        }
+
+        budget.count_expansions(1);
+        expanded.extend(lines.into_iter().map(|text| SourceLine { text, ..source_line.clone() }));
+    }
+
+    Ok(expanded)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::byte_access::*;
+    use crate::limits::ResourceLimits;
+    use crate::preprocessor::join_continuation_lines;
+    use crate::testing::tokens_from_str;
+
+
+    fn expand(source:&str) -> Result<Vec<String>, AsmValidationError> {
+        let source_lines = join_continuation_lines(source.lines().map(|line| line.to_owned()).collect()).unwrap();
+        Ok(expand_byte_accesses(source_lines, &mut ResourceBudget::new(ResourceLimits::UNLIMITED))?.into_iter().map(|line| line.text).collect())
+    }
+
+
+    /// Takes the lines of an expanded byte access, the register it reads or writes, its value, and the word
+    /// in memory, and returns the register and the word after running the lines
+    fn run(lines:&[String], register:&str, value:u16, word:u16) -> (u16, u16) {
+        let mut registers:HashMap<&str, u16> = HashMap::from([("$zero", 0), (register, value)]);
+        let mut word = word;
+        for line in lines {
+            let (opcode, operands) = split_instr(split_label(line).1);
+            let reg = |name:&str| registers.get(name).copied().unwrap_or(0);
+            let imm = |operand:&str| validate_int_immediate(operand, 16, false).unwrap() as u16;
+            let result = match opcode {
+                "LOAD" => word,
+                "STORE" => { word = reg(operands[0]); continue; },
+                "ADDI" => reg(operands[1]) + imm(operands[2]),
+                "SLL" => reg(operands[1]) << reg(operands[2]),
+                "SRL" => reg(operands[1]) >> reg(operands[2]),
+                "NAND" => !(reg(operands[1]) & reg(operands[2])),
+                "OR" => reg(operands[1]) | reg(operands[2]),
+                "MOVLI" => (reg(operands[0]) & 0xFF00) | imm(operands[1]),
+                "MOVUI" => (reg(operands[0]) & 0x00FF) | (imm(operands[1]) << 8),
+                _ => panic!("unexpected {}", line)
+            };
+
+            registers.insert(operands[0], result);
+        }
+
+        (registers[register], word)
+    }
+
+
+    #[test]
+    fn test_byte_accesses() {
+        let load_lower = expand("LOADB $g0, $g1, $g2, 0").unwrap();
+        assert_eq!(load_lower, vec!["LOAD $g0, $g1, $g2", "ADDI $g9, $zero, 8", "SLL $g0, $g0, $g9", "SRL $g0, $g0, $g9"]);
+        assert_eq!(run(&load_lower, "$g0", 0xFFFF, 0xABCD), (0x00CD, 0xABCD));
+        assert_eq!(run(&expand("LOADB $g0, $g1, $g2, 1").unwrap(), "$g0", 0xFFFF, 0xABCD), (0x00AB, 0xABCD));
+
+        assert_eq!(run(&expand("STOREB $g0, $g1, $g2, 0").unwrap(), "$g0", 0x1234, 0xABCD), (0x1234, 0xAB34));
+        assert_eq!(run(&expand("STOREB $g0, $g1, $g2, 1").unwrap(), "$g0", 0x1234, 0xABCD), (0x1234, 0x34CD));
+
+        let store = expand("put: STOREB $g3, $sp, $zero, 1 ; upper").unwrap();
+        assert_eq!(store[0], "put: MOVLI $g9, 0xFF");
+        assert_eq!(store[store.len() - 1], "STORE $g8, $sp, $zero");
+        assert_eq!(tokens_from_str("init:\n    LOADB $g0, $g1, $g2, 1\n    STOREB $g0, $g1, $g2, 0\n    HALT\n").len(), 14);
+    }
+
+
+    #[test]
+    fn test_invalid_byte_accesses() {
+        assert!(expand("LOADB $g0, $g1, $g2").unwrap_err().0.contains("should be LOADB <rd>, <rs>, <rt>, <parity>"));
+        assert!(expand("STOREB $g0, $g1, $g2, 2").unwrap_err().0.contains("must be 0 for the lower byte of the word or 1"));
+        assert_eq!(expand("STOREB $g9, $g1, $g2, 0").unwrap_err().0,
+            "1:1: the byte access STOREB $g9, $g1, $g2, 0 cannot use $g8 or $g9, which its expansion uses");
+    }
+}
@@ -0,0 +1,1411 @@
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap::error::ErrorKind;
+use crate::byte_access::BYTE_ACCESS_OPCODES;
+use crate::constants::is_constant_name;
+use crate::demangle::Demangler;
+use crate::diagnostics::DiagnosticFormat;
+use crate::encoding::InputEncoding;
+use crate::errors::{find_error_code, CmdArgsError};
+use crate::generate_code::{OutputFormat, OPCODE_BINARIES};
+use crate::label_table::PAGE_SIZE;
+use crate::lang::LanguageLevel;
+use crate::lints::ForbiddenOpcodes;
+use crate::org::SECTION_NAMES;
+use crate::rom_split::RomSplit;
+use crate::trace::{TraceOutput, TRACE_PORT};
+use crate::validation::validate_int_immediate;
+use crate::warnings::{WarningFlag, WarningKind, WarningSettings};
+
+
+/// Represents how much the assembler prints while it runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    /// Only errors are printed, with `--quiet`
+    Quiet,
+
+    /// Warnings and a line for the start and end of each build are printed
+    #[default]
+    Normal,
+
+    /// The time taken by each stage, the label table, and every token are also printed, with `--verbose`
+    Verbose
+}
+
+
+/// Represents where the `link` subcommand places a fragment in the base binary, either at the address of a
+/// label in the map file of the base build or at a word address in the code section
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkAddress {
+    Label(String),
+    Address(i64)
+}
+
+
+/// Represents the options the assembler was invoked with, which are the subcommand and its own options along
+/// with the options every subcommand shares. Assembling is the `asm` subcommand, although the subcommand may be
+/// left out. The subcommand is only missing if the user asked for `--version`, or for `--help`, in which case
+/// `help_text` is set and nothing else is filled in.
+#[derive(Debug, Clone, Default, Parser)]
+#[command(name = "iridium_assembler", about = "Assembles Iridium assembly source files into a program binary",
+    override_usage = "iridium_assembler [asm] <SOURCE.asm> [TARGET] [OPTIONS]\n       \
+        iridium_assembler [asm] <SOURCE.asm>... -o <TARGET> [OPTIONS]\n       \
+        iridium_assembler <COMMAND> [OPTIONS]",
+    disable_version_flag = true, args_override_self = true)]
+pub struct CmdArgs {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Print the version of the assembler and the features it supports
+    #[arg(long)]
+    pub version: bool,
+
+    #[command(flatten)]
+    pub shared: SharedArgs,
+
+    #[arg(skip)]
+    pub help_text: Option<String>
+}
+
+
+/// Represents the options shared by every subcommand, which can be given before or after it
+#[derive(Debug, Clone, Default, Args)]
+pub struct SharedArgs {
+    /// Also print the time taken by each stage, the label table, and every token
+    #[arg(short = 'v', long, conflicts_with = "quiet", global = true)]
+    pub verbose: bool,
+
+    /// Print only errors
+    #[arg(short = 'q', long, global = true)]
+    pub quiet: bool,
+
+    /// Show labels mangled by a compiler, such as _Zmath_sqrt_ii, by their readable names in diagnostics,
+    /// listings, map files, dumps, and disassemblies
+    #[arg(long, global = true)]
+    pub demangle: bool,
+
+    /// Demangle labels with the given program instead, which reads one label on each line of stdin and writes
+    /// each back, demangled or not, on stdout
+    #[arg(long = "demangler", value_name = "COMMAND", conflicts_with = "demangle", global = true)]
+    pub demangler_command: Option<String>
+}
+
+
+impl SharedArgs {
+    /// Returns how much the assembler should print
+    pub fn verbosity(&self) -> Verbosity {
+        match (self.verbose, self.quiet) {
+            (true, _) => Verbosity::Verbose,
+            (_, true) => Verbosity::Quiet,
+            _ => Verbosity::Normal
+        }
+    }
+
+
+    /// Returns the demangler the labels should be shown through, if any
+    pub fn demangler(&self) -> Option<Demangler> {
+        match (self.demangle, &self.demangler_command) {
+            (_, Some(command)) => Some(Demangler::Command(command.clone())),
+            (true, None) => Some(Demangler::Builtin),
+            (false, None) => None
+        }
+    }
+}
+
+
+/// Represents the subcommands of the assembler, each with its own options
+#[derive(Debug, Clone, Subcommand)]
+#[allow(clippy::large_enum_variant)]
+pub enum Command {
+    /// Assemble source files into a program binary, which is also done when no subcommand is given
+    #[command(override_usage = "iridium_assembler asm <SOURCE.asm> [TARGET] [OPTIONS]\n       \
+        iridium_assembler asm <SOURCE.asm>... -o <TARGET> [OPTIONS]", args_override_self = true)]
+    Asm(AsmArgs),
+
+    /// Print the code section of a program binary as assembly
+    Disasm(BinaryArgs),
+
+    /// Print every section of a program binary, decoding the data using the types in the map file
+    Dump(BinaryArgs),
+
+    /// Patch a fragment assembled with --only over the binary of a full build
+    Link(LinkArgs),
+
+    /// Report the labels moved, added, removed, or resized between the map files of two builds
+    Mapdiff(MapDiffArgs),
+
+    /// Print a longer description of an error code, with an example which gives the error
+    Explain(ExplainArgs),
+
+    /// Assemble every .asm file in a .zip, .tar, .tar.gz, or .tgz archive independently, writing whether each
+    /// passed to a JSON report
+    Batch(BatchArgs),
+
+    /// Serve POST /assemble and POST /check over HTTP, returning binaries and JSON diagnostics
+    Serve(ServeArgs)
+}
+
+
+/// Represents the options for assembling a program, where the source and target filenames are required
+/// unless the options say otherwise and everything else is an optional flag. The target file is worked out
+/// from the positional arguments and `-o` by `parse_cmd_args`, which leaves only the source files in
+/// `input_files`.
+#[derive(Debug, Clone, Default, Args)]
+pub struct AsmArgs {
+    /// The source files followed by the target file, or only source files if -o is given
+    #[arg(value_name = "FILES")]
+    pub input_files: Vec<String>,
+
+    /// The target file, allowing several source files to be assembled into one program
+    #[arg(short = 'o', value_name = "FILE")]
+    pub output: Option<String>,
+
+    #[arg(skip)]
+    pub output_file: String,
+
+    /// The map file to write label addresses to, or to read them from with --only
+    #[arg(long = "map", value_name = "FILE")]
+    pub map_file: Option<String>,
+
+    /// Assemble only the instructions from START up to, but not including, END against the map file of a
+    /// previous build
+    #[arg(long = "only", value_name = "@START..@END", value_parser = parse_label_range, requires = "map_file",
+        conflicts_with_all = ["layout_only", "check"])]
+    pub only_range: Option<(String, String)>,
+
+    /// Allow the code section to run into the data section without a terminator
+    #[arg(long)]
+    pub allow_fallthrough: bool,
+
+    /// Turn on every warning with -Wall, or turn the given warning on or off, such as -Wunused-label or
+    /// -Wno-branch-pair
+    #[arg(short = 'W', value_name = "WARNING", value_parser = parse_warning_flag)]
+    pub warning_flags: Vec<WarningFlag>,
+
+    /// Warn about every .half, .q8_8, or .q16_16 which cannot be stored exactly, giving the value stored
+    /// instead, the same as -Wprecision
+    #[arg(long)]
+    pub warn_precision: bool,
+
+    /// Fail if any warning is given, after printing them all
+    #[arg(long, value_name = "warnings", value_parser = ["warnings"])]
+    pub deny: Option<String>,
+
+    /// Write an example of every instruction form and its encoding as JSON
+    #[arg(long = "emit-test-vectors", value_name = "FILE")]
+    pub test_vectors_file: Option<String>,
+
+    /// Write a listing of every word in the program and the source item it came from
+    #[arg(long = "listing", value_name = "FILE")]
+    pub listing_file: Option<String>,
+
+    /// Write an ASCII map of the pages, sections, and label extents of the program
+    #[arg(long = "memmap", value_name = "FILE")]
+    pub memmap_file: Option<String>,
+
+    /// Fail if any section is larger than its region of memory in the given description
+    #[arg(long = "memory", value_name = "FILE")]
+    pub memory_file: Option<String>,
+
+    /// Also write the low and high byte of every word to two separate ROM images
+    #[arg(long = "split-rom", value_name = "LO,HI", value_parser = parse_filename_pair, conflicts_with = "split_rom_words")]
+    pub split_rom_bytes: Option<(String, String)>,
+
+    /// Also write the even and odd words to two separate ROM images
+    #[arg(long = "split-rom-words", value_name = "EVEN,ODD", value_parser = parse_filename_pair)]
+    pub split_rom_words: Option<(String, String)>,
+
+    /// Name the binary after the start of the SHA-256 of its contents, such as prog-<HASH>.ird, and print the
+    /// name it was given
+    #[arg(long, conflicts_with_all = ["check", "layout_only", "only_range"])]
+    pub output_hash_name: bool,
+
+    /// Print the number of tokens processed, expansions performed, relaxation iterations, and words emitted
+    #[arg(long = "stats", conflicts_with_all = ["layout_only", "only_range", "audit_only"])]
+    pub show_stats: bool,
+
+    /// Fail if the sections of the program occupy more than the given number of pages in total
+    #[arg(long, value_name = "PAGES", value_parser = clap::value_parser!(i64).range(0..))]
+    pub max_pages: Option<i64>,
+
+    /// Fail if any label listed in the lock file has moved from the address recorded for it, and record the
+    /// address of any listed without one
+    #[arg(long = "freeze-api", value_name = "FILE", conflicts_with_all = ["layout_only", "only_range"])]
+    pub freeze_api_file: Option<String>,
+
+    /// Record the address of every label in the --freeze-api lock file, rather than failing if one has moved
+    #[arg(long, requires = "freeze_api_file")]
+    pub refreeze: bool,
+
+    /// Write the program as an ird program binary, or as a bin memory image with every word at its address
+    #[arg(long = "format", value_name = "FORMAT", value_parser = parse_output_format, default_value = "ird",
+        conflicts_with = "only_range")]
+    pub output_format: OutputFormat,
+
+    /// Start the code, data, text, or bss section at the given word address, as if it began with an .org
+    #[arg(long = "section-start", value_name = "SECTION=ADDRESS", value_parser = parse_section_start)]
+    pub section_starts: Vec<(char, i64)>,
+
+    /// The word to fill any gap left by an .org with
+    #[arg(long = "fill", value_name = "WORD", value_parser = parse_fill_word, default_value = "0x0000")]
+    pub fill_word: u16,
+
+    /// The number of words in a page, which each section starts on the boundary of
+    #[arg(long, value_name = "WORDS", value_parser = parse_page_size, default_value_t = PAGE_SIZE)]
+    pub page_size: i64,
+
+    /// The target being assembled for, which .if TARGET == NAME blocks can test
+    #[arg(long, value_name = "NAME")]
+    pub target: Option<String>,
+
+    /// Define a constant before the source is read, which .if blocks and immediates can use as if it were
+    /// defined with .equ, or as 1 if no value is given
+    #[arg(short = 'D', long = "define", value_name = "NAME=VALUE", value_parser = parse_definition)]
+    pub definitions: Vec<(String, i64)>,
+
+    /// The encoding of the source files, which is utf-8, latin1, or auto to read each file which is not UTF-8
+    /// as Latin-1
+    #[arg(long, value_name = "ENCODING", value_parser = parse_input_encoding, default_value = "utf-8")]
+    pub input_encoding: InputEncoding,
+
+    /// Fail if the source uses any of the given opcodes, such as HALT,IN,OUT, or only in a file, directory,
+    /// or namespace, such as app/=IN,OUT or @app=IN,OUT
+    #[arg(long = "forbid", value_name = "[SCOPE=]OPCODES", value_parser = parse_forbidden_opcodes)]
+    pub forbidden_opcodes: Vec<ForbiddenOpcodes>,
+
+    /// Insert a stack canary check around the body of every .func and a bounds check before every access
+    /// marked with .bounds
+    #[arg(long)]
+    pub harden: bool,
+
+    /// Replace every straight-line sequence of MOVLI, MOVUI, ADDI, and SUBI whose values are known by the
+    /// fewest instructions which load the same values
+    #[arg(long)]
+    pub fold_constants: bool,
+
+    /// The deepest .include may be nested, so that a mistake which includes files without end fails with the
+    /// chain of files
+    #[arg(long, value_name = "DEPTH", default_value_t = 16)]
+    pub max_include_depth: usize,
+
+    /// The deepest macros may be invoked inside each other, so that a mistake which expands macros without
+    /// end fails with the chain of macros
+    #[arg(long, value_name = "DEPTH", default_value_t = 16)]
+    pub max_macro_expansion: usize,
+
+    /// Insert a beacon which writes the number of the block to port 15 at the start of every labelled block
+    /// of code, and write the label and source line of each number to the given file
+    #[arg(long = "instrument-coverage", value_name = "FILE", conflicts_with = "only_range")]
+    pub coverage_file: Option<String>,
+
+    /// The I/O port every .trace writes the number of its message to with OUT
+    #[arg(long, value_name = "PORT", value_parser = clap::value_parser!(u8).range(0..16), default_value_t = TRACE_PORT)]
+    pub trace_port: u8,
+
+    /// Make every .trace call the given syscall with the number of its message in $g8, instead of writing it
+    /// to a port
+    #[arg(long, value_name = "SERVICE", conflicts_with = "trace_port")]
+    pub trace_syscall: Option<u8>,
+
+    /// Write the number, source line, and message of every .trace to the given file
+    #[arg(long = "trace-table", value_name = "FILE", conflicts_with = "only_range")]
+    pub trace_file: Option<String>,
+
+    /// Place the functions and labelled data items in an order chosen by the seed, so that different seeds
+    /// give different layouts
+    #[arg(long = "shuffle-layout", value_name = "SEED")]
+    pub shuffle_seed: Option<u64>,
+
+    /// Print the final layout of the program without writing any files, in which case the target file may be
+    /// left out
+    #[arg(long)]
+    pub layout_only: bool,
+
+    /// Assemble the program again every time a source file changes, printing only errors or a success line
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Check that the source files are valid and every label resolves without writing any files, in which
+    /// case every positional argument is a source file
+    #[arg(long, conflicts_with_all = ["output", "layout_only"])]
+    pub check: bool,
+
+    /// The level of the language the source was written for, which decides the meaning of any construct that
+    /// changed between levels
+    #[arg(long, value_name = "LEVEL", value_parser = parse_language_level, default_value = "1")]
+    pub lang: LanguageLevel,
+
+    /// Check the source files as --check does, warning about every construct which is assembled differently
+    /// at another language level, the same as -Wmigration
+    #[arg(long, conflicts_with_all = ["output", "layout_only", "only_range", "output_hash_name", "audit_only"])]
+    pub migrate: bool,
+
+    /// Print errors and warnings as human, rendered with the source line they are about, or as json, one
+    /// object on each line of stderr
+    #[arg(long = "diagnostics", value_name = "FORMAT", value_parser = parse_diagnostic_format, default_value = "human")]
+    pub diagnostic_format: DiagnosticFormat,
+
+    /// Assemble the program at two base addresses and report every word which differs, failing if any is not
+    /// a label relocation, without writing any files
+    #[arg(long = "audit-determinism", conflicts_with_all = ["output", "layout_only", "check", "only_range"])]
+    pub audit_only: bool
+}
+
+
+impl AsmArgs {
+    /// Returns true if the program is only checked without writing any files, with `--check` or `--migrate`
+    pub fn check_only(&self) -> bool {
+        self.check || self.migrate
+    }
+
+
+    /// Returns the warnings which are turned on and whether they are denied. `--warn-precision` and `--migrate`
+    /// come after every -W flag, so they turn their warning on even after -Wno-.
+    pub fn warning_settings(&self) -> WarningSettings {
+        let mut warning_flags = self.warning_flags.clone();
+        if self.warn_precision {
+            warning_flags.push(WarningFlag::Enable(WarningKind::Precision));
+        }
+
+        if self.migrate {
+            warning_flags.push(WarningFlag::Enable(WarningKind::Migration));
+        }
+
+        WarningSettings::from_flags(&warning_flags, self.deny.is_some())
+    }
+
+
+    /// Returns the filenames of the ROM images to split the program into and how it is split, if it is
+    pub fn split_rom(&self) -> Option<((String, String), RomSplit)> {
+        match (&self.split_rom_bytes, &self.split_rom_words) {
+            (Some(filenames), _) => Some((filenames.clone(), RomSplit::Bytes)),
+            (None, Some(filenames)) => Some((filenames.clone(), RomSplit::Words)),
+            (None, None) => None
+        }
+    }
+
+
+    /// Returns how every `.trace` hands the number of its message to the emulator or operating system
+    pub fn trace_output(&self) -> TraceOutput {
+        match self.trace_syscall {
+            Some(service) => TraceOutput::Syscall(service),
+            None => TraceOutput::Port(self.trace_port)
+        }
+    }
+}
+
+
+/// Represents the binary to read and the map file to read its labels from, which the `dump` and `disasm`
+/// subcommands both take
+#[derive(Debug, Clone, Args)]
+pub struct BinaryArgs {
+    /// The program binary to read
+    #[arg(value_name = "BINARY")]
+    pub binary_file: String,
+
+    /// The map file of the build which wrote the binary, to show its labels
+    #[arg(long = "map", value_name = "FILE")]
+    pub map_file: Option<String>
+}
+
+
+/// Represents the files given to the `link` subcommand, which patches the fragment written by `--only`
+/// over the binary of a full build
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub struct LinkArgs {
+    /// The binary of the full build
+    #[arg(value_name = "BASE")]
+    pub base_file: String,
+
+    /// The fragment to patch over it
+    #[arg(value_name = "FRAGMENT")]
+    pub fragment_file: String,
+
+    /// The file to write the patched binary to
+    #[arg(short = 'o', value_name = "FILE")]
+    pub output_file: String,
+
+    /// Where the fragment starts, as a label in the map file or a word address
+    #[arg(long = "at", value_name = "@LABEL|ADDRESS", value_parser = parse_link_address)]
+    pub address: LinkAddress,
+
+    /// The map file of the full build
+    #[arg(long = "map", value_name = "FILE")]
+    pub map_file: Option<String>
+}
+
+
+/// Represents the map files of the two builds the `mapdiff` subcommand compares
+#[derive(Debug, Clone, Args)]
+pub struct MapDiffArgs {
+    /// The map file of the old build
+    #[arg(value_name = "OLD.map")]
+    pub old_file: String,
+
+    /// The map file of the new build
+    #[arg(value_name = "NEW.map")]
+    pub new_file: String
+}
+
+
+/// Represents the error code the `explain` subcommand describes
+#[derive(Debug, Clone, Args)]
+pub struct ExplainArgs {
+    /// The error code, such as E0101, which is shown in brackets after error in diagnostics
+    #[arg(value_name = "CODE", value_parser = parse_error_code)]
+    pub code: String
+}
+
+
+/// Represents the archive the `batch` subcommand assembles and the file its report is written to
+#[derive(Debug, Clone, Args)]
+pub struct BatchArgs {
+    /// The archive of source files, such as the submissions of a class
+    #[arg(value_name = "ARCHIVE")]
+    pub archive: String,
+
+    /// The file to write the JSON report to, or - for stdout
+    #[arg(long = "report", value_name = "FILE")]
+    pub report_file: String
+}
+
+
+/// Represents the address the `serve` subcommand listens on
+#[derive(Debug, Clone, Args)]
+pub struct ServeArgs {
+    /// The port to listen on
+    #[arg(short = 'p', long, value_name = "PORT", default_value_t = 8080)]
+    pub port: u16,
+
+    /// The address to listen on, which only accepts local connections by default
+    #[arg(long, value_name = "HOST", default_value = "127.0.0.1")]
+    pub host: String
+}
+
+
+/// Takes a label range in the form `@start..@end` and returns the start and end labels without their
+/// '@' symbols, or an error message if the range is malformed.
+fn parse_label_range(range:&str) -> Result<(String, String), String> {
+    let malformed = || format!("{} should be a label range in the form @start..@end", range);
+    let (start, end) = range.split_once("..").ok_or_else(malformed)?;
+    match (start.strip_prefix('@'), end.strip_prefix('@')) {
+        (Some(start), Some(end)) if !start.is_empty() && !end.is_empty() => {
+            Ok((start.to_owned(), end.to_owned()))
+        },
+        _ => Err(malformed())
+    }
+}
+
+
+/// Takes a pair of filenames in the form `first,second` and returns them, or an error message if there
+/// are not exactly two.
+fn parse_filename_pair(pair:&str) -> Result<(String, String), String> {
+    match pair.split_once(',') {
+        Some((first, second)) if !first.is_empty() && !second.is_empty() && !second.contains(',') => {
+            Ok((first.to_owned(), second.to_owned()))
+        },
+        _ => Err(format!("{} should be two filenames separated by a comma", pair))
+    }
+}
+
+
+/// Takes a 16-bit word in binary, decimal, or hexadecimal, such as `0xFFFF`, and returns it, or an error
+/// message if it is not a valid word.
+fn parse_fill_word(word:&str) -> Result<u16, String> {
+    match validate_int_immediate(word, 16, false) {
+        Ok(word) => Ok(word as u16),
+        Err(_) => Err(format!("{} is not a 16-bit word", word))
+    }
+}
+
+
+/// Takes a number of words in binary, decimal, or hexadecimal, such as `0x1000`, and returns it as the size
+/// of a page, or an error message if it is not a power of two which fits into 32 bits.
+fn parse_page_size(words:&str) -> Result<i64, String> {
+    match validate_int_immediate(words, 32, false) {
+        Ok(words) if words > 0 && words & (words - 1) == 0 => Ok(words),
+        _ => Err(format!("{} is not a page size, which should be a power of two number of words", words))
+    }
+}
+
+
+/// Takes the value of a `-D` flag in the form `NAME=VALUE`, or only `NAME` to define the constant as 1, and
+/// returns the name and value of the constant, or an error message if the name cannot be a constant or the
+/// value is not an integer that fits into 32 bits.
+fn parse_definition(definition:&str) -> Result<(String, i64), String> {
+    let (name, value) = definition.split_once('=').unwrap_or((definition, "1"));
+    if !is_constant_name(name) {
+        return Err(format!("{} is not a valid constant name, which should be in the form -D NAME=VALUE", name));
+    }
+
+    match validate_int_immediate(value, 32, true) {
+        Ok(value) => Ok((name.to_owned(), value)),
+        Err(_) => Err(format!("the value {} of {} is not an integer that fits into 32 bits", value, name))
+    }
+}
+
+
+/// Takes the value of a `--forbid` flag, which is a list of opcodes in the form `HALT,IN,OUT`, optionally
+/// after the scope it applies to and `=`, such as `app/=IN,OUT` or `@app=IN,OUT`, and returns it, or an error
+/// message if any of them is not an opcode or a pseudo-instruction written as one, such as `LOADB`. Opcodes
+/// are matched regardless of case.
+fn parse_forbidden_opcodes(value:&str) -> Result<ForbiddenOpcodes, String> {
+    let (scope, list) = match value.split_once('=') {
+        Some((scope, _)) if scope.is_empty() || scope == "@" => return Err(format!("{} should name a file, directory, or @namespace before =", value)),
+        Some((scope, list)) => (Some(scope.to_owned()), list),
+        None => (None, value)
+    };
+
+    let opcodes = list.split(',').map(|opcode| {
+        match OPCODE_BINARIES.keys().chain(BYTE_ACCESS_OPCODES.iter()).find(|valid| valid.eq_ignore_ascii_case(opcode.trim())) {
+            Some(valid) => Ok(valid.to_string()),
+            None => Err(format!("{} is not an opcode", opcode))
+        }
+    }).collect::<Result<Vec<String>, String>>()?;
+
+    Ok(ForbiddenOpcodes { scope, opcodes })
+}
+
+
+/// Takes the value of a `-W` flag, such as `all`, `unused-label`, or `no-branch-pair`, and returns the flag, or
+/// an error message if it does not name a warning.
+fn parse_warning_flag(flag:&str) -> Result<WarningFlag, String> {
+    WarningFlag::from_name(flag)
+        .ok_or_else(|| format!("{} does not name a warning, which should be in the form -Wall, -W<warning>, or -Wno-<warning>", flag))
+}
+
+
+/// Takes the language level given to `--lang`, such as `2`, and returns it, or an error message if there is no
+/// such level.
+fn parse_language_level(level:&str) -> Result<LanguageLevel, String> {
+    LanguageLevel::from_name(level).ok_or_else(|| {
+        let levels:Vec<String> = LanguageLevel::ALL.iter().map(|level| level.to_string()).collect();
+        format!("{} is not a language level, which should be one of {}", level, levels.join(", "))
+    })
+}
+
+
+/// Takes the name of an encoding given to `--input-encoding`, such as `latin1`, and returns it, or an error
+/// message if it does not name an encoding.
+fn parse_input_encoding(name:&str) -> Result<InputEncoding, String> {
+    InputEncoding::from_name(name).ok_or_else(|| format!("{} is not an input encoding, which should be utf-8, latin1, or auto", name))
+}
+
+
+/// Takes the name of a format given to `--diagnostics`, such as `json`, and returns it, or an error message
+/// if it does not name a format.
+fn parse_diagnostic_format(name:&str) -> Result<DiagnosticFormat, String> {
+    DiagnosticFormat::from_name(name).ok_or_else(|| format!("{} is not a diagnostics format, which should be human or json", name))
+}
+
+
+/// Takes the name of a format given to `--format`, such as `bin`, and returns it, or an error message if it
+/// does not name a format.
+fn parse_output_format(name:&str) -> Result<OutputFormat, String> {
+    OutputFormat::from_name(name).ok_or_else(|| {
+        let names:Vec<&str> = OutputFormat::ALL.iter().map(|format| format.name()).collect();
+        format!("{} is not an output format, which should be one of {}", name, names.join(", "))
+    })
+}
+
+
+/// Takes the value of a `--section-start` flag in the form `SECTION=ADDRESS`, such as `data=0x4000`, and
+/// returns the section and the word address it starts at, or an error message if it does not name a section
+/// or the address is not an integer that fits into 32 bits.
+fn parse_section_start(start:&str) -> Result<(char, i64), String> {
+    let (name, address) = start.split_once('=')
+        .ok_or_else(|| format!("{} should be in the form SECTION=ADDRESS, such as data=0x4000", start))?;
+    let section = SECTION_NAMES.iter().find(|(section_name, _)| *section_name == name).map(|(_, section)| *section)
+        .ok_or_else(|| format!("{} is not a section, which should be code, data, text, or bss", name))?;
+
+    match validate_int_immediate(address, 32, false) {
+        Ok(address) => Ok((section, address)),
+        Err(_) => Err(format!("the start {} of the {} section is not an address that fits into 32 bits", address, name))
+    }
+}
+
+
+/// Takes an error code given to the `explain` subcommand, such as `E0101` in any case, and returns it as it is
+/// written in `ERROR_CODES`, or an error message if there is no such code.
+fn parse_error_code(code:&str) -> Result<String, String> {
+    match find_error_code(code) {
+        Some(error_code) => Ok(error_code.code.to_owned()),
+        None => Err(format!("{} is not an error code, which are in the form E0101", code))
+    }
+}
+
+
+/// Takes the address given to `link --at`, which is either a label such as `@start` or a word address, and
+/// returns it, or an error message if it is neither.
+fn parse_link_address(address:&str) -> Result<LinkAddress, String> {
+    match address.strip_prefix('@') {
+        Some(label) if !label.is_empty() => Ok(LinkAddress::Label(label.to_owned())),
+        Some(_) => Err(format!("{} should be a label or an address", address)),
+        None => match validate_int_immediate(address, 16, false) {
+            Ok(address) => Ok(LinkAddress::Address(address)),
+            Err(_) => Err(format!("{} should be a label or an address", address))
+        }
+    }
+}
+
+
+/// The names of the subcommands, including the `help` subcommand clap adds
+const SUBCOMMAND_NAMES:[&str; 9] = ["asm", "disasm", "dump", "link", "mapdiff", "explain", "batch", "serve", "help"];
+
+/// The deprecated options which are aliases of a subcommand, kept so that existing scripts still work
+const DEPRECATED_ALIASES:[&str; 3] = ["dump", "explain", "batch"];
+
+
+/// Takes the command line arguments (excluding the executable name) and returns them with the subcommand
+/// first, which is `asm` if none was given. The options shared by every subcommand may come before it, and
+/// are moved after it. The deprecated `--dump`, `--explain`, and `--batch` are replaced by their subcommand,
+/// taking their value as its first argument. The arguments are returned unchanged if the version or help of
+/// the assembler itself was asked for, or if they already start with a subcommand.
+pub fn normalize_args(args:&[String]) -> Vec<String> {
+    let mut shared_len = 0;
+    while let Some(arg) = args.get(shared_len) {
+        match arg.as_str() {
+            "-v" | "--verbose" | "-q" | "--quiet" | "--demangle" => shared_len += 1,
+            "--demangler" => shared_len += 2,
+            _ if arg.starts_with("--demangler=") => shared_len += 1,
+            _ => break
+        }
+    }
+
+    let (shared, rest) = args.split_at(shared_len.min(args.len()));
+    match rest.first().map(|arg| arg.as_str()) {
+        Some(name) if SUBCOMMAND_NAMES.contains(&name) => rest[..1].iter().chain(shared).chain(&rest[1..]).cloned().collect(),
+        Some("--version" | "-h" | "--help") => args.to_vec(),
+        _ => {
+            let alias = args.iter().enumerate().find_map(|(i, arg)| {
+                let name = arg.strip_prefix("--")?;
+                match name.split_once('=') {
+                    Some((name, value)) if DEPRECATED_ALIASES.contains(&name) => Some((i, name, Some(value.to_owned()), 1)),
+                    None if DEPRECATED_ALIASES.contains(&name) => Some((i, name, args.get(i + 1).cloned(), 2)),
+                    _ => None
+                }
+            });
+
+            match alias {
+                Some((i, name, value, len)) => std::iter::once(name.to_owned()).chain(value)
+                    .chain(args[..i].iter().cloned()).chain(args[(i + len).min(args.len())..].iter().cloned()).collect(),
+                None => std::iter::once("asm".to_owned()).chain(args.iter().cloned()).collect()
+            }
+        }
+    }
+}
+
+
+/// Returns the definition of every command line option the assembler accepts, which is used both to parse
+/// the arguments and to generate the `--help` output
+pub fn command() -> clap::Command {
+    CmdArgs::command()
+}
+
+
+/// Takes the command line arguments (excluding the executable name) and returns the `CmdArgs` they
+/// represent, after moving the subcommand first with `normalize_args`. The options for assembling are then
+/// resolved by `resolve_assembly_args`, and the `link` subcommand checks that a label is given with a map file.
+///
+/// Returns a `CmdArgsError` explaining what is wrong if the arguments are not valid.
+pub fn parse_cmd_args(args:&[String]) -> Result<CmdArgs, CmdArgsError> {
+    let args = normalize_args(args);
+    let mut cmd_args = match CmdArgs::try_parse_from(std::iter::once(&"iridium_assembler".to_owned()).chain(&args)) {
+        Ok(cmd_args) => cmd_args,
+        Err(e) if e.kind() == ErrorKind::DisplayHelp => {
+            return Ok(CmdArgs { help_text: Some(e.render().to_string()), ..CmdArgs::default() });
+        },
+        Err(e) => {
+            // only the first paragraph is kept, as the error already says how to get help
+            let message = e.render().to_string();
+            let message:Vec<&str> = message.lines().take_while(|line| !line.is_empty()).map(|line| line.trim()).collect();
+            return Err(CmdArgsError(message.join(" ").trim_start_matches("error: ").to_owned()));
+        }
+    };
+
+    match &mut cmd_args.command {
+        Some(Command::Asm(asm_args)) => resolve_assembly_args(asm_args)?,
+        Some(Command::Link(link_args)) if matches!(link_args.address, LinkAddress::Label(_)) && link_args.map_file.is_none() => {
+            return Err(CmdArgsError("a label can only be given to --at with the --map of the full build".to_owned()));
+        },
+        _ => ()
+    }
+
+    Ok(cmd_args)
+}
+
+
+/// Takes the parsed options for assembling a program and moves the target file out of the positional
+/// arguments into `output_file`. The first two positional arguments are the source and target files, unless
+/// the target is given with `-o`, in which case every positional argument is a source file. If only a source
+/// file is given, the target is the same file with the extension of the output format. Source files must end
+/// in `.asm`, although they may be left out if only the test vectors are being emitted, and the target file
+/// may be left out with `--layout-only`. With `--check`, every positional argument is a source file. A source
+/// or target file of `-` means stdin or stdout.
+///
+/// Returns a `CmdArgsError` if the files given are not valid.
+fn resolve_assembly_args(asm_args:&mut AsmArgs) -> Result<(), CmdArgsError> {
+    let positional = &mut asm_args.input_files;
+    let check_only = asm_args.check || asm_args.migrate;
+
+    // a later start of the same section overrides an earlier one, such as one from iridium.toml
+    let mut section_starts:Vec<(char, i64)> = Vec::new();
+    for (section, address) in &asm_args.section_starts {
+        section_starts.retain(|(other, _)| other != section);
+        section_starts.push((*section, *address));
+    }
+    asm_args.section_starts = section_starts;
+
+    // the test vectors do not depend on a source file, so they can be emitted on their own
+    asm_args.output_file = if positional.is_empty() && asm_args.output.is_none() && asm_args.test_vectors_file.is_some() {
+        String::new()
+    } else if let Some(output_file) = &asm_args.output {
+        output_file.clone()
+    } else if check_only || asm_args.audit_only || (positional.len() == 1 && asm_args.layout_only) {
+        String::new()
+    } else if positional.len() == 2 && positional[1].ends_with(".asm") {
+        // a second source file is more likely a mistake than a target, which would overwrite it
+        return Err(CmdArgsError(format!(
+            "the target file {} ends in .asm, so give -o <FILE> to assemble more than one source file", positional[1]
+        )));
+    } else if positional.len() == 2 {
+        positional.remove(1)
+    } else if positional.len() == 1 && positional[0] != "-" {
+        // the binary goes next to the source, with the same name
+        let source = &positional[0];
+        format!("{}.{}", source.strip_suffix(".asm").unwrap_or(source), asm_args.output_format.name())
+    } else if positional.len() == 1 {
+        return Err(CmdArgsError("the target file must be given when the source is read from stdin".to_owned()));
+    } else {
+        return Err(CmdArgsError(
+            "expected a source file and a target file, or -o <FILE> with any number of source files".to_owned()
+        ));
+    };
+
+    if positional.is_empty() && (!asm_args.output_file.is_empty() || asm_args.layout_only || check_only || asm_args.audit_only) {
+        return Err(CmdArgsError("no source files were given".to_owned()));
+    }
+
+    if let Some(file) = positional.iter().find(|file| !file.ends_with(".asm") && *file != "-") {
+        return Err(CmdArgsError(format!("source file {} must end in .asm", file)));
+    }
+
+    if positional.iter().filter(|file| *file == "-").count() > 1 {
+        return Err(CmdArgsError("stdin can only be given as a source file once".to_owned()));
+    }
+
+    if asm_args.watch && (positional.is_empty() || positional.iter().any(|file| file == "-")) {
+        return Err(CmdArgsError("--watch needs source files to watch, which cannot include stdin".to_owned()));
+    }
+
+    // the ROM images, fixups, and hashed name are written alongside a named output file, which stdout is not
+    if asm_args.output_file == "-" && (asm_args.split_rom().is_some() || asm_args.only_range.is_some() || asm_args.output_hash_name) {
+        return Err(CmdArgsError(
+            "the program cannot be written to stdout with --split-rom, --split-rom-words, --only, or --output-hash-name".to_owned()
+        ));
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::cmd_args::*;
+    use crate::trace::TRACE_PORT;
+
+
+    fn to_args(args:&[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+
+    fn asm_args(args:&[&str]) -> AsmArgs {
+        match parse_cmd_args(&to_args(args)).unwrap().command {
+            Some(Command::Asm(asm_args)) => asm_args,
+            command => panic!("{:?}", command)
+        }
+    }
+
+
+    #[test]
+    fn test_positional_args() {
+        let cmd_args = asm_args(&["prog.asm", "prog.ird"]);
+        assert_eq!(cmd_args.input_files, vec!["prog.asm"]);
+        assert_eq!(cmd_args.output_file, "prog.ird");
+        assert!(cmd_args.map_file.is_none());
+        assert!(cmd_args.only_range.is_none());
+        assert!(!cmd_args.allow_fallthrough);
+        assert_eq!(cmd_args.warning_settings(), WarningSettings::default());
+    }
+
+
+    #[test]
+    fn test_multiple_input_files() {
+        let cmd_args = asm_args(&["main.asm", "lib.asm", "io.asm", "-o", "prog.ird"]);
+        assert_eq!(cmd_args.input_files, vec!["main.asm", "lib.asm", "io.asm"]);
+        assert_eq!(cmd_args.output_file, "prog.ird");
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_multiple_input_files_without_output() {
+        asm_args(&["main.asm", "lib.asm", "io.asm"]);
+    }
+
+
+    #[test]
+    fn test_source_file_as_target() {
+        let error = parse_cmd_args(&to_args(&["a.asm", "b.asm"])).unwrap_err();
+        assert_eq!(error.0, "the target file b.asm ends in .asm, so give -o <FILE> to assemble more than one source file");
+        assert_eq!(asm_args(&["a.asm", "b.asm", "-o", "prog.ird"]).input_files, vec!["a.asm", "b.asm"]);
+    }
+
+
+    #[test]
+    fn test_disabled_warnings() {
+        let cmd_args = asm_args(&["prog.asm", "prog.ird", "-Wno-branch-pair"]);
+        assert!(!cmd_args.warning_settings().is_enabled(WarningKind::BranchPair));
+    }
+
+
+    #[test]
+    fn test_warning_flags() {
+        let cmd_args = asm_args(&["prog.asm", "prog.ird", "-Wall", "-Wno-unused-label", "--deny", "warnings"]);
+        assert_eq!(cmd_args.warning_settings().enabled, vec![
+            WarningKind::BranchPair, WarningKind::ZeroDestination, WarningKind::UnreachableCode, WarningKind::Precision,
+            WarningKind::Deprecated, WarningKind::Migration
+        ]);
+        assert!(cmd_args.warning_settings().deny);
+
+        let cmd_args = asm_args(&["prog.asm", "prog.ird", "-Wunused-label"]);
+        assert!(cmd_args.warning_settings().is_enabled(WarningKind::UnusedLabel));
+        assert!(!cmd_args.warning_settings().deny);
+
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "prog.ird", "-Wno-such-warning"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "prog.ird", "--deny", "errors"])).is_err());
+
+        let cmd_args = asm_args(&["prog.asm", "prog.ird", "-Wno-precision", "--warn-precision"]);
+        assert!(cmd_args.warning_settings().is_enabled(WarningKind::Precision));
+    }
+
+
+    #[test]
+    fn test_test_vectors_without_source() {
+        let cmd_args = asm_args(&["--emit-test-vectors", "vectors.json"]);
+        assert_eq!(cmd_args.test_vectors_file.unwrap(), "vectors.json");
+        assert!(cmd_args.input_files.is_empty());
+    }
+
+
+    #[test]
+    fn test_version_without_source() {
+        let cmd_args = parse_cmd_args(&to_args(&["--version"])).unwrap();
+        assert!(cmd_args.version);
+        assert!(cmd_args.command.is_none());
+    }
+
+
+    #[test]
+    fn test_layout_only_without_target() {
+        let cmd_args = asm_args(&["prog.asm", "--layout-only"]);
+        assert!(cmd_args.layout_only);
+        assert_eq!(cmd_args.input_files, vec!["prog.asm"]);
+        assert!(cmd_args.output_file.is_empty());
+    }
+
+
+    #[test]
+    fn test_check_only() {
+        let cmd_args = asm_args(&["--check", "main.asm", "lib.asm"]);
+        assert!(cmd_args.check_only());
+        assert_eq!(cmd_args.input_files, vec!["main.asm", "lib.asm"]);
+        assert!(cmd_args.output_file.is_empty());
+
+        assert!(parse_cmd_args(&to_args(&["--check"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["--check", "prog.asm", "prog.ird"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["--check", "prog.asm", "-o", "prog.ird"])).is_err());
+    }
+
+
+    #[test]
+    fn test_language_level() {
+        assert_eq!(asm_args(&["prog.asm", "prog.ird"]).lang, LanguageLevel::One);
+        assert_eq!(asm_args(&["prog.asm", "prog.ird", "--lang", "2"]).lang, LanguageLevel::Two);
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "prog.ird", "--lang", "3"])).unwrap_err().0.contains("not a language level"));
+
+        let cmd_args = asm_args(&["--migrate", "main.asm", "-Wno-migration"]);
+        assert!(cmd_args.check_only());
+        assert!(cmd_args.output_file.is_empty());
+        assert!(cmd_args.warning_settings().is_enabled(WarningKind::Migration));
+        assert!(!asm_args(&["main.asm", "main.ird"]).warning_settings().is_enabled(WarningKind::Migration));
+        assert!(parse_cmd_args(&to_args(&["--migrate", "prog.asm", "-o", "prog.ird"])).is_err());
+    }
+
+
+    #[test]
+    fn test_stdin_and_stdout() {
+        let cmd_args = asm_args(&["-", "-"]);
+        assert_eq!(cmd_args.input_files, vec!["-"]);
+        assert_eq!(cmd_args.output_file, "-");
+
+        assert!(parse_cmd_args(&to_args(&["-", "-", "-o", "prog.ird"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "-", "--split-rom", "lo.bin,hi.bin"])).is_err());
+    }
+
+
+    #[test]
+    fn test_derived_output_file() {
+        let cmd_args = asm_args(&["programs/prog.asm"]);
+        assert_eq!(cmd_args.input_files, vec!["programs/prog.asm"]);
+        assert_eq!(cmd_args.output_file, "programs/prog.ird");
+
+        assert_eq!(asm_args(&["prog.asm", "--map", "prog.map"]).output_file, "prog.ird");
+        assert_eq!(parse_cmd_args(&to_args(&["prog.s"])).unwrap_err().0, "source file prog.s must end in .asm");
+        assert!(parse_cmd_args(&to_args(&["-"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["a.asm", "b.asm", "c.asm"])).is_err());
+    }
+
+
+    #[test]
+    fn test_serve() {
+        let serve_args = |args:&[&str]| match parse_cmd_args(&to_args(args)).unwrap().command {
+            Some(Command::Serve(serve_args)) => (serve_args.host, serve_args.port),
+            command => panic!("{:?}", command)
+        };
+        assert_eq!(serve_args(&["serve", "--port", "9000"]), ("127.0.0.1".to_owned(), 9000));
+        assert_eq!(serve_args(&["serve"]), ("127.0.0.1".to_owned(), 8080));
+        assert!(parse_cmd_args(&to_args(&["serve", "--port", "99999"])).is_err());
+    }
+
+
+    #[test]
+    fn test_verbosity() {
+        assert_eq!(parse_cmd_args(&to_args(&["prog.asm", "prog.ird"])).unwrap().shared.verbosity(), Verbosity::Normal);
+        assert_eq!(parse_cmd_args(&to_args(&["prog.asm", "prog.ird", "--verbose"])).unwrap().shared.verbosity(), Verbosity::Verbose);
+        assert_eq!(parse_cmd_args(&to_args(&["-q", "prog.asm", "prog.ird"])).unwrap().shared.verbosity(), Verbosity::Quiet);
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "prog.ird", "-v", "-q"])).is_err());
+    }
+
+
+    #[test]
+    fn test_demangler() {
+        assert!(parse_cmd_args(&to_args(&["prog.asm"])).unwrap().shared.demangler().is_none());
+        assert!(matches!(parse_cmd_args(&to_args(&["prog.asm", "--demangle"])).unwrap().shared.demangler(), Some(Demangler::Builtin)));
+        assert!(matches!(parse_cmd_args(&to_args(&["disasm", "prog.ird", "--demangle"])).unwrap().shared.demangler(), Some(Demangler::Builtin)));
+        match parse_cmd_args(&to_args(&["dump", "prog.ird", "--demangler", "c++filt -n"])).unwrap().shared.demangler() {
+            Some(Demangler::Command(command)) => assert_eq!(command, "c++filt -n"),
+            demangler => panic!("{:?}", demangler)
+        }
+
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--demangle", "--demangler", "c++filt"])).is_err());
+    }
+
+
+    #[test]
+    fn test_definitions() {
+        assert!(asm_args(&["prog.asm"]).definitions.is_empty());
+        let cmd_args = asm_args(&["prog.asm", "-D", "DEBUG", "-DBAUD=9600", "--define", "MASK=0xFF", "-D", "DEBUG=0"]);
+        assert_eq!(cmd_args.definitions, vec![
+            ("DEBUG".to_owned(), 1), ("BAUD".to_owned(), 9600), ("MASK".to_owned(), 0xFF), ("DEBUG".to_owned(), 0)
+        ]);
+
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "-D", "2FAST=1"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "-D", "BAUD=fast"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "-D", "BIG=0x100000000"])).is_err());
+    }
+
+
+    #[test]
+    fn test_input_encoding() {
+        assert_eq!(asm_args(&["prog.asm"]).input_encoding, InputEncoding::Utf8);
+        assert_eq!(asm_args(&["prog.asm", "--input-encoding", "latin1"]).input_encoding, InputEncoding::Latin1);
+        assert_eq!(asm_args(&["prog.asm", "--input-encoding=auto"]).input_encoding, InputEncoding::Auto);
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--input-encoding", "utf-16"])).is_err());
+    }
+
+
+    #[test]
+    fn test_fold_constants() {
+        assert!(!asm_args(&["prog.asm"]).fold_constants);
+        assert!(asm_args(&["prog.asm", "--fold-constants"]).fold_constants);
+    }
+
+
+    #[test]
+    fn test_watch() {
+        assert!(asm_args(&["prog.asm", "prog.ird", "--watch"]).watch);
+        assert!(!asm_args(&["prog.asm", "prog.ird"]).watch);
+        assert!(parse_cmd_args(&to_args(&["-", "prog.ird", "--watch"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["--version", "--watch"])).is_err());
+    }
+
+
+    #[test]
+    fn test_dump_with_map() {
+        match parse_cmd_args(&to_args(&["--dump", "prog.ird", "--map", "prog.map"])).unwrap().command {
+            Some(Command::Dump(binary_args)) => {
+                assert_eq!(binary_args.binary_file, "prog.ird");
+                assert_eq!(binary_args.map_file.as_deref(), Some("prog.map"));
+            },
+            command => panic!("{:?}", command)
+        }
+
+        assert!(parse_cmd_args(&to_args(&["--dump", "prog.ird", "prog.asm"])).is_err());
+        assert!(!command().render_help().to_string().contains("--dump"));
+    }
+
+
+    #[test]
+    fn test_split_rom() {
+        let cmd_args = asm_args(&["prog.asm", "prog.ird", "--split-rom", "lo.bin,hi.bin"]);
+        assert_eq!(cmd_args.split_rom().unwrap(), (("lo.bin".to_owned(), "hi.bin".to_owned()), RomSplit::Bytes));
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "prog.ird", "--split-rom-words", "even.bin"])).is_err());
+    }
+
+
+    #[test]
+    fn test_output_hash_name() {
+        assert!(!asm_args(&["prog.asm", "prog.ird"]).output_hash_name);
+        assert!(asm_args(&["prog.asm", "prog.ird", "--output-hash-name"]).output_hash_name);
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "-", "--output-hash-name"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--check", "--output-hash-name"])).is_err());
+    }
+
+
+    #[test]
+    fn test_fill_word() {
+        assert_eq!(asm_args(&["prog.asm", "prog.ird"]).fill_word, 0x0000);
+        assert_eq!(asm_args(&["prog.asm", "prog.ird", "--fill", "0xFFFF"]).fill_word, 0xFFFF);
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "prog.ird", "--fill", "0x10000"])).is_err());
+    }
+
+
+    #[test]
+    fn test_page_size() {
+        assert_eq!(asm_args(&["prog.asm", "prog.ird"]).page_size, 0x1000);
+        assert_eq!(asm_args(&["prog.asm", "prog.ird", "--page-size", "0x100"]).page_size, 0x100);
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "prog.ird", "--page-size", "0"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "prog.ird", "--page-size", "1000"])).is_err());
+    }
+
+
+    #[test]
+    fn test_output_format() {
+        assert_eq!(asm_args(&["prog.asm"]).output_format, OutputFormat::Ird);
+
+        let cmd_args = asm_args(&["prog.asm", "--format", "bin"]);
+        assert_eq!((cmd_args.output_format, cmd_args.output_file.as_str()), (OutputFormat::Bin, "prog.bin"));
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--format", "hex"])).unwrap_err().0.contains("should be one of ird, bin"));
+    }
+
+
+    #[test]
+    fn test_section_start() {
+        let cmd_args = asm_args(&["prog.asm", "--section-start", "data=0x4000", "--section-start", "code=256"]);
+        assert_eq!(cmd_args.section_starts, vec![('d', 0x4000), ('c', 256)]);
+
+        let cmd_args = asm_args(&["prog.asm", "--section-start", "data=0x4000", "--section-start", "data=0x8000"]);
+        assert_eq!(cmd_args.section_starts, vec![('d', 0x8000)]);
+
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--section-start", "stack=0x4000"])).unwrap_err().0.contains("stack is not a section"));
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--section-start", "data"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--section-start", "data=-1"])).is_err());
+    }
+
+
+    #[test]
+    fn test_target() {
+        assert_eq!(asm_args(&["prog.asm", "prog.ird"]).target, None);
+        let cmd_args = asm_args(&["prog.asm", "prog.ird", "--target", "board-a"]);
+        assert_eq!(cmd_args.target.unwrap(), "board-a");
+        assert_eq!(cmd_args.input_files, vec!["prog.asm"]);
+    }
+
+
+    #[test]
+    fn test_forbidden_opcodes() {
+        assert!(asm_args(&["prog.asm", "prog.ird"]).forbidden_opcodes.is_empty());
+        let cmd_args = asm_args(&["prog.asm", "prog.ird", "--forbid", "HALT,in", "--forbid", "app/=Syscall,loadb"]);
+        assert_eq!(cmd_args.forbidden_opcodes, vec![
+            ForbiddenOpcodes { scope: None, opcodes: vec!["HALT".to_owned(), "IN".to_owned()] },
+            ForbiddenOpcodes { scope: Some("app/".to_owned()), opcodes: vec!["syscall".to_owned(), "LOADB".to_owned()] }
+        ]);
+
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "prog.ird", "--forbid", "HALT,PUSH"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "prog.ird", "--forbid", "=HALT"])).is_err());
+    }
+
+
+    #[test]
+    fn test_help() {
+        let help_text = parse_cmd_args(&to_args(&["--help"])).unwrap().help_text.unwrap();
+        assert!(help_text.contains("asm"));
+        assert!(help_text.contains("--verbose"));
+        assert!(!help_text.contains("--split-rom"));
+
+        let help_text = parse_cmd_args(&to_args(&["asm", "--help"])).unwrap().help_text.unwrap();
+        assert!(help_text.contains("--split-rom <LO,HI>"));
+        assert!(help_text.contains("-W <WARNING>"));
+        assert!(help_text.contains("[default: ird]"));
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "prog.ird"])).unwrap().help_text.is_none());
+    }
+
+
+    #[test]
+    fn test_error_messages() {
+        let err = parse_cmd_args(&to_args(&["prog.asm", "prog.ird", "--bogus"])).unwrap_err();
+        assert_eq!(err.0, "unexpected argument '--bogus' found");
+
+        let err = parse_cmd_args(&to_args(&["prog.s", "prog.ird"])).unwrap_err();
+        assert_eq!(err.0, "source file prog.s must end in .asm");
+
+        let err = parse_cmd_args(&to_args(&["prog.asm", "prog.ird", "--only", "@a..@b"])).unwrap_err();
+        assert!(err.0.contains("--map <FILE>"));
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_unknown_warning() {
+        asm_args(&["prog.asm", "prog.ird", "-Wno-everything"]);
+    }
+
+
+    #[test]
+    fn test_only_range() {
+        let cmd_args = asm_args(&["prog.asm", "patch.ird", "--only", "@start..@end", "--map", "prog.map"]);
+        assert_eq!(cmd_args.map_file.unwrap(), "prog.map");
+        assert_eq!(cmd_args.only_range.unwrap(), ("start".to_owned(), "end".to_owned()));
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_only_range_without_map() {
+        asm_args(&["prog.asm", "patch.ird", "--only", "@start..@end"]);
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_malformed_only_range() {
+        asm_args(&["prog.asm", "patch.ird", "--only", "start..@end", "--map", "prog.map"]);
+    }
+
+
+    #[test]
+    fn test_asm_subcommand() {
+        let cmd_args = parse_cmd_args(&to_args(&["asm", "prog.asm", "-v", "--map", "prog.map"])).unwrap();
+        assert_eq!(cmd_args.shared.verbosity(), Verbosity::Verbose);
+        let Some(Command::Asm(asm_args)) = cmd_args.command else { panic!("{:?}", cmd_args.command) };
+        assert_eq!(asm_args.input_files, vec!["prog.asm"]);
+        assert_eq!(asm_args.output_file, "prog.ird");
+        assert_eq!(asm_args.map_file.as_deref(), Some("prog.map"));
+
+        assert_eq!(parse_cmd_args(&to_args(&["-q", "asm", "prog.asm"])).unwrap().shared.verbosity(), Verbosity::Quiet);
+        assert!(parse_cmd_args(&to_args(&["asm", "prog.asm", "--version"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["asm"])).is_err());
+    }
+
+
+    #[test]
+    fn test_binary_subcommands() {
+        match parse_cmd_args(&to_args(&["disasm", "prog.ird", "--map", "prog.map"])).unwrap().command {
+            Some(Command::Disasm(binary_args)) => {
+                assert_eq!(binary_args.binary_file, "prog.ird");
+                assert_eq!(binary_args.map_file.as_deref(), Some("prog.map"));
+            },
+            command => panic!("{:?}", command)
+        }
+
+        match parse_cmd_args(&to_args(&["dump", "prog.ird"])).unwrap().command {
+            Some(Command::Dump(binary_args)) => {
+                assert_eq!(binary_args.binary_file, "prog.ird");
+                assert!(binary_args.map_file.is_none());
+            },
+            command => panic!("{:?}", command)
+        }
+
+        assert!(parse_cmd_args(&to_args(&["disasm"])).is_err());
+    }
+
+
+    #[test]
+    fn test_link() {
+        let link_args = |args:&[&str]| match parse_cmd_args(&to_args(args)).unwrap().command {
+            Some(Command::Link(link_args)) => link_args,
+            command => panic!("{:?}", command)
+        };
+        assert_eq!(link_args(&["link", "prog.ird", "patch.ird", "-o", "out.ird", "--at", "@start", "--map", "prog.map"]), LinkArgs {
+            base_file: "prog.ird".to_owned(),
+            fragment_file: "patch.ird".to_owned(),
+            output_file: "out.ird".to_owned(),
+            address: LinkAddress::Label("start".to_owned()),
+            map_file: Some("prog.map".to_owned())
+        });
+        assert_eq!(link_args(&["link", "prog.ird", "patch.ird", "-o", "out.ird", "--at", "0x20"]).address, LinkAddress::Address(0x20));
+
+        assert!(parse_cmd_args(&to_args(&["link", "prog.ird", "patch.ird", "-o", "out.ird", "--at", "@start"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["link", "prog.ird", "patch.ird", "--at", "0x20"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["link", "prog.ird", "patch.ird", "-o", "out.ird", "--at", "start"])).is_err());
+    }
+
+
+    #[test]
+    fn test_freeze_api() {
+        let cmd_args = asm_args(&["prog.asm", "--freeze-api", "entries.lock", "--refreeze"]);
+        assert_eq!(cmd_args.freeze_api_file.as_deref(), Some("entries.lock"));
+        assert!(cmd_args.refreeze);
+
+        assert!(!asm_args(&["prog.asm", "--freeze-api", "entries.lock"]).refreeze);
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--refreeze"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--freeze-api", "entries.lock", "--layout-only"])).is_err());
+    }
+
+
+    #[test]
+    fn test_mapdiff() {
+        let cmd_args = parse_cmd_args(&to_args(&["mapdiff", "v1.map", "v2.map", "--demangle"])).unwrap();
+        assert!(cmd_args.shared.demangler().is_some());
+        match cmd_args.command {
+            Some(Command::Mapdiff(map_diff_args)) => assert_eq!((map_diff_args.old_file.as_str(), map_diff_args.new_file.as_str()), ("v1.map", "v2.map")),
+            command => panic!("{:?}", command)
+        }
+
+        assert!(parse_cmd_args(&to_args(&["mapdiff", "v1.map"])).is_err());
+    }
+
+
+    #[test]
+    fn test_harden() {
+        assert!(!asm_args(&["prog.asm"]).harden);
+        assert!(asm_args(&["asm", "prog.asm", "--harden"]).harden);
+    }
+
+
+    #[test]
+    fn test_max_pages() {
+        assert_eq!(asm_args(&["prog.asm"]).max_pages, None);
+        assert_eq!(asm_args(&["prog.asm", "--max-pages", "4"]).max_pages, Some(4));
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--max-pages", "-1"])).is_err());
+    }
+
+
+    #[test]
+    fn test_stats() {
+        assert!(!asm_args(&["prog.asm"]).show_stats);
+        assert!(asm_args(&["prog.asm", "prog.ird", "--stats"]).show_stats);
+        assert!(asm_args(&["prog.asm", "--check", "--stats"]).show_stats);
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--layout-only", "--stats"])).is_err());
+    }
+
+
+    #[test]
+    fn test_trace_options() {
+        let cmd_args = asm_args(&["prog.asm"]);
+        assert_eq!((cmd_args.trace_output(), cmd_args.trace_file), (TraceOutput::Port(TRACE_PORT), None));
+        assert_eq!(asm_args(&["prog.asm", "--trace-port", "3"]).trace_output(), TraceOutput::Port(3));
+        assert_eq!(asm_args(&["prog.asm", "--trace-syscall", "20"]).trace_output(), TraceOutput::Syscall(20));
+        assert_eq!(asm_args(&["prog.asm", "prog.ird", "--trace-table", "prog.trc"]).trace_file.as_deref(), Some("prog.trc"));
+
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--trace-port", "16"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--trace-port", "3", "--trace-syscall", "20"])).is_err());
+    }
+
+
+    #[test]
+    fn test_max_include_depth() {
+        assert_eq!(asm_args(&["prog.asm"]).max_include_depth, 16);
+        assert_eq!(asm_args(&["prog.asm", "--max-include-depth", "2"]).max_include_depth, 2);
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--max-include-depth", "-1"])).is_err());
+    }
+
+
+    #[test]
+    fn test_max_macro_expansion() {
+        assert_eq!(asm_args(&["prog.asm"]).max_macro_expansion, 16);
+        assert_eq!(asm_args(&["prog.asm", "--max-macro-expansion", "3"]).max_macro_expansion, 3);
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--max-macro-expansion", "x"])).is_err());
+    }
+
+
+    #[test]
+    fn test_instrument_coverage() {
+        assert_eq!(asm_args(&["prog.asm"]).coverage_file, None);
+        let cmd_args = asm_args(&["prog.asm", "prog.ird", "--instrument-coverage", "prog.cov"]);
+        assert_eq!(cmd_args.coverage_file.as_deref(), Some("prog.cov"));
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--instrument-coverage"])).is_err());
+    }
+
+
+    #[test]
+    fn test_audit_determinism() {
+        let cmd_args = asm_args(&["prog.asm", "lib.asm", "--audit-determinism"]);
+        assert!(cmd_args.audit_only);
+        assert_eq!(cmd_args.input_files, vec!["prog.asm", "lib.asm"]);
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--audit-determinism", "--check"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["--audit-determinism"])).is_err());
+    }
+
+    #[test]
+    fn test_shuffle_layout() {
+        assert_eq!(asm_args(&["prog.asm"]).shuffle_seed, None);
+        assert_eq!(asm_args(&["prog.asm", "--shuffle-layout", "42"]).shuffle_seed, Some(42));
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--shuffle-layout", "abc"])).is_err());
+    }
+
+
+    #[test]
+    fn test_batch() {
+        let batch_args = |args:&[&str]| match parse_cmd_args(&to_args(args)).unwrap().command {
+            Some(Command::Batch(batch_args)) => (batch_args.archive, batch_args.report_file),
+            command => panic!("{:?}", command)
+        };
+        assert_eq!(batch_args(&["batch", "submissions.zip", "--report", "report.json"]), ("submissions.zip".to_owned(), "report.json".to_owned()));
+        assert!(parse_cmd_args(&to_args(&["batch", "submissions.zip"])).is_err());
+
+        // the deprecated flags are still accepted
+        assert_eq!(batch_args(&["--batch", "submissions.zip", "--report", "report.json"]), ("submissions.zip".to_owned(), "report.json".to_owned()));
+
+        assert!(parse_cmd_args(&to_args(&["--batch", "submissions.zip"])).is_err());
+        assert!(parse_cmd_args(&to_args(&["--batch", "submissions.zip", "--report", "report.json", "prog.asm"])).is_err());
+    }
+
+
+    #[test]
+    fn test_explain() {
+        let explain_code = |args:&[&str]| match parse_cmd_args(&to_args(args)).unwrap().command {
+            Some(Command::Explain(explain_args)) => explain_args.code,
+            command => panic!("{:?}", command)
+        };
+        assert_eq!(explain_code(&["explain", "e0101"]), "E0101");
+        assert!(parse_cmd_args(&to_args(&["explain", "E9999"])).unwrap_err().0.contains("E9999 is not an error code"));
+        assert!(parse_cmd_args(&to_args(&["explain"])).is_err());
+
+        // the deprecated flag is still accepted
+        assert_eq!(explain_code(&["--explain", "e0101"]), "E0101");
+    }
+
+
+    #[test]
+    fn test_normalize_args() {
+        assert_eq!(normalize_args(&to_args(&["-v", "prog.asm"])), to_args(&["asm", "-v", "prog.asm"]));
+        assert_eq!(normalize_args(&to_args(&["-q", "--demangler", "c++filt", "dump", "prog.ird"])),
+            to_args(&["dump", "-q", "--demangler", "c++filt", "prog.ird"]));
+        assert_eq!(normalize_args(&to_args(&["-v", "--explain=E0101"])), to_args(&["explain", "E0101", "-v"]));
+        assert_eq!(normalize_args(&to_args(&["--version"])), to_args(&["--version"]));
+
+        let args = normalize_args(&to_args(&["--map", "prog.map", "--dump", "prog.ird"]));
+        assert_eq!(args, to_args(&["dump", "prog.ird", "--map", "prog.map"]));
+        assert_eq!(normalize_args(&args), args);
+    }
+
+
+    #[test]
+    fn test_diagnostic_format() {
+        assert_eq!(asm_args(&["prog.asm"]).diagnostic_format, DiagnosticFormat::Human);
+        assert_eq!(asm_args(&["prog.asm", "--diagnostics=json"]).diagnostic_format, DiagnosticFormat::Json);
+        assert!(parse_cmd_args(&to_args(&["prog.asm", "--diagnostics", "xml"])).unwrap_err().0.contains("xml is not a diagnostics format"));
+    }
+}
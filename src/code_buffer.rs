@@ -0,0 +1,299 @@
+use crate::assemble_tokens;
+use crate::errors::{AsmValidationError, AssembleError};
+use crate::token_types::{DataTokens, FileTokens, InstrTokens};
+use crate::validation::validate_label;
+
+
+/// Represents a register, which is written in assembly as its name with a '$', such as `$g0`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reg {
+    Zero, G0, G1, G2, G3, G4, G5, G6, G7, G8, G9, Ua, Sp, Fp, Ra, Pc
+}
+
+
+impl Reg {
+    /// Gets the name of the register as written in assembly
+    pub fn name(&self) -> &'static str {
+        match self {
+            Reg::Zero => "$zero", Reg::G0 => "$g0", Reg::G1 => "$g1", Reg::G2 => "$g2", Reg::G3 => "$g3",
+            Reg::G4 => "$g4", Reg::G5 => "$g5", Reg::G6 => "$g6", Reg::G7 => "$g7", Reg::G8 => "$g8",
+            Reg::G9 => "$g9", Reg::Ua => "$ua", Reg::Sp => "$sp", Reg::Fp => "$fp", Reg::Ra => "$ra",
+            Reg::Pc => "$pc"
+        }
+    }
+}
+
+
+/// Represents the branch and jump instructions, which can all branch to a label
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Branch {
+    Jump, Jal, Beq, Bne, Blt, Bgt
+}
+
+
+impl Branch {
+    /// Gets the opcode of the branch as written in assembly
+    pub fn opcode(&self) -> &'static str {
+        match self {
+            Branch::Jump => "JUMP", Branch::Jal => "JAL", Branch::Beq => "BEQ",
+            Branch::Bne => "BNE", Branch::Blt => "BLT", Branch::Bgt => "BGT"
+        }
+    }
+}
+
+
+/// Builds a program one instruction at a time without writing or parsing any assembly, for compilers which
+/// generate Iridium code directly. Instructions go in the code section in the order they are added, and
+/// data in the data section. `assemble` runs the same pseudo substitution, label table, and code generation
+/// as the assembler, so the binary is the same as that of the equivalent source:
+///
+/// ```
+/// use iridium_assembler::code_buffer::{Branch, CodeBuffer, Reg};
+///
+/// let mut buf = CodeBuffer::new();
+/// buf.label("init").addi(Reg::G0, Reg::Zero, 5);
+/// buf.label("loop").subi(Reg::G0, Reg::G0, 1).branch_to(Branch::Bne, Reg::G8, Reg::G9, "loop").halt();
+/// let binary = buf.assemble().unwrap();
+/// ```
+///
+/// Any invalid operand, such as an immediate which does not fit, is reported by `assemble`.
+#[derive(Debug, Clone, Default)]
+pub struct CodeBuffer {
+    instrs: Vec<FileTokens>,
+    data: Vec<FileTokens>,
+    next_label: Option<String>,
+    error: Option<AsmValidationError>
+}
+
+
+impl CodeBuffer {
+    /// Creates an empty `CodeBuffer`
+    pub fn new() -> CodeBuffer {
+        CodeBuffer::default()
+    }
+
+
+    /// Records the first error found while building the program, so that it can be returned by `assemble`
+    fn fail(&mut self, message:String) {
+        if self.error.is_none() {
+            self.error = Some(AsmValidationError(message));
+        }
+    }
+
+
+    /// Takes the name of an instruction, an immediate, and the number of bits it must fit in, and records
+    /// an error if it does not fit
+    fn check_immediate(&mut self, opcode:&str, immediate:u8, bits:u32) {
+        if immediate as u32 >= 1 << bits {
+            self.fail(format!("The immediate {} of {} does not fit in {} bits", immediate, opcode, bits));
+        }
+    }
+
+
+    /// Adds an instruction with the given operands to the code section, with any pending label
+    fn instr(&mut self, opcode:&str, regs:&[Reg], immediate:Option<u8>, op_label:Option<&str>) -> &mut Self {
+        if let Some(op_label) = op_label {
+            if let Err(e) = validate_label(op_label, op_label) {
+                self.fail(e.0);
+            }
+        }
+
+        let mut regs = regs.iter().map(|reg| Some(reg.name().to_owned()));
+        self.instrs.push(FileTokens::InstrTokens(InstrTokens::new(
+            self.next_label.take(), opcode.to_owned(), regs.next().flatten(), regs.next().flatten(), regs.next().flatten(),
+            immediate.map(|immediate| immediate as u64), op_label.map(|label| format!("@{}", label))
+        )));
+
+        self
+    }
+
+
+    /// Labels the next instruction added with the given name. Will record an error if the name is not a
+    /// valid label, or if the previous label has not been given an instruction.
+    pub fn label(&mut self, name:&str) -> &mut Self {
+        if let Err(e) = validate_label(name, name) {
+            self.fail(e.0);
+        }
+
+        if let Some(label) = self.next_label.replace(name.to_owned()) {
+            self.fail(format!("The label {} is followed by the label {} without an instruction between them", label, name));
+        }
+
+        self
+    }
+
+
+    // the instructions which take only registers, and are named after their opcodes
+    pub fn nop(&mut self) -> &mut Self { self.instr("NOP", &[], None, None) }
+    pub fn add(&mut self, a:Reg, b:Reg, c:Reg) -> &mut Self { self.instr("ADD", &[a, b, c], None, None) }
+    pub fn sub(&mut self, a:Reg, b:Reg, c:Reg) -> &mut Self { self.instr("SUB", &[a, b, c], None, None) }
+    pub fn sll(&mut self, a:Reg, b:Reg, c:Reg) -> &mut Self { self.instr("SLL", &[a, b, c], None, None) }
+    pub fn srl(&mut self, a:Reg, b:Reg, c:Reg) -> &mut Self { self.instr("SRL", &[a, b, c], None, None) }
+    pub fn sra(&mut self, a:Reg, b:Reg, c:Reg) -> &mut Self { self.instr("SRA", &[a, b, c], None, None) }
+    pub fn nand(&mut self, a:Reg, b:Reg, c:Reg) -> &mut Self { self.instr("NAND", &[a, b, c], None, None) }
+    pub fn or(&mut self, a:Reg, b:Reg, c:Reg) -> &mut Self { self.instr("OR", &[a, b, c], None, None) }
+    pub fn load(&mut self, a:Reg, b:Reg, c:Reg) -> &mut Self { self.instr("LOAD", &[a, b, c], None, None) }
+    pub fn store(&mut self, a:Reg, b:Reg, c:Reg) -> &mut Self { self.instr("STORE", &[a, b, c], None, None) }
+    pub fn addc(&mut self, a:Reg, b:Reg) -> &mut Self { self.instr("ADDC", &[a, b], None, None) }
+    pub fn subc(&mut self, a:Reg, b:Reg) -> &mut Self { self.instr("SUBC", &[a, b], None, None) }
+    pub fn cmp(&mut self, a:Reg, b:Reg) -> &mut Self { self.instr("CMP", &[a, b], None, None) }
+    pub fn atom(&mut self) -> &mut Self { self.instr("ATOM", &[], None, None) }
+    pub fn halt(&mut self) -> &mut Self { self.instr("HALT", &[], None, None) }
+
+
+    /// Adds `ADDI a, b, immediate`, where the immediate must fit in 4 bits
+    pub fn addi(&mut self, a:Reg, b:Reg, immediate:u8) -> &mut Self {
+        self.check_immediate("ADDI", immediate, 4);
+        self.instr("ADDI", &[a, b], Some(immediate), None)
+    }
+
+
+    /// Adds `SUBI a, b, immediate`, where the immediate must fit in 4 bits
+    pub fn subi(&mut self, a:Reg, b:Reg, immediate:u8) -> &mut Self {
+        self.check_immediate("SUBI", immediate, 4);
+        self.instr("SUBI", &[a, b], Some(immediate), None)
+    }
+
+
+    /// Adds `MOVUI a, immediate`, which sets the upper byte of the register
+    pub fn movui(&mut self, a:Reg, immediate:u8) -> &mut Self {
+        self.instr("MOVUI", &[a], Some(immediate), None)
+    }
+
+
+    /// Adds `MOVLI a, immediate`, which sets the lower byte of the register
+    pub fn movli(&mut self, a:Reg, immediate:u8) -> &mut Self {
+        self.instr("MOVLI", &[a], Some(immediate), None)
+    }
+
+
+    /// Adds `IN a, port`, where the port must fit in 4 bits
+    pub fn input(&mut self, a:Reg, port:u8) -> &mut Self {
+        self.check_immediate("IN", port, 4);
+        self.instr("IN", &[a], Some(port), None)
+    }
+
+
+    /// Adds `OUT a, port`, where the port must fit in 4 bits
+    pub fn output(&mut self, a:Reg, port:u8) -> &mut Self {
+        self.check_immediate("OUT", port, 4);
+        self.instr("OUT", &[a], Some(port), None)
+    }
+
+
+    /// Adds `syscall immediate`
+    pub fn syscall(&mut self, immediate:u8) -> &mut Self {
+        self.instr("syscall", &[], Some(immediate), None)
+    }
+
+
+    /// Adds a branch or jump to the address held in the registers `a` (upper half) and `b` (lower half)
+    pub fn branch(&mut self, branch:Branch, a:Reg, b:Reg) -> &mut Self {
+        self.instr(branch.opcode(), &[a, b], None, None)
+    }
+
+
+    /// Adds a branch or jump to the given label, which loads the address of the label into `a` and `b`
+    /// first, in the same way as `JUMP a, b, @label`
+    pub fn branch_to(&mut self, branch:Branch, a:Reg, b:Reg, label:&str) -> &mut Self {
+        self.instr(branch.opcode(), &[a, b], None, Some(label))
+    }
+
+
+    /// Adds `LOAD a, b, c, @label`, which loads the address of the label into `b` and `c` first
+    pub fn load_from(&mut self, a:Reg, b:Reg, c:Reg, label:&str) -> &mut Self {
+        self.instr("LOAD", &[a, b, c], None, Some(label))
+    }
+
+
+    /// Adds `STORE a, b, c, @label`, which loads the address of the label into `b` and `c` first
+    pub fn store_to(&mut self, a:Reg, b:Reg, c:Reg, label:&str) -> &mut Self {
+        self.instr("STORE", &[a, b, c], None, Some(label))
+    }
+
+
+    /// Adds `MOVUI a, @label`, which sets the register to the upper byte of the lower half of the address
+    pub fn movui_label(&mut self, a:Reg, label:&str) -> &mut Self {
+        self.instr("MOVUI", &[a], None, Some(label))
+    }
+
+
+    /// Adds `MOVLI a, @label`, which sets the register to the lower byte of the address
+    pub fn movli_label(&mut self, a:Reg, label:&str) -> &mut Self {
+        self.instr("MOVLI", &[a], None, Some(label))
+    }
+
+
+    /// Adds the given words to the data section with the given label, in the same way as a `.section`
+    pub fn data(&mut self, label:&str, words:&[u16]) -> &mut Self {
+        if let Err(e) = validate_label(label, label) {
+            self.fail(e.0);
+        }
+
+        if words.is_empty() {
+            self.fail(format!("The data {} must be at least 1 word", label));
+        }
+
+        self.data.push(FileTokens::DataTokens(DataTokens::new(Some(label.to_owned()), "section".to_owned(), words.to_vec())));
+        self
+    }
+
+
+    /// Returns the tokens of the program, with the code section followed by the data section, as the
+    /// tokenizer would produce them from the equivalent source
+    pub fn tokens(&self) -> Vec<FileTokens> {
+        self.instrs.iter().chain(&self.data).cloned().collect()
+    }
+
+
+    /// Assembles the program into the bytes of a program binary, in the same way as `assemble_source`. Will
+    /// return an `AssembleError` if any instruction added was invalid, if a label was never given an
+    /// instruction, or if the program cannot be assembled.
+    pub fn assemble(&self) -> Result<Vec<u8>, AssembleError> {
+        if let Some(e) = &self.error {
+            return Err(AssembleError(e.0.to_owned()));
+        }
+
+        if let Some(label) = &self.next_label {
+            return Err(AssembleError(format!("The label {} is not followed by an instruction", label)));
+        }
+
+        assemble_tokens(self.tokens())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::assemble_source;
+    use crate::code_buffer::*;
+
+
+    #[test]
+    fn test_matches_text_pipeline() {
+        let mut buf = CodeBuffer::new();
+        buf.label("init").addi(Reg::G0, Reg::Zero, 5).movli(Reg::G1, 0x20).input(Reg::G2, 3);
+        buf.label("loop").subi(Reg::G0, Reg::G0, 1).cmp(Reg::G0, Reg::Zero)
+            .branch_to(Branch::Bne, Reg::G8, Reg::G9, "loop")
+            .load_from(Reg::G3, Reg::G8, Reg::G9, "count")
+            .branch(Branch::Jump, Reg::Ra, Reg::Zero)
+            .halt();
+        buf.data("count", &[1, 2, 3]);
+
+        let source = "init:\n    ADDI $g0, $zero, 5\n    MOVLI $g1, 0x20\n    IN $g2, 3\n\
+            loop:\n    SUBI $g0, $g0, 1\n    CMP $g0, $zero\n    BNE $g8, $g9, @loop\n    LOAD $g3, $g8, $g9, @count\n\
+            JUMP $ra, $zero\n    HALT\n\ndata:\n    count: .section 3 [1, 2, 3]\n";
+        assert_eq!(buf.assemble().unwrap(), assemble_source(source).unwrap());
+    }
+
+
+    #[test]
+    fn test_invalid_code_buffer() {
+        assert!(CodeBuffer::new().label("init").addi(Reg::G0, Reg::G0, 16).halt().assemble().is_err());
+        assert!(CodeBuffer::new().label("init").label("start").halt().assemble().is_err());
+        assert!(CodeBuffer::new().label("init").halt().label("end").assemble().is_err());
+        assert!(CodeBuffer::new().label("init").branch_to(Branch::Jump, Reg::G8, Reg::G9, "nowhere").halt().assemble().is_err());
+        assert!(CodeBuffer::new().label("1st").halt().assemble().is_err());
+        assert!(CodeBuffer::new().label("init").nop().data("count", &[1]).assemble().is_err());
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader};
+use crate::errors::AsmValidationError;
+use crate::lang::LanguageLevel;
+use crate::limits::ResourceBudget;
+use crate::preprocessor::Configuration;
+use crate::process_reader_into_tokens_with_warnings;
+use crate::token_types::FileTokens;
+use crate::warnings::WarningSink;
+
+
+/// Takes the source files given on the command line and returns the tokens of all of them joined into a
+/// single program, as if the files had been concatenated. The instructions of every file are placed first
+/// in the order the files were given, followed by the data of every file, then the text, and then the bss,
+/// so that each section stays together on its own pages. All files share a single namespace of labels, other
+/// than the file-local labels starting with `.L`, which each file can define for itself. The files are all
+/// assembled in the given configuration and language level, with the checks of `hardening` if `harden` is
+/// true, and may nest `.include` and macros no deeper than the limits of the budget allow. Each file is
+/// tokenized under a budget of its own with the same limits, and the expansions of every file are counted in
+/// the given budget. The source file `-` is read from stdin. Any warnings found while validating the lines are
+/// pushed into the sink.
+///
+/// Will return an `AsmValidationError` giving the position of the problem if a file cannot be read or has an
+/// invalid line, or giving both positions if a label is defined in more than one file. Every file is checked
+/// before returning, so the error holds all of the problems found, one on each line. Stdin is named
+/// `<stdin>` in diagnostics.
+pub fn process_files_into_tokens(input_files:&[String], config:&Configuration, harden:bool, lang:LanguageLevel, budget:&mut ResourceBudget,
+        warnings:&mut WarningSink)
+        -> Result<Vec<FileTokens>, AsmValidationError> {
+    let mut label_files:HashMap<String, String> = HashMap::new();
+    let mut code:Vec<FileTokens> = Vec::new();
+    let mut data:Vec<FileTokens> = Vec::new();
+    let mut text:Vec<FileTokens> = Vec::new();
+    let mut bss:Vec<FileTokens> = Vec::new();
+    let mut errors:Vec<String> = Vec::new();
+    for input_file in input_files {
+        let mut file_budget = ResourceBudget::new(budget.limits);
+        let file_tokens = if input_file == "-" {
+            process_reader_into_tokens_with_warnings(io::stdin().lock(), "<stdin>", config, harden, lang, &mut file_budget, warnings)
+        } else {
+            File::open(input_file)
+                .map_err(|e| AsmValidationError(format!("could not read {}: {}", input_file, e)))
+                .and_then(|file| process_reader_into_tokens_with_warnings(BufReader::new(file), input_file, config, harden, lang, &mut file_budget,
+                    warnings))
+        };
+
+        budget.count_expansions(file_budget.expansions());
+
+        let file_tokens = match file_tokens {
+            Ok(file_tokens) => file_tokens,
+            Err(e) => {
+                errors.push(e.0);
+                continue;
+            }
+        };
+
+        let mut file_labels:Vec<(String, String)> = Vec::new();
+        for tokens in file_tokens {
+            let label = tokens.label();
+
+            // duplicates within a single file are reported when the label table is built, so each file is
+            // only checked against the files before it
+            if let Some(label) = label {
+                let location = tokens.pos().map(|pos| pos.to_string()).unwrap_or_else(|| input_file.to_owned());
+                if !file_labels.iter().any(|(file_label, _)| file_label == label) {
+                    match label_files.get(label) {
+                        Some(other_location) => errors.push(format!(
+                            "{}: Duplicate label \"{}\", which is already defined at {}", location, label, other_location
+                        )),
+                        None => file_labels.push((label.to_owned(), location))
+                    }
+                }
+            }
+
+            match tokens {
+                FileTokens::InstrTokens(_) => code.push(tokens),
+                FileTokens::DataTokens(_) => data.push(tokens),
+                FileTokens::TextTokens(_) => text.push(tokens),
+                FileTokens::BssTokens(_) => bss.push(tokens),
+                FileTokens::OrgTokens(ref t) => match t.section {
+                    'c' => code.push(tokens),
+                    'd' => data.push(tokens),
+                    't' => text.push(tokens),
+                    _ => bss.push(tokens)
+                },
+                FileTokens::AliasTokens(ref t) => match t.section {
+                    'c' => code.push(tokens),
+                    'd' => data.push(tokens),
+                    't' => text.push(tokens),
+                    _ => bss.push(tokens)
+                },
+                FileTokens::DeprecatedTokens(ref t) => match t.section {
+                    'c' => code.push(tokens),
+                    'd' => data.push(tokens),
+                    't' => text.push(tokens),
+                    _ => bss.push(tokens)
+                },
+                FileTokens::TraceTokens(_) | FileTokens::ExportTableTokens(_) => code.push(tokens)
+            }
+        }
+
+        label_files.extend(file_labels);
+    }
+
+    if !errors.is_empty() {
+        return Err(AsmValidationError(errors.join("\n")));
+    }
+
+    code.append(&mut data);
+    code.append(&mut text);
+    code.append(&mut bss);
+    Ok(code)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::concatenation::process_files_into_tokens;
+    use crate::lang::LanguageLevel;
+    use crate::limits::{ResourceBudget, ResourceLimits};
+use crate::preprocessor::Configuration;
+    use crate::warnings::WarningSink;
+    use crate::label_table::generate_label_table;
+    use crate::pseudo_substitution::substitute_pseudo_instrs;
+
+
+    fn to_files(files:&[&str]) -> Vec<String> {
+        files.iter().map(|file| format!("test_files/{}", file)).collect()
+    }
+
+
+    #[test]
+    fn test_concatenate_files() {
+        let tokens = process_files_into_tokens(&to_files(&["test_multi_file_main.asm", "test_multi_file_lib.asm"]), &Configuration::default(), false, LanguageLevel::default(), &mut ResourceBudget::new(ResourceLimits::UNLIMITED), &mut WarningSink::default()).unwrap();
+        assert_eq!(tokens.len(), 9);
+        assert_eq!(tokens[4].try_get_instr_tokens().unwrap().label.unwrap(), "double");
+        assert_eq!(tokens[6].try_get_data_tokens().unwrap().label.unwrap(), "value");
+        assert_eq!(tokens[7].try_get_data_tokens().unwrap().label.unwrap(), "factor");
+
+        let label_table = generate_label_table(&substitute_pseudo_instrs(tokens)).unwrap();
+        assert_eq!(label_table.get("double"), Some(&0x000A));
+        assert_eq!(label_table.get("factor"), Some(&0x1001));
+        assert_eq!(label_table.get("greeting"), Some(&0x2000));
+    }
+
+
+    #[test]
+    fn test_duplicate_label_across_files() {
+        let err = process_files_into_tokens(&to_files(&["test_multi_file_main.asm", "test_multi_file_dup.asm"]), &Configuration::default(), false, LanguageLevel::default(), &mut ResourceBudget::new(ResourceLimits::UNLIMITED), &mut WarningSink::default()).unwrap_err();
+        assert_eq!(err.0, "test_files/test_multi_file_dup.asm:6:5: Duplicate label \"value\", which is already defined at test_files/test_multi_file_main.asm:8:5");
+    }
+
+
+    #[test]
+    fn test_file_labels_across_files() {
+        let tokens = process_files_into_tokens(&to_files(&["test_file_labels_main.asm", "test_file_labels_lib.asm"]), &Configuration::default(), false, LanguageLevel::default(), &mut ResourceBudget::new(ResourceLimits::UNLIMITED), &mut WarningSink::default()).unwrap();
+        let label_table = generate_label_table(&substitute_pseudo_instrs(tokens)).unwrap();
+        assert_eq!(label_table.get("__local_test__files_2Ftest__file__labels__main_Lloop"), Some(&0x0001));
+        assert_eq!(label_table.get("__local_test__files_2Ftest__file__labels__lib_Lloop"), Some(&0x000F));
+        assert_eq!(label_table.get(".Lloop"), None);
+    }
+}
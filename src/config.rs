@@ -0,0 +1,258 @@
+use std::fs;
+use std::io::ErrorKind;
+use toml::{Table, Value};
+use crate::cmd_args::normalize_args;
+use crate::errors::ConfigError;
+use crate::warnings::WarningKind;
+
+
+/// The name of the configuration file which is read from the working directory, if there is one
+pub const CONFIG_FILENAME:&str = "iridium.toml";
+
+/// The options which take a single value that the configuration file can set, by their long name
+const VALUE_OPTIONS:[&str; 9] = ["target", "fill", "map", "listing", "memmap", "memory", "lang", "format", "page-size"];
+
+
+/// Takes a key of the configuration file and its value, and returns the value as a string, or a
+/// `ConfigError` if it is not a string. Integers are also accepted for `fill`, `lang`, and `page-size`, as
+/// they are numbers.
+fn value_to_string(key:&str, value:&Value) -> Result<String, ConfigError> {
+    match value {
+        Value::String(value) => Ok(value.to_owned()),
+        Value::Integer(value) if key == "fill" || key == "lang" || key == "page-size" => Ok(value.to_string()),
+        _ => Err(ConfigError(format!("{} should be a string, but is {}", key, value)))
+    }
+}
+
+
+/// Takes the contents of a configuration file in TOML and returns the command line options it sets, which
+/// are placed before the options the assembler was invoked with so that those override them. The file can
+/// set any of `VALUE_OPTIONS`, `allow-fallthrough` and `harden` to true or false, `forbid` to a list of opcodes
+/// or to a table of the opcodes forbidden in each scope, the start address of any section in a `[section-start]` table, and each warning in a `[warnings]` table to
+/// either "warn" or "off", such as:
+///
+/// ```toml
+/// target = "board-a"
+/// fill = 0xFFFF
+/// format = "bin"
+/// page-size = 0x100
+/// forbid = ["IN", "OUT"]
+///
+/// [section-start]
+/// data = 0x4000
+///
+/// [warnings]
+/// branch-pair = "off"
+/// ```
+///
+/// Will return a `ConfigError` if the file is not valid TOML, or if it has a key which is not one of these
+/// or a value of the wrong type. The values themselves are checked when the options are parsed.
+pub fn parse_config(source:&str) -> Result<Vec<String>, ConfigError> {
+    let table:Table = source.parse().map_err(|e:toml::de::Error| ConfigError(e.message().to_owned()))?;
+    let mut args:Vec<String> = Vec::new();
+    for (key, value) in &table {
+        match (key.as_str(), value) {
+            (key, value) if VALUE_OPTIONS.contains(&key) => {
+                args.push(format!("--{}", key));
+                args.push(value_to_string(key, value)?);
+            },
+
+            ("allow-fallthrough" | "harden", Value::Boolean(enabled)) => {
+                if *enabled {
+                    args.push(format!("--{}", key));
+                }
+            },
+
+            ("forbid", Value::Array(opcodes)) => {
+                let opcodes = opcodes.iter().map(|opcode| value_to_string(key, opcode)).collect::<Result<Vec<String>, _>>()?;
+                if !opcodes.is_empty() {
+                    args.push("--forbid".to_owned());
+                    args.push(opcodes.join(","));
+                }
+            },
+
+            // a table forbids the opcodes listed under each scope only within that scope
+            ("forbid", Value::Table(scopes)) => {
+                for (scope, opcodes) in scopes {
+                    let opcodes = match opcodes {
+                        Value::Array(opcodes) => opcodes.iter().map(|opcode| value_to_string(key, opcode)).collect::<Result<Vec<String>, _>>()?,
+                        _ => return Err(ConfigError(format!("the opcodes forbidden in {} should be a list, but are {}", scope, opcodes)))
+                    };
+
+                    if !opcodes.is_empty() {
+                        args.push("--forbid".to_owned());
+                        args.push(format!("{}={}", scope, opcodes.join(",")));
+                    }
+                }
+            },
+
+            ("warnings", Value::Table(warnings)) => {
+                for (name, level) in warnings {
+                    if WarningKind::from_name(name).is_none() {
+                        return Err(ConfigError(format!("{} is not a warning", name)));
+                    }
+
+                    match level.as_str() {
+                        Some("off") => args.push(format!("-Wno-{}", name)),
+                        Some("warn") => args.push(format!("-W{}", name)),
+                        _ => return Err(ConfigError(format!("the {} warning should be \"warn\" or \"off\", but is {}", name, level)))
+                    }
+                }
+            },
+
+            ("section-start", Value::Table(starts)) => {
+                for (section, address) in starts {
+                    match address {
+                        Value::Integer(address) => {
+                            args.push("--section-start".to_owned());
+                            args.push(format!("{}={}", section, address));
+                        },
+                        _ => return Err(ConfigError(format!("the start of the {} section should be an address, but is {}", section, address)))
+                    }
+                }
+            },
+
+            ("allow-fallthrough" | "harden" | "forbid" | "section-start" | "warnings", value) => {
+                return Err(ConfigError(format!("{} has the wrong type of value {}", key, value)));
+            },
+
+            (key, _) => {
+                return Err(ConfigError(format!(
+                    "{} is not an option, which should be one of {}, allow-fallthrough, harden, forbid, section-start, or warnings",
+                    key, VALUE_OPTIONS.join(", ")
+                )));
+            }
+        }
+    }
+
+    Ok(args)
+}
+
+
+/// Takes the filename of a configuration file and returns the command line options it sets, or no options
+/// if the file does not exist. Will return a `ConfigError` if the file cannot be read or is not valid.
+pub fn read_config(filename:&str) -> Result<Vec<String>, ConfigError> {
+    match fs::read_to_string(filename) {
+        Ok(source) => parse_config(&source).map_err(|e| ConfigError(format!("{}: {}", filename, e.0))),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(ConfigError(format!("could not read {}: {}", filename, e)))
+    }
+}
+
+
+/// Takes the options set by the configuration file and the command line arguments, and returns the
+/// arguments with the options placed where they apply. The arguments are first put in the order of
+/// `normalize_args`, after which the options go straight after `asm`, and are left out for the other
+/// subcommands and for the version or help of the assembler, which take none of the options for assembling.
+pub fn insert_config_args(config_args:Vec<String>, args:Vec<String>) -> Vec<String> {
+    let args = normalize_args(&args);
+    match args.first().map(|arg| arg.as_str()) {
+        Some("asm") => args[..1].iter().cloned().chain(config_args).chain(args[1..].iter().cloned()).collect(),
+        _ => args
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::cmd_args::{parse_cmd_args, AsmArgs, Command};
+    use crate::config::*;
+    use crate::generate_code::OutputFormat;
+    use crate::lang::LanguageLevel;
+    use crate::lints::ForbiddenOpcodes;
+    use crate::warnings::WarningKind;
+
+
+    fn with_config(config:&str, args:&[&str]) -> Vec<String> {
+        parse_config(config).unwrap().into_iter().chain(args.iter().map(|arg| arg.to_string())).collect()
+    }
+
+
+    fn asm_args(args:&[String]) -> AsmArgs {
+        match parse_cmd_args(args).unwrap().command {
+            Some(Command::Asm(asm_args)) => asm_args,
+            command => panic!("{:?}", command)
+        }
+    }
+
+
+    #[test]
+    fn test_parse_config() {
+        let config = "target = \"board-a\"\nfill = 0xFFFF\nallow-fallthrough = true\nharden = true\nforbid = [\"IN\", \"out\"]\nlang = 2\n\
+            format = \"bin\"\npage-size = 0x100\n\n[section-start]\ndata = 0x4000\n\n[warnings]\nbranch-pair = \"off\"\n";
+
+        let cmd_args = asm_args(&with_config(config, &["prog.asm", "prog.ird"]));
+        assert_eq!(cmd_args.target.as_deref(), Some("board-a"));
+        assert_eq!(cmd_args.fill_word, 0xFFFF);
+        assert!(cmd_args.allow_fallthrough);
+        assert!(cmd_args.harden);
+        assert_eq!(cmd_args.forbidden_opcodes, vec![ForbiddenOpcodes { scope: None, opcodes: vec!["IN".to_owned(), "OUT".to_owned()] }]);
+        assert_eq!(cmd_args.lang, LanguageLevel::Two);
+        assert_eq!(cmd_args.output_format, OutputFormat::Bin);
+        assert_eq!(cmd_args.page_size, 0x100);
+        assert_eq!(cmd_args.section_starts, vec![('d', 0x4000)]);
+        assert!(!cmd_args.warning_settings().is_enabled(WarningKind::BranchPair));
+        assert_eq!(cmd_args.input_files, vec!["prog.asm"]);
+    }
+
+
+    #[test]
+    fn test_command_line_overrides_config() {
+        let cmd_args = asm_args(&with_config("target = \"board-a\"\nmap = \"prog.map\"",
+            &["prog.asm", "prog.ird", "--target", "board-b"]));
+        assert_eq!(cmd_args.target.as_deref(), Some("board-b"));
+        assert_eq!(cmd_args.map_file.as_deref(), Some("prog.map"));
+
+        let cmd_args = asm_args(&with_config("[section-start]\ndata = 0x4000\ntext = 0x8000",
+            &["prog.asm", "prog.ird", "--section-start", "data=0x6000"]));
+        assert_eq!(cmd_args.section_starts, vec![('t', 0x8000), ('d', 0x6000)]);
+    }
+
+
+    #[test]
+    fn test_scoped_forbid() {
+        let cmd_args = asm_args(&with_config("[forbid]\n\"app/\" = [\"IN\", \"OUT\"]\n\"@drivers\" = [\"halt\"]", &["prog.asm", "prog.ird"]));
+        assert_eq!(cmd_args.forbidden_opcodes, vec![
+            ForbiddenOpcodes { scope: Some("@drivers".to_owned()), opcodes: vec!["HALT".to_owned()] },
+            ForbiddenOpcodes { scope: Some("app/".to_owned()), opcodes: vec!["IN".to_owned(), "OUT".to_owned()] }
+        ]);
+    }
+
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(parse_config("stack-size = 4096").unwrap_err().0.starts_with("stack-size is not an option"));
+        assert!(parse_cmd_args(&with_config("page-size = 1000", &["prog.asm", "prog.ird"])).is_err());
+        assert!(parse_config("[section-start]\ndata = \"high\"").is_err());
+        assert!(parse_config("[forbid]\n\"app/\" = \"IN\"").is_err());
+        assert!(parse_cmd_args(&with_config("[section-start]\nstack = 0x4000", &["prog.asm", "prog.ird"])).is_err());
+        assert!(parse_config("target = 5").is_err());
+        assert!(parse_config("allow-fallthrough = \"yes\"").is_err());
+        assert!(parse_config("[warnings]\nnot-a-warning = \"off\"").is_err());
+        assert!(parse_config("[warnings]\nbranch-pair = \"loud\"").is_err());
+        assert!(parse_config("target = ").is_err());
+        assert!(parse_cmd_args(&with_config("fill = 0x10000", &["prog.asm", "prog.ird"])).is_err());
+        assert!(parse_cmd_args(&with_config("lang = 3", &["prog.asm", "prog.ird"])).is_err());
+    }
+
+
+    #[test]
+    fn test_missing_config() {
+        assert!(read_config("test_files/no_such_config.toml").unwrap().is_empty());
+    }
+
+
+    #[test]
+    fn test_insert_config_args() {
+        let config_args = parse_config("target = \"board-a\"").unwrap();
+        let args = |args:&[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>();
+        assert_eq!(insert_config_args(config_args.clone(), args(&["prog.asm"])), args(&["asm", "--target", "board-a", "prog.asm"]));
+        assert_eq!(insert_config_args(config_args.clone(), args(&["-q", "prog.asm"])), args(&["asm", "--target", "board-a", "-q", "prog.asm"]));
+        assert_eq!(insert_config_args(config_args.clone(), args(&["--version"])), args(&["--version"]));
+        assert_eq!(insert_config_args(config_args.clone(), args(&["asm", "prog.asm"])), args(&["asm", "--target", "board-a", "prog.asm"]));
+        assert_eq!(insert_config_args(config_args, args(&["disasm", "prog.ird"])), args(&["disasm", "prog.ird"]));
+
+        let cmd_args = asm_args(&insert_config_args(parse_config("target = \"board-a\"").unwrap(), args(&["asm", "prog.asm"])));
+        assert_eq!(cmd_args.target.as_deref(), Some("board-a"));
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use crate::token_types::{FileTokens, InstrTokens, SourcePos};
+
+
+/// The instructions which a folded sequence is made of, each of which only writes a register from an
+/// immediate and another register
+const FOLDABLE_OPCODES:[&str; 4] = ["MOVLI", "MOVUI", "ADDI", "SUBI"];
+
+
+/// The instructions which read the flags, which must not see the flags left by a sequence that was folded
+const FLAG_READERS:[&str; 6] = ["ADDC", "SUBC", "BEQ", "BNE", "BLT", "BGT"];
+
+
+/// Represents what is known about the value of a register part way through a sequence, as the bits which
+/// are known and the values of those bits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Known {
+    mask: u16,
+    value: u16
+}
+
+
+/// Takes an instruction and returns true if it can be part of a folded sequence, which it can if it is one
+/// of `FOLDABLE_OPCODES`, has no label operand, whose value is not known until the label table is built, and
+/// does not write `$pc`.
+fn is_foldable(t:&InstrTokens) -> bool {
+    FOLDABLE_OPCODES.contains(&t.opcode.as_str()) && t.op_label.is_none() && t.operand_a.as_deref() != Some("$pc")
+}
+
+
+/// Takes the tokens after a sequence and returns true if the flags it leaves cannot be read, which is when a
+/// `CMP` sets them again or a `HALT` stops the program before any instruction reads them. Anything which may
+/// go elsewhere, such as a jump or a `syscall`, or the end of the code, counts as reading them.
+fn flags_are_dead(rest:&[FileTokens]) -> bool {
+    for token in rest {
+        match token {
+            FileTokens::InstrTokens(t) => match t.opcode.as_str() {
+                "CMP" | "HALT" => return true,
+                "JUMP" | "JAL" | "syscall" => return false,
+                opcode if FLAG_READERS.contains(&opcode) => return false,
+                _ => ()
+            },
+
+            _ if token.is_zero_width() => (),
+            _ => return false
+        }
+    }
+
+    false
+}
+
+
+/// Takes a register, what is known about its value, and the position of the sequence it was folded from, and
+/// returns the fewest instructions which load the known bits into it. A register which is known in full is
+/// loaded with a single `ADDI` or `SUBI` from `$zero` if its value is within 15 of 0, and otherwise with a
+/// `MOVLI` and a `MOVUI`, as is each known byte of one which is only known in part.
+fn load_known(register:&str, known:Known, pos:&Option<SourcePos>) -> Vec<InstrTokens> {
+    let instr = |opcode:&str, operand_b:Option<&str>, immediate:u16| {
+        InstrTokens::new(None, opcode.to_owned(), Some(register.to_owned()), operand_b.map(|operand| operand.to_owned()), None, Some(immediate as u64), None)
+            .with_pos(pos.clone())
+    };
+
+    if known.mask == 0xFFFF && known.value <= 0xF {
+        return vec![instr("ADDI", Some("$zero"), known.value)];
+    } else if known.mask == 0xFFFF && known.value.wrapping_neg() <= 0xF {
+        return vec![instr("SUBI", Some("$zero"), known.value.wrapping_neg())];
+    }
+
+    let mut loads:Vec<InstrTokens> = Vec::new();
+    if known.mask & 0x00FF == 0x00FF {
+        loads.push(instr("MOVLI", None, known.value & 0xFF));
+    }
+
+    if known.mask & 0xFF00 == 0xFF00 {
+        loads.push(instr("MOVUI", None, known.value >> 8));
+    }
+
+    loads
+}
+
+
+/// Takes a sequence of foldable instructions, of which only the first may be labelled, and returns the
+/// instructions it folds into. Each instruction is executed on the values known so far, starting from only
+/// `$zero` being known, and every register written is then loaded with its final value by `load_known`. An
+/// instruction which reads a register that is not known is kept, after loading anything known about that
+/// register, and what it writes is no longer known.
+fn fold_sequence(sequence:&[InstrTokens]) -> Vec<InstrTokens> {
+    let pos = &sequence[0].pos;
+    let mut state:HashMap<&str, Known> = HashMap::from([("$zero", Known { mask: 0xFFFF, value: 0 })]);
+    let mut pending:Vec<&str> = Vec::new();
+    let mut folded:Vec<InstrTokens> = Vec::new();
+    for t in sequence {
+        let destination = t.operand_a.as_deref().unwrap_or("$zero");
+        let source = t.operand_b.as_deref().unwrap_or("$zero");
+        let immediate = t.immediate.unwrap_or(0) as u16;
+        let known = state.get(destination).copied().unwrap_or_default();
+        let result = match (t.opcode.as_str(), state.get(source)) {
+            ("MOVLI", _) => Some(Known { mask: known.mask | 0x00FF, value: (known.value & 0xFF00) | (immediate & 0xFF) }),
+            ("MOVUI", _) => Some(Known { mask: known.mask | 0xFF00, value: (known.value & 0x00FF) | (immediate << 8) }),
+            ("ADDI", Some(source)) if source.mask == 0xFFFF => Some(Known { mask: 0xFFFF, value: source.value.wrapping_add(immediate) }),
+            ("SUBI", Some(source)) if source.mask == 0xFFFF => Some(Known { mask: 0xFFFF, value: source.value.wrapping_sub(immediate) }),
+            _ => None
+        };
+
+        // writing to $zero has no effect, and it is always known
+        if destination == "$zero" {
+            continue;
+        }
+
+        match result {
+            Some(result) => {
+                state.insert(destination, result);
+                if !pending.contains(&destination) {
+                    pending.push(destination);
+                }
+            },
+
+            None => {
+                if let Some(index) = pending.iter().position(|register| *register == source) {
+                    folded.extend(load_known(source, state[source], pos));
+                    pending.remove(index);
+                }
+
+                let mut kept = t.clone();
+                kept.label = None;
+                folded.push(kept);
+                state.remove(destination);
+                pending.retain(|register| *register != destination);
+            }
+        }
+    }
+
+    for register in pending {
+        folded.extend(load_known(register, state[register], pos));
+    }
+
+    folded
+}
+
+
+/// Takes a stream of tokens after pseudo-instructions are substituted and returns it with every straight-line
+/// sequence of `MOVLI`, `MOVUI`, `ADDI`, and `SUBI` replaced by the fewest instructions which leave the same
+/// values in the registers it writes, such as the verbose address computations generated by a macro. The
+/// values are found by executing the sequence on the values known at assembly time, as described for
+/// `fold_sequence`. A sequence ends at the first label after its start, so nothing can jump into the middle
+/// of one, and the label of its first instruction moves to the first instruction it folds into.
+///
+/// The flags after a folded sequence are not the same as before, so a sequence is only folded if its flags
+/// are never read, as described for `flags_are_dead`, and only if it folds into fewer instructions. As the
+/// program gets shorter, this must happen before the label table is built.
+pub fn fold_constants(tokens:Vec<FileTokens>) -> Vec<FileTokens> {
+    let mut folded:Vec<FileTokens> = Vec::with_capacity(tokens.len());
+    let mut index = 0;
+    while index < tokens.len() {
+        let end = tokens[index..].iter().enumerate()
+            .take_while(|(offset, token)| match token {
+                FileTokens::InstrTokens(t) => is_foldable(t) && (*offset == 0 || t.label.is_none()),
+                _ => false
+            })
+            .count() + index;
+
+        if end == index {
+            folded.push(tokens[index].clone());
+            index += 1;
+            continue;
+        }
+
+        let sequence:Vec<InstrTokens> = tokens[index..end].iter().filter_map(|token| token.try_get_instr_tokens().ok()).collect();
+        let mut instrs = fold_sequence(&sequence);
+        let label = &sequence[0].label;
+        if instrs.len() < sequence.len() && (label.is_none() || !instrs.is_empty()) && flags_are_dead(&tokens[end..]) {
+            if let Some(first) = instrs.first_mut() {
+                first.label = label.clone();
+            }
+        } else {
+            instrs = sequence;
+        }
+
+        folded.extend(instrs.into_iter().map(FileTokens::InstrTokens));
+        index = end;
+    }
+
+    folded
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::constant_folding::*;
+    use crate::process_source_into_tokens;
+    use crate::pseudo_substitution::substitute_pseudo_instrs;
+
+
+    /// Takes the source of a program and returns its instructions after folding, each as it would be written
+    fn fold(source:&str) -> Vec<String> {
+        let tokens = fold_constants(substitute_pseudo_instrs(process_source_into_tokens(source, None).unwrap()));
+        tokens.iter().filter_map(|token| token.try_get_instr_tokens().ok()).map(|t| {
+            let operands:Vec<String> = [t.operand_a, t.operand_b].into_iter().flatten()
+                .chain(t.immediate.map(|immediate| immediate.to_string()))
+                .collect();
+            format!("{}{} {}", t.label.map(|label| format!("{}: ", label)).unwrap_or_default(), t.opcode, operands.join(", ")).trim_end().to_owned()
+        }).collect()
+    }
+
+
+    #[test]
+    fn test_fold_sequence() {
+        // an address computed a nibble at a time collapses into a single load
+        let folded = fold("init:\n    ADDI $g0, $zero, 15\n    ADDI $g0, $g0, 15\n    ADDI $g0, $g0, 15\n    SUBI $g0, $g0, 3\n    OUT $g0, 1\n    HALT\n");
+        assert_eq!(folded, vec!["init: MOVLI $g0, 42", "MOVUI $g0, 0", "OUT $g0, 1", "HALT"]);
+
+        let folded = fold("init:\n    MOVLI $g1, 0xFF\n    MOVUI $g1, 0xFF\n    SUBI $g1, $g1, 3\n    ADDI $g1, $g1, 1\n    HALT\n");
+        assert_eq!(folded, vec!["init: SUBI $g1, $zero, 3", "HALT"]);
+
+        // the upper byte of $g2 is not known, so only its lower byte is loaded before it is read
+        let folded = fold("init:\n    MOVLI $g2, 1\n    MOVLI $g2, 2\n    ADDI $g3, $g2, 1\n    ADDI $g4, $zero, 1\n    ADDI $g4, $g4, 1\n    HALT\n");
+        assert_eq!(folded, vec!["init: MOVLI $g2, 2", "ADDI $g3, $g2, 1", "ADDI $g4, $zero, 2", "HALT"]);
+    }
+
+
+    #[test]
+    fn test_sequences_which_are_not_folded() {
+        // the branch reads the flags the sequence leaves
+        let source = "init:\n    ADDI $g0, $zero, 1\n    ADDI $g0, $g0, 1\n    BEQ $g8, $g9, @init\n    HALT\n";
+        assert_eq!(&fold(source)[..2], ["init: ADDI $g0, $zero, 1", "ADDI $g0, $g0, 1"]);
+
+        // a label in the middle can be jumped to, so the sequences either side of it are folded on their own
+        let source = "init:\n    ADDI $g0, $zero, 1\n    ADDI $g0, $g0, 1\nmid:\n    ADDI $g0, $g0, 1\n    ADDI $g0, $g0, 1\n    HALT\n";
+        assert_eq!(fold(source), vec!["init: ADDI $g0, $zero, 2", "mid: ADDI $g0, $g0, 1", "ADDI $g0, $g0, 1", "HALT"]);
+
+        // a sequence which is already as short as it can be is kept as it was written
+        let source = "init:\n    MOVLI $g5, 0x34\n    MOVUI $g5, 0x12\n    CMP $g5, $zero\n    HALT\n";
+        assert_eq!(&fold(source)[..2], ["init: MOVLI $g5, 52", "MOVUI $g5, 18"]);
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use crate::errors::AsmValidationError;
+use crate::expressions::{evaluate_int_expression, is_int_expression};
+use crate::immediate_parts::PART_OPERATORS;
+use crate::isa::{Isa, OperandKind};
+use crate::validation::{remove_label, validate_int_immediate, validate_label};
+
+
+/// Takes a line of assembly and returns true if it defines a named constant, such as `UART_PORT: .equ 3` or
+/// `BUFFER_SIZE: .const 64`
+pub fn is_constant_definition(line:&str) -> bool {
+    let instr = remove_label(line);
+    instr.starts_with(".equ ") || instr.starts_with(".const ")
+}
+
+
+/// Takes a line of assembly defining a named constant in the form `<name>: .equ <value>`, where `.const` is
+/// the same as `.equ`, and returns the name and value of the constant, or an `AsmValidationError` if the
+/// name or value is invalid. The value can be any integer that fits into 32 bits.
+pub fn parse_constant_definition(line:&str) -> Result<(String, i64), AsmValidationError> {
+    let name = match line.find(':') {
+        Some(index) => line[..index].trim(),
+        None => {
+            return Err(AsmValidationError(format!("The constant on line {} must have a name, such as NAME: .equ 5", line)));
+        }
+    };
+
+    validate_label(line, name)?;
+    let tokens:Vec<&str> = remove_label(line).split_whitespace().collect();
+    if tokens.len() != 2 {
+        return Err(AsmValidationError(format!("Incorrect format for constant on line {}", line)));
+    }
+
+    let value = validate_int_immediate(tokens[1], 32, true)?;
+    Ok((name.to_owned(), value))
+}
+
+
+/// Takes an operand and returns true if it is written as the name of a constant rather than as a number
+pub fn is_constant_name(operand:&str) -> bool {
+    operand.starts_with(|c:char| c.is_alphabetic() || c == '_') && operand.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+
+/// Takes a line of assembly and, if it is an instruction with any immediate operand given as a named
+/// constant or an expression, such as `BUFFER_SIZE-1`, returns the line with each replaced by its value so it
+/// can be validated and tokenized as a normal immediate. Will return an `AsmValidationError` if a constant has
+/// not been defined or an expression is not valid, or if the value cannot fit into the immediate field of
+/// the operand in the ISA table, such as the 4-bit port of `IN` and `OUT` or the 8-bit immediate of `MOVLI`.
+pub fn substitute_constants(line:&str, constants:&HashMap<String, i64>) -> Result<String, AsmValidationError> {
+    let instr = remove_label(line);
+    let opcode = instr.split_whitespace().next().unwrap_or("");
+    let spec = match Isa::lookup(opcode) {
+        // a part operator is left to `immediate_parts`, as its brackets and comma are not an expression
+        Some(spec) if !PART_OPERATORS.iter().any(|operator| line.contains(operator)) => spec,
+        _ => return Ok(line.to_owned())
+    };
+
+    let operands_start = line.find(instr).unwrap_or(0) + opcode.len();
+    let operands_end = line.find(';').unwrap_or(line.len()).max(operands_start);
+    let operands = line[operands_start..operands_end].split(',').enumerate().map(|(index, piece)| {
+        let operand = piece.trim();
+        let bits = match spec.encoded_form().get(index) {
+            Some(OperandKind::Immediate(bits)) if is_constant_name(operand) || is_int_expression(operand) => *bits,
+            _ => return Ok(piece.to_owned())
+        };
+
+        let (kind, value) = match constants.get(operand) {
+            Some(value) => ("constant", *value),
+            None if is_int_expression(operand) => ("expression", evaluate_int_expression(operand, constants)
+                .map_err(|e| AsmValidationError(format!("{} on line {}", e.0, line)))?),
+            None => {
+                return Err(AsmValidationError(format!("{} on line {} is not a defined constant", operand, line)));
+            }
+        };
+
+        if !(0..1 << bits).contains(&value) {
+            return Err(AsmValidationError(format!(
+                "The {} {} = {} on line {} cannot fit into the {}-bit immediate of {}", kind, operand, value, line, bits, opcode
+            )));
+        }
+
+        Ok(piece.replacen(operand, &value.to_string(), 1))
+    }).collect::<Result<Vec<String>, AsmValidationError>>()?;
+
+    Ok(format!("{}{}{}", &line[..operands_start], operands.join(","), &line[operands_end..]))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::constants::*;
+    use crate::process_source_into_tokens;
+    use crate::testing::tokens_from_str;
+
+
+    #[test]
+    fn test_parse_constant_definition() {
+        assert!(is_constant_definition("UART_PORT: .equ 3"));
+        assert!(!is_constant_definition("port: .int 3"));
+
+        let (name, value) = parse_constant_definition("UART_PORT: .equ 0x3").unwrap();
+        assert_eq!(name, "UART_PORT");
+        assert_eq!(value, 3);
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_constant_without_name() {
+        parse_constant_definition(".equ 3").unwrap();
+    }
+
+
+    #[test]
+    fn test_substitute_port_constant() {
+        let constants = HashMap::from([("UART_PORT".to_owned(), 3)]);
+        assert_eq!(substitute_constants("OUT $g0, UART_PORT", &constants).unwrap(), "OUT $g0, 3");
+        assert_eq!(substitute_constants("read: IN $g1, UART_PORT ; get a byte", &constants).unwrap(), "read: IN $g1, 3 ; get a byte");
+        assert_eq!(substitute_constants("OUT $g0, UART_PORT ; UART_PORT", &constants).unwrap(), "OUT $g0, 3 ; UART_PORT");
+        assert_eq!(substitute_constants("OUT $g0, 2", &constants).unwrap(), "OUT $g0, 2");
+        assert_eq!(substitute_constants("ADD $g0, $g1, $g2", &constants).unwrap(), "ADD $g0, $g1, $g2");
+    }
+
+
+    #[test]
+    fn test_substitute_immediate_constants() {
+        assert!(is_constant_definition("BUFFER_SIZE: .const 64"));
+        assert_eq!(parse_constant_definition("BUFFER_SIZE: .const 64").unwrap(), ("BUFFER_SIZE".to_owned(), 64));
+
+        let constants = HashMap::from([("BUFFER_SIZE".to_owned(), 64), ("STEP".to_owned(), 4), ("NEG".to_owned(), -1)]);
+        assert_eq!(substitute_constants("MOVLI $g1, BUFFER_SIZE", &constants).unwrap(), "MOVLI $g1, 64");
+        assert_eq!(substitute_constants("ADDI $g0, $zero, STEP", &constants).unwrap(), "ADDI $g0, $zero, 4");
+        assert_eq!(substitute_constants("syscall STEP", &constants).unwrap(), "syscall 4");
+        assert_eq!(substitute_constants("MOVUI $g0, IMM(BUFFER_SIZE, 1)", &constants).unwrap(), "MOVUI $g0, IMM(BUFFER_SIZE, 1)");
+
+        // the range is checked against the value rather than the name
+        assert!(substitute_constants("ADDI $g0, $zero, BUFFER_SIZE", &constants).unwrap_err().0.contains("4-bit immediate of ADDI"));
+        assert!(substitute_constants("MOVLI $g0, NEG", &constants).is_err());
+
+        let tokens = process_source_into_tokens("BUFFER_SIZE: .equ 64\ninit:\n    MOVLI $g1, BUFFER_SIZE\n    HALT\n", None).unwrap();
+        assert_eq!(tokens[0].try_get_instr_tokens().unwrap().immediate, Some(64));
+    }
+
+
+    #[test]
+    fn test_substitute_expressions() {
+        let constants = HashMap::from([("BUFFER_SIZE".to_owned(), 64)]);
+        assert_eq!(substitute_constants("ADDI $g0, $zero, (3*4)+1", &constants).unwrap(), "ADDI $g0, $zero, 13");
+        assert_eq!(substitute_constants("MOVLI $g0, BUFFER_SIZE-1 ; last", &constants).unwrap(), "MOVLI $g0, 63 ; last");
+
+        // the range is checked against the value the expression folds to
+        assert_eq!(substitute_constants("ADDI $g0, $zero, 4*4", &constants).unwrap_err().0,
+            "The expression 4*4 = 16 on line ADDI $g0, $zero, 4*4 cannot fit into the 4-bit immediate of ADDI");
+        assert!(substitute_constants("MOVLI $g0, 1-BUFFER_SIZE", &constants).is_err());
+        assert!(substitute_constants("MOVLI $g0, SIZE-1", &constants).unwrap_err().0.contains("SIZE is not a defined constant"));
+
+        let tokens = process_source_into_tokens("SIZE: .equ 64\ninit:\n    MOVLI $g1, SIZE*2-1\n    OUT $g1, (SIZE/16)\n    HALT\n\
+            data:\n    value: .int (2+3)*4\n", None).unwrap();
+        assert_eq!(tokens[0].try_get_instr_tokens().unwrap().immediate, Some(127));
+        assert_eq!(tokens[1].try_get_instr_tokens().unwrap().immediate, Some(4));
+        assert_eq!(tokens[3].try_get_data_tokens().unwrap().bytes, vec![20]);
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_undefined_port_constant() {
+        substitute_constants("OUT $g0, UART_PORT", &HashMap::new()).unwrap();
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_port_constant_too_large() {
+        let constants = HashMap::from([("BIG_PORT".to_owned(), 16)]);
+        substitute_constants("OUT $g0, BIG_PORT", &constants).unwrap();
+    }
+
+
+    #[test]
+    fn test_port_constants_in_source() {
+        let tokens = tokens_from_str("UART_PORT: .equ 3\nVGA_PORT: .equ 0x0A\n\ninit:\n    IN $g0, UART_PORT\n    OUT $g0, VGA_PORT\n    OUT $g1, 2\n    HALT\n");
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(tokens[0].try_get_instr_tokens().unwrap().immediate, Some(3));
+        assert_eq!(tokens[1].try_get_instr_tokens().unwrap().immediate, Some(0x0A));
+        assert_eq!(tokens[2].try_get_instr_tokens().unwrap().immediate, Some(2));
+    }
+}
@@ -0,0 +1,59 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use sha2::{Digest, Sha256};
+
+
+/// The number of hex digits of the SHA-256 of a binary used in its content-addressed name, which is plenty
+/// to tell apart every build a boot server will ever hold
+pub const HASH_PREFIX_LENGTH:usize = 16;
+
+
+/// Takes the bytes of a program binary and returns the first `HASH_PREFIX_LENGTH` lowercase hex digits of
+/// their SHA-256.
+pub fn content_hash_prefix(binary:&[u8]) -> String {
+    Sha256::digest(binary).iter().map(|byte| format!("{:02x}", byte)).collect::<String>()[..HASH_PREFIX_LENGTH].to_owned()
+}
+
+
+/// Takes the filename of a program binary and its bytes, and returns the filename it has when named by its
+/// contents, which puts the hash prefix after the name in the same directory and keeps the extension, so
+/// `build/prog.ird` becomes `build/prog-<hash prefix>.ird`.
+pub fn content_addressed_name(filename:&str, binary:&[u8]) -> String {
+    let path = Path::new(filename);
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    let name = match path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, content_hash_prefix(binary), extension.to_string_lossy()),
+        None => format!("{}-{}", stem, content_hash_prefix(binary))
+    };
+
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+
+/// Takes the filename of a program binary which has been written, and renames it to its content-addressed
+/// name from `content_addressed_name`, replacing any file already there, which will hold the same bytes.
+/// Returns the new filename.
+pub fn rename_to_content_name(filename:&str) -> Result<String, Box<dyn Error>> {
+    let hashed_name = content_addressed_name(filename, &fs::read(filename)?);
+    fs::rename(filename, &hashed_name)?;
+    Ok(hashed_name)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::content_name::*;
+
+
+    #[test]
+    fn test_content_addressed_name() {
+        // the SHA-256 of the empty string starts e3b0c44298fc1c14
+        assert_eq!(content_hash_prefix(b""), "e3b0c44298fc1c14");
+        assert_eq!(content_addressed_name("prog.ird", b""), "prog-e3b0c44298fc1c14.ird");
+        assert_eq!(content_addressed_name("build/prog.ird", b""), "build/prog-e3b0c44298fc1c14.ird");
+        assert_eq!(content_addressed_name("prog", b""), "prog-e3b0c44298fc1c14");
+        assert_ne!(content_addressed_name("prog.ird", b"HALT"), content_addressed_name("prog.ird", b""));
+    }
+
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use crate::errors::AsmValidationError;
+use crate::token_generator::generate_instr_tokens;
+use crate::token_types::{FileTokens, SourcePos};
+
+
+/// The I/O port every coverage beacon writes the number of its block to
+pub const COVERAGE_PORT:u8 = 0xF;
+
+
+/// Represents a basic block of code which has been instrumented for coverage, which starts at a label and
+/// is given a number that its beacon writes to `COVERAGE_PORT` every time it runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageBlock {
+    pub id: u16,
+    pub label: String,
+    pub pos: Option<SourcePos>
+}
+
+
+/// Takes the number of a block and returns the lines of its beacon, which writes the number to
+/// `COVERAGE_PORT` and keeps the value of `$g9` by holding it on the stack while the number is written.
+fn beacon(id:u16) -> Vec<String> {
+    vec![
+        "SUBI $sp, $sp, 1".to_owned(),
+        "STORE $g9, $sp, $zero".to_owned(),
+        format!("MOVLI $g9, {:#04X}", id & 0xFF),
+        format!("MOVUI $g9, {:#04X}", id >> 8),
+        format!("OUT $g9, {}", COVERAGE_PORT),
+        "LOAD $g9, $sp, $zero".to_owned(),
+        "ADDI $sp, $sp, 1".to_owned()
+    ]
+}
+
+
+/// Takes a stream of tokens before pseudo-instructions are substituted and inserts a beacon at the start of
+/// every basic block of code, which is every labelled instruction, and returns the instrumented tokens and
+/// the blocks in the order they were numbered. The label moves to the first word of the beacon, so every
+/// jump or branch to the block runs it. Running the program in an emulator which records the words written
+/// to `COVERAGE_PORT` then gives the blocks that ran, which the mapping written by `write_coverage_map`
+/// ties back to the source.
+///
+/// The beacon keeps the value of every register, but not the flags, and needs one word of stack. Will
+/// return an `AsmValidationError` if there are more blocks than can be numbered in 16 bits.
+pub fn instrument_coverage(tokens_stream:Vec<FileTokens>) -> Result<(Vec<FileTokens>, Vec<CoverageBlock>), AsmValidationError> {
+    let mut instrumented:Vec<FileTokens> = Vec::with_capacity(tokens_stream.len());
+    let mut blocks:Vec<CoverageBlock> = Vec::new();
+    for tokens in tokens_stream {
+        let mut t = match tokens {
+            FileTokens::InstrTokens(t) if t.label.is_some() => t,
+            tokens => {
+                instrumented.push(tokens);
+                continue;
+            }
+        };
+
+        let id = u16::try_from(blocks.len())
+            .map_err(|_| AsmValidationError("The program has more than 65536 blocks to instrument for coverage".to_owned()))?;
+        let mut label = t.label.take();
+        blocks.push(CoverageBlock { id, label: label.clone().unwrap(), pos: t.pos.clone() });
+        for line in beacon(id) {
+            let beacon_tokens = generate_instr_tokens(&line, label.take()).map_err(|e| AsmValidationError(e.0))?;
+            instrumented.push(FileTokens::InstrTokens(beacon_tokens.with_pos(t.pos.clone())));
+        }
+
+        instrumented.push(FileTokens::InstrTokens(t));
+    }
+
+    Ok((instrumented, blocks))
+}
+
+
+/// Takes the blocks instrumented for coverage and the label table of the program, and returns the mapping
+/// of each block, one per line, as the number its beacon writes, the address it starts at, its label, and
+/// the position of its first instruction in the source.
+pub fn render_coverage_map(blocks:&[CoverageBlock], label_table:&HashMap<String, i64>) -> String {
+    blocks.iter()
+        .map(|block| {
+            let pos = block.pos.as_ref().map(|pos| pos.to_string()).unwrap_or_else(|| "-".to_owned());
+            format!("{:04X} {:08X} {} {}\n", block.id, label_table[&block.label], block.label, pos)
+        })
+        .collect()
+}
+
+
+/// Takes the blocks instrumented for coverage and the label table of the program, and writes the mapping
+/// given by `render_coverage_map` to the given file.
+pub fn write_coverage_map(filename:&str, blocks:&[CoverageBlock], label_table:&HashMap<String, i64>) -> Result<(), Box<dyn Error>> {
+    let mut coverage_file = BufWriter::new(
+        OpenOptions::new().create(true).write(true).truncate(true).open(filename)?);
+
+    write!(coverage_file, "{}", render_coverage_map(blocks, label_table))?;
+    coverage_file.flush()?;
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::coverage::*;
+    use crate::label_table::generate_label_table;
+    use crate::token_types::InstrTokens;
+    use crate::{assemble_tokens, process_source_into_tokens};
+
+
+    const SOURCE:&str = "init:\n    ADDI $g0, $zero, 3\nloop:\n    SUBI $g0, $g0, 1\n    CMP $g0, $zero\n    BNE $g8, $g9, @loop\n    HALT\n";
+
+
+    /// Takes the tokens of an instrumented program and returns the instructions which are not part of a
+    /// beacon
+    fn without_beacons(tokens:&[FileTokens]) -> Vec<InstrTokens> {
+        let instrs:Vec<InstrTokens> = tokens.iter().filter_map(|token| token.try_get_instr_tokens().ok()).collect();
+        let mut kept:Vec<InstrTokens> = Vec::new();
+        let mut index = 0;
+        while index < instrs.len() {
+            if instrs[index].opcode == "SUBI" && instrs.get(index + 4).is_some_and(|t| t.opcode == "OUT") {
+                index += beacon(0).len();
+            } else {
+                kept.push(instrs[index].clone());
+                index += 1;
+            }
+        }
+
+        kept
+    }
+
+
+    #[test]
+    fn test_instrument_coverage() {
+        let tokens = process_source_into_tokens(SOURCE, None).unwrap();
+        let (instrumented, blocks) = instrument_coverage(tokens.clone()).unwrap();
+        let labels:Vec<(u16, &str, usize)> = blocks.iter().map(|block| (block.id, block.label.as_str(), block.pos.as_ref().unwrap().line)).collect();
+        assert_eq!(labels, vec![(0, "init", 2), (1, "loop", 4)]);
+
+        let instrs:Vec<InstrTokens> = instrumented.iter().filter_map(|token| token.try_get_instr_tokens().ok()).collect();
+        assert_eq!(instrs[8].label.as_deref(), Some("loop"));
+        assert_eq!((instrs[10].opcode.as_str(), instrs[10].immediate), ("MOVLI", Some(1)));
+        assert_eq!((instrs[12].opcode.as_str(), instrs[12].immediate), ("OUT", Some(COVERAGE_PORT as u64)));
+        assert_eq!(instrs[15].label, None);
+
+        let original:Vec<String> = tokens.iter().filter_map(|token| token.try_get_instr_tokens().ok()).map(|t| t.opcode).collect();
+        let kept:Vec<String> = without_beacons(&instrumented).into_iter().map(|t| t.opcode).collect();
+        assert_eq!(kept, original);
+        assert!(assemble_tokens(instrumented).is_ok());
+    }
+
+
+    #[test]
+    fn test_render_coverage_map() {
+        let (tokens, blocks) = instrument_coverage(process_source_into_tokens(SOURCE, None).unwrap()).unwrap();
+        let map = render_coverage_map(&blocks, &generate_label_table(&tokens).unwrap());
+        assert_eq!(map, "0000 00000000 init <source>:2:5\n0001 00000008 loop <source>:4:5\n");
+    }
+}
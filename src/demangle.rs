@@ -0,0 +1,215 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{ErrorKind, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use crate::errors::DemangleError;
+
+
+/// The prefix of every label mangled in the built-in scheme
+pub const MANGLED_PREFIX:&str = "_Z";
+
+
+/// The letters which stand for the type of each parameter of a label mangled in the built-in scheme, and the
+/// names of the types they are demangled into
+const PARAM_TYPES:[(char, &str); 8] = [
+    ('i', "int"), ('l', "long"), ('h', "half"), ('f', "float"), ('c', "char"), ('b', "byte"), ('t', "text"), ('p', "ptr")
+];
+
+
+/// Represents how labels are turned from the mangled form a compiler gives them back into readable names,
+/// which is done for the labels shown in listings, map files, diagnostics, dumps, and disassemblies
+#[derive(Debug, Clone)]
+pub enum Demangler {
+    /// The built-in scheme described for `demangle_builtin`
+    Builtin,
+    /// An external program, given as its command line, which reads one label on each line of stdin and
+    /// writes each back on its own line of stdout, demangled or as it was, in the same way as `c++filt`
+    Command(String),
+    /// A function given by a tool which uses the assembler as a library, which returns the readable name of a
+    /// label, or `None` if it is not mangled
+    Function(fn(&str) -> Option<String>)
+}
+
+
+impl Demangler {
+    /// Takes some labels and returns the readable name of each which is mangled, keyed by the label. Will
+    /// return a `DemangleError` if an external demangler cannot be run, fails, or does not write one line for
+    /// each label.
+    pub fn demangle_names<'a, I:IntoIterator<Item = &'a str>>(&self, labels:I) -> Result<HashMap<String, String>, DemangleError> {
+        let labels:Vec<&str> = labels.into_iter().collect::<HashSet<&str>>().into_iter().collect();
+        let demangled:Vec<Option<String>> = match self {
+            Demangler::Builtin => labels.iter().map(|label| demangle_builtin(label)).collect(),
+            Demangler::Function(demangle) => labels.iter().map(|label| demangle(label)).collect(),
+            Demangler::Command(command) => run_demangler(command, &labels)?.into_iter().map(Some).collect()
+        };
+
+        Ok(labels.into_iter().zip(demangled)
+            .filter_map(|(label, name)| name.filter(|name| name != label).map(|name| (label.to_owned(), name)))
+            .collect())
+    }
+
+
+    /// Takes some text, such as the message of an error, and returns it with every word which is a mangled
+    /// label replaced by its readable name.
+    pub fn demangle_text(&self, text:&str) -> Result<String, DemangleError> {
+        let words:Vec<&str> = text.split(|c:char| !is_label_char(c)).filter(|word| !word.is_empty()).collect();
+        Ok(replace_names(text, &self.demangle_names(words)?))
+    }
+}
+
+
+/// Takes a character and returns true if it can be part of a label
+fn is_label_char(c:char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+
+/// Takes a label and returns its readable name if it is mangled in the built-in scheme, which is `_Z`
+/// followed by the namespaces and name of a function separated by underscores, then an underscore and a
+/// letter for the type of each parameter, or `v` if it has none. The types are `i` for int, `l` for long,
+/// `h` for half, `f` for float, `c` for char, `b` for byte, `t` for text, and `p` for a pointer, so
+/// `_Zmath_sqrt_ii` is demangled into `math.sqrt(int, int)` and `_Zmain_v` into `main()`. Returns `None` if
+/// the label is not mangled in this scheme.
+pub fn demangle_builtin(label:&str) -> Option<String> {
+    let parts:Vec<&str> = label.strip_prefix(MANGLED_PREFIX)?.split('_').collect();
+    let (types, path) = parts.split_last()?;
+    if path.is_empty() || path.iter().any(|part| part.is_empty() || !part.chars().all(|c| c.is_alphanumeric())) {
+        return None;
+    }
+
+    let params:Vec<&str> = match *types {
+        "v" => Vec::new(),
+        "" => return None,
+        types => types.chars()
+            .map(|c| PARAM_TYPES.iter().find(|(letter, _)| *letter == c).map(|(_, name)| *name))
+            .collect::<Option<Vec<&str>>>()?
+    };
+
+    Some(format!("{}({})", path.join("."), params.join(", ")))
+}
+
+
+/// Takes the command line of an external demangler and some labels, and returns the line it writes back for
+/// each label.
+fn run_demangler(command:&str, labels:&[&str]) -> Result<Vec<String>, DemangleError> {
+    let mut words = command.split_whitespace();
+    let program = words.next().ok_or_else(|| DemangleError("no demangler command was given".to_owned()))?;
+    let mut child = Command::new(program).args(words)
+        .stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| DemangleError(format!("could not run {}: {}", command, e)))?;
+
+    // the labels are written from another thread while the output is read, as a demangler which writes each
+    // name as it reads the label would otherwise fill the output pipe and block before reading all the labels
+    let input:String = labels.iter().map(|label| format!("{}\n", label)).collect();
+    let mut stdin = child.stdin.take().unwrap();
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output().map_err(|e| DemangleError(format!("could not run {}: {}", command, e)))?;
+
+    // a demangler which exits without reading its input closes the pipe, which is reported by its status
+    match writer.join().expect("the thread writing the labels panicked") {
+        Err(e) if e.kind() != ErrorKind::BrokenPipe => {
+            return Err(DemangleError(format!("could not write the labels to {}: {}", command, e)));
+        },
+        _ => ()
+    }
+
+    if !output.status.success() {
+        return Err(DemangleError(format!("{} failed with {}", command, output.status)));
+    }
+
+    let names:Vec<String> = String::from_utf8_lossy(&output.stdout).lines().map(|line| line.trim().to_owned()).collect();
+    if names.len() != labels.len() {
+        return Err(DemangleError(format!("{} wrote {} lines for {} labels, when it should write one for each", command, names.len(), labels.len())));
+    }
+
+    Ok(names)
+}
+
+
+/// Takes some text and the readable names of mangled labels, and returns the text with every whole word
+/// which is one of the labels replaced by its name.
+pub fn replace_names(text:&str, names:&HashMap<String, String>) -> String {
+    if names.is_empty() {
+        return text.to_owned();
+    }
+
+    let mut replaced = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(is_label_char) {
+        replaced.push_str(&rest[..start]);
+        let end = rest[start..].find(|c:char| !is_label_char(c)).map_or(rest.len(), |length| start + length);
+        let word = &rest[start..end];
+        replaced.push_str(names.get(word).map_or(word, |name| name.as_str()));
+        rest = &rest[end..];
+    }
+
+    replaced.push_str(rest);
+    replaced
+}
+
+
+/// Takes a label table and the readable names of mangled labels, and returns the table with every mangled
+/// label renamed, so that a dump or disassembly shows the readable names.
+pub fn rename_labels(label_table:&HashMap<String, i64>, names:&HashMap<String, String>) -> HashMap<String, i64> {
+    label_table.iter().map(|(label, addr)| (names.get(label).unwrap_or(label).to_owned(), *addr)).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::demangle::*;
+
+
+    #[test]
+    fn test_demangle_builtin() {
+        assert_eq!(demangle_builtin("_Zmath_sqrt_ii").as_deref(), Some("math.sqrt(int, int)"));
+        assert_eq!(demangle_builtin("_Zmain_v").as_deref(), Some("main()"));
+        assert_eq!(demangle_builtin("_Zgfx_draw_line_pthc").as_deref(), Some("gfx.draw.line(ptr, text, half, char)"));
+        assert_eq!(demangle_builtin("math_sqrt_ii"), None);
+        assert_eq!(demangle_builtin("_Zsqrt"), None);
+        assert_eq!(demangle_builtin("_Zsqrt_iq"), None);
+        assert_eq!(demangle_builtin("_Zmath__i"), None);
+        assert_eq!(demangle_builtin("_Zsqrt_"), None);
+    }
+
+
+    #[test]
+    fn test_demangle_text() {
+        let names = Demangler::Builtin.demangle_names(["_Zmath_sqrt_ii", "loop"]).unwrap();
+        assert_eq!(names, HashMap::from([("_Zmath_sqrt_ii".to_owned(), "math.sqrt(int, int)".to_owned())]));
+        assert_eq!(replace_names("JAL @_Zmath_sqrt_ii, _Zmath_sqrt_iix", &names), "JAL @math.sqrt(int, int), _Zmath_sqrt_iix");
+
+        let error = "prog.asm:3:5: the label _Zmath_sqrt_ii is defined more than once";
+        assert_eq!(Demangler::Builtin.demangle_text(error).unwrap(), "prog.asm:3:5: the label math.sqrt(int, int) is defined more than once");
+
+        let upper = Demangler::Function(|label| label.strip_prefix("__").map(|name| name.to_uppercase()));
+        assert_eq!(upper.demangle_text("call __start now").unwrap(), "call START now");
+
+        let table = HashMap::from([("_Zmain_v".to_owned(), 0), ("end".to_owned(), 4)]);
+        let renamed = rename_labels(&table, &Demangler::Builtin.demangle_names(table.keys().map(|label| label.as_str())).unwrap());
+        assert_eq!(renamed, HashMap::from([("main()".to_owned(), 0), ("end".to_owned(), 4)]));
+    }
+
+
+    #[cfg(unix)]
+    #[test]
+    fn test_demangler_command() {
+        let names = Demangler::Command("tr a-z A-Z".to_owned()).demangle_names(["_Zmain_v"]).unwrap();
+        assert_eq!(names["_Zmain_v"], "_ZMAIN_V");
+        assert!(Demangler::Command("cat".to_owned()).demangle_names(["loop"]).unwrap().is_empty());
+        assert!(Demangler::Command("false".to_owned()).demangle_names(["loop"]).unwrap_err().0.contains("failed"));
+        assert!(Demangler::Command("no_such_demangler".to_owned()).demangle_names(["loop"]).is_err());
+    }
+
+
+    #[cfg(unix)]
+    #[test]
+    fn test_demangler_command_with_many_labels() {
+        // far more than a pipe holds, so the labels must be written while the names are read
+        let labels:Vec<String> = (0..20000).map(|index| format!("_Zlabel{}_v", index)).collect();
+        let names = Demangler::Command("tr a-z A-Z".to_owned()).demangle_names(labels.iter().map(|label| label.as_str())).unwrap();
+        assert_eq!(names.len(), labels.len());
+        assert_eq!(names["_Zlabel19999_v"], "_ZLABEL19999_V");
+    }
+}
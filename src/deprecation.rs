@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use crate::errors::AsmValidationError;
+use crate::token_types::{located, DeprecatedTokens, FileTokens, SourcePos};
+use crate::validation::{split_label_offset, validate_label};
+use crate::warnings::{AsmWarning, WarningKind, WarningSink};
+
+
+/// Takes a line of assembly and returns true if it marks a label as deprecated, such as
+/// `.deprecated old_func "use new_func"`
+pub fn is_deprecated_directive(line:&str) -> bool {
+    line.starts_with(".deprecated ") || line == ".deprecated"
+}
+
+
+/// Takes a line of assembly marking a label as deprecated in the form `.deprecated <label> ["<message>"]`, the
+/// section it is in, the namespace it is in, and its position in the source, and returns its tokens. The label
+/// is qualified by the namespace, as it is expected to be next to the label's definition. Will return an
+/// `AsmValidationError` if the label is missing or invalid, or the message is not in double quotes.
+pub fn parse_deprecated_directive(line:&str, mode:char, namespace:&Option<String>, pos:Option<SourcePos>)
+        -> Result<DeprecatedTokens, AsmValidationError> {
+    let malformed = || AsmValidationError(format!(
+        "Incorrect format for deprecation on line {}, which should be .deprecated <label> \"<message>\"", line
+    ));
+
+    let rest = line[".deprecated".len()..].trim();
+    let (label, message) = match rest.split_once(char::is_whitespace) {
+        Some((label, message)) => (label, Some(message.trim())),
+        None => (rest, None)
+    };
+
+    if label.is_empty() {
+        return Err(malformed());
+    }
+
+    let message = match message {
+        Some(message) => Some(message.strip_prefix('"').and_then(|message| message.strip_suffix('"')).ok_or_else(malformed)?),
+        None => None
+    };
+
+    validate_label(line, label)?;
+    let label = match namespace {
+        Some(namespace) => format!("{}.{}", namespace, label),
+        None => label.to_owned()
+    };
+
+    Ok(DeprecatedTokens::new(mode, label, message.map(|message| message.to_owned()), pos))
+}
+
+
+/// Takes a stream of tokens and the label table built from their labels, and returns an error for every
+/// label marked with `.deprecated` which is not in the table, so that a typo in the directive is not missed.
+pub fn check_deprecated_labels_exist(tokens_stream:&[FileTokens], label_table:&HashMap<String, i64>) -> Vec<String> {
+    tokens_stream.iter()
+        .filter_map(|tokens| match tokens {
+            FileTokens::DeprecatedTokens(t) if !label_table.contains_key(&t.label) => Some(format!(
+                "{}The deprecated label {} was not found", located(t.pos.as_ref()), t.label
+            )),
+            _ => None
+        })
+        .collect()
+}
+
+
+/// Takes a stream of tokens before pseudo-instructions are substituted, and gives a deprecated warning for
+/// every instruction or alias which refers to a label marked with `.deprecated`, with the message given in
+/// the directive. Each warning starts with the position of the reference, if it came from a source file.
+pub fn check_deprecated_references(tokens_stream:&[FileTokens], warnings:&mut WarningSink) {
+    let deprecated:HashMap<&str, &Option<String>> = tokens_stream.iter()
+        .filter_map(|tokens| match tokens {
+            FileTokens::DeprecatedTokens(t) => Some((t.label.as_str(), &t.message)),
+            _ => None
+        })
+        .collect();
+
+    if deprecated.is_empty() {
+        return;
+    }
+
+    for tokens in tokens_stream {
+        let (label, pos) = match tokens {
+            FileTokens::InstrTokens(t) => match t.op_label.as_deref().and_then(|label| label.rsplit('@').next()).map(|label| split_label_offset(label).0) {
+                Some(label) => (label, t.pos.as_ref()),
+                None => continue
+            },
+            FileTokens::AliasTokens(t) => (t.target.as_str(), t.pos.as_ref()),
+            _ => continue
+        };
+
+        let message = match deprecated.get(label) {
+            Some(Some(message)) => format!("{}{} is deprecated: {}", located(pos), label, message),
+            Some(None) => format!("{}{} is deprecated", located(pos), label),
+            None => continue
+        };
+
+        warnings.push(AsmWarning::new(WarningKind::Deprecated, message));
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::deprecation::*;
+    use crate::label_table::generate_label_table;
+    use crate::process_source_into_tokens;
+    use crate::warnings::WarningSettings;
+
+
+    #[test]
+    fn test_parse_deprecated_directive() {
+        assert!(is_deprecated_directive(".deprecated old_func \"use new_func\""));
+        assert!(!is_deprecated_directive("deprecated: .int 5"));
+
+        let tokens = parse_deprecated_directive(".deprecated old_func \"use new_func\"", 'c', &None, None).unwrap();
+        assert_eq!((tokens.label.as_str(), tokens.message.as_deref()), ("old_func", Some("use new_func")));
+
+        let tokens = parse_deprecated_directive(".deprecated write", 'c', &Some("uart".to_owned()), None).unwrap();
+        assert_eq!((tokens.label.as_str(), tokens.message), ("uart.write", None));
+
+        assert!(parse_deprecated_directive(".deprecated", 'c', &None, None).is_err());
+        assert!(parse_deprecated_directive(".deprecated old_func use new_func", 'c', &None, None).is_err());
+        assert!(parse_deprecated_directive(".deprecated 2old \"use new_func\"", 'c', &None, None).is_err());
+    }
+
+
+    #[test]
+    fn test_deprecated_references() {
+        let source = "init:\n    JAL $g8, $g9, @old_func\n    JAL $g8, $g9, @new_func\n    HALT\n\
+            .deprecated old_func \"use new_func\"\nold_func:\nnew_func:\n    JUMP $ra\n.set legacy, @old_func\n";
+        let tokens = process_source_into_tokens(source, None).unwrap();
+        let mut warnings = WarningSink::default();
+        check_deprecated_references(&tokens, &mut warnings);
+
+        let messages:Vec<&str> = warnings.enabled(&WarningSettings::default()).iter().map(|warning| warning.message.as_str()).collect();
+        assert_eq!(messages, vec![
+            "<source>:2:5: old_func is deprecated: use new_func",
+            "<source>:9:1: old_func is deprecated: use new_func"
+        ]);
+    }
+
+
+    #[test]
+    fn test_deprecated_label_not_found() {
+        let source = "init:\n    HALT\n.deprecated missing\n";
+        let error = generate_label_table(&process_source_into_tokens(source, None).unwrap()).unwrap_err().0;
+        assert_eq!(error, "<source>:3:1: The deprecated label missing was not found");
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::error::Error;
+use crate::errors::AddressDependenceError;
+use crate::generate_code::get_binary_from_tokens;
+use crate::label_table::{generate_label_table_with_page_size, generate_token_addresses_with_page_size};
+use crate::pseudo_substitution::substitute_labels;
+use crate::token_types::{located, FileTokens};
+
+
+/// The second base address the program is assembled at, which is added to the address of every label. It
+/// changes every byte of every address, so any word derived from an address is different at both bases.
+pub const AUDIT_BASE:i64 = 0x0101_0101;
+
+
+/// Represents a token of the program whose encoding is different at the two base addresses, with its
+/// address at the first base and the words it was encoded into at each. If it was derived from a label
+/// operand, `label` is that label, and the difference is an expected relocation.
+#[derive(Debug, Clone)]
+pub struct EncodingDifference {
+    pub address: i64,
+    pub description: String,
+    pub label: Option<String>,
+    pub words: (Vec<u16>, Vec<u16>)
+}
+
+
+/// Takes a stream of tokens which has already had pseudo-instructions substituted and the number of words in
+/// a page, and assembles it both at address 0 and with every label moved by `AUDIT_BASE`, returning every token whose encoding differs
+/// between the two. Those derived from label operands are expected relocations, and any others are
+/// encodings which depend on the address of the program in some other way, so cannot be relocated by
+/// patching the label operands alone.
+///
+/// Will return an error if the program cannot be assembled, such as if it uses a label which does not
+/// exist.
+pub fn audit_determinism(tokens:&[FileTokens], page_size:i64) -> Result<Vec<EncodingDifference>, Box<dyn Error>> {
+    let label_table = generate_label_table_with_page_size(tokens, page_size)?;
+    let moved_table:HashMap<String, i64> = label_table.iter().map(|(label, addr)| (label.to_owned(), addr + AUDIT_BASE)).collect();
+    let at_zero = substitute_labels(tokens.to_vec(), &label_table)?;
+    let moved = substitute_labels(tokens.to_vec(), &moved_table)?;
+
+    let mut differences:Vec<EncodingDifference> = Vec::new();
+    for (((token, address), first), second) in tokens.iter().zip(generate_token_addresses_with_page_size(tokens, page_size)).zip(at_zero).zip(moved) {
+        let words = (get_binary_from_tokens(first)?, get_binary_from_tokens(second)?);
+        if words.0 == words.1 {
+            continue;
+        }
+
+        let (description, label) = match token {
+            FileTokens::InstrTokens(t) => (format!("{}{}", located(t.pos.as_ref()), t.opcode), t.op_label.clone()),
+            FileTokens::DataTokens(t) if !t.op_labels.is_empty() => (format!("{}data", located(t.pos.as_ref())), Some(t.op_labels.join(", "))),
+            _ => (format!("{}data", located(token.pos())), None)
+        };
+
+        differences.push(EncodingDifference { address, description, label, words });
+    }
+
+    Ok(differences)
+}
+
+
+/// Takes the differences found by `audit_determinism` and returns a report of them, with a line for each
+/// giving its address, where it came from, and its words at both bases, followed by a count of each kind.
+/// Will return an `AddressDependenceError` with the report if any difference is not a relocation.
+pub fn render_audit_report(differences:&[EncodingDifference]) -> Result<String, AddressDependenceError> {
+    let format_words = |words:&[u16]| words.iter().map(|word| format!("{:04X}", word)).collect::<Vec<String>>().join(" ");
+    let mut report = String::new();
+    for difference in differences {
+        let kind = match &difference.label {
+            Some(label) => format!("relocation of {}", label),
+            None => "UNEXPECTED".to_owned()
+        };
+
+        report.push_str(&format!("{:08X}  {}  {} -> {}  {}\n", difference.address, difference.description,
+            format_words(&difference.words.0), format_words(&difference.words.1), kind));
+    }
+
+    let unexpected = differences.iter().filter(|difference| difference.label.is_none()).count();
+    report.push_str(&format!("{} relocations, {} unexpected differences between base 0x00000000 and 0x{:08X}",
+        differences.len() - unexpected, unexpected, AUDIT_BASE));
+
+    if unexpected > 0 {
+        return Err(AddressDependenceError(report));
+    }
+
+    Ok(report)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::determinism_audit::*;
+    use crate::label_table::PAGE_SIZE;
+    use crate::pseudo_substitution::substitute_pseudo_instrs;
+    use crate::testing::{tokens_from_str, FIBONACCI_PROGRAM, SUBROUTINES_PROGRAM};
+    use crate::token_types::InstrTokens;
+
+
+    #[test]
+    fn test_audit_determinism() {
+        let tokens = substitute_pseudo_instrs(tokens_from_str(SUBROUTINES_PROGRAM));
+        let differences = audit_determinism(&tokens, PAGE_SIZE).unwrap();
+
+        // the three LOAD and STORE label operands load 16-bit addresses, and the three JAL load 32-bit ones
+        assert_eq!(differences.len(), 18);
+        assert!(differences.iter().all(|difference| difference.label.is_some()));
+        assert_eq!(differences[0].address, 0);
+        assert_eq!(differences[0].description, "<source>:2:5: MOVLI");
+        assert_eq!(differences[0].words, (vec![0xD900], vec![0xD901]));
+
+        let report = render_audit_report(&differences).unwrap();
+        assert!(report.starts_with("00000000  <source>:2:5: MOVLI  D900 -> D901  relocation of l@first\n"));
+        assert!(report.ends_with("18 relocations, 0 unexpected differences between base 0x00000000 and 0x01010101"));
+    }
+
+
+    #[test]
+    fn test_audit_other_programs() {
+        let tokens = substitute_pseudo_instrs(tokens_from_str(FIBONACCI_PROGRAM));
+        assert!(audit_determinism(&tokens, PAGE_SIZE).unwrap().iter().all(|difference| difference.label.is_some()));
+
+        let tokens = vec![FileTokens::InstrTokens(InstrTokens::new(None, "HALT".to_owned(), None, None, None, None, None))];
+        assert!(audit_determinism(&tokens, PAGE_SIZE).unwrap().is_empty());
+    }
+
+
+    #[test]
+    fn test_unexpected_difference() {
+        let difference = EncodingDifference { address: 4, description: "data".to_owned(), label: None, words: (vec![1], vec![2]) };
+        let err = render_audit_report(&[difference]).unwrap_err();
+        assert!(err.0.contains("00000004  data  0001 -> 0002  UNEXPECTED\n"));
+        assert!(err.0.ends_with("0 relocations, 1 unexpected differences between base 0x00000000 and 0x01010101"));
+    }
+}
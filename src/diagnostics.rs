@@ -0,0 +1,408 @@
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{self, IsTerminal};
+use crate::encoding::{decode_lines, read_lines, InputEncoding};
+use crate::errors::*;
+use crate::preprocessor::{normalize_line_endings, SourceLine};
+use crate::assemble_source;
+use crate::token_types::SourcePos;
+
+
+const RED:&str = "\x1b[1;31m";
+const BLUE:&str = "\x1b[1;34m";
+const BOLD:&str = "\x1b[1m";
+const RESET:&str = "\x1b[0m";
+
+
+/// Represents how errors and warnings are printed, chosen with `--diagnostics`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiagnosticFormat {
+    /// Each is rendered by `render_diagnostics` with the line of source it is about
+    #[default]
+    Human,
+
+    /// Each is printed as a JSON object on its own line by `render_json_diagnostics`, for editors and CI
+    Json
+}
+
+
+impl DiagnosticFormat {
+    /// Takes the name of a format as given to `--diagnostics` and returns it, or `None` if there is no such
+    /// format
+    pub fn from_name(name:&str) -> Option<DiagnosticFormat> {
+        match name {
+            "human" => Some(DiagnosticFormat::Human),
+            "json" => Some(DiagnosticFormat::Json),
+            _ => None
+        }
+    }
+}
+
+
+/// Represents whether a diagnostic stops the program from being assembled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning
+}
+
+
+impl Severity {
+    /// Gets the name of the severity as given in JSON diagnostics
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning"
+        }
+    }
+}
+
+
+/// Takes a string and returns it as a JSON string literal, with its quotes
+pub fn json_string(value:&str) -> String {
+    let mut escaped = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c)
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+
+/// Takes an error raised while assembling and returns the name of its kind in JSON diagnostics, such as
+/// `invalid-instruction`, in the same form as the names of warnings. An error of a kind the assembler does
+/// not raise itself has the kind `error`.
+pub fn error_kind(e:&(dyn Error + 'static)) -> &'static str {
+    if e.is::<AsmValidationError>() {
+        "invalid-instruction"
+    } else if e.is::<LabelNotFoundError>() {
+        "invalid-label"
+    } else if e.is::<TokenTypeError>() {
+        "invalid-token-type"
+    } else if e.is::<TokenGenerationError>() {
+        "token-generation"
+    } else if e.is::<EncodingError>() {
+        "encoding"
+    } else if e.is::<PartialAssemblyError>() {
+        "partial-assembly"
+    } else if e.is::<BinaryFormatError>() {
+        "binary-format"
+    } else if e.is::<MemoryFitError>() {
+        "memory-fit"
+    } else if e.is::<AddressDependenceError>() {
+        "address-dependence"
+    } else if e.is::<AssembleError>() {
+        "assemble"
+    } else if e.is::<ConfigError>() {
+        "config"
+    } else if e.is::<ArchiveError>() {
+        "archive"
+    } else if e.is::<ResourceLimitExceeded>() {
+        "resource-limit"
+    } else if e.is::<DeniedWarningsError>() {
+        "denied-warnings"
+    } else if e.is::<DemangleError>() {
+        "demangle"
+    } else if e.is::<TokenFormatError>() {
+        "token-format"
+    } else if e.is::<FrozenApiError>() {
+        "frozen-api"
+    } else if e.is::<io::Error>() {
+        "io"
+    } else {
+        "error"
+    }
+}
+
+
+/// Represents a single error from the message of an assembler error, with the position in the source it is
+/// about and its code from `ERROR_CODES` if it gives them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub pos: Option<SourcePos>,
+    pub code: Option<String>,
+    pub message: String
+}
+
+
+/// Takes a part of an error message and returns the position it gives if it is one in the form
+/// `prog.asm:12:5`
+fn parse_position(part:&str) -> Option<SourcePos> {
+    let mut fields = part.rsplitn(3, ':');
+    let column = fields.next()?.parse().ok()?;
+    let line = fields.next()?.parse().ok()?;
+    let file = fields.next()?.to_owned();
+    Some(SourcePos { file, line, column })
+}
+
+
+/// Takes the end of an error message and returns the error code it starts with and the rest of the message,
+/// if it starts with one in the form `[E0101] `
+fn parse_code(message:&str) -> Option<(String, String)> {
+    let (code, rest) = message.strip_prefix('[')?.split_once("] ")?;
+    let is_code = code.len() == 5 && code.starts_with('E') && code[1..].chars().all(|c| c.is_ascii_digit());
+    is_code.then(|| (code.to_owned(), rest.to_owned()))
+}
+
+
+/// Takes a single error from the message of an assembler error and returns it as a `Diagnostic`. An error
+/// which says where it is with a position such as `prog.asm:12:5: ` has the position taken out, along with
+/// anything before it such as the name of the kind of error, and an error code such as `[E0101] ` is taken
+/// out in the same way. Others are kept whole.
+pub fn parse_diagnostic(error:&str) -> Diagnostic {
+    let parts:Vec<&str> = error.split(": ").collect();
+    let position = parts.iter().enumerate().find_map(|(index, part)| {
+        parse_position(part).map(|pos| (pos, parts[index + 1..].join(": ")))
+    });
+
+    let (pos, message) = match position {
+        Some((pos, message)) => (Some(pos), message),
+        None => (None, error.to_owned())
+    };
+
+    let code = message.match_indices('[').find_map(|(index, _)| {
+        if index == 0 || message[..index].ends_with(": ") { parse_code(&message[index..]) } else { None }
+    });
+
+    match code {
+        Some((code, message)) => Diagnostic { pos, code: Some(code), message },
+        None => Diagnostic { pos, code: None, message }
+    }
+}
+
+
+/// Takes the message of an assembler error, which has one error on each line when several are found
+/// together, and returns the `Diagnostic` of each
+pub fn parse_diagnostics(message:&str) -> Vec<Diagnostic> {
+    message.split('\n').map(parse_diagnostic).collect()
+}
+
+
+/// Returns true if diagnostics written to stderr should be coloured, which is when it is a terminal, unless
+/// the `NO_COLOR` environment variable is set or `TERM` is `dumb`
+pub fn use_color() -> bool {
+    env::var_os("NO_COLOR").is_none() && env::var("TERM").map_or(true, |term| term != "dumb") && io::stderr().is_terminal()
+}
+
+
+/// Takes the text of a source line, the column a diagnostic gives, and its message, and returns the index of
+/// the character to underline from and how many to underline. A diagnostic about a whole statement points at
+/// the first word of it which the message names, such as a label operand, and the underline covers the
+/// operand or immediate at the column up to the next separator.
+fn underline_span(text:&str, column:usize, message:&str) -> (usize, usize) {
+    let source_line = SourceLine { line_num: 0, text: text.to_owned(), file: None };
+    let column = if column == source_line.column() { source_line.error_column(message) } else { column };
+
+    let chars:Vec<char> = text.chars().collect();
+    let start = (column - 1).min(chars.len());
+    let length = chars[start..].iter().take_while(|c| !c.is_whitespace() && **c != ',' && **c != ';').count();
+    (start, length.max(1))
+}
+
+
+/// Takes a diagnostic, the lines of its source file if they could be read, and whether to colour it, and
+/// returns it rendered in the style of rustc, with the source line it is about and the operand or immediate
+/// the error is about underlined beneath it.
+fn render_diagnostic(diagnostic:&Diagnostic, lines:Option<&Vec<String>>, color:bool) -> String {
+    let paint = |style:&str, text:&str| if color { format!("{}{}{}", style, text, RESET) } else { text.to_owned() };
+    let severity = match &diagnostic.code {
+        Some(code) => format!("error[{}]", code),
+        None => "error".to_owned()
+    };
+
+    let mut rendered = format!("{}{}\n", paint(RED, &severity), paint(BOLD, &format!(": {}", diagnostic.message)));
+    let pos = match &diagnostic.pos {
+        Some(pos) => pos,
+        None => return rendered
+    };
+
+    let width = pos.line.to_string().len();
+    rendered.push_str(&format!("{}{} {}\n", " ".repeat(width), paint(BLUE, "-->"), pos));
+
+    let text = match lines.and_then(|lines| lines.get(pos.line.wrapping_sub(1))) {
+        Some(text) => text,
+        None => return rendered
+    };
+
+    // the padding keeps any tabs of the line, so the carets line up however wide the terminal shows them
+    let (start, length) = underline_span(text, pos.column.max(1), &diagnostic.message);
+    let padding:String = text.chars().take(start).map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+    let gutter = paint(BLUE, &format!("{} |", " ".repeat(width)));
+    rendered.push_str(&format!("{}\n", gutter));
+    rendered.push_str(&format!("{} {}\n", paint(BLUE, &format!("{} |", pos.line)), text));
+    rendered.push_str(&format!("{} {}{}\n", gutter, padding, paint(RED, &"^".repeat(length))));
+    rendered
+}
+
+
+/// Takes the message of an assembler error and whether to colour it, and returns each of its errors
+/// rendered with the line of source it is about and the part of the line it is about underlined, separated
+/// by blank lines. Source files are read again to show their lines, so an error about a source which cannot
+/// be read, such as `<stdin>`, or which gives no position, is shown with only its message and position.
+pub fn render_diagnostics(message:&str, color:bool) -> String {
+    let mut sources:HashMap<String, Option<Vec<String>>> = HashMap::new();
+    let mut rendered:Vec<String> = Vec::new();
+    for diagnostic in parse_diagnostics(message) {
+        let lines = diagnostic.pos.as_ref().and_then(|pos| {
+            sources.entry(pos.file.to_owned()).or_insert_with(|| {
+                // a file which is not UTF-8 can only have been assembled as Latin-1, so it is shown as Latin-1
+                let lines = fs::read(&pos.file).ok().and_then(|source| read_lines(&source[..], usize::MAX).ok())?;
+                decode_lines(lines, InputEncoding::Auto).ok().map(normalize_line_endings)
+            }).as_ref()
+        });
+
+        rendered.push(render_diagnostic(&diagnostic, lines, color));
+    }
+
+    rendered.join("\n")
+}
+
+
+/// Takes the message of an error or warning, which has one on each line when several are found together,
+/// along with their severity and kind, and returns each as a JSON object on its own line, such as:
+///
+/// ```json
+/// {"file": "prog.asm", "line": 3, "column": 9, "severity": "error", "code": "E0201", "kind": "invalid-instruction", "message": "$g12 is not a valid register"}
+/// ```
+///
+/// Those which give no position have a file, line, and column of null, and those without a code from
+/// `ERROR_CODES` have a code of null.
+pub fn render_json_diagnostics(message:&str, severity:Severity, kind:&str) -> String {
+    parse_diagnostics(message).iter().map(|diagnostic| {
+        let (file, line, column) = match &diagnostic.pos {
+            Some(pos) => (json_string(&pos.file), pos.line.to_string(), pos.column.to_string()),
+            None => ("null".to_owned(), "null".to_owned(), "null".to_owned())
+        };
+
+        let code = diagnostic.code.as_deref().map(json_string).unwrap_or_else(|| "null".to_owned());
+        format!("{{\"file\": {}, \"line\": {}, \"column\": {}, \"severity\": \"{}\", \"code\": {}, \"kind\": {}, \"message\": {}}}\n",
+            file, line, column, severity.name(), code, json_string(kind), json_string(&diagnostic.message))
+    }).collect()
+}
+
+
+/// Takes an error code such as `E0101` and returns the explanation of it printed by `explain`, with its
+/// summary and description from `ERROR_CODES` followed by its example and the error the example gives,
+/// which is found by assembling it so that it always matches what the assembler does. Returns `None` if
+/// there is no such code.
+pub fn render_explanation(code:&str) -> Option<String> {
+    let error_code = find_error_code(code)?;
+    let example:String = error_code.example.lines().map(|line| format!("    {}\n", line)).collect();
+    let mut explanation = format!("{}: {}\n\n{}\n\nFor example:\n\n{}\n",
+        error_code.code, error_code.summary, error_code.description, example);
+
+    if let Err(e) = assemble_source(error_code.example) {
+        let lines:Vec<String> = error_code.example.lines().map(|line| line.to_owned()).collect();
+        let diagnostic = parse_diagnostics(&e.to_string()).into_iter().find(|diagnostic| diagnostic.code.as_deref() == Some(error_code.code))?;
+        explanation.push_str("gives:\n\n");
+        explanation.push_str(&render_diagnostic(&diagnostic, Some(&lines), false));
+    }
+
+    Some(explanation)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::diagnostics::*;
+
+
+    #[test]
+    fn test_parse_diagnostic() {
+        let diagnostic = parse_diagnostic("Found invalid instruction: prog.asm:12:7: \"BAD\" is not valid");
+        assert_eq!(diagnostic.pos, Some(SourcePos { file: "prog.asm".to_owned(), line: 12, column: 7 }));
+        assert_eq!(diagnostic.message, "\"BAD\" is not valid");
+        assert_eq!(parse_diagnostic("The label x was not found!"), Diagnostic { pos: None, code: None, message: "The label x was not found!".to_owned() });
+    }
+
+
+    #[test]
+    fn test_render_diagnostics() {
+        let rendered = render_diagnostics("Found invalid instruction: test_files/test_label_table_gen.asm:4:25: \
+            The label target was not found!", false);
+        assert_eq!(rendered, "error: The label target was not found!\n \
+            --> test_files/test_label_table_gen.asm:4:25\n  |\n\
+            4 |     LOAD $g5, $g8, $g9, @target\n  |                         ^^^^^^^\n");
+
+        // a diagnostic about a whole statement points at the operand its message names
+        let rendered = render_diagnostics("test_files/test_label_table_gen.asm:12:5: $g5 is not a valid register", false);
+        assert!(rendered.ends_with("12 |     CMP $g1, $g5\n   |              ^^^\n"));
+    }
+
+
+    #[test]
+    fn test_render_without_source() {
+        let rendered = render_diagnostics("<stdin>:3:5: BAD is not valid\nThe label x was not found!", false);
+        assert_eq!(rendered, "error: BAD is not valid\n --> <stdin>:3:5\n\nerror: The label x was not found!\n");
+
+        let rendered = render_diagnostics("<stdin>:3:5: BAD is not valid", true);
+        assert!(rendered.starts_with("\x1b[1;31merror\x1b[0m\x1b[1m: BAD is not valid\x1b[0m\n"));
+    }
+
+
+    #[test]
+    fn test_render_json_diagnostics() {
+        let e = AsmValidationError("prog.asm:3:9: $g12 is not a valid register\n<stdin>:1:1: \"BAD\" is not valid".to_owned());
+        let rendered = render_json_diagnostics(&e.to_string(), Severity::Error, error_kind(&e));
+        assert_eq!(rendered, "{\"file\": \"prog.asm\", \"line\": 3, \"column\": 9, \"severity\": \"error\", \"code\": null, \
+            \"kind\": \"invalid-instruction\", \"message\": \"$g12 is not a valid register\"}\n{\"file\": \"<stdin>\", \"line\": 1, \"column\": 1, \
+            \"severity\": \"error\", \"code\": null, \"kind\": \"invalid-instruction\", \"message\": \"\\\"BAD\\\" is not valid\"}\n");
+
+        let rendered = render_json_diagnostics("x is too big", Severity::Warning, "branch-pair");
+        assert_eq!(rendered, "{\"file\": null, \"line\": null, \"column\": null, \"severity\": \"warning\", \"code\": null, \
+            \"kind\": \"branch-pair\", \"message\": \"x is too big\"}\n");
+    }
+
+
+    #[test]
+    fn test_error_codes() {
+        let diagnostic = parse_diagnostic("Found invalid instruction: prog.asm:2:22: [E0101] Immediate 99 cannot fit into 4 bits");
+        assert_eq!(diagnostic.code.as_deref(), Some("E0101"));
+        assert_eq!(diagnostic.message, "Immediate 99 cannot fit into 4 bits");
+
+        let diagnostic = parse_diagnostic("Found invalid instruction: [E0201] $g12 is not a valid register");
+        assert_eq!((diagnostic.pos, diagnostic.code.as_deref(), diagnostic.message.as_str()), (None, Some("E0201"), "$g12 is not a valid register"));
+        assert_eq!(parse_diagnostic("[note] x").code, None);
+
+        let rendered = render_json_diagnostics("<stdin>:1:1: [E0301] MUL is not a valid opcode", Severity::Error, "invalid-instruction");
+        assert!(rendered.contains("\"code\": \"E0301\", \"kind\": \"invalid-instruction\", \"message\": \"MUL is not a valid opcode\""));
+        assert!(render_diagnostics("<stdin>:1:1: [E0301] MUL is not a valid opcode", false).starts_with("error[E0301]: MUL"));
+    }
+
+
+    #[test]
+    fn test_render_explanation() {
+        let explanation = render_explanation("e0101").unwrap();
+        assert!(explanation.starts_with("E0101: immediate out of range\n\nAn immediate is too large"));
+        assert!(explanation.ends_with("For example:\n\n    init:\n        ADDI $g0, $zero, 99\n        HALT\n\ngives:\n\n\
+            error[E0101]: Immediate 99 cannot fit into 4 bits\n --> <source>:2:22\n  |\n2 |     ADDI $g0, $zero, 99\n  |                      ^^\n"));
+        assert!(render_explanation("E9999").is_none());
+
+        // the example of every code must give that error, or its explanation would show none
+        for error_code in ERROR_CODES {
+            assert!(render_explanation(error_code.code).unwrap().contains(&format!("gives:\n\nerror[{}]: ", error_code.code)), "{}", error_code.code);
+        }
+    }
+
+
+    #[test]
+    fn test_error_kind() {
+        assert_eq!(error_kind(&LabelNotFoundError("x".to_owned())), "invalid-label");
+        assert_eq!(error_kind(&MemoryFitError("x".to_owned())), "memory-fit");
+        assert_eq!(error_kind(&io::Error::other("x")), "io");
+        assert_eq!(error_kind(&CmdArgsError("x".to_owned())), "error");
+        assert_eq!(DiagnosticFormat::from_name("json"), Some(DiagnosticFormat::Json));
+        assert_eq!(DiagnosticFormat::from_name("xml"), None);
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use crate::errors::BinaryFormatError;
+use crate::generate_code::{OPCODE_BINARIES, REGISTER_BINARIES};
+use crate::label_table::PAGE_SIZE;
+use crate::listing::describe_data_words;
+
+
+/// Represents the words of each section of a program binary, with the header and section markers removed
+#[derive(Debug, Clone, Default)]
+pub struct BinarySections {
+    pub code: Vec<u16>,
+    pub data: Vec<u16>,
+    pub text: Vec<u16>
+}
+
+
+/// Takes the bytes of a program binary written by `generate_binary` and returns the number of bytes in its
+/// header, which is 0 if it has no header. Returns a `BinaryFormatError` if the header is not terminated.
+pub fn header_length(bytes:&[u8]) -> Result<usize, BinaryFormatError> {
+    if !bytes.starts_with(b"info:") {
+        return Ok(0);
+    }
+
+    let index = match bytes.iter().position(|byte| *byte == 0) {
+        Some(end) => end + 1,
+        None => return Err(BinaryFormatError("the header is not terminated by a null byte".to_owned()))
+    };
+
+    // the header is padded so that the words after it are aligned
+    Ok(index + index % 2)
+}
+
+
+/// Takes the bytes of a program binary written by `generate_binary` and returns the number of words in the
+/// pages its sections were placed on, which is given by `page=<words>` in its header, or is `PAGE_SIZE` if
+/// the header does not give it or there is no header.
+pub fn header_page_size(bytes:&[u8]) -> Result<i64, BinaryFormatError> {
+    let header = String::from_utf8_lossy(&bytes[..header_length(bytes)?]);
+    match header.trim_end_matches('\0').split(' ').find_map(|field| field.strip_prefix("page=")) {
+        Some(page_size) => page_size.parse().ok().filter(|page_size| *page_size > 0)
+            .ok_or_else(|| BinaryFormatError(format!("the page size {} in the header is not a number of words", page_size))),
+        None => Ok(PAGE_SIZE)
+    }
+}
+
+
+/// Takes the bytes of a program binary written by `generate_binary` and splits it into the words of each
+/// section. The header is skipped if there is one, and the `data:` and `text:` markers are used to find
+/// where the data and text sections start. Returns a `BinaryFormatError` if the header is not terminated
+/// or the binary ends part way through a word.
+pub fn split_binary(bytes:&[u8]) -> Result<BinarySections, BinaryFormatError> {
+    let mut index = header_length(bytes)?;
+    let mut sections = BinarySections::default();
+    let mut mode = 'c';
+    while index < bytes.len() {
+        if mode == 'c' && bytes[index..].starts_with(b"data:\0") {
+            mode = 'd';
+            index += 6;
+            continue;
+        } else if mode != 't' && bytes[index..].starts_with(b"text:\0") {
+            mode = 't';
+            index += 6;
+            continue;
+        }
+
+        if index + 1 >= bytes.len() {
+            return Err(BinaryFormatError(format!("the binary ends part way through the word at byte {}", index)));
+        }
+
+        let word = bytes[index] as u16 | (bytes[index + 1] as u16) << 8;
+        match mode {
+            'c' => sections.code.push(word),
+            'd' => sections.data.push(word),
+            _ => sections.text.push(word)
+        }
+
+        index += 2;
+    }
+
+    Ok(sections)
+}
+
+
+/// Takes a word from the code section and returns the instruction it encodes as it would be written in
+/// assembly, or the word in hex if it is not a valid instruction.
+pub fn disassemble_instr(word:u16) -> String {
+    let register = |bits:u16| REGISTER_BINARIES.entries().find(|(_, value)| **value == bits & 0xF).unwrap().0;
+    let opcode_bits = if word & 0xF000 == 0xF000 { word & 0xFF00 } else { word & 0xF000 };
+    let opcode = match OPCODE_BINARIES.entries().find(|(_, value)| **value == opcode_bits || **value == word) {
+        Some((opcode, _)) => *opcode,
+        None => return format!("0x{:04X}", word)
+    };
+
+    match opcode {
+        "NOP" | "ATOM" | "HALT" if word == OPCODE_BINARIES[opcode] => opcode.to_owned(),
+        "NOP" | "ATOM" | "HALT" => format!("0x{:04X}", word),
+        "ADD" | "SUB" | "SLL" | "SRL" | "SRA" | "NAND" | "OR" | "LOAD" | "STORE" => {
+            format!("{} {}, {}, {}", opcode, register(word >> 8), register(word >> 4), register(word))
+        },
+        "ADDI" | "SUBI" => format!("{} {}, {}, {}", opcode, register(word >> 8), register(word >> 4), word & 0xF),
+        "MOVUI" | "MOVLI" => format!("{} {}, 0x{:02X}", opcode, register(word >> 8), word & 0xFF),
+        "IN" | "OUT" => format!("{} {}, {}", opcode, register(word >> 4), word & 0xF),
+        "syscall" => format!("syscall {}", word & 0xFF),
+        _ => format!("{} {}, {}", opcode, register(word >> 4), register(word))
+    }
+}
+
+
+/// Takes the words of the data or text section, the address the section starts at, the directive type and
+/// size of each labelled item, and whether the section holds text, and returns a description of every word.
+/// Words of an item whose type is known are decoded as that type, and text is shown as characters, while
+/// any other data is shown in hex.
+fn describe_section_words(words:&[u16], start:i64, data_types:&HashMap<i64, (String, usize)>, is_text:bool) -> Vec<String> {
+    let mut descriptions:Vec<String> = Vec::with_capacity(words.len());
+    while descriptions.len() < words.len() {
+        let index = descriptions.len();
+        match data_types.get(&(start + index as i64)) {
+            Some((category, size)) if *size > 0 && index + size <= words.len() => {
+                descriptions.append(&mut describe_data_words(category, &words[index..index + size]));
+            },
+            _ if is_text => {
+                descriptions.push(format!("{:?}", char::from_u32(words[index] as u32).unwrap_or(char::REPLACEMENT_CHARACTER)));
+            },
+            _ => descriptions.push(format!("0x{:04X}", words[index]))
+        }
+    }
+
+    descriptions
+}
+
+
+/// Takes the bytes of a program binary, along with the label table and data types from its map file if
+/// there is one, and returns a dump of the program in address order, with its sections on pages of the size
+/// given by `header_page_size`. Each line gives the address and value of a word, any label at that address,
+/// and the instruction or data item the word holds.
+pub fn render_dump(bytes:&[u8], label_table:&HashMap<String, i64>, data_types:&HashMap<i64, (String, usize)>) -> Result<String, BinaryFormatError> {
+    let sections = split_binary(bytes)?;
+    let page_size = header_page_size(bytes)?;
+    let data_start = (sections.code.len() as i64 / page_size + 1) * page_size;
+    let text_start = if sections.data.is_empty() {
+        data_start
+    } else {
+        ((data_start + sections.data.len() as i64) / page_size + 1) * page_size
+    };
+
+    let mut labels:HashMap<i64, Vec<&str>> = HashMap::new();
+    for (label, addr) in label_table {
+        labels.entry(*addr).or_default().push(label);
+    }
+
+    let code_descriptions:Vec<String> = sections.code.iter().map(|word| disassemble_instr(*word)).collect();
+    let regions = [
+        (&sections.code, 0, code_descriptions),
+        (&sections.data, data_start, describe_section_words(&sections.data, data_start, data_types, false)),
+        (&sections.text, text_start, describe_section_words(&sections.text, text_start, data_types, true))
+    ];
+
+    let mut lines:Vec<String> = vec![format!("{:<8}  {:<4}  {:<16} SOURCE", "ADDRESS", "WORD", "LABEL")];
+    for (words, start, descriptions) in regions {
+        for (index, (word, description)) in words.iter().zip(descriptions).enumerate() {
+            let addr = start + index as i64;
+            let label = match labels.get_mut(&addr) {
+                Some(names) => {
+                    names.sort();
+                    names.join(", ")
+                },
+                None => String::new()
+            };
+
+            lines.push(format!("{:08X}  {:04X}  {:<16} {}", addr, word, label, description));
+        }
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+
+/// Takes the bytes of a program binary and the label table from its map file, if there is one, and returns
+/// the code section as assembly, with one instruction per line and each label on the line before the
+/// instruction it marks. Words which are not valid instructions, such as those filling an `.org` gap, are
+/// shown in hex.
+pub fn render_disassembly(bytes:&[u8], label_table:&HashMap<String, i64>) -> Result<String, BinaryFormatError> {
+    let sections = split_binary(bytes)?;
+    let mut labels:Vec<(&i64, &String)> = label_table.iter().map(|(label, addr)| (addr, label)).collect();
+    labels.sort();
+
+    let mut lines:Vec<String> = Vec::new();
+    for (addr, word) in sections.code.iter().enumerate() {
+        for (_, label) in labels.iter().filter(|(label_addr, _)| **label_addr == addr as i64) {
+            lines.push(format!("{}:", label));
+        }
+
+        lines.push(format!("    {}", disassemble_instr(*word)));
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::dump::*;
+
+
+    #[test]
+    fn test_disassemble_instr() {
+        assert_eq!(disassemble_instr(0x1123), "ADD $g0, $g1, $g2");
+        assert_eq!(disassemble_instr(0x39AA), "ADDI $g8, $g9, 10");
+        assert_eq!(disassemble_instr(0xD6FF), "MOVLI $g5, 0xFF");
+        assert_eq!(disassemble_instr(0xF223), "JUMP $g1, $g2");
+        assert_eq!(disassemble_instr(0xFAAF), "OUT $g9, 15");
+        assert_eq!(disassemble_instr(0xFC13), "syscall 19");
+        assert_eq!(disassemble_instr(0xFFFF), "HALT");
+        assert_eq!(disassemble_instr(0x0001), "0x0001");
+        assert_eq!(disassemble_instr(0xFB00), "0xFB00");
+    }
+
+
+    #[test]
+    fn test_split_binary() {
+        let mut bytes = b"info:iridium_assembler\0\0".to_vec();
+        bytes.extend([0xFF, 0xFF]);
+        bytes.extend(b"data:\0");
+        bytes.extend([0x05, 0x00]);
+        bytes.extend(b"text:\0");
+        bytes.extend([0x48, 0x00, 0x00, 0x00]);
+
+        let sections = split_binary(&bytes).unwrap();
+        assert_eq!(sections.code, vec![0xFFFF]);
+        assert_eq!(sections.data, vec![0x0005]);
+        assert_eq!(sections.text, vec![0x0048, 0x0000]);
+    }
+
+
+    #[test]
+    fn test_render_dump_with_data_types() {
+        let mut bytes = vec![0xFF, 0xFF];
+        bytes.extend(b"data:\0");
+        bytes.extend([0xFF, 0xFF, 0xBE, 0x26, 0x80, 0x36, 0x07, 0x00]);
+        bytes.extend(b"text:\0");
+        bytes.extend([0x48, 0x00, 0x69, 0x00]);
+
+        let label_table = HashMap::from([("count".to_owned(), 0x1000), ("big".to_owned(), 0x1001), ("msg".to_owned(), 0x2000)]);
+        let data_types = HashMap::from([(0x1000, ("int".to_owned(), 1)), (0x1001, ("long".to_owned(), 2))]);
+        let dump = render_dump(&bytes, &label_table, &data_types).unwrap();
+        assert!(dump.contains("00000000  FFFF                   HALT\n"));
+        assert!(dump.contains("00001000  FFFF  count            .int -1\n"));
+        assert!(dump.contains("00001002  3680                   .long 650000000 (lower half)\n"));
+        assert!(dump.contains("00001003  0007                   0x0007\n"));
+        assert!(dump.contains("00002000  0048  msg              'H'\n"));
+
+        let dump = render_dump(&bytes, &HashMap::new(), &HashMap::new()).unwrap();
+        assert!(dump.contains("00001000  FFFF                   0xFFFF\n"));
+
+        let bytes = [&b"info:iridium_assembler page=256\0"[..], &bytes].concat();
+        assert_eq!(header_page_size(&bytes).unwrap(), 256);
+        let dump = render_dump(&bytes, &HashMap::new(), &HashMap::new()).unwrap();
+        assert!(dump.contains("00000100  FFFF                   0xFFFF\n"));
+        assert!(dump.contains("00000200  0048                   'H'\n"));
+        assert!(header_page_size(b"info:iridium_assembler page=0\0\0").is_err());
+    }
+
+
+    #[test]
+    fn test_render_disassembly() {
+        let binary = crate::assemble_source("init:\n    ADDI $g0, $zero, 5\nend:\n    HALT\n").unwrap();
+        let label_table = HashMap::from([("init".to_owned(), 0), ("end".to_owned(), 1)]);
+        assert_eq!(render_disassembly(&binary, &label_table).unwrap(), "init:\n    ADDI $g0, $zero, 5\nend:\n    HALT\n");
+        assert_eq!(render_disassembly(&binary, &HashMap::new()).unwrap(), "    ADDI $g0, $zero, 5\n    HALT\n");
+    }
+}
@@ -0,0 +1,125 @@
+use std::io::{self, BufRead};
+use crate::errors::AsmValidationError;
+
+
+/// Represents the encoding source files are read in, chosen with `--input-encoding`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputEncoding {
+    /// Every file must be UTF-8, which includes any file which is plain ASCII
+    #[default]
+    Utf8,
+
+    /// Every byte is a character of Latin-1 (ISO 8859-1), as written by older tools
+    Latin1,
+
+    /// Each file is read as UTF-8 if it is valid UTF-8, and as Latin-1 if it is not
+    Auto
+}
+
+
+impl InputEncoding {
+    /// Takes the name of an encoding as given to `--input-encoding` and returns it, or `None` if there is no
+    /// such encoding
+    pub fn from_name(name:&str) -> Option<InputEncoding> {
+        match name.to_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(InputEncoding::Utf8),
+            "latin1" | "latin-1" | "iso-8859-1" => Some(InputEncoding::Latin1),
+            "auto" => Some(InputEncoding::Auto),
+            _ => None
+        }
+    }
+}
+
+
+/// Takes a reader of a source file and returns no more than the given number of its lines as bytes, with
+/// their `\n` or `\r\n` endings removed in the same way as `BufRead::lines`, so that they can be decoded by
+/// `decode_lines` whatever encoding they are in.
+pub fn read_lines<R:BufRead>(reader:R, max_lines:usize) -> io::Result<Vec<Vec<u8>>> {
+    reader.split(b'\n').take(max_lines).map(|line| line.map(|mut line| {
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+
+        line
+    })).collect()
+}
+
+
+/// Takes the lines of a source file as read by `read_lines` and returns them as text, decoded in the given
+/// encoding. A file read as `Auto` is decoded as Latin-1 as a whole if any of its lines is not UTF-8, so
+/// that its lines are never decoded differently to each other. Will return an `AsmValidationError` starting
+/// with the line and column of the first character which is not UTF-8 if the file must be UTF-8 and is not.
+pub fn decode_lines(lines:Vec<Vec<u8>>, encoding:InputEncoding) -> Result<Vec<String>, AsmValidationError> {
+    let invalid = lines.iter().enumerate().find_map(|(index, line)| std::str::from_utf8(line).err().map(|e| (index, e.valid_up_to())));
+    match (invalid, encoding) {
+        (_, InputEncoding::Latin1) | (Some(_), InputEncoding::Auto) => Ok(lines.iter().map(|line| decode_latin1(line)).collect()),
+        (None, _) => Ok(lines.into_iter().map(|line| String::from_utf8(line).unwrap()).collect()),
+        (Some((index, valid_up_to)), InputEncoding::Utf8) => {
+            let column = String::from_utf8_lossy(&lines[index][..valid_up_to]).chars().count() + 1;
+            Err(AsmValidationError(format!(
+                "{}:{}: the file is not UTF-8, use --input-encoding latin1 to read it as Latin-1, or --input-encoding auto to read \
+                it as Latin-1 only if it is not UTF-8", index + 1, column
+            )))
+        }
+    }
+}
+
+
+/// Takes some bytes of Latin-1 text and returns the text, in which each byte is the character with the same
+/// code point
+fn decode_latin1(bytes:&[u8]) -> String {
+    bytes.iter().map(|byte| *byte as char).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::encoding::*;
+    use crate::lang::LanguageLevel;
+    use crate::limits::{ResourceBudget, ResourceLimits};
+    use crate::preprocessor::Configuration;
+    use crate::testing::tokens_from_str;
+    use crate::warnings::WarningSink;
+    use crate::{process_reader_into_tokens, process_reader_into_tokens_with_warnings};
+
+
+    /// Takes the bytes of a source file and returns its lines decoded in the given encoding
+    fn decode(source:&[u8], encoding:InputEncoding) -> Result<Vec<String>, AsmValidationError> {
+        decode_lines(read_lines(source, usize::MAX).unwrap(), encoding)
+    }
+
+
+    #[test]
+    fn test_decode_lines() {
+        let latin1 = b"caf\xE9 \xA9 2003\r\ninit:\n    HALT";
+        assert_eq!(decode(latin1, InputEncoding::Latin1).unwrap(), vec!["café © 2003", "init:", "    HALT"]);
+        assert_eq!(decode(latin1, InputEncoding::Auto).unwrap(), vec!["café © 2003", "init:", "    HALT"]);
+
+        let utf8 = "café\ninit:\n".as_bytes();
+        assert_eq!(decode(utf8, InputEncoding::Utf8).unwrap(), vec!["café", "init:"]);
+        assert_eq!(decode(utf8, InputEncoding::Auto).unwrap(), vec!["café", "init:"]);
+        assert_eq!(decode(utf8, InputEncoding::Latin1).unwrap(), vec!["cafÃ©", "init:"]);
+
+        let error = decode(b"init:\n    .text \xE9t\xE9\n", InputEncoding::Utf8).unwrap_err();
+        assert!(error.0.starts_with("2:11: the file is not UTF-8, use --input-encoding latin1"));
+
+        assert_eq!(InputEncoding::from_name("ISO-8859-1"), Some(InputEncoding::Latin1));
+        assert_eq!(InputEncoding::from_name("utf-16"), None);
+    }
+
+
+    #[test]
+    fn test_latin1_source() {
+        let source = "init:\n    HALT\ntext:\n    .include \"include/latin1.asm\"\n";
+        let tokenize = |encoding| process_reader_into_tokens_with_warnings(source.as_bytes(), "test_files/prog.asm",
+            &Configuration { encoding, ..Configuration::default() }, false, LanguageLevel::default(), &mut ResourceBudget::new(ResourceLimits::UNLIMITED), &mut WarningSink::default());
+
+        let expected = tokens_from_str("init:\n    HALT\ntext:\n    greeting: .text 6 \"grüß\"\n");
+        assert_eq!(format!("{:?}", tokenize(InputEncoding::Latin1).unwrap()), format!("{:?}", expected));
+        assert_eq!(format!("{:?}", tokenize(InputEncoding::Auto).unwrap()), format!("{:?}", expected));
+        assert!(tokenize(InputEncoding::Utf8).unwrap_err().0.starts_with("test_files/include/latin1.asm:1:22: the file is not UTF-8"));
+
+        let error = process_reader_into_tokens(&b"text:\n    msg: .text 4 \"\xE9t\xE9\"\n"[..], "legacy.asm", None, false).unwrap_err();
+        assert!(error.0.starts_with("legacy.asm:2:19: the file is not UTF-8"));
+    }
+}
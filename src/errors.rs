@@ -4,18 +4,35 @@ use std::error::Error;
 
 /// Used if the command line arguments supplied are incorrect
 #[derive(Debug, Clone)]
-pub struct CmdArgsError;
+pub struct CmdArgsError(pub String);
 impl Error for CmdArgsError {}
 
-/// Ensures that the `CmdArgsError` error type is displayed appropriately in the console when raised.
+/// Ensures that the `CmdArgsError` error type is displayed appropriately in the console when raised, 
+/// including a custom string to add to the error.
 impl fmt::Display for CmdArgsError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Incorrect number or format of command line instructions. Proper usage is 'iridium_assembler [source filename] [target_filename]'")
+        write!(f, "Incorrect command line arguments: {}\nRun 'iridium_assembler --help' to see how to use the assembler", self.0)
     }
 }
 
 
-/// Used if the assembly validator finds an instruction that is not valid, such as `ADDQ $z0, 80`
+/// Takes a prefix and a message holding one error on each line, and writes every line with the prefix
+/// before it, so that each of several errors found together is displayed as its own error.
+fn write_each_line(f:&mut fmt::Formatter, prefix:&str, message:&str) -> fmt::Result {
+    for (index, line) in message.split('\n').enumerate() {
+        if index > 0 {
+            writeln!(f)?;
+        }
+
+        write!(f, "{}{}", prefix, line)?;
+    }
+
+    Ok(())
+}
+
+
+/// Used if the assembly validator finds an instruction that is not valid, such as `ADDQ $z0, 80`. When
+/// several are found together, the message has one on each line.
 #[derive(Debug, Clone)]
 pub struct AsmValidationError(pub String);
 impl Error for AsmValidationError {}
@@ -24,11 +41,182 @@ impl Error for AsmValidationError {}
 /// including a custom string to add to the error.
 impl fmt::Display for AsmValidationError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Found invalid instruction: {}", self.0)
+        write_each_line(f, "Found invalid instruction: ", &self.0)
+    }
+}
+
+
+impl AsmValidationError {
+    /// Takes the code of the error from `ERROR_CODES` and its message, and returns the error with the code
+    /// in front of the message, such as `[E0101] Immediate 99 cannot fit into 4 bits`
+    pub fn coded(code:&str, message:String) -> AsmValidationError {
+        AsmValidationError(format!("[{}] {}", code, message))
     }
 }
 
 
+/// Represents the stable code given to a kind of validation error, with a short summary of it, a longer
+/// description printed by `explain`, and an example program which gives the error
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorCode {
+    pub code: &'static str,
+    pub summary: &'static str,
+    pub description: &'static str,
+    pub example: &'static str
+}
+
+
+/// The code of every kind of validation error. Codes are never reused or renumbered, so that they can be
+/// searched for and recorded by other tools.
+pub const ERROR_CODES:[ErrorCode; 19] = [
+    ErrorCode {
+        code: "E0101",
+        summary: "immediate out of range",
+        description: "An immediate is too large for the field it is encoded into, or too small if it is signed. \
+            ADDI, SUBI, IN, and OUT take a 4-bit immediate, MOVUI, MOVLI, and syscall an 8-bit one, and .int and \
+            .long 16-bit and 32-bit values. A larger value can be built in a register with MOVUI and MOVLI.",
+        example: "init:\n    ADDI $g0, $zero, 99\n    HALT\n"
+    },
+    ErrorCode {
+        code: "E0102",
+        summary: "immediate cannot be parsed",
+        description: "An immediate is not a number. Immediates are written in decimal, in hexadecimal with a \
+            0x prefix, or in binary with a 0b prefix, and floats in decimal.",
+        example: "init:\n    ADDI $g0, $zero, 0xZZ\n    HALT\n"
+    },
+    ErrorCode {
+        code: "E0103",
+        summary: "negative unsigned immediate",
+        description: "A negative immediate is given where only unsigned values are accepted, such as the \
+            immediate of an instruction or a value written in hexadecimal or binary. Subtract with SUBI \
+            instead of adding a negative number.",
+        example: "init:\n    ADDI $g0, $zero, -1\n    HALT\n"
+    },
+    ErrorCode {
+        code: "E0104",
+        summary: "float out of range",
+        description: "A .half or .float value is too large to be stored in a 16-bit or 32-bit IEEE 754 number.",
+        example: "init:\n    HALT\ndata:\nbig: .half 9999999999\n"
+    },
+    ErrorCode {
+        code: "E0105",
+        summary: "fixed-point out of range",
+        description: "A .q8_8 or .q16_16 value is outside the range of its signed fixed-point format once it is \
+            rounded to the nearest step, which is -128 to 127.99609375 for .q8_8 and -32768 to \
+            32767.9999847412109375 for .q16_16.",
+        example: "init:\n    HALT\ndata:\ngain: .q8_8 200\n"
+    },
+    ErrorCode {
+        code: "E0201",
+        summary: "invalid register",
+        description: "An operand which should be a register is not one. The registers are $zero, $g0 to \
+            $g9, $ua, $sp, $ra, $fp, and $pc.",
+        example: "init:\n    ADD $g12, $g1, $g2\n    HALT\n"
+    },
+    ErrorCode {
+        code: "E0301",
+        summary: "invalid opcode",
+        description: "A line in the code section does not start with an opcode the Iridium ISA has. Opcodes \
+            are written in capitals, except for syscall.",
+        example: "init:\n    MUL $g0, $g1, $g2\n    HALT\n"
+    },
+    ErrorCode {
+        code: "E0302",
+        summary: "wrong number of operands",
+        description: "An instruction or data item is given more or fewer operands than it takes, such as an \
+            ADD with two registers instead of three, or a HALT with any.",
+        example: "init:\n    ADD $g0, $g1\n    HALT\n"
+    },
+    ErrorCode {
+        code: "E0303",
+        summary: "missing operand between commas",
+        description: "A comma has no operand on one side of it, which is usually an operand which was left out \
+            or a comma which was left in.",
+        example: "init:\n    ADD $g0, , $g1\n    HALT\n"
+    },
+    ErrorCode {
+        code: "E0304",
+        summary: "single register branch",
+        description: "A branch or jump is given a single register which cannot hold a whole address. Only $sp, \
+            $fp, $ra, and $pc are 32 bits wide, and any other address must be given as a pair of registers.",
+        example: "init:\n    JUMP $g8\n    HALT\n"
+    },
+    ErrorCode {
+        code: "E0401",
+        summary: "invalid label",
+        description: "A label starts with a digit, has a character which is not alphanumeric or _, or has an \
+            empty namespace or name.",
+        example: "1st:\n    HALT\n"
+    },
+    ErrorCode {
+        code: "E0402",
+        summary: "label operand without @",
+        description: "An operand where a label is expected does not start with @. Label operands are written \
+            as @name, such as JUMP $g8, $g9, @loop.",
+        example: "init:\n    JUMP $g8, $g9, loop\n    HALT\n"
+    },
+    ErrorCode {
+        code: "E0403",
+        summary: "invalid label offset",
+        description: "The offset added to a label operand is not an integer which fits into 32 bits. An offset \
+            is written after the label with + or -, such as MOVLI $g0, @table+8.",
+        example: "init:\n    MOVLI $g0, @init+eight\n    HALT\n"
+    },
+    ErrorCode {
+        code: "E0404",
+        summary: "label not found",
+        description: "A label operand names a label which is not defined in any of the files being assembled. \
+            The error suggests the closest label which is defined if the name looks like a typo of it.",
+        example: "init:\n    JUMP $g8, $g9, @lop\nloop:\n    HALT\n"
+    },
+    ErrorCode {
+        code: "E0501",
+        summary: "item in the wrong section",
+        description: "An instruction is in a data section, data is in the code section, or a data item is in \
+            a section which does not hold its type. Instructions go before data:, .text, .ascii, and .asciiz only \
+            go in text:, and only .space and .zero go in bss:.",
+        example: "init:\n    HALT\ndata:\n    ADD $g0, $g1, $g2\n"
+    },
+    ErrorCode {
+        code: "E0502",
+        summary: "invalid data type",
+        description: "A line in a data section does not start with a data type. The data types are .int, \
+            .long, .half, .float, .q8_8, .q16_16, .le32, .be32, .byte, .section, .char, .text, .ascii, \
+            .asciiz, .space, .zero, .rand, .crc16_table, .table, and .vectors.",
+        example: "init:\n    HALT\ndata:\nvalue: .word 5\n"
+    },
+    ErrorCode {
+        code: "E0503",
+        summary: "malformed data",
+        description: "The value of a data item is not written in the form its type takes, such as a .char \
+            with more than one character, a .text or .asciiz without double quotes, a string with an unknown \
+            escape sequence, or a .section without square brackets.",
+        example: "init:\n    HALT\ndata:\nletter: .char 'ab'\n"
+    },
+    ErrorCode {
+        code: "E0504",
+        summary: "data too long",
+        description: "The contents of a .text or .section are longer than the size it is given. The size of a \
+            .text must include the null terminator. An .ascii or .asciiz is as long as its string, which can be \
+            at most 65535 words.",
+        example: "init:\n    HALT\ntext:\ngreeting: .text 3 \"hello\"\n"
+    },
+    ErrorCode {
+        code: "E0505",
+        summary: "empty reservation",
+        description: "A .space, .zero, or .rand is given a size of 0 words, and must reserve at least 1.",
+        example: "init:\n    HALT\nbss:\nbuffer: .space 0\n"
+    }
+];
+
+
+/// Takes an error code such as `E0101`, in any case, and returns its entry in `ERROR_CODES`, or `None` if
+/// there is no such code
+pub fn find_error_code(code:&str) -> Option<&'static ErrorCode> {
+    ERROR_CODES.iter().find(|error_code| error_code.code.eq_ignore_ascii_case(code))
+}
+
+
 /// Used if the wrong type of token is detected after processing the file into tokens
 #[derive(Debug, Clone)]
 pub struct TokenTypeError(pub String);
@@ -44,7 +232,7 @@ impl fmt::Display for TokenTypeError {
 
 
 
-/// Used if a non-existant label is used
+/// Used if a non-existant label is used. When several are found together, the message has one on each line.
 #[derive(Debug, Clone)]
 pub struct LabelNotFoundError(pub String);
 impl Error for LabelNotFoundError {}
@@ -53,7 +241,220 @@ impl Error for LabelNotFoundError {}
 /// including a custom string to add to the error.
 impl fmt::Display for LabelNotFoundError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Found invalid label: {}", self.0)
+        write_each_line(f, "Found invalid label: ", &self.0)
     }
 }
 
+
+
+/// Used if a region of the program cannot be assembled on its own against an existing map file
+#[derive(Debug, Clone)]
+pub struct PartialAssemblyError(pub String);
+impl Error for PartialAssemblyError {}
+
+/// Ensures that the `PartialAssemblyError` error type is displayed appropriately in the console when raised, 
+/// including a custom string to add to the error.
+impl fmt::Display for PartialAssemblyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Could not assemble region: {}", self.0)
+    }
+}
+
+
+
+/// Used if a program binary cannot be read back, such as when dumping it
+#[derive(Debug, Clone)]
+pub struct BinaryFormatError(pub String);
+impl Error for BinaryFormatError {}
+
+/// Ensures that the `BinaryFormatError` error type is displayed appropriately in the console when raised, 
+/// including a custom string to add to the error.
+impl fmt::Display for BinaryFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Could not read binary: {}", self.0)
+    }
+}
+
+
+
+/// Used if the program does not fit into the memory of the target it is being assembled for
+#[derive(Debug, Clone)]
+pub struct MemoryFitError(pub String);
+impl Error for MemoryFitError {}
+
+/// Ensures that the `MemoryFitError` error type is displayed appropriately in the console when raised, 
+/// including a custom string to add to the error.
+impl fmt::Display for MemoryFitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Program does not fit into memory: {}", self.0)
+    }
+}
+
+
+
+/// Used if a program cannot be assembled through the library API, such as by `assemble_source`
+#[derive(Debug, Clone)]
+pub struct AssembleError(pub String);
+impl Error for AssembleError {}
+
+/// Ensures that the `AssembleError` error type is displayed appropriately in the console when raised, 
+/// including a custom string to add to the error.
+impl fmt::Display for AssembleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Could not assemble program: {}", self.0)
+    }
+}
+
+
+
+/// Used if the configuration file in the working directory cannot be read or sets an invalid option
+#[derive(Debug, Clone)]
+pub struct ConfigError(pub String);
+impl Error for ConfigError {}
+
+/// Ensures that the `ConfigError` error type is displayed appropriately in the console when raised, 
+/// including a custom string to add to the error.
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid configuration file: {}", self.0)
+    }
+}
+
+
+
+/// Used if the encoding of a program depends on the address it is loaded at in a way which is not explained
+/// by the addresses of labels it loads
+#[derive(Debug, Clone)]
+pub struct AddressDependenceError(pub String);
+impl Error for AddressDependenceError {}
+
+/// Ensures that the `AddressDependenceError` error type is displayed appropriately in the console when
+/// raised, including a custom string to add to the error.
+impl fmt::Display for AddressDependenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Program depends on its base address: {}", self.0)
+    }
+}
+
+
+
+/// Used if a line which passed validation cannot be turned into tokens, such as an immediate which does
+/// not fit in the instruction
+#[derive(Debug, Clone)]
+pub struct TokenGenerationError(pub String);
+impl Error for TokenGenerationError {}
+
+/// Ensures that the `TokenGenerationError` error type is displayed appropriately in the console when
+/// raised, including a custom string to add to the error.
+impl fmt::Display for TokenGenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Could not generate tokens: {}", self.0)
+    }
+}
+
+
+
+/// Used if a token cannot be encoded into words, such as an instruction with a register which does not
+/// exist, which can happen to tokens built directly rather than from validated source
+#[derive(Debug, Clone)]
+pub struct EncodingError(pub String);
+impl Error for EncodingError {}
+
+/// Ensures that the `EncodingError` error type is displayed appropriately in the console when raised,
+/// including a custom string to add to the error.
+impl fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Could not encode instruction: {}", self.0)
+    }
+}
+
+
+
+/// Used if an archive of submissions given to the `batch` subcommand cannot be read, or is not a zip or tar archive
+#[derive(Debug, Clone)]
+pub struct ArchiveError(pub String);
+impl Error for ArchiveError {}
+
+/// Ensures that the `ArchiveError` error type is displayed appropriately in the console when raised,
+/// including a custom string to add to the error.
+impl fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Could not read archive: {}", self.0)
+    }
+}
+
+
+/// Used if assembling a program would use more of a resource than its `ResourceLimits` allow, naming the
+/// limit reached and its maximum so that a service can report which one it was
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceLimitExceeded {
+    pub limit: &'static str,
+    pub maximum: u64
+}
+impl Error for ResourceLimitExceeded {}
+
+/// Ensures that the `ResourceLimitExceeded` error type is displayed appropriately in the console when raised,
+/// including the limit and its maximum.
+impl fmt::Display for ResourceLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Resource limit exceeded: the program needs more than {} {}", self.maximum, self.limit)
+    }
+}
+
+
+/// Used if any warnings are given while warnings are denied with `--deny warnings`
+#[derive(Debug, Clone)]
+pub struct DeniedWarningsError(pub String);
+impl Error for DeniedWarningsError {}
+
+/// Ensures that the `DeniedWarningsError` error type is displayed appropriately in the console when raised,
+/// including a custom string to add to the error.
+impl fmt::Display for DeniedWarningsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Warnings are denied: {}", self.0)
+    }
+}
+
+
+/// Used if the labels of a program cannot be demangled, such as when an external demangler cannot be run
+#[derive(Debug, Clone)]
+pub struct DemangleError(pub String);
+impl Error for DemangleError {}
+
+/// Ensures that the `DemangleError` error type is displayed appropriately in the console when raised,
+/// including a custom string to add to the error.
+impl fmt::Display for DemangleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Could not demangle labels: {}", self.0)
+    }
+}
+
+
+/// Used if serialized tokens cannot be read, such as when they were written in a different version of the
+/// format
+#[derive(Debug, Clone)]
+pub struct TokenFormatError(pub String);
+impl Error for TokenFormatError {}
+
+/// Ensures that the `TokenFormatError` error type is displayed appropriately in the console when raised,
+/// including a custom string to add to the error.
+impl fmt::Display for TokenFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Could not read the tokens: {}", self.0)
+    }
+}
+
+
+/// Used if a label frozen with `--freeze-api` has moved from the address recorded for it, or is no longer in
+/// the program
+#[derive(Debug, Clone)]
+pub struct FrozenApiError(pub String);
+impl Error for FrozenApiError {}
+
+/// Ensures that the `FrozenApiError` error type is displayed appropriately in the console when raised,
+/// including a custom string to add to the error.
+impl fmt::Display for FrozenApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The frozen API has changed: {}", self.0)
+    }
+}
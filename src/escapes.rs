@@ -0,0 +1,65 @@
+use crate::errors::AsmValidationError;
+
+
+/// Takes the contents of a string or character literal, without its quotes, and the line it is on, and
+/// returns it with its escape sequences replaced by the characters they stand for:
+///  - `\n`, `\t`, `\r`, and `\0` for a newline, tab, carriage return, and null character,
+///  - `\"`, `\'`, and `\\` for a double quote, single quote, and backslash,
+///  - `\xNN` for the character with the value of the two hexadecimal digits, from 0x00 to 0xFF.
+///
+/// Will return an `AsmValidationError` if a backslash is not followed by one of these.
+pub fn parse_escapes(line:&str, text:&str) -> Result<String, AsmValidationError> {
+    let invalid = |sequence:&str| AsmValidationError::coded("E0503", format!(
+        "\\{} is not a valid escape sequence on line {}, which can be \\n, \\t, \\r, \\0, \\\", \\', \\\\, or \\xNN",
+        sequence, line
+    ));
+
+    let mut parsed = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            parsed.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => parsed.push('\n'),
+            Some('t') => parsed.push('\t'),
+            Some('r') => parsed.push('\r'),
+            Some('0') => parsed.push('\0'),
+            Some(c @ ('"' | '\'' | '\\')) => parsed.push(c),
+            Some('x') => {
+                let digits:String = chars.by_ref().take(2).collect();
+                match u8::from_str_radix(&digits, 16) {
+                    Ok(value) if digits.len() == 2 => parsed.push(char::from(value)),
+                    _ => return Err(invalid(&format!("x{}", digits)))
+                }
+            },
+
+            Some(c) => return Err(invalid(&c.to_string())),
+            None => return Err(invalid(""))
+        }
+    }
+
+    Ok(parsed)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::escapes::*;
+
+
+    #[test]
+    fn test_parse_escapes() {
+        assert_eq!(parse_escapes("", "Hello world!").unwrap(), "Hello world!");
+        assert_eq!(parse_escapes("", r"line\nnext\ttab\r\0").unwrap(), "line\nnext\ttab\r\0");
+        assert_eq!(parse_escapes("", r#"say \"hi\" it\'s \\"#).unwrap(), "say \"hi\" it's \\");
+        assert_eq!(parse_escapes("", r"\x41\x7a\xFF").unwrap(), "Az\u{FF}");
+
+        assert!(parse_escapes("", r"\q").unwrap_err().0.starts_with("[E0503]"));
+        assert!(parse_escapes("", r"\x4").is_err());
+        assert!(parse_escapes("", r"\xG1").is_err());
+        assert!(parse_escapes("", "trailing\\").is_err());
+    }
+}
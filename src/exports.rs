@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+use crate::errors::AsmValidationError;
+use crate::hardening::strip_comment;
+use crate::namespaces::{is_namespace_directive, parse_namespace_directive};
+use crate::preprocessor::SourceLine;
+use crate::token_generator::generate_instr_tokens;
+use crate::token_types::{located, ExportTableTokens, ExportedFunction, FileTokens, OrgTokens, SourcePos};
+use crate::validation::validate_int_immediate;
+
+
+/// The start of the label of each slot of an export table, which is followed by the function it jumps to,
+/// such as `__export_putc`
+pub const EXPORT_SLOT_PREFIX:&str = "__export_";
+
+/// The number of words in each slot of an export table, which is the jump to a label a `JUMP` with a label
+/// operand is expanded into
+pub const EXPORT_SLOT_WORDS:i64 = 5;
+
+
+/// Takes the name of an exported function, qualified by its namespace if it is in one, and returns the label
+/// of its slot in the export table, such as `__export_math_sqrt` for `math.sqrt`
+pub fn slot_label(function:&str) -> String {
+    format!("{}{}", EXPORT_SLOT_PREFIX, function.replace('.', "_"))
+}
+
+
+/// Takes a line of assembly and returns true if it places the export table, such as `.export_table @ 0x0100`
+pub fn is_export_table_directive(line:&str) -> bool {
+    line.split_whitespace().next() == Some(".export_table")
+}
+
+
+/// Takes a line of assembly holding an export table in the form `.export_table @ <address>`, the section it
+/// is in, the functions exported by its file, and its position in the source, and returns its tokens. Will
+/// return an `AsmValidationError` if it is not in the code section or not in that form.
+pub fn parse_export_table_directive(line:&str, mode:char, functions:Vec<ExportedFunction>, pos:Option<SourcePos>)
+        -> Result<ExportTableTokens, AsmValidationError> {
+    if mode != 'c' {
+        return Err(AsmValidationError(format!("The .export_table on line {} must be in the code section", line)));
+    }
+
+    match line.split_whitespace().collect::<Vec<&str>>()[..] {
+        [".export_table", "@", address] => Ok(ExportTableTokens::new(Some(validate_int_immediate(address, 32, false)?), functions, pos)),
+        _ => Err(AsmValidationError(format!(
+            "Incorrect format for .export_table on line {}, which should be .export_table @ <address>", line
+        )))
+    }
+}
+
+
+/// Takes a line of assembly holding a `.global` in the form `.global [<slot>]` and returns the slot of the
+/// export table it gives the function, if any, or an `AsmValidationError` if it is not in that form.
+fn parse_global_directive(line:&str) -> Result<Option<u16>, AsmValidationError> {
+    match line.split_whitespace().collect::<Vec<&str>>()[..] {
+        [".global"] => Ok(None),
+        [".global", slot] => Ok(Some(validate_int_immediate(slot, 16, false)? as u16)),
+        _ => Err(AsmValidationError(format!("Incorrect format for .global on line {}, which should be .global [<slot>]", line)))
+    }
+}
+
+
+/// Takes the lines of a source file and its name, and returns the lines with the `.global` marking each
+/// exported function removed, along with each function marked with `.global` on a line before its `.func`,
+/// qualified by the namespace it is in, in the order they are defined. Will return an `AsmValidationError`
+/// if a `.global` is not in the form `.global [<slot>]` or does not come before a `.func`, although an `.isr`
+/// may come between them.
+pub fn take_exported_functions(source_lines:Vec<SourceLine>, name:&str)
+        -> Result<(Vec<SourceLine>, Vec<ExportedFunction>), AsmValidationError> {
+    let mut kept:Vec<SourceLine> = Vec::with_capacity(source_lines.len());
+    let mut exported:Vec<ExportedFunction> = Vec::new();
+    let mut namespace:Option<String> = None;
+    let mut global:Option<(SourceLine, Option<u16>)> = None;
+    for source_line in source_lines {
+        let line = strip_comment(&source_line.text);
+        if is_namespace_directive(line) {
+            namespace = parse_namespace_directive(line).ok();
+        }
+
+        match line.split_whitespace().next() {
+            None | Some(".isr") => (),
+            Some(".global") if global.is_none() => {
+                let slot = parse_global_directive(line).map_err(|e| source_line.locate(e))?;
+                global = Some((source_line, slot));
+                continue;
+            },
+            Some(".func") if global.is_some() => {
+                let (global_line, slot) = global.take().unwrap();
+                let function = line.split_whitespace().nth(1).unwrap_or_default();
+                exported.push(ExportedFunction {
+                    name: namespace.as_ref().map_or(function.to_owned(), |namespace| format!("{}.{}", namespace, function)),
+                    slot,
+                    pos: Some(global_line.pos(name))
+                });
+            },
+            Some(_) if global.is_some() => {
+                return Err(AsmValidationError(format!("{}: .global must come before a .func", global.unwrap().0.location())));
+            },
+            Some(_) => ()
+        }
+
+        kept.push(source_line);
+    }
+
+    match global {
+        Some((source_line, _)) => Err(AsmValidationError(format!("{}: .global must come before a .func", source_line.location()))),
+        None => Ok((kept, exported))
+    }
+}
+
+
+/// Takes the position of an item in the source and returns it as it is written in diagnostics, or `-` if it
+/// did not come from a source file
+fn describe_pos(pos:&Option<SourcePos>) -> String {
+    pos.as_ref().map(|pos| pos.to_string()).unwrap_or_else(|| "-".to_owned())
+}
+
+
+/// Takes a stream of tokens and returns them with the export table of the program replaced by a table of
+/// jumps to the functions exported by every file, and the exports of files without a table removed. Each
+/// function goes in the slot given by its `.global`, or in the slot after that of the function exported
+/// before it, counting from 0 and in the order the files are given, so functions whose slots must not move
+/// between releases should be given them. The table is an `.org` to its address followed by a slot for each
+/// function in the order of their slots, labelled as given by `slot_label`, which jumps to the function with
+/// `JUMP $g8, $g9, @<name>`, with another `.org` over any slot no function is given. Every slot is
+/// `EXPORT_SLOT_WORDS` words, so the function in slot `n` is always at the address of the table plus `n`
+/// times that, however the functions themselves move. As a slot is reached with `JAL`, the function returns
+/// straight to its caller, but `$g8` and `$g9` are not kept.
+///
+/// Will return an `AsmValidationError` if there is more than one `.export_table`, if two functions are given
+/// the same slot, or if there is an `.export_table` but no function is exported for it to hold.
+pub fn expand_export_tables(tokens_stream:Vec<FileTokens>) -> Result<Vec<FileTokens>, AsmValidationError> {
+    let mut table:Option<&ExportTableTokens> = None;
+    let mut slots:BTreeMap<u16, &ExportedFunction> = BTreeMap::new();
+    let mut next_slot:u32 = 0;
+    for t in tokens_stream.iter().filter_map(|tokens| match tokens { FileTokens::ExportTableTokens(t) => Some(t), _ => None }) {
+        if t.address.is_some() {
+            if let Some(other) = table {
+                return Err(AsmValidationError(format!("{}there is already an .export_table at {}", located(t.pos.as_ref()),
+                    describe_pos(&other.pos))));
+            }
+
+            table = Some(t);
+        }
+
+        for function in &t.functions {
+            let slot = u16::try_from(function.slot.map_or(next_slot, u32::from)).map_err(|_| AsmValidationError(format!(
+                "{}the export table has no slot left for {} after slot 65535", located(function.pos.as_ref()), function.name
+            )))?;
+
+            if let Some(other) = slots.insert(slot, function) {
+                return Err(AsmValidationError(format!("{}slot {} of the export table is already given to {} at {}",
+                    located(function.pos.as_ref()), slot, other.name, describe_pos(&other.pos))));
+            }
+
+            next_slot = u32::from(slot) + 1;
+        }
+    }
+
+    let slots:Vec<(u16, String)> = slots.into_iter().map(|(slot, function)| (slot, function.name.clone())).collect();
+    if let Some(t) = table.filter(|_| slots.is_empty()) {
+        return Err(AsmValidationError(format!("{}the .export_table has no functions marked with .global to hold", located(t.pos.as_ref()))));
+    }
+
+    let mut expanded:Vec<FileTokens> = Vec::with_capacity(tokens_stream.len() + slots.len());
+    for tokens in tokens_stream {
+        let (address, pos) = match tokens {
+            FileTokens::ExportTableTokens(ExportTableTokens { address: Some(address), pos, .. }) => (address, pos),
+            FileTokens::ExportTableTokens(_) => continue,
+            tokens => {
+                expanded.push(tokens);
+                continue;
+            }
+        };
+
+        let mut next_slot:Option<u32> = None;
+        for (slot, function) in &slots {
+            if next_slot != Some(u32::from(*slot)) {
+                expanded.push(FileTokens::OrgTokens(OrgTokens::new('c', address + i64::from(*slot) * EXPORT_SLOT_WORDS, None)));
+            }
+
+            let line = format!("JUMP $g8, $g9, @{}", function);
+            let instr_tokens = generate_instr_tokens(&line, Some(slot_label(function))).map_err(|e| AsmValidationError(e.0))?;
+            expanded.push(FileTokens::InstrTokens(instr_tokens.with_pos(pos.clone())));
+            next_slot = Some(u32::from(*slot) + 1);
+        }
+    }
+
+    Ok(expanded)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::exports::*;
+    use crate::label_table::generate_label_table;
+    use crate::preprocessor::join_continuation_lines;
+    use crate::process_source_into_tokens;
+    use crate::pseudo_substitution::substitute_pseudo_instrs;
+
+
+    fn take(source:&str) -> Result<(Vec<String>, Vec<ExportedFunction>), AsmValidationError> {
+        let source_lines = join_continuation_lines(source.lines().map(|line| line.to_owned()).collect()).unwrap();
+        let (source_lines, exported) = take_exported_functions(source_lines, "<source>")?;
+        Ok((source_lines.into_iter().map(|line| line.text).collect(), exported))
+    }
+
+
+    fn label_table(sources:&[&str]) -> Result<HashMap<String, i64>, AsmValidationError> {
+        let tokens = sources.iter().flat_map(|source| process_source_into_tokens(source, None).unwrap()).collect();
+        Ok(generate_label_table(&substitute_pseudo_instrs(expand_export_tables(tokens)?)).unwrap())
+    }
+
+
+    #[test]
+    fn test_take_exported_functions() {
+        let source = ".global\n.func putc\n    JUMP $ra\n.endfunc\n.namespace math\n.global 4 ; exported\n.isr\n.func sqrt\n    JUMP $ra\n\
+            .endfunc\n.export_table @ 0x0100\n";
+        let (lines, exported) = take(source).unwrap();
+        assert_eq!(lines, vec![
+            ".func putc", "    JUMP $ra", ".endfunc", ".namespace math", ".isr", ".func sqrt", "    JUMP $ra", ".endfunc",
+            ".export_table @ 0x0100"
+        ]);
+        let exported:Vec<(&str, Option<u16>)> = exported.iter().map(|function| (function.name.as_str(), function.slot)).collect();
+        assert_eq!(exported, vec![("putc", None), ("math.sqrt", Some(4))]);
+    }
+
+
+    #[test]
+    fn test_expand_export_tables() {
+        let source = "init:\n    HALT\n.global\n.func putc\n    JUMP $ra\n.endfunc\n.global 3\n.func getc\n    JUMP $ra\n.endfunc\n\
+            .global\n.func puts\n    JUMP $ra\n.endfunc\n.export_table @ 0x0100\n";
+        let tokens = expand_export_tables(process_source_into_tokens(source, None).unwrap()).unwrap();
+        assert!(!tokens.iter().any(|tokens| matches!(tokens, FileTokens::ExportTableTokens(_))));
+
+        let slots:Vec<String> = tokens[tokens.len() - 5..].iter().map(|tokens| match tokens {
+            FileTokens::OrgTokens(t) => format!(".org {:#06X}", t.address),
+            tokens => format!("{}: {}", tokens.label().unwrap(), tokens.try_get_instr_tokens().unwrap().op_label.unwrap())
+        }).collect();
+        assert_eq!(slots, vec![".org 0x0100", "__export_putc: @putc", ".org 0x010F", "__export_getc: @getc", "__export_puts: @puts"]);
+    }
+
+
+    #[test]
+    fn test_export_slots_are_stable() {
+        let slots = |body:&str| {
+            let source = format!("init:\n    HALT\n.global\n.func putc\n{}    JUMP $ra\n.endfunc\n.global\n.func getc\n    JUMP $ra\n.endfunc\n\
+                .export_table @ 0x0100\n", body);
+            let label_table = label_table(&[&source]).unwrap();
+            (label_table["__export_putc"], label_table["__export_getc"], label_table["getc"])
+        };
+
+        assert_eq!(slots(""), (0x0100, 0x0105, 0x0002));
+        assert_eq!(slots("    NOP\n    NOP\n"), (0x0100, 0x0105, 0x0004));
+    }
+
+
+    #[test]
+    fn test_exports_across_files() {
+        let rom = "init:\n    HALT\n.global 0\n.func putc\n    JUMP $ra\n.endfunc\n.export_table @ 0x0100\n";
+        let math = ".namespace math\n.global 2\n.func sqrt\n    JUMP $ra\n.endfunc\n.global\n.func abs\n    JUMP $ra\n.endfunc\n";
+        let io = ".global 1\n.func getc\n    JUMP $ra\n.endfunc\n";
+
+        // the slots are the same whichever order the files are given in
+        for sources in [[rom, math, io], [io, math, rom]] {
+            let label_table = label_table(&sources).unwrap();
+            let slots:Vec<i64> = ["putc", "getc", "math_sqrt", "math_abs"].iter()
+                .map(|function| label_table[&format!("{}{}", EXPORT_SLOT_PREFIX, function)])
+                .collect();
+            assert_eq!(slots, vec![0x0100, 0x0105, 0x010A, 0x010F]);
+        }
+
+        // a file with exports but no table does not need one
+        assert!(label_table(&[math]).unwrap().keys().all(|label| !label.starts_with(EXPORT_SLOT_PREFIX)));
+    }
+
+
+    #[test]
+    fn test_invalid_export_tables() {
+        let function = ".global\n.func putc\n    JUMP $ra\n.endfunc\n";
+        assert_eq!(take(".global\ninit: HALT\n").unwrap_err().0, "1:1: .global must come before a .func");
+        assert_eq!(take(".global\n").unwrap_err().0, "1:1: .global must come before a .func");
+        assert!(take(".global putc\n.func putc\n").unwrap_err().0.starts_with("1:9: "));
+        assert!(take(".global 1 2\n.func putc\n").unwrap_err().0.contains("should be .global [<slot>]"));
+
+        let invalid = |sources:&[&str]| label_table(sources).unwrap_err().0;
+        assert!(process_source_into_tokens(&format!("{}.export_table 0x0100\n", function), None).unwrap_err().0
+            .contains("should be .export_table @ <address>"));
+        assert!(process_source_into_tokens(&format!("{}data:\n.export_table @ 0x0100\n", function), None).unwrap_err().0
+            .ends_with("must be in the code section"));
+        assert_eq!(invalid(&[&format!("{}.export_table @ 0x0100\n", function), ".export_table @ 0x0200\n"]),
+            "<source>:1:1: there is already an .export_table at <source>:5:1");
+        assert_eq!(invalid(&[".global 1\n.func putc\n    JUMP $ra\n.endfunc\n", ".global 1\n.func getc\n    JUMP $ra\n.endfunc\n"]),
+            "<source>:1:1: slot 1 of the export table is already given to putc at <source>:1:1");
+        assert_eq!(invalid(&[".export_table @ 0x0100\n"]), "<source>:1:1: the .export_table has no functions marked with .global to hold");
+    }
+}
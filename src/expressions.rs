@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+use std::f64::consts::{E, PI};
+use crate::errors::AsmValidationError;
+use crate::validation::{remove_label, validate_int_immediate};
+
+
+/// The functions which can be called in an expression, and the number of arguments each takes
+const FUNCTIONS:[(&str, usize); 12] = [
+    ("sin", 1), ("cos", 1), ("tan", 1), ("sqrt", 1), ("abs", 1), ("floor", 1),
+    ("ceil", 1), ("round", 1), ("exp", 1), ("ln", 1), ("min", 2), ("max", 2)
+];
+
+
+/// Represents an arithmetic expression of a single variable, such as `sin(i / 256 * 2 * pi) * 32767`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Variable,
+    Negate(Box<Expr>),
+    Binary(char, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>)
+}
+
+
+impl Expr {
+    /// Takes the value of the variable and returns the value of the expression
+    pub fn evaluate(&self, variable:f64) -> f64 {
+        match self {
+            Expr::Number(value) => *value,
+            Expr::Variable => variable,
+            Expr::Negate(expr) => -expr.evaluate(variable),
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (left.evaluate(variable), right.evaluate(variable));
+                match op {
+                    '+' => left + right,
+                    '-' => left - right,
+                    '*' => left * right,
+                    '/' => left / right,
+                    '%' => left % right,
+                    '^' => left.powf(right),
+                    _ => panic!("Invalid operator {}", op)
+                }
+            },
+
+            Expr::Call(function, args) => {
+                let args:Vec<f64> = args.iter().map(|arg| arg.evaluate(variable)).collect();
+                match function.as_str() {
+                    "sin" => args[0].sin(),
+                    "cos" => args[0].cos(),
+                    "tan" => args[0].tan(),
+                    "sqrt" => args[0].sqrt(),
+                    "abs" => args[0].abs(),
+                    "floor" => args[0].floor(),
+                    "ceil" => args[0].ceil(),
+                    "round" => args[0].round(),
+                    "exp" => args[0].exp(),
+                    "ln" => args[0].ln(),
+                    "min" => args[0].min(args[1]),
+                    "max" => args[0].max(args[1]),
+                    _ => panic!("Invalid function {}", function)
+                }
+            }
+        }
+    }
+}
+
+
+/// Parses an expression by recursive descent, where each method parses one level of precedence
+struct ExprParser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    variable: &'a str,
+    constants: &'a HashMap<String, i64>,
+    source: &'a str
+}
+
+
+impl ExprParser<'_> {
+    fn error(&self, message:&str) -> AsmValidationError {
+        AsmValidationError(format!("{} at column {} of the expression {}", message, self.pos + 1, self.source))
+    }
+
+
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.chars.len() && self.chars[self.pos].is_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.get(self.pos).copied()
+    }
+
+
+    fn expect(&mut self, expected:char) -> Result<(), AsmValidationError> {
+        if self.peek() != Some(expected) {
+            return Err(self.error(&format!("Expected '{}'", expected)));
+        }
+
+        self.pos += 1;
+        Ok(())
+    }
+
+
+    /// sum := product (('+' | '-') product)*
+    fn parse_sum(&mut self) -> Result<Expr, AsmValidationError> {
+        let mut expr = self.parse_product()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.parse_product()?));
+        }
+
+        Ok(expr)
+    }
+
+
+    /// product := unary (('*' | '/' | '%') unary)*
+    fn parse_product(&mut self) -> Result<Expr, AsmValidationError> {
+        let mut expr = self.parse_unary()?;
+        while let Some(op @ ('*' | '/' | '%')) = self.peek() {
+            self.pos += 1;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.parse_unary()?));
+        }
+
+        Ok(expr)
+    }
+
+
+    /// unary := '-' unary | power
+    fn parse_unary(&mut self) -> Result<Expr, AsmValidationError> {
+        if self.peek() == Some('-') {
+            self.pos += 1;
+            return Ok(Expr::Negate(Box::new(self.parse_unary()?)));
+        }
+
+        self.parse_power()
+    }
+
+
+    /// power := atom ('^' unary)?
+    fn parse_power(&mut self) -> Result<Expr, AsmValidationError> {
+        let base = self.parse_atom()?;
+        if self.peek() == Some('^') {
+            self.pos += 1;
+            return Ok(Expr::Binary('^', Box::new(base), Box::new(self.parse_unary()?)));
+        }
+
+        Ok(base)
+    }
+
+
+    /// atom := number | constant | variable | function '(' sum (',' sum)* ')' | '(' sum ')'
+    fn parse_atom(&mut self) -> Result<Expr, AsmValidationError> {
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let expr = self.parse_sum()?;
+                self.expect(')')?;
+                Ok(expr)
+            },
+
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let start = self.pos;
+                while self.pos < self.chars.len() && (self.chars[self.pos].is_ascii_alphanumeric() || ['.', '_'].contains(&self.chars[self.pos])) {
+                    self.pos += 1;
+                }
+
+                let number:String = self.chars[start..self.pos].iter().collect();
+                match validate_int_immediate(&number, 32, false) {
+                    Ok(value) => Ok(Expr::Number(value as f64)),
+                    Err(_) => number.parse().map(Expr::Number).map_err(|_| self.error(&format!("{} is not a number", number)))
+                }
+            },
+
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let start = self.pos;
+                while self.pos < self.chars.len() && (self.chars[self.pos].is_alphanumeric() || self.chars[self.pos] == '_') {
+                    self.pos += 1;
+                }
+
+                let name:String = self.chars[start..self.pos].iter().collect();
+                if name == self.variable {
+                    return Ok(Expr::Variable);
+                }
+
+                match name.as_str() {
+                    "pi" => return Ok(Expr::Number(PI)),
+                    "e" => return Ok(Expr::Number(E)),
+                    _ => ()
+                }
+
+                if let Some(value) = self.constants.get(&name) {
+                    return Ok(Expr::Number(*value as f64));
+                }
+
+                let arg_count = match FUNCTIONS.iter().find(|(function, _)| *function == name) {
+                    Some((_, arg_count)) => *arg_count,
+                    None if self.variable.is_empty() => return Err(self.error(&format!("{} is not a defined constant or a function", name))),
+                    None => return Err(self.error(&format!("{} is not the variable {}, a constant, or a function", name, self.variable)))
+                };
+
+                self.expect('(')?;
+                let mut args = vec![self.parse_sum()?];
+                while self.peek() == Some(',') {
+                    self.pos += 1;
+                    args.push(self.parse_sum()?);
+                }
+
+                self.expect(')')?;
+                if args.len() != arg_count {
+                    return Err(self.error(&format!("{} takes {} arguments, but was given {}", name, arg_count, args.len())));
+                }
+
+                Ok(Expr::Call(name, args))
+            },
+
+            Some(c) => Err(self.error(&format!("Unexpected '{}'", c))),
+            None => Err(self.error("Unexpected end"))
+        }
+    }
+}
+
+
+/// Takes an expression and the name of its variable, and returns the parsed `Expr`. Expressions can use
+/// numbers, the variable, the constants `pi` and `e`, the operators `+ - * / % ^` with the usual
+/// precedence, brackets, and the functions in `FUNCTIONS`. Will return an `AsmValidationError` saying
+/// where the expression is invalid if it cannot be parsed.
+pub fn parse_expression(source:&str, variable:&str) -> Result<Expr, AsmValidationError> {
+    parse_expression_with_constants(source, variable, &HashMap::new())
+}
+
+
+/// Takes an expression, the name of its variable, which is empty if it has none, and the constants defined
+/// with `.equ`, and returns the parsed `Expr` in the same way as `parse_expression`, with each constant used
+/// replaced by its value.
+fn parse_expression_with_constants(source:&str, variable:&str, constants:&HashMap<String, i64>) -> Result<Expr, AsmValidationError> {
+    let mut parser = ExprParser { chars: source.chars().collect(), pos: 0, variable, constants, source };
+    let expr = parser.parse_sum()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("Unexpected text after the end"));
+    }
+
+    Ok(expr)
+}
+
+
+/// Takes an operand and returns true if it is an expression rather than a single number or name, which it is
+/// if it has brackets or an operator anywhere but a leading minus sign, such as `(3*4)+1` or `SIZE-1`. A
+/// label operand with an offset, such as `@table+8`, is not an expression, as it is added once labels are known.
+pub fn is_int_expression(operand:&str) -> bool {
+    !operand.starts_with('@')
+        && (operand.contains(['(', ')', '+', '*', '/', '%', '^']) || operand.get(1..).is_some_and(|rest| rest.contains('-')))
+}
+
+
+/// Takes an expression given as an immediate, such as `(3*4)+1` or `BUFFER_SIZE-1`, and the constants defined
+/// with `.equ`, and returns its value, so that the immediate can be range checked as if the value had been
+/// written instead. Expressions are written as described for `parse_expression`, without a variable.
+///
+/// Will return an `AsmValidationError` if the expression cannot be parsed, uses a constant which has not been
+/// defined, or does not evaluate to a whole number that fits into 32 bits, such as `7/2`.
+pub fn evaluate_int_expression(source:&str, constants:&HashMap<String, i64>) -> Result<i64, AsmValidationError> {
+    let value = parse_expression_with_constants(source, "", constants)?.evaluate(0.0);
+    if value.fract() != 0.0 || !(-(1_i64 << 31) as f64..=u32::MAX as f64).contains(&value) {
+        return Err(AsmValidationError(format!("The expression {} is {}, which is not a whole number that fits into 32 bits", source, value)));
+    }
+
+    Ok(value as i64)
+}
+
+
+/// Takes a line of assembly for a lookup table in the form `.table <count>, <variable> => <expression>`
+/// and returns the words of the table, where each word is the expression evaluated with the variable set
+/// to its index and rounded to the nearest whole number. Will return an `AsmValidationError` if the line
+/// is not formatted correctly, or if any value is not a number or does not fit in a 16-bit word, whether
+/// signed or unsigned.
+pub fn generate_table_words(line:&str) -> Result<Vec<u16>, AsmValidationError> {
+    let format_error = || AsmValidationError(format!(
+        "Incorrect format for table on line {}, which should be .table <count>, <variable> => <expression>", line
+    ));
+
+    let (count, definition) = remove_label(line)[".table".len()..].split_once(',').ok_or_else(format_error)?;
+    let (variable, expression) = definition.split_once("=>").ok_or_else(format_error)?;
+    let variable = variable.trim();
+    if variable.is_empty() || !variable.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err(format_error());
+    }
+
+    let count = validate_int_immediate(count.trim(), 16, false)?;
+    if count == 0 {
+        return Err(AsmValidationError(format!("The table on line {} must be at least 1 word", line)));
+    }
+
+    let expr = parse_expression(expression.trim(), variable)?;
+    (0..count).map(|index| {
+        let value = expr.evaluate(index as f64).round();
+        if !(-32768.0..=65535.0).contains(&value) {
+            return Err(AsmValidationError(format!(
+                "The table on line {} has the value {} at index {}, which does not fit in 16 bits", line, value, index
+            )));
+        }
+
+        Ok(value as i64 as u16)
+    }).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::expressions::*;
+
+
+    #[test]
+    fn test_parse_expression() {
+        let eval = |source:&str, i:f64| parse_expression(source, "i").unwrap().evaluate(i);
+        assert_eq!(eval("1 + 2 * 3", 0.0), 7.0);
+        assert_eq!(eval("(1 + 2) * 3", 0.0), 9.0);
+        assert_eq!(eval("-i ^ 2", 3.0), -9.0);
+        assert_eq!(eval("2 ^ 3 ^ 2", 0.0), 512.0);
+        assert_eq!(eval("0x10 + i % 4", 7.0), 19.0);
+        assert_eq!(eval("max(i, 2.5) - min(1, abs(-4))", 1.0), 1.5);
+        assert_eq!(eval("round(sin(pi / 2) * 100)", 0.0), 100.0);
+    }
+
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert_eq!(parse_expression("1 +", "i").unwrap_err().0, "Unexpected end at column 4 of the expression 1 +");
+        assert!(parse_expression("x * 2", "i").is_err());
+        assert!(parse_expression("sin(1, 2)", "i").is_err());
+        assert!(parse_expression("(1 + 2", "i").is_err());
+        assert!(parse_expression("1 2", "i").is_err());
+    }
+
+
+    #[test]
+    fn test_evaluate_int_expression() {
+        let constants = HashMap::from([("BUFFER_SIZE".to_owned(), 64), ("BASE".to_owned(), 0x1000)]);
+        assert_eq!(evaluate_int_expression("(3*4)+1", &constants).unwrap(), 13);
+        assert_eq!(evaluate_int_expression("BUFFER_SIZE-1", &constants).unwrap(), 63);
+        assert_eq!(evaluate_int_expression("BASE + BUFFER_SIZE * 2 % 0x30", &constants).unwrap(), 0x1020);
+        assert_eq!(evaluate_int_expression("-(BUFFER_SIZE / 8)", &constants).unwrap(), -8);
+
+        assert_eq!(evaluate_int_expression("7/2", &constants).unwrap_err().0,
+            "The expression 7/2 is 3.5, which is not a whole number that fits into 32 bits");
+        assert_eq!(evaluate_int_expression("SIZE-1", &constants).unwrap_err().0,
+            "SIZE is not a defined constant or a function at column 5 of the expression SIZE-1");
+        assert!(evaluate_int_expression("2^32", &constants).is_err());
+
+        assert!(is_int_expression("(3*4)+1") && is_int_expression("SIZE-1") && is_int_expression("-(4)"));
+        assert!(!is_int_expression("-4") && !is_int_expression("0x1F") && !is_int_expression("SIZE"));
+    }
+
+
+    #[test]
+    fn test_generate_table_words() {
+        let words = generate_table_words("sine: .table 256, i => sin(i/256*2*pi)*32767").unwrap();
+        assert_eq!(words.len(), 256);
+        assert_eq!(words[0], 0);
+        assert_eq!(words[64], 32767);
+        assert_eq!(words[192], (-32767i16) as u16);
+
+        assert_eq!(generate_table_words(".table 4, n => n * n").unwrap(), vec![0, 1, 4, 9]);
+        assert!(generate_table_words(".table 4, i => i * 30000").is_err());
+        assert!(generate_table_words(".table 0, i => i").is_err());
+        assert!(generate_table_words(".table 4 i => i").is_err());
+        assert!(generate_table_words(".table 4, i => 1 / 0").is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::errors::AsmValidationError;
+
+
+/// Represents a signed fixed-point format with some number of integer and fractional bits, such as Q8.8,
+/// which is stored in a 16-bit word, or Q16.16, which is stored in two words with the upper half first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedPointFormat {
+    pub integer_bits: u32,
+    pub fraction_bits: u32
+}
+
+
+impl FixedPointFormat {
+    /// Takes a data type such as `.q8_8` or `q16_16`, with or without the dot, and returns its format, or
+    /// `None` if it is not a fixed-point data type.
+    pub fn from_data_type(data_type:&str) -> Option<FixedPointFormat> {
+        match data_type.trim_start_matches('.') {
+            "q8_8" => Some(FixedPointFormat { integer_bits: 8, fraction_bits: 8 }),
+            "q16_16" => Some(FixedPointFormat { integer_bits: 16, fraction_bits: 16 }),
+            _ => None
+        }
+    }
+
+
+    /// Returns the name of the data type of the format, such as `.q8_8`
+    pub fn name(&self) -> String {
+        format!(".q{}_{}", self.integer_bits, self.fraction_bits)
+    }
+
+
+    /// Returns the number of 16-bit words a value of the format takes up
+    pub fn words(&self) -> usize {
+        ((self.integer_bits + self.fraction_bits) / 16) as usize
+    }
+
+
+    /// Returns the smallest and largest values the format can hold
+    pub fn range(&self) -> (f64, f64) {
+        let scale = (1u64 << self.fraction_bits) as f64;
+        let max_raw = (1u64 << (self.integer_bits + self.fraction_bits - 1)) as f64;
+        (-max_raw / scale, (max_raw - 1.0) / scale)
+    }
+
+
+    /// Takes a decimal literal and the line it came from, and returns the raw fixed-point value nearest to
+    /// it, which is the literal multiplied by 2 to the power of the fractional bits and rounded. Will return
+    /// an `AsmValidationError` if the literal is not a number or is outside the range of the format.
+    pub fn to_raw(&self, literal:&str, line:&str) -> Result<i64, AsmValidationError> {
+        let value:f64 = literal.parse().ok().filter(|value:&f64| value.is_finite()).ok_or_else(|| {
+            AsmValidationError::coded("E0102", format!("{} is not a valid immediate on line {}", literal, line))
+        })?;
+
+        // the range is checked after rounding, so a value just above the largest rounds up out of range
+        let (min, max) = self.range();
+        let scale = (1u64 << self.fraction_bits) as f64;
+        let raw = (value * scale).round();
+        if raw < min * scale || raw > max * scale {
+            return Err(AsmValidationError::coded("E0105", format!(
+                "{} is outside the range of {} on line {}, which is {} to {}", literal, self.name(), line, min, max
+            )));
+        }
+
+        Ok(raw as i64)
+    }
+
+
+    /// Takes a raw fixed-point value and returns the words it is stored in, with the most significant first
+    pub fn to_words(&self, raw:i64) -> Vec<u16> {
+        (0..self.words()).rev().map(|index| (raw >> (index * 16)) as u16).collect()
+    }
+
+
+    /// Takes the words a value of the format is stored in and returns the value they hold
+    pub fn from_words(&self, words:&[u16]) -> f64 {
+        let bits = self.integer_bits + self.fraction_bits;
+        let raw = words.iter().fold(0i64, |raw, word| raw << 16 | *word as i64);
+        let raw = (raw << (64 - bits)) >> (64 - bits); // sign-extend
+        raw as f64 / (1u64 << self.fraction_bits) as f64
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed_point::*;
+
+
+    #[test]
+    fn test_fixed_point_conversion() {
+        let q8_8 = FixedPointFormat::from_data_type(".q8_8").unwrap();
+        assert_eq!(q8_8.to_words(q8_8.to_raw("1.5", "").unwrap()), vec![0x0180]);
+        assert_eq!(q8_8.to_words(q8_8.to_raw("-1.5", "").unwrap()), vec![0xFE80]);
+        assert_eq!(q8_8.to_words(q8_8.to_raw("127.99609375", "").unwrap()), vec![0x7FFF]);
+        assert_eq!(q8_8.from_words(&[0xFE80]), -1.5);
+
+        let q16_16 = FixedPointFormat::from_data_type("q16_16").unwrap();
+        assert_eq!(q16_16.to_words(q16_16.to_raw("3.14159265359", "").unwrap()), vec![0x0003, 0x243F]);
+        assert_eq!(q16_16.to_words(q16_16.to_raw("-32768", "").unwrap()), vec![0x8000, 0x0000]);
+        assert_eq!(q16_16.from_words(&[0xFFFF, 0x8000]), -0.5);
+        assert_eq!(FixedPointFormat::from_data_type(".half"), None);
+    }
+
+
+    #[test]
+    fn test_fixed_point_out_of_range() {
+        let q8_8 = FixedPointFormat::from_data_type(".q8_8").unwrap();
+        assert_eq!(q8_8.range(), (-128.0, 127.99609375));
+        assert!(q8_8.to_raw("128", "gain: .q8_8 128").unwrap_err().0.starts_with("[E0105]"));
+        assert!(q8_8.to_raw("127.999", "gain: .q8_8 127.999").unwrap_err().0.starts_with("[E0105]"));
+        assert!(q8_8.to_raw("-128.5", "gain: .q8_8 -128.5").unwrap_err().0.starts_with("[E0105]"));
+        assert!(q8_8.to_raw("inf", "gain: .q8_8 inf").unwrap_err().0.starts_with("[E0102]"));
+        assert!(q8_8.to_raw("0x10", "gain: .q8_8 0x10").unwrap_err().0.starts_with("[E0102]"));
+    }
+}
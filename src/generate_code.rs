@@ -1,13 +1,49 @@
 use std::fs::OpenOptions;
-use std::io::{BufWriter, Write};
+use std::io::{self, BufWriter, Write};
 use std::error::Error;
 use phf::phf_map;
-use crate::errors::TokenTypeError;
+use crate::errors::EncodingError;
+use crate::label_table::generate_token_addresses_with_page_size;
 use crate::token_types::FileTokens;
+use crate::version::output_header;
 
 
+/// Represents the kind of file the assembled program is written as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// The program binary, with a header recording the assembler and a marker in front of the data and text
+    #[default]
+    Ird,
 
-static OPCODE_BINARIES:phf::Map<&'static str, u16> = phf_map!{
+    /// The words of the program laid out at their addresses as given by `generate_memory_image`, with no
+    /// header, which can be loaded straight into memory or programmed into a single ROM
+    Bin
+}
+
+
+impl OutputFormat {
+    /// Every format, in the order they are listed by `--version`
+    pub const ALL:[OutputFormat; 2] = [OutputFormat::Ird, OutputFormat::Bin];
+
+
+    /// Takes the name of a format as given to `--format` and returns it, or `None` if there is no such format
+    pub fn from_name(name:&str) -> Option<OutputFormat> {
+        OutputFormat::ALL.into_iter().find(|format| format.name() == name)
+    }
+
+
+    /// Gets the name of the format as given to `--format`, which is also the extension of the target file
+    /// when only a source file is given
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputFormat::Ird => "ird",
+            OutputFormat::Bin => "bin"
+        }
+    }
+}
+
+
+pub static OPCODE_BINARIES:phf::Map<&'static str, u16> = phf_map!{
     "NOP"   => 0x0000,   "ADD"   => 0x1000, "SUB"   => 0x2000, "ADDI"  => 0x3000, "SUBI"  => 0x4000, 
     "SLL"   => 0x5000,   "SRL"   => 0x6000, "SRA"   => 0x7000, "NAND"  => 0x8000, "OR"    => 0x9000, 
     "LOAD"  => 0xA000,   "STORE" => 0xB000, "MOVUI" => 0xC000, "MOVLI" => 0xD000, "ADDC"  => 0xF000, 
@@ -16,25 +52,36 @@ static OPCODE_BINARIES:phf::Map<&'static str, u16> = phf_map!{
     "syscall" => 0xFC00, "ATOM"  => 0xFD00, "HALT"  => 0xFFFF
 };
 
-static REGISTER_BINARIES:phf::Map<&'static str, u16> = phf_map!{
+pub static REGISTER_BINARIES:phf::Map<&'static str, u16> = phf_map!{
     "$zero" => 0x0, "$g0" => 0x1, "$g1" => 0x2, "$g2" => 0x3, "$g3" => 0x4, "$g4" => 0x5, 
     "$g5"   => 0x6, "$g6" => 0x7, "$g7" => 0x8, "$g8" => 0x9, "$g9" => 0xA, "$ua" => 0xB, 
     "$sp"   => 0xC, "$fp" => 0xD, "$ra" => 0xE, "$pc" => 0xF
 };
 
 
+/// Takes a register operand of an instruction, where an operand which is left out is `$zero`, and returns its
+/// number, or an `EncodingError` if there is no such register.
+fn get_register_binary(register:&Option<String>) -> Result<u16, EncodingError> {
+    let register = register.as_deref().unwrap_or("$zero");
+    REGISTER_BINARIES.get(register).copied().ok_or_else(|| EncodingError(format!("{} is not a valid register", register)))
+}
+
+
 /// Takes a token in the form of a `FileTokens` struct and converts it into a vector f bytes which can be written to a file or printed.
-pub fn get_binary_from_tokens(tokens:FileTokens) -> Result<Vec<u16>, TokenTypeError> {
+/// Will return an `EncodingError` if the opcode or a register is not valid, or an immediate is missing.
+pub fn get_binary_from_tokens(tokens:FileTokens) -> Result<Vec<u16>, EncodingError> {
     match tokens {
         FileTokens::InstrTokens(t) => {
             let mut binary:u16 = 0x0000;
-            let opcode = *OPCODE_BINARIES.get(&t.opcode as &str).unwrap();
+            let opcode = *OPCODE_BINARIES.get(&t.opcode as &str)
+                .ok_or_else(|| EncodingError(format!("{} is not a valid opcode", t.opcode)))?;
+            let immediate = t.immediate.ok_or_else(|| EncodingError(format!("{} is missing its immediate", t.opcode)));
             binary |= opcode;
 
             // Insert the opcode and first register into the binary instruction based on if the opcode is 4 or 8 bits unless it is a 
             // syscall, in which case skip as there is no register, only immediate
             if opcode != 0xFC00 {
-                let register_a:u16 = *REGISTER_BINARIES.get(&t.clone().operand_a.unwrap_or("$zero".to_owned()) as &str).unwrap() as u16;
+                let register_a:u16 = get_register_binary(&t.operand_a)?;
                 if binary & 0xF000 == 0xF000 {
                     binary |= register_a << 4;
                 } else {
@@ -48,105 +95,223 @@ pub fn get_binary_from_tokens(tokens:FileTokens) -> Result<Vec<u16>, TokenTypeEr
                 },
 
                 0x1000 | 0x2000 | 0x5000 | 0x6000 | 0x7000 | 0x8000 | 0x9000 | 0xA000 | 0xB000 => { // rrr format
-                    binary |= (*REGISTER_BINARIES.get(&t.operand_b.unwrap_or("$zero".to_owned()) as &str).unwrap() << 4) as u16;
-                    binary |= *REGISTER_BINARIES.get(&t.operand_c.unwrap_or("$zero".to_owned()) as &str).unwrap() as u16;
+                    binary |= get_register_binary(&t.operand_b)? << 4;
+                    binary |= get_register_binary(&t.operand_c)?;
                 },
 
                 0x3000 | 0x4000 => { // rri format
-                    binary |= (*REGISTER_BINARIES.get(&t.operand_b.unwrap_or("$zero".to_owned()) as &str).unwrap() << 4) as u16;
-                    binary |= (t.immediate.unwrap() & 0x000F) as u16; // TODO: this could be unsafe? 
+                    binary |= get_register_binary(&t.operand_b)? << 4;
+                    binary |= (immediate? & 0x000F) as u16;
                 },
 
                 0xC000 | 0xD000 => { // rii format
-                    binary |= (*REGISTER_BINARIES.get(&t.operand_b.unwrap_or("$zero".to_owned()) as &str).unwrap() << 4) as u16;
-                    binary |= (t.immediate.unwrap() & 0x00FF) as u16;
+                    binary |= get_register_binary(&t.operand_b)? << 4;
+                    binary |= (immediate? & 0x00FF) as u16;
                 },
 
                 0xF000 | 0xF100 | 0xF200 | 0xF300 | 0xF400 | 0xF500 | 0xF600 | 0xF700 | 0xF800 => { // orr format
-                    binary |= *REGISTER_BINARIES.get(&t.operand_b.unwrap_or("$zero".to_owned()) as &str).unwrap() as u16;
+                    binary |= get_register_binary(&t.operand_b)?;
                 },
 
                 0xF900 | 0xFA00 => { // ori format
-                    binary |= (t.immediate.unwrap() & 0x000F) as u16;
+                    binary |= (immediate? & 0x000F) as u16;
                 },
 
                 0xFC00 => {
-                    binary |= (t.immediate.unwrap() & 0x00FF) as u16;
+                    binary |= (immediate? & 0x00FF) as u16;
                 },
 
-                _ => { // TODO: replace with an error
-                    return Err(TokenTypeError(format!("{} is not a valid opcode", opcode)));
+                _ => {
+                    return Err(EncodingError(format!("{} is not a valid opcode", t.opcode)));
                 }
             }
-            return Ok(vec![binary]);
+            Ok(vec![binary])
         },
 
         FileTokens::DataTokens(t) => {
-            return Ok(t.bytes);
+            Ok(t.bytes)
         },
 
         FileTokens::TextTokens(t) => {
-            return Ok(t.bytes);
+            Ok(t.bytes)
+        },
+
+        FileTokens::BssTokens(_) | FileTokens::OrgTokens(_) | FileTokens::AliasTokens(_) | FileTokens::DeprecatedTokens(_) | FileTokens::TraceTokens(_)
+            | FileTokens::ExportTableTokens(_) => {
+            Ok(Vec::new())
+        }
+    }
+}
+
+
+/// Takes a stream of tokens and the number of words in a page, and returns the address the bss section starts
+/// at and its size in words, or `None` if the program has no bss section.
+pub fn get_bss_extent(tokens:&[FileTokens], page_size:i64) -> Option<(i64, i64)> {
+    let mut extent:Option<(i64, i64)> = None;
+    for (token, addr) in tokens.iter().zip(generate_token_addresses_with_page_size(tokens, page_size)) {
+        if let FileTokens::BssTokens(t) = token {
+            let start = extent.map(|(start, _)| start).unwrap_or(addr);
+            extent = Some((start, addr + t.size as i64 - start));
         }
     }
+
+    extent
 }
 
 
-/// Takes a `Vec<FileTokens>` as input and converts it to binary[0], then writes it to the given file
-pub fn generate_binary(filename:&str, tokens:&Vec<FileTokens>) -> Result<(), Box<dyn Error>> {
+/// Takes a `Vec<FileTokens>` as input and converts it to the bytes of a program binary, with its sections on
+/// pages of `page_size` words. If `with_header` is true, the binary starts with a header recording the
+/// assembler which produced it, which is left out of fragments that are patched over an existing binary. The
+/// bss section is not included, but its address and size are recorded in the header so it can be filled with
+/// zeros when loaded, as is the page size if it is not `PAGE_SIZE`.
+///
+/// Any gap left by an `.org` is filled with `fill_word`, such as 0xFFFF to leave flash memory unprogrammed,
+/// while the padding added by an `.align` is always zeros.
+pub fn generate_binary_bytes(tokens:&[FileTokens], with_header:bool, fill_word:u16, page_size:i64) -> Result<Vec<u8>, EncodingError> {
     let mut section_mode = 'c';
-    let mut output_file = BufWriter::new(
-        OpenOptions::new().create(true).write(true).open(filename.to_owned()).unwrap());
-    let mut text_instrs:Vec<FileTokens> = Vec::new(); // These are for the text section, processed last
+    let mut binary_bytes:Vec<u8> = Vec::new();
+    if with_header {
+        binary_bytes.extend(output_header(get_bss_extent(tokens, page_size), page_size));
+    }
+    let mut text_words:Vec<u16> = Vec::new(); // These are for the text section, processed last
     
-    for token in tokens {
-        let binary_vec = match token {
-            FileTokens::InstrTokens(_) => get_binary_from_tokens(token.clone()).unwrap(),
-            FileTokens::TextTokens(_) => {
-                text_instrs.push(token.clone());
-                continue;
-            },
+    for (token, addr) in tokens.iter().zip(generate_token_addresses_with_page_size(tokens, page_size)) {
+        let (section, binary_vec) = match token {
+            FileTokens::InstrTokens(_) => ('c', get_binary_from_tokens(token.clone())?),
+            FileTokens::DataTokens(_) => ('d', get_binary_from_tokens(token.clone())?),
+            FileTokens::TextTokens(_) => ('t', get_binary_from_tokens(token.clone())?),
+            FileTokens::BssTokens(_) | FileTokens::AliasTokens(_) | FileTokens::DeprecatedTokens(_) | FileTokens::TraceTokens(_)
+                | FileTokens::ExportTableTokens(_) => continue,
+            FileTokens::OrgTokens(t) => {
+                let fill_word = if t.alignment.is_some() { 0 } else { fill_word };
+                (t.section, vec![fill_word; (t.target(addr) - addr).max(0) as usize])
+            }
+        };
 
-            FileTokens::DataTokens(_) => {
+        match section {
+            'c' => (),
+            'd' => {
                 // switch to data mode if a non-text data instr is found
                 if section_mode == 'c' {
                     section_mode = 'd';
-                    output_file.write("data:\0".as_bytes())?;
+                    binary_bytes.extend("data:\0".as_bytes());
                 }
-                
-                get_binary_from_tokens(token.clone()).unwrap()
-            }
-        };
+            },
+
+            't' => {
+                text_words.extend(binary_vec);
+                continue;
+            },
+
+            _ => continue
+        }
 
-        // write instr to file
+        // write instr to binary
         for binary in binary_vec {
-            output_file.write(&[(binary & 0x00FF) as u8])?;
-            output_file.write(&[((binary & 0xFF00) >> 8) as u8])?;
+            binary_bytes.push((binary & 0x00FF) as u8);
+            binary_bytes.push(((binary & 0xFF00) >> 8) as u8);
         }
     }
 
-    if !text_instrs.is_empty() {
-        output_file.write("text:\0".as_bytes())?;
+    if !text_words.is_empty() {
+        binary_bytes.extend("text:\0".as_bytes());
         
-        for token in text_instrs {
-            for binary in get_binary_from_tokens(token.clone()).unwrap() {
-                output_file.write(&[(binary & 0x00FF) as u8])?;
-                output_file.write(&[((binary & 0xFF00) >> 8) as u8])?;
-            }
+        for binary in text_words {
+            binary_bytes.push((binary & 0x00FF) as u8);
+            binary_bytes.push(((binary & 0xFF00) >> 8) as u8);
         }
     }
 
-    output_file.flush().unwrap();
+    Ok(binary_bytes)
+}
+
+
+/// Takes a `Vec<FileTokens>` and the number of words in a page, and returns the words of the program laid out
+/// at their addresses, as they would be in memory, starting from address 0 and ending with the last word of the program. Unlike the program
+/// binary, there is no header and there are no section markers, so the word at each index is the word at that
+/// address. The gaps between sections and any gap left by an `.org` are filled with `fill_word`, while the
+/// padding added by an `.align` is always zeros. The bss section is not included.
+pub fn generate_memory_image(tokens:&[FileTokens], fill_word:u16, page_size:i64) -> Result<Vec<u16>, EncodingError> {
+    let mut image:Vec<u16> = Vec::new();
+    for (token, addr) in tokens.iter().zip(generate_token_addresses_with_page_size(tokens, page_size)) {
+        let words = match token {
+            FileTokens::InstrTokens(_) | FileTokens::DataTokens(_) | FileTokens::TextTokens(_) => get_binary_from_tokens(token.clone())?,
+            FileTokens::OrgTokens(t) if t.alignment.is_some() => vec![0; (t.target(addr) - addr).max(0) as usize],
+            FileTokens::OrgTokens(t) => vec![fill_word; (t.target(addr) - addr).max(0) as usize],
+            FileTokens::BssTokens(_) | FileTokens::AliasTokens(_) | FileTokens::DeprecatedTokens(_) | FileTokens::TraceTokens(_)
+                | FileTokens::ExportTableTokens(_) => continue
+        };
+
+        let start = addr as usize;
+        if image.len() < start + words.len() {
+            image.resize(start + words.len(), fill_word);
+        }
+
+        image[start..start + words.len()].copy_from_slice(&words);
+    }
+
+    Ok(image)
+}
+
+
+/// Takes a `Vec<FileTokens>` and returns the bytes of the file it is written as in the given format, with its
+/// sections on pages of `page_size` words. A program
+/// binary has a header unless `with_header` is false, while a memory image never has one.
+pub fn generate_output_bytes(tokens:&[FileTokens], format:OutputFormat, with_header:bool, fill_word:u16, page_size:i64)
+        -> Result<Vec<u8>, EncodingError> {
+    match format {
+        OutputFormat::Ird => generate_binary_bytes(tokens, with_header, fill_word, page_size),
+        OutputFormat::Bin => Ok(generate_memory_image(tokens, fill_word, page_size)?.iter().flat_map(|word| word.to_le_bytes()).collect())
+    }
+}
+
+
+/// Takes a `Vec<FileTokens>` as input and converts it to the given format using `generate_output_bytes`,
+/// then writes it to the given writer, such as a file or stdout.
+pub fn write_binary<W:Write>(writer:&mut W, tokens:&[FileTokens], format:OutputFormat, with_header:bool, fill_word:u16, page_size:i64)
+        -> Result<(), Box<dyn Error>> {
+    writer.write_all(&generate_output_bytes(tokens, format, with_header, fill_word, page_size)?)?;
+    writer.flush()?;
     Ok(())
 }
 
 
+/// Takes a `Vec<FileTokens>` as input and writes it in the given format to the given file using
+/// `write_binary`, or to stdout if the filename is `-`.
+pub fn generate_binary(filename:&str, tokens:&[FileTokens], format:OutputFormat, with_header:bool, fill_word:u16, page_size:i64)
+        -> Result<(), Box<dyn Error>> {
+    if filename == "-" {
+        return write_binary(&mut io::stdout().lock(), tokens, format, with_header, fill_word, page_size);
+    }
+
+    let mut output_file = BufWriter::new(
+        OpenOptions::new().create(true).write(true).truncate(true).open(filename)?);
+    write_binary(&mut output_file, tokens, format, with_header, fill_word, page_size)
+}
+
+
 #[cfg(test)]
 mod tests {
     use crate::generate_code::*;
+    use crate::label_table::PAGE_SIZE;
+    use crate::pseudo_substitution::substitute_pseudo_instrs;
+    use crate::testing::tokens_from_str;
     use crate::token_types::*;
 
 
+    #[test]
+    fn test_output_formats() {
+        let tokens = substitute_pseudo_instrs(tokens_from_str("init:\n    HALT\ndata:\nvalue: .int 0x1234\n"));
+        let image = generate_output_bytes(&tokens, OutputFormat::Bin, true, 0xAAAA, PAGE_SIZE).unwrap();
+        assert_eq!(image.len(), 0x2002);
+        assert_eq!((&image[..4], &image[0x2000..]), (&[0xFF, 0xFF, 0xAA, 0xAA][..], &[0x34, 0x12][..]));
+        assert_eq!(generate_output_bytes(&tokens, OutputFormat::Ird, true, 0xAAAA, PAGE_SIZE).unwrap(), generate_binary_bytes(&tokens, true, 0xAAAA, PAGE_SIZE).unwrap());
+
+        assert_eq!(OutputFormat::from_name("bin"), Some(OutputFormat::Bin));
+        assert_eq!(OutputFormat::from_name("hex"), None);
+    }
+
+
     #[test]
     fn test_nop_token() {
         let token = FileTokens::InstrTokens(InstrTokens::new(None, "NOP".to_string(), None, None, None, None, None));
@@ -307,4 +472,47 @@ mod tests {
         assert_eq!(binary[3], 0x1000);
         assert_eq!(binary[4], 0x0000);
     }
+
+
+    #[test]
+    fn test_bss_extent() {
+        let tokens = vec![
+            FileTokens::InstrTokens(InstrTokens::new(None, "HALT".to_string(), None, None, None, None, None)),
+            FileTokens::BssTokens(BssTokens::new(Some("buffer".to_string()), 0x100)),
+            FileTokens::BssTokens(BssTokens::new(Some("scratch".to_string()), 0x10))
+        ];
+
+        assert!(get_binary_from_tokens(tokens[1].clone()).unwrap().is_empty());
+        assert_eq!(get_bss_extent(&tokens, PAGE_SIZE), Some((0x1000, 0x110)));
+        assert_eq!(get_bss_extent(&tokens[..1], PAGE_SIZE), None);
+    }
+
+
+    #[test]
+    fn test_write_binary() {
+        let tokens = vec![
+            FileTokens::InstrTokens(InstrTokens::new(None, "HALT".to_string(), None, None, None, None, None)),
+            FileTokens::DataTokens(DataTokens::new(Some("count".to_string()), "int".to_string(), vec![0x1234]))
+        ];
+
+        let mut writer:Vec<u8> = Vec::new();
+        write_binary(&mut writer, &tokens, OutputFormat::Ird, false, 0x0000, PAGE_SIZE).unwrap();
+        assert_eq!(writer, vec![0xFF, 0xFF, b'd', b'a', b't', b'a', b':', 0x00, 0x34, 0x12]);
+        assert_eq!(writer, generate_binary_bytes(&tokens, false, 0x0000, PAGE_SIZE).unwrap());
+    }
+
+
+    #[test]
+    fn test_invalid_encoding() {
+        let token = FileTokens::InstrTokens(InstrTokens::new(None, "ADD".to_string(), Some("$g0".to_string()),
+            Some("$g99".to_string()), None, None, None));
+        assert_eq!(get_binary_from_tokens(token).unwrap_err().0, "$g99 is not a valid register");
+
+        let token = FileTokens::InstrTokens(InstrTokens::new(None, "ADDI".to_string(), Some("$g0".to_string()), None, None, None, None));
+        assert_eq!(get_binary_from_tokens(token.clone()).unwrap_err().0, "ADDI is missing its immediate");
+        assert!(generate_binary_bytes(&[token], true, 0x0000, PAGE_SIZE).is_err());
+
+        let token = FileTokens::InstrTokens(InstrTokens::new(None, "FLY".to_string(), None, None, None, None, None));
+        assert_eq!(get_binary_from_tokens(token).unwrap_err().0, "FLY is not a valid opcode");
+    }
 }
@@ -0,0 +1,358 @@
+use crate::errors::AsmValidationError;
+use crate::interrupts::{restore_registers, return_label, save_registers};
+use crate::preprocessor::SourceLine;
+use crate::validation::{validate_int_immediate, validate_label};
+
+
+/// The word pushed onto the stack on entry to a hardened function and checked before it returns. It is
+/// fixed rather than random so that builds are reproducible, which is enough to catch an overflow in a lab.
+pub const CANARY:u16 = 0xC0DE;
+
+/// The registers the inserted checks use, which do not keep their values across a check
+pub const SCRATCH_REGISTERS:[&str; 2] = ["$g8", "$g9"];
+
+
+/// Takes a line of assembly and returns it without its comment or surrounding whitespace
+pub fn strip_comment(line:&str) -> &str {
+    line[..line.find(';').unwrap_or(line.len())].trim()
+}
+
+
+/// Takes a line of assembly and returns its label, if it has one, and the rest of the line
+pub fn split_label(line:&str) -> (Option<&str>, &str) {
+    match line.split_once(':') {
+        Some((label, rest)) if !label.contains(char::is_whitespace) => (Some(label), rest.trim()),
+        _ => (None, line)
+    }
+}
+
+
+/// Takes an instruction without its label and returns its opcode and operands
+pub fn split_instr(instr:&str) -> (&str, Vec<&str>) {
+    let (opcode, operands) = instr.split_once(char::is_whitespace).unwrap_or((instr, ""));
+    (opcode, operands.split(',').map(|operand| operand.trim()).filter(|operand| !operand.is_empty()).collect())
+}
+
+
+/// Takes an instruction without its label and returns true if it returns from a function, which is a jump
+/// to `$ra`
+fn is_return(instr:&str) -> bool {
+    matches!(split_instr(instr), ("JUMP", operands) if operands == ["$ra"] || operands == ["$zero", "$ra"])
+}
+
+
+/// Returns the lines which load the given word into `$g9`
+fn load_scratch(word:u16) -> [String; 2] {
+    [format!("MOVLI $g9, {:#04X}", word & 0xFF), format!("MOVUI $g9, {:#04X}", word >> 8)]
+}
+
+
+/// Returns the lines which push the canary below the return address on entry to a function
+fn canary_setup() -> Vec<String> {
+    let mut lines = load_scratch(CANARY).to_vec();
+    lines.push("SUBI $sp, $sp, 1".to_owned());
+    lines.push("STORE $g9, $sp, $zero".to_owned());
+    lines
+}
+
+
+/// Returns the lines which pop the canary before a function returns and branch to the failure handler if
+/// it has been overwritten
+fn canary_check(fail_label:&str) -> Vec<String> {
+    let mut lines = vec!["LOAD $g8, $sp, $zero".to_owned(), "ADDI $sp, $sp, 1".to_owned()];
+    lines.extend(load_scratch(CANARY));
+    lines.push("CMP $g8, $g9".to_owned());
+    lines.push(format!("BNE $g8, $g9, @{}", fail_label));
+    lines
+}
+
+
+/// Returns the lines which branch to the failure handler unless the index register is at least 0 and less
+/// than the limit, which `.switch` also uses to send an index with no case to the default
+pub fn bounds_check(index:&str, limit:u16, fail_label:&str) -> Vec<String> {
+    let mut lines = vec![format!("CMP {}, $zero", index), format!("BGT $g8, $g9, @{}", fail_label)];
+    lines.extend(load_scratch(limit));
+    lines.push(format!("CMP {}, $g9", index));
+    lines.push(format!("BEQ $g8, $g9, @{}", fail_label));
+    lines.push(format!("BLT $g8, $g9, @{}", fail_label));
+    lines
+}
+
+
+/// Takes a line of assembly holding a bounds annotation in the form `.bounds <limit>` and returns the
+/// limit, or an `AsmValidationError` if it is missing or is not between 1 and 0x7FFF, as indexes are
+/// compared as signed words.
+fn parse_bounds_directive(line:&str) -> Result<u16, AsmValidationError> {
+    let tokens:Vec<&str> = line.split_whitespace().collect();
+    if tokens.len() != 2 {
+        return Err(AsmValidationError(format!("Incorrect format for .bounds on line {}, which should be .bounds <limit>", line)));
+    }
+
+    match validate_int_immediate(tokens[1], 16, false)? {
+        limit @ 1..=0x7FFF => Ok(limit as u16),
+        limit => Err(AsmValidationError(format!("The limit {} of .bounds on line {} must be between 1 and 0x7FFF", limit, line)))
+    }
+}
+
+
+/// Takes the logical lines of a source file and expands the `.func <name>` and `.endfunc` directives around
+/// each function into its label, and removes the `.bounds <limit>` annotations on the line before a `LOAD`
+/// or `STORE` in a function. If `harden` is true, checks for teaching memory safety are inserted as well:
+///  - On entry to every function, a canary word is pushed onto the stack, and before every `JUMP $ra` it
+///    is popped and compared with the value it should have.
+///  - Before every annotated access, its index, which is the third register, is checked to be at least 0
+///    and less than the limit.
+///
+/// A failed check branches to the label `<name>_check_fail` after the function, which halts. The checks
+/// use `$g8` and `$g9` as scratch registers, so their values are not kept across a check, and the stack
+/// must be back where it was on entry when a function returns. Each inserted line keeps the number of the
+/// line it was inserted for so that diagnostics point at the right place.
+///
+/// A function with `.isr` on the line before its `.func` is an interrupt service routine, which saves every
+/// one of `interrupts::SAVED_REGISTERS` on entry. Every `JUMP $ra` in it instead jumps to the label
+/// `<name>_isr_return` at its end, which restores them and returns, so `$g8` and `$g9` hold the address of
+/// that label rather than their values on a return.
+///
+/// Will return an `AsmValidationError` if a function is not in the code section, is nested in another, or
+/// is never closed, if `.isr` does not come before a `.func`, or if an annotation does not come before a
+/// `LOAD` or `STORE` with three registers in a function, or its access uses one of the scratch registers.
+pub fn expand_functions(source_lines:Vec<SourceLine>, harden:bool) -> Result<Vec<SourceLine>, AsmValidationError> {
+    let mut expanded:Vec<SourceLine> = Vec::new();
+    let mut function:Option<(String, String, bool)> = None;
+    let mut bounds:Option<(u16, usize)> = None;
+    let mut isr:Option<String> = None;
+    let mut in_code = true;
+    for source_line in source_lines {
+        let line_num = source_line.line_num;
+        let file = source_line.file.clone();
+        let location = source_line.location();
+        let line = strip_comment(&source_line.text).to_owned();
+        let mut push = |text:String| expanded.push(SourceLine { line_num, text, file: file.clone() });
+        if isr.is_some() && !line.is_empty() && line.split_whitespace().next() != Some(".func") {
+            return Err(AsmValidationError(format!("{}: .isr must come on the line before a .func", location)));
+        }
+
+        match line.split_whitespace().next() {
+            Some("data:" | "text:" | "bss:") => in_code = false,
+            Some(".func") => {
+                let name = match line.split_whitespace().collect::<Vec<&str>>()[..] {
+                    [_, name] => name.to_owned(),
+                    _ => return Err(source_line.locate(AsmValidationError(format!(
+                        "Incorrect format for .func on line {}, which should be .func <name>", line
+                    ))))
+                };
+
+                validate_label(&line, &name).map_err(|e| source_line.locate(e))?;
+                if !in_code {
+                    return Err(AsmValidationError(format!("{}: the function {} must be in the code section", location, name)));
+                } else if let Some((outer, outer_location, _)) = &function {
+                    return Err(AsmValidationError(format!(
+                        "{}: the function {} is inside the function {} from {}, which has no .endfunc", location, name, outer, outer_location
+                    )));
+                }
+
+                let is_isr = isr.take().is_some();
+                push(format!("{}:", name));
+                if is_isr {
+                    save_registers().into_iter().for_each(&mut push);
+                }
+
+                if harden {
+                    canary_setup().into_iter().for_each(&mut push);
+                }
+
+                function = Some((name, location, is_isr));
+                continue;
+            },
+
+            Some(".endfunc") => {
+                let (name, _, is_isr) = function.take()
+                    .ok_or_else(|| AsmValidationError(format!("{}: .endfunc has no .func", location)))?;
+                if bounds.is_some() {
+                    return Err(AsmValidationError(format!("{}: .bounds must come before a LOAD or STORE", location)));
+                }
+
+                if is_isr {
+                    push(format!("{}:", return_label(&name)));
+                    restore_registers().into_iter().for_each(&mut push);
+                }
+
+                if harden {
+                    push(format!("{}_check_fail:", name));
+                    push("HALT".to_owned());
+                }
+
+                continue;
+            },
+
+            Some(".bounds") => {
+                if function.is_none() || bounds.is_some() {
+                    return Err(AsmValidationError(format!("{}: .bounds must come before a LOAD or STORE in a function", location)));
+                }
+
+                bounds = Some((parse_bounds_directive(&line).map_err(|e| source_line.locate(e))?, line_num));
+                continue;
+            },
+
+            Some(".isr") => {
+                if line != ".isr" {
+                    return Err(AsmValidationError(format!("{}: .isr takes no operands", location)));
+                } else if function.is_some() {
+                    return Err(AsmValidationError(format!("{}: .isr must come on the line before a .func", location)));
+                }
+
+                isr = Some(location);
+                continue;
+            },
+
+            _ => ()
+        }
+
+        let (label, instr) = split_label(&line);
+        let checks = match (&function, bounds.take()) {
+            (_, Some((limit, _))) if !instr.is_empty() => {
+                let (opcode, operands) = split_instr(instr);
+                if !["LOAD", "STORE"].contains(&opcode) || operands.len() != 3 {
+                    return Err(AsmValidationError(format!(
+                        "{}: .bounds must come before a LOAD or STORE with three registers, not {}", location, instr
+                    )));
+                }
+
+                if operands.iter().any(|operand| SCRATCH_REGISTERS.contains(operand)) {
+                    return Err(AsmValidationError(format!(
+                        "{}: the checked access {} cannot use $g8 or $g9, which the bounds check uses", location, instr
+                    )));
+                }
+
+                bounds_check(operands[2], limit, &format!("{}_check_fail", function.as_ref().unwrap().0))
+            },
+
+            (_, Some(pending)) => {
+                // the annotation stays with the instruction after a label on its own line
+                bounds = Some(pending);
+                Vec::new()
+            },
+
+            (Some((name, _, _)), None) if is_return(instr) => canary_check(&format!("{}_check_fail", name)),
+            _ => Vec::new()
+        };
+
+        let isr_return = match &function {
+            Some((name, _, true)) if is_return(instr) => Some(format!("JUMP $g8, $g9, @{}", return_label(name))),
+            _ => None
+        };
+
+        let checks = if harden { checks } else { Vec::new() };
+        if checks.is_empty() && isr_return.is_none() {
+            expanded.push(source_line);
+            continue;
+        }
+
+        // the label of the instruction now marks the start of its checks
+        if let Some(label) = label {
+            push(format!("{}:", label));
+        }
+
+        checks.into_iter().for_each(&mut push);
+        push(isr_return.unwrap_or_else(|| instr.to_owned()));
+    }
+
+    if let Some((name, location, _)) = function {
+        return Err(AsmValidationError(format!("{}: the function {} has no .endfunc", location, name)));
+    } else if let Some(location) = isr {
+        return Err(AsmValidationError(format!("{}: .isr must come on the line before a .func", location)));
+    }
+
+    Ok(expanded)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use crate::hardening::*;
+    use crate::preprocessor::join_continuation_lines;
+    use crate::process_reader_into_tokens;
+    use crate::token_types::FileTokens;
+
+
+    fn to_lines(source:&str) -> Vec<SourceLine> {
+        join_continuation_lines(source.lines().map(|line| line.to_owned()).collect()).unwrap()
+    }
+
+
+    fn expand(source:&str, harden:bool) -> Result<Vec<String>, AsmValidationError> {
+        Ok(expand_functions(to_lines(source), harden)?.into_iter().map(|line| line.text).collect())
+    }
+
+
+    #[test]
+    fn test_expand_functions() {
+        let source = ".func double\n    ADD $g0, $g0, $g0\n    JUMP $ra ; return\n.endfunc\n";
+        assert_eq!(expand(source, false).unwrap(), vec!["double:", "    ADD $g0, $g0, $g0", "    JUMP $ra ; return"]);
+
+        let expanded = expand(source, true).unwrap();
+        assert_eq!(expanded[..5], ["double:", "MOVLI $g9, 0xDE", "MOVUI $g9, 0xC0", "SUBI $sp, $sp, 1", "STORE $g9, $sp, $zero"]);
+        assert_eq!(expanded[6..13], [
+            "LOAD $g8, $sp, $zero", "ADDI $sp, $sp, 1", "MOVLI $g9, 0xDE", "MOVUI $g9, 0xC0", "CMP $g8, $g9",
+            "BNE $g8, $g9, @double_check_fail", "JUMP $ra"
+        ]);
+        assert_eq!(expanded[13..], ["double_check_fail:", "HALT"]);
+    }
+
+
+    #[test]
+    fn test_hardened_isr() {
+        let source = ".isr\n.func tick\n    JUMP $ra\n.endfunc\n";
+        let expanded = expand(source, true).unwrap();
+        assert_eq!(expanded[23..27], ["MOVLI $g9, 0xDE", "MOVUI $g9, 0xC0", "SUBI $sp, $sp, 1", "STORE $g9, $sp, $zero"]);
+        assert_eq!(expanded[32..35], ["BNE $g8, $g9, @tick_check_fail", "JUMP $g8, $g9, @tick_isr_return", "tick_isr_return:"]);
+        assert_eq!(expanded[expanded.len() - 3..], ["JUMP $ra", "tick_check_fail:", "HALT"]);
+    }
+
+
+    #[test]
+    fn test_bounds_check() {
+        let source = ".func get\n    .bounds 16\nread: LOAD $g0, $g1, $g2\n    JUMP $zero, $ra\n.endfunc\n";
+        assert_eq!(expand(source, false).unwrap(), vec!["get:", "read: LOAD $g0, $g1, $g2", "    JUMP $zero, $ra"]);
+
+        let expanded = expand(source, true).unwrap();
+        assert_eq!(expanded[5..14], [
+            "read:", "CMP $g2, $zero", "BGT $g8, $g9, @get_check_fail", "MOVLI $g9, 0x10", "MOVUI $g9, 0x00",
+            "CMP $g2, $g9", "BEQ $g8, $g9, @get_check_fail", "BLT $g8, $g9, @get_check_fail", "LOAD $g0, $g1, $g2"
+        ]);
+    }
+
+
+    #[test]
+    fn test_hardened_program_assembles() {
+        let source = fs::read_to_string("test_files/test_hardening.asm").unwrap();
+        let plain = process_reader_into_tokens(source.as_bytes(), "test_hardening.asm", None, false).unwrap();
+        let hardened = process_reader_into_tokens(source.as_bytes(), "test_hardening.asm", None, true).unwrap();
+        assert!(hardened.len() > plain.len());
+
+        let labels:Vec<String> = hardened.iter().filter_map(|token| match token {
+            FileTokens::InstrTokens(t) => t.label.clone(),
+            _ => None
+        }).collect();
+        assert!(labels.contains(&"sum_check_fail".to_owned()));
+        assert!(crate::assemble_tokens(hardened).is_ok());
+    }
+
+
+    #[test]
+    fn test_invalid_functions() {
+        assert!(expand(".func a\n.func b\n.endfunc\n.endfunc\n", true).unwrap_err().0.contains("inside the function a"));
+        assert!(expand(".func a\n    HALT\n", false).unwrap_err().0.contains("has no .endfunc"));
+        assert!(expand(".endfunc\n", false).is_err());
+        assert!(expand("data:\n.func a\n.endfunc\n", false).is_err());
+        assert!(expand(".func 1a\n.endfunc\n", false).is_err());
+        assert!(expand(".isr\n    HALT\n.func a\n.endfunc\n", false).unwrap_err().0.contains("before a .func"));
+        assert!(expand(".func a\n.isr\n.endfunc\n", false).is_err());
+        assert!(expand(".isr\n", false).is_err());
+        assert!(expand("    .bounds 4\n    LOAD $g0, $g1, $g2\n", true).is_err());
+        assert!(expand(".func a\n    .bounds 4\n    ADD $g0, $g1, $g2\n.endfunc\n", true).is_err());
+        assert!(expand(".func a\n    .bounds 4\n    STORE $g0, $g1, $g9\n.endfunc\n", true).is_err());
+        assert!(expand(".func a\n    .bounds 0x8000\n    LOAD $g0, $g1, $g2\n.endfunc\n", true).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use crate::errors::AsmValidationError;
+use crate::expressions::{evaluate_int_expression, is_int_expression};
+use crate::isa::{Isa, OperandKind};
+use crate::validation::{remove_label, validate_int_immediate};
+
+
+/// The start of each immediate part operator, up to its opening bracket
+pub const PART_OPERATORS:[&str; 3] = ["IMM(", "%hi(", "%lo("];
+
+
+/// Takes an operand and, if it is an immediate part operator such as `IMM(0x1234, 1)`, `%hi(0x1234)`, or
+/// `%lo(0x1234)`, returns the value and the index of the part it selects. `%lo` is part 0 and `%hi` is
+/// part 1.
+fn parse_part_operator(operand:&str) -> Option<(&str, &str)> {
+    let (name, rest) = operand.split_once('(')?;
+    let args = rest.strip_suffix(')')?;
+    match name {
+        "IMM" => Some(args.split_once(',').map(|(value, part)| (value.trim(), part.trim())).unwrap_or((args.trim(), ""))),
+        "%lo" => Some((args.trim(), "0")),
+        "%hi" => Some((args.trim(), "1")),
+        _ => None
+    }
+}
+
+
+/// Takes the text of an operator starting at its name and returns the index of the bracket closing its
+/// opening bracket, counting the depth of the brackets in its value so `%hi((0x12 + 1) * 0x100)` is not
+/// ended early, or `None` if it is never closed.
+fn find_closing_bracket(text:&str) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 1 => return Some(index),
+            ')' => depth -= 1,
+            _ => ()
+        }
+    }
+
+    None
+}
+
+
+/// Takes the value of an immediate part operator, which is an integer that fits into 32 bits, a constant
+/// defined with `.equ`, or an expression of them, and returns it, or an `AsmValidationError` if it is none.
+fn get_part_value(value:&str, line:&str, constants:&HashMap<String, i64>) -> Result<i64, AsmValidationError> {
+    if is_int_expression(value) {
+        return evaluate_int_expression(value, constants).map_err(|e| AsmValidationError(format!("{} on line {}", e.0, line)));
+    } else if value.starts_with(|c:char| c.is_alphabetic() || c == '_') {
+        return constants.get(value).copied()
+            .ok_or_else(|| AsmValidationError(format!("{} on line {} is not a defined constant", value, line)));
+    }
+
+    validate_int_immediate(value, 32, true)
+}
+
+
+/// Takes a line of assembly and, if any immediate operand is an immediate part operator, returns the line
+/// with the operator replaced by the part of the value it selects, so it can be validated and tokenized as a
+/// normal immediate. The value is split into parts as wide as the field of the operand in the ISA table,
+/// counting from the least significant bits, so `IMM(0x1234, 1)` is `0x12` as the 8-bit immediate of
+/// `MOVUI` but `0x3` as the 4-bit immediate of `ADDI`. A negative value is split as its 32-bit two's
+/// complement.
+///
+/// Will return an `AsmValidationError` if the operator is malformed, is not used for an immediate
+/// operand, names a constant which has not been defined, or selects a part beyond the 32 bits of the value.
+pub fn substitute_immediate_parts(line:&str, constants:&HashMap<String, i64>) -> Result<String, AsmValidationError> {
+    let instr = remove_label(line);
+    let opcode = instr.split_whitespace().next().unwrap_or("");
+    let spec = match Isa::lookup(opcode) {
+        Some(spec) => spec,
+        None => return Ok(line.to_owned())
+    };
+
+    // earlier operators are replaced by a number, so counting the commas before one gives its operand index
+    let operands_start = line.find(instr).unwrap_or(0) + opcode.len();
+    let mut line = line.to_owned();
+    loop {
+        let operands_end = line.find(';').unwrap_or(line.len()).max(operands_start);
+        let start = match PART_OPERATORS.iter().filter_map(|name| line[operands_start..operands_end].find(name)).min() {
+            Some(start) => operands_start + start,
+            None => break
+        };
+
+        let end = find_closing_bracket(&line[start..operands_end]).map(|end| start + end + 1)
+            .ok_or_else(|| AsmValidationError(format!("The operator on line {} is missing a closing bracket", line)))?;
+        let operator = &line[start..end];
+        let (value, part) = parse_part_operator(operator)
+            .ok_or_else(|| AsmValidationError(format!("{} on line {} should be in the form IMM(value, part)", operator, line)))?;
+
+        let index = line[operands_start..start].matches(',').count();
+        let bits = match spec.encoded_form().get(index) {
+            Some(OperandKind::Immediate(bits)) => *bits as u32,
+            _ => return Err(AsmValidationError(format!("{} on line {} is not used for an immediate operand of {}", operator, line, opcode)))
+        };
+
+        let part:u32 = part.parse()
+            .map_err(|_| AsmValidationError(format!("{} on line {} should be in the form IMM(value, part)", operator, line)))?;
+        if bits * part >= 32 {
+            return Err(AsmValidationError(format!(
+                "{} on line {} selects part {} of {} bits, which is beyond the 32 bits of the value", operator, line, part, bits
+            )));
+        }
+
+        let value = get_part_value(value, &line, constants)?;
+        let selected = ((value as u32) >> (bits * part)) & ((1 << bits) - 1);
+        line = format!("{}{}{}", &line[..start], selected, &line[end..]);
+    }
+
+    Ok(line)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::immediate_parts::*;
+    use crate::process_source_into_tokens;
+
+
+    #[test]
+    fn test_substitute_immediate_parts() {
+        let constants = HashMap::from([("BAUD".to_owned(), 0x1C200)]);
+        assert_eq!(substitute_immediate_parts("MOVUI $g0, IMM(0x1234, 1)", &constants).unwrap(), "MOVUI $g0, 18");
+        assert_eq!(substitute_immediate_parts("set: MOVLI $g0, %lo(0x1234)", &constants).unwrap(), "set: MOVLI $g0, 52");
+        assert_eq!(substitute_immediate_parts("MOVUI $g0, %hi(0x1234) ; upper", &constants).unwrap(), "MOVUI $g0, 18 ; upper");
+        assert_eq!(substitute_immediate_parts("ADDI $g0, $g0, IMM(0x1234, 1)", &constants).unwrap(), "ADDI $g0, $g0, 3");
+        assert_eq!(substitute_immediate_parts("ADDI $g0, $g0, IMM(BAUD, 4)", &constants).unwrap(), "ADDI $g0, $g0, 1");
+        assert_eq!(substitute_immediate_parts("MOVUI $g0, IMM(-1, 3)", &constants).unwrap(), "MOVUI $g0, 255");
+        assert_eq!(substitute_immediate_parts("ADD $g0, $g1, $g2", &constants).unwrap(), "ADD $g0, $g1, $g2");
+    }
+
+
+    #[test]
+    fn test_nested_brackets_in_immediate_parts() {
+        let constants = HashMap::new();
+        assert_eq!(substitute_immediate_parts("MOVUI $g0, %hi((0x12 + 1) * 0x100)", &constants).unwrap(), "MOVUI $g0, 19");
+        assert_eq!(substitute_immediate_parts("MOVLI $g0, IMM((0x1234 + (2 * 2)), 0) ; low", &constants).unwrap(), "MOVLI $g0, 56 ; low");
+        assert!(substitute_immediate_parts("MOVUI $g0, %hi((0x12 + 1)", &constants).unwrap_err().0.contains("missing a closing bracket"));
+    }
+
+
+    #[test]
+    fn test_invalid_immediate_parts() {
+        let constants = HashMap::new();
+        assert!(substitute_immediate_parts("MOVUI $g0, IMM(0x1234, 4)", &constants).unwrap_err().0.contains("beyond the 32 bits"));
+        assert!(substitute_immediate_parts("MOVUI $g0, IMM(0x1234)", &constants).unwrap_err().0.contains("IMM(value, part)"));
+        assert!(substitute_immediate_parts("MOVUI $g0, IMM(MISSING, 0)", &constants).unwrap_err().0.contains("not a defined constant"));
+        assert!(substitute_immediate_parts("ADD $g0, $g1, IMM(1, 0)", &constants).unwrap_err().0.contains("not used for an immediate"));
+    }
+
+
+    #[test]
+    fn test_immediate_parts_in_source() {
+        let tokens = process_source_into_tokens("ADDR: .equ 0x00ABCDEF\ninit:\n    MOVUI $g8, IMM(ADDR, 2)\n    MOVLI $g8, IMM(ADDR, 1)\n    HALT\n", None).unwrap();
+        let immediates:Vec<u64> = tokens.iter().filter_map(|token| token.try_get_instr_tokens().unwrap().immediate).collect();
+        assert_eq!(immediates, vec![0xAB, 0xCD]);
+    }
+}
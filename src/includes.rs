@@ -0,0 +1,380 @@
+use std::fs;
+use std::path::Path;
+use crate::encoding::{decode_lines, read_lines};
+use crate::errors::AsmValidationError;
+use crate::limits::ResourceBudget;
+use crate::preprocessor::{apply_conditionals, join_continuation_lines, normalize_line_endings, Configuration, SourceLine};
+use crate::token_generator::pack_bytes;
+use crate::token_types::{DataTokens, SourcePos};
+use crate::validation::{remove_label, validate_label};
+
+
+/// The most words an `.incbin` can embed, which is capped at a 16-bit count like the size of a `.text`, so
+/// that one line cannot exhaust memory
+pub const MAX_INCBIN_WORDS:usize = 0xFFFF;
+
+
+/// Takes a line of assembly and returns true if it splices in another source file, such as
+/// `.include "macros.asm"`
+pub fn is_include_directive(line:&str) -> bool {
+    let line = line.trim();
+    line.starts_with(".include ") || line == ".include"
+}
+
+
+/// Takes a line of assembly holding an include in the form `.include "<path>"` and returns the path, or an
+/// `AsmValidationError` if it is not in this form.
+fn parse_include_directive(line:&str) -> Result<&str, AsmValidationError> {
+    let malformed = || AsmValidationError(format!(
+        "Incorrect format for .include on line {}, which should be .include \"<path>\"", line.trim()
+    ));
+
+    let rest = line.trim()[".include".len()..].trim();
+    let (path, rest) = rest.strip_prefix('"').and_then(|rest| rest.split_once('"')).ok_or_else(malformed)?;
+    if path.is_empty() || !rest.trim().is_empty() {
+        return Err(malformed());
+    }
+
+    Ok(path)
+}
+
+
+/// Takes a line of assembly and returns true if it embeds a binary file, such as `table: .incbin "sine.bin"`
+pub fn is_incbin_directive(line:&str) -> bool {
+    let instr = remove_label(line);
+    instr.starts_with(".incbin ") || instr == ".incbin"
+}
+
+
+/// Takes a line of assembly embedding a binary file and returns the path in its double quotes, or `None` if it
+/// has none
+fn parse_incbin_path(line:&str) -> Option<&str> {
+    let path = remove_label(line)[".incbin".len()..].trim();
+    path.strip_prefix('"').and_then(|path| path.strip_suffix('"')).filter(|path| !path.is_empty())
+}
+
+
+/// Takes a line of assembly embedding a binary file in the form `[<label>:] .incbin "<path>"`, the section it
+/// is in, the label of the line before it if it has none of its own, the name of the file it is in, the
+/// deepest an include may be nested, and its position in the source, and returns a data item holding the
+/// bytes of the file packed two to a word in the same way as `.byte`, with an odd byte at the end padded with
+/// zero. The path is relative to the directory of the file the line is in, as for `.include`.
+///
+/// Will return an `AsmValidationError` if it is not in the data section, no file may be included as
+/// `max_depth` is 0, the path is not in double quotes, the label is invalid, or the file cannot be read or
+/// is empty or larger than `MAX_INCBIN_WORDS` words.
+pub fn parse_incbin_directive(line:&str, mode:char, prev_label:Option<String>, name:&str, max_depth:usize, pos:Option<SourcePos>)
+        -> Result<DataTokens, AsmValidationError> {
+    if mode != 'd' {
+        return Err(AsmValidationError(format!(".incbin can only be used in the data section, but is used on line {}", line)));
+    } else if max_depth == 0 {
+        return Err(AsmValidationError(format!(".incbin cannot be used here, as no file may be included, on line {}", line)));
+    }
+
+    let malformed = || AsmValidationError(format!(
+        "Incorrect format for .incbin on line {}, which should be .incbin \"<path>\"", line
+    ));
+
+    let label = line.find(':').map(|index| line[..index].trim().to_owned());
+    let path = parse_incbin_path(line).ok_or_else(malformed)?;
+    if let Some(label) = &label {
+        validate_label(line, label)?;
+    }
+
+    let path = resolve_include_path(name, path);
+    let bytes = fs::read(&path)
+        .map_err(|e| AsmValidationError(format!("could not read the binary file {} embedded on line {}: {}", path, line, e)))?;
+    if bytes.is_empty() || bytes.len().div_ceil(2) > MAX_INCBIN_WORDS {
+        return Err(AsmValidationError(format!(
+            "The binary file {} embedded on line {} must be between 1 and {} bytes long, but is {}", path, line, MAX_INCBIN_WORDS * 2, bytes.len()
+        )));
+    }
+
+    Ok(DataTokens::new(label.or(prev_label), "incbin".to_owned(), pack_bytes(&bytes)).with_pos(pos))
+}
+
+
+/// Takes the name of the file a line is in and the path given to an `.include` on it, and returns the path
+/// of the included file, which is relative to the directory of the including file unless it is absolute.
+fn resolve_include_path(including_file:&str, path:&str) -> String {
+    let directory = Path::new(including_file).parent().unwrap_or(Path::new(""));
+    directory.join(path).to_string_lossy().into_owned()
+}
+
+
+/// Takes the chain of files which include each other, starting with the file being assembled, and returns
+/// it as it is written in diagnostics, such as `prog.asm -> lib/uart.asm -> lib/macros.asm`
+fn render_chain(chain:&[String]) -> String {
+    chain.join(" -> ")
+}
+
+
+/// Takes the logical lines of a source file, which have already had their `.if` blocks applied, the name of
+/// the file, the configuration being assembled for, and the budget it is assembled under, and returns the
+/// lines with every `.include "<path>"` replaced by the lines of the file it names, so that they are assembled
+/// as if they had been written there. The path is relative to the directory of the file the `.include` is in.
+/// An included file has its own continuations and `.if` blocks applied for the same configuration, may include other
+/// files in turn, and each of its lines records the file it came from so that diagnostics and the positions
+/// of tokens point into it. Every include is counted as an expansion of the budget.
+///
+/// Will return an `AsmValidationError` if an include is malformed or its file cannot be read or is not valid,
+/// if a file includes itself through any chain of includes, or if includes are nested more deeply than the
+/// limits of the budget allow. The last two give the whole chain of files, such as `prog.asm -> a.asm -> prog.asm`.
+pub fn expand_includes(source_lines:Vec<SourceLine>, name:&str, config:&Configuration, budget:&mut ResourceBudget)
+        -> Result<Vec<SourceLine>, AsmValidationError> {
+    if !source_lines.iter().any(|source_line| is_include_directive(&source_line.text)) {
+        return Ok(source_lines);
+    }
+
+    expand_file_includes(source_lines, &mut vec![name.to_owned()], config, budget)
+}
+
+
+/// Takes the lines of a file and the chain of files which included it, ending with the file itself, and
+/// returns them with their includes expanded as described for `expand_includes`.
+fn expand_file_includes(source_lines:Vec<SourceLine>, chain:&mut Vec<String>, config:&Configuration, budget:&mut ResourceBudget)
+        -> Result<Vec<SourceLine>, AsmValidationError> {
+    let max_depth = budget.limits.max_include_depth;
+    let mut expanded:Vec<SourceLine> = Vec::with_capacity(source_lines.len());
+    for source_line in source_lines {
+        if !is_include_directive(&source_line.text) {
+            expanded.push(source_line);
+            continue;
+        }
+
+        let path = parse_include_directive(&source_line.text).map_err(|e| source_line.locate(e))?;
+        let path = resolve_include_path(chain.last().unwrap(), path);
+        let same_file = |file:&String| match (fs::canonicalize(file), fs::canonicalize(&path)) {
+            (Ok(file), Ok(included)) => file == included,
+            _ => *file == path
+        };
+
+        let mut included_chain = chain.clone();
+        included_chain.push(path.clone());
+        if chain.iter().any(same_file) {
+            return Err(AsmValidationError(format!(
+                "{}: the file {} includes itself through {}", source_line.location(), path, render_chain(&included_chain)
+            )));
+        } else if max_depth == 0 {
+            return Err(AsmValidationError(format!("{}: .include cannot be used here, as no file may be included", source_line.location())));
+        } else if chain.len() > max_depth {
+            return Err(AsmValidationError(format!(
+                "{}: includes are nested more than {} deep, through {}", source_line.location(), max_depth, render_chain(&included_chain)
+            )));
+        }
+
+        budget.count_expansions(1);
+        let source = fs::read(&path)
+            .map_err(|e| AsmValidationError(format!("{}: could not read the included file {}: {}", source_line.location(), path, e)))?;
+        // reading lines from bytes already in memory cannot fail
+        let lines = read_lines(&source[..], usize::MAX).unwrap();
+        let lines = normalize_line_endings(decode_lines(lines, config.encoding).map_err(|e| AsmValidationError(format!("{}:{}", path, e.0)))?);
+
+        // the errors of a continuation do not know the file they are in yet
+        let mut included_lines = join_continuation_lines(lines).map_err(|e| AsmValidationError(format!("{}:{}", path, e.0)))?;
+        for included_line in &mut included_lines {
+            included_line.file = Some(path.clone());
+        }
+
+        let included_lines = apply_conditionals(included_lines, config)?;
+        chain.push(path);
+        expanded.extend(expand_file_includes(included_lines, chain, config, budget)?);
+        chain.pop();
+    }
+
+    Ok(expanded)
+}
+
+
+/// Takes the name of a source file, the configuration being assembled for, and the deepest an include may be
+/// nested, and returns the path of every file it includes with `.include` or embeds with `.incbin`, directly
+/// or through the files it includes, resolved in the same way as when it is assembled, so that a tool can
+/// tell when the program needs to be assembled again. Unlike assembling, this never fails: a file which cannot
+/// be read or is not valid is still returned, so it can be watched until it is fixed, but the files it
+/// includes are not.
+pub fn find_included_files(name:&str, config:&Configuration, max_depth:usize) -> Vec<String> {
+    let mut included:Vec<String> = Vec::new();
+    collect_included_files(name, config, max_depth, &mut included);
+    included.retain(|path| path != name);
+    included
+}
+
+
+/// Takes the name of a file, the configuration being assembled for, and how many more includes may be nested
+/// inside it, and adds every file it includes or embeds which has not already been found to `included`, as
+/// described for `find_included_files`.
+fn collect_included_files(name:&str, config:&Configuration, depth_left:usize, included:&mut Vec<String>) {
+    let source_lines = fs::read(name).ok()
+        .and_then(|source| decode_lines(read_lines(&source[..], usize::MAX).unwrap(), config.encoding).ok())
+        .and_then(|lines| join_continuation_lines(normalize_line_endings(lines)).ok())
+        .and_then(|source_lines| apply_conditionals(source_lines, config).ok())
+        .unwrap_or_default();
+
+    for source_line in source_lines {
+        let (path, is_source) = if is_include_directive(&source_line.text) {
+            (parse_include_directive(&source_line.text).ok(), true)
+        } else if is_incbin_directive(&source_line.text) {
+            (parse_incbin_path(&source_line.text), false)
+        } else {
+            continue;
+        };
+
+        let path = match path {
+            Some(path) => resolve_include_path(name, path),
+            None => continue
+        };
+
+        if path != name && !included.contains(&path) {
+            included.push(path.clone());
+            if is_source && depth_left > 1 {
+                collect_included_files(&path, config, depth_left - 1, included);
+            }
+        }
+    }
+}
+
+
+/// Takes an error from assembling a file and returns true if it starts with the position of a line that was
+/// included from another file, such as `lib/macros.asm:3:5: `, rather than with a line and column of the file
+/// itself, which have no file in front.
+pub fn is_located_in_included_file(error:&str) -> bool {
+    let parts:Vec<&str> = error.splitn(4, ':').collect();
+    let is_number = |part:&str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+    parts.len() == 4 && !is_number(parts[0]) && is_number(parts[1]) && is_number(parts[2])
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::includes::*;
+    use crate::process_file_into_tokens;
+    use crate::lang::LanguageLevel;
+    use crate::limits::ResourceLimits;
+    use crate::warnings::WarningSink;
+    use crate::label_table::generate_label_table;
+    use crate::{assemble_tokens, process_reader_into_tokens, process_reader_into_tokens_with_warnings};
+
+
+    #[test]
+    fn test_parse_include_directive() {
+        assert!(is_include_directive(".include \"macros.asm\""));
+        assert!(!is_include_directive("include: .int 5"));
+
+        assert_eq!(parse_include_directive(".include \"macros.asm\"").unwrap(), "macros.asm");
+        assert_eq!(parse_include_directive("    .include \"lib/uart.asm\"  ").unwrap(), "lib/uart.asm");
+        assert!(parse_include_directive(".include macros.asm").is_err());
+        assert!(parse_include_directive(".include \"\"").is_err());
+        assert!(parse_include_directive(".include \"macros.asm\" extra").is_err());
+
+        assert_eq!(resolve_include_path("test_files/prog.asm", "lib/uart.asm"), "test_files/lib/uart.asm");
+        assert_eq!(resolve_include_path("<source>", "uart.asm"), "uart.asm");
+    }
+
+
+    #[test]
+    fn test_included_lines_are_spliced() {
+        let tokens = process_file_into_tokens("test_files/test_include.asm");
+        let instrs:Vec<(String, String)> = tokens.iter().filter_map(|token| token.try_get_instr_tokens().ok())
+            .map(|t| (t.opcode, t.pos.unwrap().file))
+            .collect();
+
+        assert_eq!(instrs, vec![
+            ("ADDI".to_owned(), "test_files/test_include.asm".to_owned()),
+            ("OUT".to_owned(), "test_files/include/uart.asm".to_owned()),
+            ("HALT".to_owned(), "test_files/test_include.asm".to_owned())
+        ]);
+
+        let data = tokens.iter().find_map(|token| token.try_get_data_tokens().ok()).unwrap();
+        assert_eq!((data.label.as_deref(), data.bytes), (Some("baud"), vec![9600]));
+    }
+
+
+    #[test]
+    fn test_find_included_files() {
+        let config = Configuration::default();
+        assert_eq!(find_included_files("test_files/test_include.asm", &config, 8), vec![
+            "test_files/include/uart.asm", "test_files/include/uart_port.asm", "test_files/include/baud.asm"
+        ]);
+        assert_eq!(find_included_files("test_files/test_include.asm", &config, 1), vec!["test_files/include/uart.asm", "test_files/include/baud.asm"]);
+        assert_eq!(find_included_files("test_files/include/cycle_a.asm", &config, 8), vec!["test_files/include/cycle_b.asm"]);
+        assert_eq!(find_included_files("test_files/test_incbin.asm", &config, 8), vec!["test_files/include/baud.asm", "test_files/include/sprite.bin"]);
+        assert!(find_included_files("test_files/missing.asm", &config, 8).is_empty());
+    }
+
+
+    #[test]
+    fn test_include_errors() {
+        let error = process_reader_into_tokens("init:\n    .include \"include/bad.asm\"\n".as_bytes(), "test_files/prog.asm", None, false)
+            .unwrap_err().0;
+        assert!(error.starts_with("test_files/include/bad.asm:2:5: "), "{}", error);
+
+        let error = process_reader_into_tokens(".include \"missing.asm\"\n".as_bytes(), "test_files/prog.asm", None, false)
+            .unwrap_err().0;
+        assert!(error.starts_with("test_files/prog.asm:1:1: could not read the included file test_files/missing.asm"), "{}", error);
+
+        let error = process_file_into_tokens_result("test_files/include/cycle_a.asm");
+        assert_eq!(error, "test_files/include/cycle_b.asm:1:1: the file test_files/include/cycle_a.asm includes itself through \
+            test_files/include/cycle_a.asm -> test_files/include/cycle_b.asm -> test_files/include/cycle_a.asm");
+    }
+
+
+    #[test]
+    fn test_include_depth() {
+        let source = fs::read_to_string("test_files/test_include.asm").unwrap();
+        let tokenize = |max_include_depth| process_reader_into_tokens_with_warnings(source.as_bytes(), "test_files/test_include.asm",
+            &Configuration::default(), false, LanguageLevel::default(), &mut ResourceBudget::new(ResourceLimits { max_include_depth, ..ResourceLimits::UNLIMITED }), &mut WarningSink::default());
+
+        assert!(tokenize(2).is_ok());
+        assert_eq!(tokenize(1).unwrap_err().0, "test_files/include/uart.asm:1:1: includes are nested more than 1 deep, through \
+            test_files/test_include.asm -> test_files/include/uart.asm -> test_files/include/uart_port.asm");
+        assert_eq!(tokenize(0).unwrap_err().0, "test_files/test_include.asm:3:5: .include cannot be used here, as no file may be included");
+    }
+
+
+    fn process_file_into_tokens_result(filename:&str) -> String {
+        let source = fs::read_to_string(filename).unwrap();
+        process_reader_into_tokens(source.as_bytes(), filename, None, false).unwrap_err().0
+    }
+
+
+    #[test]
+    fn test_parse_incbin_directive() {
+        assert!(is_incbin_directive("sine: .incbin \"sine.bin\""));
+        assert!(!is_incbin_directive("incbin: .int 5"));
+
+        let t = parse_incbin_directive("sprite: .incbin \"include/sprite.bin\"", 'd', None, "test_files/prog.asm", 1, None).unwrap();
+        assert_eq!((t.label.as_deref(), t.category.as_str()), (Some("sprite"), "incbin"));
+        assert_eq!(t.bytes, vec![0x0201, 0x0403, 0x0005]);
+
+        let t = parse_incbin_directive(".incbin \"include/sprite.bin\"", 'd', Some("prev".to_owned()), "test_files/prog.asm", 1, None).unwrap();
+        assert_eq!(t.label.as_deref(), Some("prev"));
+
+        let parse = |line, mode, max_depth| parse_incbin_directive(line, mode, None, "test_files/prog.asm", max_depth, None);
+        assert!(parse(".incbin \"include/sprite.bin\"", 'c', 1).unwrap_err().0.contains("data section"));
+        assert!(parse(".incbin \"include/sprite.bin\"", 'd', 0).unwrap_err().0.contains("cannot be used here"));
+        assert!(parse(".incbin include/sprite.bin", 'd', 1).is_err());
+        assert!(parse(".incbin \"include/missing.bin\"", 'd', 1).unwrap_err().0.contains("test_files/include/missing.bin"));
+    }
+
+
+    #[test]
+    fn test_incbin_in_source() {
+        let source = "init:\n    HALT\ndata:\n    before: .int 7\n    sprite: .incbin \"include/sprite.bin\"\n    after: .int 9\n";
+        let tokens = process_reader_into_tokens(source.as_bytes(), "test_files/prog.asm", None, false).unwrap();
+        let label_table = generate_label_table(&tokens).unwrap();
+        assert_eq!((label_table["sprite"], label_table["after"]), (0x1001, 0x1004));
+
+        let binary = assemble_tokens(tokens).unwrap();
+        let data = binary.windows(6).position(|window| window == b"data:\0").unwrap() + 6;
+        assert_eq!(binary[data + 2..data + 8], [1, 2, 3, 4, 5, 0]);
+    }
+
+
+    #[test]
+    fn test_is_located_in_included_file() {
+        assert!(is_located_in_included_file("lib/macros.asm:3:5: BAD is not a valid opcode"));
+        assert!(is_located_in_included_file("2024.asm:3:5: BAD is not a valid opcode"));
+        assert!(!is_located_in_included_file("3:5: BAD is not a valid opcode"));
+        assert!(!is_located_in_included_file("the source reads more than 3 lines"));
+    }
+}
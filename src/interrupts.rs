@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use crate::token_types::{located, FileTokens};
+
+
+/// The registers an interrupt service routine saves on entry and restores before it returns, which are every
+/// 16-bit register the interrupted code could be using
+pub const SAVED_REGISTERS:[&str; 11] = ["$g0", "$g1", "$g2", "$g3", "$g4", "$g5", "$g6", "$g7", "$g8", "$g9", "$ua"];
+
+
+/// Takes the name of an interrupt service routine and returns the label of the sequence which restores the
+/// registers and returns from it, which every return in the routine jumps to
+pub fn return_label(name:&str) -> String {
+    format!("{}_isr_return", name)
+}
+
+
+/// Returns the lines which push every one of `SAVED_REGISTERS` onto the stack on entry to an interrupt
+/// service routine
+pub fn save_registers() -> Vec<String> {
+    SAVED_REGISTERS.iter()
+        .flat_map(|register| ["SUBI $sp, $sp, 1".to_owned(), format!("STORE {}, $sp, $zero", register)])
+        .collect()
+}
+
+
+/// Returns the lines which pop every one of `SAVED_REGISTERS` off the stack in the opposite order to which
+/// they were pushed, and return to the interrupted code
+pub fn restore_registers() -> Vec<String> {
+    let mut lines:Vec<String> = SAVED_REGISTERS.iter().rev()
+        .flat_map(|register| [format!("LOAD {}, $sp, $zero", register), "ADDI $sp, $sp, 1".to_owned()])
+        .collect();
+    lines.push("JUMP $ra".to_owned());
+    lines
+}
+
+
+/// Takes a stream of tokens and the label table built from their labels, and returns an error for every entry
+/// of a `.vectors` table which is not a function marked with `.isr`, which is known by it having the label
+/// given by `return_label`.
+pub fn check_vector_targets(tokens_stream:&[FileTokens], label_table:&HashMap<String, i64>) -> Vec<String> {
+    let mut errors:Vec<String> = Vec::new();
+    for tokens in tokens_stream {
+        let t = match tokens {
+            FileTokens::DataTokens(t) if t.category == "vectors" => t,
+            _ => continue
+        };
+
+        // each entry has a word for the upper and the lower half of its address
+        for target in t.op_labels.iter().step_by(2).filter_map(|label| label.split_once('@')).map(|(_, label)| label) {
+            if label_table.contains_key(target) && !label_table.contains_key(&return_label(target)) {
+                errors.push(format!("{}The vector {} is not a function marked with .isr", located(t.pos.as_ref()), target));
+            }
+        }
+    }
+
+    errors
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::assemble_source;
+    use crate::label_table::generate_label_table;
+    use crate::process_source_into_tokens;
+
+
+    #[test]
+    fn test_isr_saves_registers() {
+        let source = "init:\n    HALT\n.isr\n.func tick\n    ADDI $g0, $g0, 1\n    JUMP $ra\n.endfunc\n";
+        let tokens = process_source_into_tokens(source, None).unwrap();
+        let instrs:Vec<String> = tokens.iter().filter_map(|token| token.try_get_instr_tokens().ok())
+            .map(|t| format!("{} {}", t.opcode, t.operand_a.unwrap_or_default()))
+            .collect();
+
+        assert_eq!(instrs[1..5], ["SUBI $sp", "STORE $g0", "SUBI $sp", "STORE $g1"]);
+        assert_eq!(instrs[23..25], ["ADDI $g0", "JUMP $g8"]);
+        assert_eq!(instrs[25..27], ["LOAD $ua", "ADDI $sp"]);
+        assert_eq!(instrs.last().unwrap(), "JUMP $ra");
+
+        assert_eq!(generate_label_table(&tokens).unwrap()["tick"], 1);
+    }
+
+
+    #[test]
+    fn test_vectors() {
+        let source = "init:\n    HALT\n.isr\n.func tick\n    JUMP $ra\n.endfunc\ndata:\nvectors: .vectors @tick, @tick\n";
+        let binary = assemble_source(source).unwrap();
+        let data = binary.windows(6).position(|window| window == b"data:\0").unwrap() + 6;
+        let words:Vec<u16> = binary[data..data + 8].chunks(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect();
+        assert_eq!(words, vec![0x0000, 0x0001, 0x0000, 0x0001]);
+    }
+
+
+    #[test]
+    fn test_vectors_only_reference_isrs() {
+        let source = "init:\n    HALT\n.func plain\n    JUMP $ra\n.endfunc\ndata:\nvectors: .vectors @plain, @init\n";
+        let error = generate_label_table(&process_source_into_tokens(source, None).unwrap()).unwrap_err().0;
+        assert_eq!(error.lines().collect::<Vec<&str>>(), vec![
+            "<source>:7:1: The vector plain is not a function marked with .isr",
+            "<source>:7:1: The vector init is not a function marked with .isr"
+        ]);
+    }
+}
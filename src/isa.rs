@@ -0,0 +1,237 @@
+use crate::generate_code::{OPCODE_BINARIES, REGISTER_BINARIES};
+use OperandKind::{Immediate, Label, Register};
+
+
+/// Represents a kind of operand an instruction takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperandKind {
+    /// A register, such as `$g0`
+    Register,
+
+    /// An unsigned immediate of the given number of bits
+    Immediate(u8),
+
+    /// A label operand, such as `@loop`, which makes the instruction expand into several words that load
+    /// the address of the label first
+    Label
+}
+
+
+/// Represents what a field of an encoded instruction holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// The bits of the opcode, which are the same for every use of the instruction
+    Opcode,
+
+    /// The operand at the given index of the encoded form
+    Operand(usize)
+}
+
+
+/// Represents a field of the 16-bit word an instruction is encoded into, which is `bits` wide and starts
+/// `shift` bits from the least significant bit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field {
+    pub kind: FieldKind,
+    pub shift: u8,
+    pub bits: u8
+}
+
+
+/// Represents everything about an instruction needed to write or encode it. The first of `forms` is the
+/// encoded form, which assembles directly into a single word laid out as in `fields`, and the rest are the
+/// other operand lists the assembler accepts, which are pseudo-instructions that expand into several words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstrSpec {
+    pub opcode: &'static str,
+    pub format: &'static str,
+    pub encoding: u16,
+    pub forms: &'static [&'static [OperandKind]],
+    pub fields: &'static [Field]
+}
+
+
+impl InstrSpec {
+    /// Returns the operands of the form which assembles directly into a single word
+    pub fn encoded_form(&self) -> &'static [OperandKind] {
+        self.forms[0]
+    }
+
+
+    /// Takes the values of the operands of the encoded form, where registers are given by their number as
+    /// returned by `Isa::register`, and returns the word the instruction is encoded into. Returns `None` if
+    /// the wrong number of operands is given, or if any of them does not fit in its field.
+    pub fn encode(&self, operands:&[u16]) -> Option<u16> {
+        if operands.len() != self.encoded_form().len() {
+            return None;
+        }
+
+        let mut word = self.encoding;
+        for field in self.fields {
+            if let FieldKind::Operand(index) = field.kind {
+                if operands[index] >> field.bits != 0 {
+                    return None;
+                }
+
+                word |= operands[index] << field.shift;
+            }
+        }
+
+        Some(word)
+    }
+}
+
+
+const fn opcode_field(shift:u8) -> Field {
+    Field { kind: FieldKind::Opcode, shift, bits: 16 - shift }
+}
+
+
+const fn operand_field(index:usize, shift:u8, bits:u8) -> Field {
+    Field { kind: FieldKind::Operand(index), shift, bits }
+}
+
+
+/// Represents an instruction in `INSTR_SPECS`, being its opcode, format, forms, and fields
+type SpecEntry = (&'static str, &'static str, &'static [&'static [OperandKind]], &'static [Field]);
+
+const NO_OPERANDS:&[&[OperandKind]] = &[&[]];
+const RRR_OPERANDS:&[&[OperandKind]] = &[&[Register, Register, Register]];
+const MEMORY_OPERANDS:&[&[OperandKind]] = &[&[Register, Register, Register], &[Register, Register, Register, Label]];
+const RRI_OPERANDS:&[&[OperandKind]] = &[&[Register, Register, Immediate(4)]];
+const RII_OPERANDS:&[&[OperandKind]] = &[&[Register, Immediate(8)], &[Register, Label]];
+const ORR_OPERANDS:&[&[OperandKind]] = &[&[Register, Register]];
+const BRANCH_OPERANDS:&[&[OperandKind]] = &[&[Register, Register], &[Register], &[Register, Register, Label]];
+const ORI_OPERANDS:&[&[OperandKind]] = &[&[Register, Immediate(4)]];
+const SYSCALL_OPERANDS:&[&[OperandKind]] = &[&[Immediate(8)]];
+
+const NO_FIELDS:&[Field] = &[opcode_field(0)];
+const RRR_FIELDS:&[Field] = &[opcode_field(12), operand_field(0, 8, 4), operand_field(1, 4, 4), operand_field(2, 0, 4)];
+const RRI_FIELDS:&[Field] = &[opcode_field(12), operand_field(0, 8, 4), operand_field(1, 4, 4), operand_field(2, 0, 4)];
+const RII_FIELDS:&[Field] = &[opcode_field(12), operand_field(0, 8, 4), operand_field(1, 0, 8)];
+const ORR_FIELDS:&[Field] = &[opcode_field(8), operand_field(0, 4, 4), operand_field(1, 0, 4)];
+const ORI_FIELDS:&[Field] = &[opcode_field(8), operand_field(0, 4, 4), operand_field(1, 0, 4)];
+const SYSCALL_FIELDS:&[Field] = &[opcode_field(8), operand_field(0, 0, 8)];
+
+
+/// Every instruction the assembler supports, by its format, in the order of their encodings
+const INSTR_SPECS:[SpecEntry; 28] = [
+    ("NOP", "none", NO_OPERANDS, NO_FIELDS),
+    ("ADD", "rrr", RRR_OPERANDS, RRR_FIELDS),
+    ("SUB", "rrr", RRR_OPERANDS, RRR_FIELDS),
+    ("ADDI", "rri", RRI_OPERANDS, RRI_FIELDS),
+    ("SUBI", "rri", RRI_OPERANDS, RRI_FIELDS),
+    ("SLL", "rrr", RRR_OPERANDS, RRR_FIELDS),
+    ("SRL", "rrr", RRR_OPERANDS, RRR_FIELDS),
+    ("SRA", "rrr", RRR_OPERANDS, RRR_FIELDS),
+    ("NAND", "rrr", RRR_OPERANDS, RRR_FIELDS),
+    ("OR", "rrr", RRR_OPERANDS, RRR_FIELDS),
+    ("LOAD", "rrr", MEMORY_OPERANDS, RRR_FIELDS),
+    ("STORE", "rrr", MEMORY_OPERANDS, RRR_FIELDS),
+    ("MOVUI", "rii", RII_OPERANDS, RII_FIELDS),
+    ("MOVLI", "rii", RII_OPERANDS, RII_FIELDS),
+    ("ADDC", "orr", ORR_OPERANDS, ORR_FIELDS),
+    ("SUBC", "orr", ORR_OPERANDS, ORR_FIELDS),
+    ("JUMP", "orr", BRANCH_OPERANDS, ORR_FIELDS),
+    ("JAL", "orr", BRANCH_OPERANDS, ORR_FIELDS),
+    ("CMP", "orr", ORR_OPERANDS, ORR_FIELDS),
+    ("BEQ", "orr", BRANCH_OPERANDS, ORR_FIELDS),
+    ("BNE", "orr", BRANCH_OPERANDS, ORR_FIELDS),
+    ("BLT", "orr", BRANCH_OPERANDS, ORR_FIELDS),
+    ("BGT", "orr", BRANCH_OPERANDS, ORR_FIELDS),
+    ("IN", "ori", ORI_OPERANDS, ORI_FIELDS),
+    ("OUT", "ori", ORI_OPERANDS, ORI_FIELDS),
+    ("syscall", "oi", SYSCALL_OPERANDS, SYSCALL_FIELDS),
+    ("ATOM", "none", NO_OPERANDS, NO_FIELDS),
+    ("HALT", "none", NO_OPERANDS, NO_FIELDS)
+];
+
+
+/// Gives tools outside the assembler, such as a compiler backend, the metadata of the instruction set so
+/// that they can write valid assembly or encode instructions themselves. The encodings come from the same
+/// tables the assembler uses.
+pub struct Isa;
+
+
+impl Isa {
+    /// Takes an opcode, such as `ADDI`, and returns its `InstrSpec`, or `None` if it is not an instruction.
+    /// Opcodes are matched exactly as they are written in assembly, so `syscall` is lowercase.
+    pub fn lookup(opcode:&str) -> Option<InstrSpec> {
+        let (opcode, format, forms, fields) = INSTR_SPECS.iter().find(|spec| spec.0 == opcode)?;
+        Some(InstrSpec { opcode, format, encoding: OPCODE_BINARIES[*opcode], forms, fields })
+    }
+
+
+    /// Returns the `InstrSpec` of every instruction in the order of their encodings
+    pub fn instructions() -> Vec<InstrSpec> {
+        INSTR_SPECS.iter().filter_map(|spec| Isa::lookup(spec.0)).collect()
+    }
+
+
+    /// Takes the name of a register, such as `$g0`, and returns its number, or `None` if there is no such
+    /// register
+    pub fn register(name:&str) -> Option<u16> {
+        REGISTER_BINARIES.get(name).copied()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::byte_access::BYTE_ACCESS_OPCODES;
+    use crate::isa::*;
+    use crate::test_vectors::generate_test_vectors;
+    use crate::validation::{validate_int_immediate, validate_opcode};
+
+
+    #[test]
+    fn test_lookup() {
+        let addi = Isa::lookup("ADDI").unwrap();
+        assert_eq!(addi.format, "rri");
+        assert_eq!(addi.encoding, 0x3000);
+        assert_eq!(addi.encoded_form(), &[Register, Register, Immediate(4)]);
+        assert_eq!(addi.encode(&[1, 0, 15]), Some(0x310F));
+        assert_eq!(addi.encode(&[1, 0, 16]), None);
+        assert_eq!(addi.encode(&[1, 0]), None);
+
+        assert_eq!(Isa::lookup("syscall").unwrap().encode(&[255]), Some(0xFCFF));
+        assert_eq!(Isa::lookup("HALT").unwrap().encode(&[]), Some(0xFFFF));
+        assert!(Isa::lookup("MOVUI").unwrap().forms.contains(&&[Register, Label][..]));
+        assert!(Isa::lookup("addi").is_none());
+        assert_eq!(Isa::register("$ra"), Some(0xE));
+    }
+
+
+    #[test]
+    fn test_instructions_match_assembler() {
+        let instructions = Isa::instructions();
+        assert_eq!(instructions.len(), OPCODE_BINARIES.len());
+        for spec in &instructions {
+            assert!(validate_opcode(spec.opcode).is_ok());
+        }
+
+        // every test vector in the encoded form of its instruction must encode the same way, while the byte
+        // access pseudo-instructions are not instructions of their own
+        let mut checked = 0;
+        for vector in generate_test_vectors().unwrap() {
+            let (opcode, operands) = vector.source.split_once(' ').unwrap_or((&vector.source, ""));
+            let operands:Vec<&str> = operands.split(',').map(|operand| operand.trim()).filter(|operand| !operand.is_empty()).collect();
+            let Some(spec) = Isa::lookup(opcode) else {
+                assert!(BYTE_ACCESS_OPCODES.contains(&opcode), "{}", vector.source);
+                continue;
+            };
+            if operands.len() != spec.encoded_form().len() || operands.iter().any(|operand| operand.starts_with('@')) {
+                continue;
+            }
+
+            let values:Vec<u16> = operands.iter().map(|operand| {
+                Isa::register(operand).unwrap_or_else(|| validate_int_immediate(operand, 8, false).unwrap() as u16)
+            }).collect();
+
+            assert_eq!(spec.encode(&values), Some(vector.encoding[0]), "{}", vector.source);
+            checked += 1;
+        }
+
+        assert_eq!(checked, 28);
+    }
+}
@@ -1,113 +1,288 @@
-use std::collections::HashMap;
-use crate::token_types::FileTokens;
+use std::collections::{HashMap, HashSet};
+use crate::token_types::{located, FileTokens};
+use crate::aliases::resolve_aliases;
+use crate::deprecation::check_deprecated_labels_exist;
+use crate::interrupts::check_vector_targets;
 use crate::errors::AsmValidationError;
+use crate::local_labels::is_local_label;
+use crate::exports::EXPORT_SLOT_PREFIX;
+use crate::validation::split_label_offset;
+use crate::warnings::{AsmWarning, WarningKind, WarningSink};
 
 
-/// Takes a filename and generates a `HashMap<String, i64>` of all labels in the instructions and data
-/// section and returns it. Will include paging (pages are 4Kb) to ensure data is on different page to
-/// instructions. 
-pub fn generate_label_table(tokens_stream:&Vec<FileTokens>) -> Result<HashMap<String, i64>, AsmValidationError> {
-    let mut instr_addr = 0;
-    let page_size = 0x1000;
-    let mut data_addr:i64 = 0;
-    let mut text_addr:i64 = 0;
+/// The sections of a program in the order they are placed in memory
+const SECTION_ORDER:[char; 4] = ['c', 'd', 't', 'b'];
+
+/// The number of words in a page of memory, which each section starts on the boundary of unless another page
+/// size is given with `--page-size`
+pub const PAGE_SIZE:i64 = 0x1000;
+
+/// The start of the labels given to data items which have none, which are followed by the number of the item
+pub const ANONYMOUS_DATA_PREFIX:&str = "__data_";
+
+
+/// Takes a token and the address it is placed at, and returns the section it belongs to and the number of
+/// words it takes up. An `.org` or `.align` takes up every word from its address up to the address it moves to.
+fn get_token_extent(tokens:&FileTokens, addr:i64) -> (char, i64) {
+    match tokens {
+        FileTokens::InstrTokens(_) => ('c', 1),
+        FileTokens::DataTokens(t) => ('d', t.bytes.len() as i64),
+        FileTokens::TextTokens(t) => ('t', t.bytes.len() as i64),
+        FileTokens::BssTokens(t) => ('b', t.size as i64),
+        FileTokens::OrgTokens(t) => (t.section, (t.target(addr) - addr).max(0)),
+        FileTokens::AliasTokens(t) => (t.section, 0),
+        FileTokens::DeprecatedTokens(t) => (t.section, 0),
+        FileTokens::TraceTokens(_) | FileTokens::ExportTableTokens(_) => ('c', 0)
+    }
+}
+
+
+/// Takes a stream of tokens and returns the address of each token in the same order, using
+/// `generate_token_addresses_with_page_size` with pages of `PAGE_SIZE` words.
+pub fn generate_token_addresses(tokens_stream:&[FileTokens]) -> Vec<i64> {
+    generate_token_addresses_with_page_size(tokens_stream, PAGE_SIZE)
+}
+
+
+/// Takes a stream of tokens and the number of words in a page, and returns the address of each token in the
+/// same order. Data is placed on the page after the last instruction, text on the page after the last data,
+/// and bss on the page after the last text, so that the instructions, data, text, and bss are always on
+/// different pages.
+pub fn generate_token_addresses_with_page_size(tokens_stream:&[FileTokens], page_size:i64) -> Vec<i64> {
+    let mut section_addrs:[i64; 4] = [0; 4];
     let mut mode:char = 'c';
-    let mut label_table:HashMap<String, i64> = HashMap::new();
+    let mut addresses:Vec<i64> = Vec::with_capacity(tokens_stream.len());
     for tokens in tokens_stream {
-        match tokens {
-            FileTokens::DataTokens(t) => {
-                if mode == 'c' {
-                    data_addr += page_size;
-                    text_addr += page_size;
-                    mode = 'd';
-                }
-
-                match &t.label {
-                    Some(label) => {
-                        if label_table.contains_key(label) {
-                            return Err(AsmValidationError(format!("Duplicate label \"{}\" detected!", label)));
-                        }
-
-                        let num_bytes:i64 = t.bytes.len().try_into().unwrap();
-                        label_table.insert(label.to_owned(), data_addr);
-
-                        data_addr += num_bytes;
-                        if data_addr % page_size == 0 && data_addr != 0 {
-                            text_addr += page_size;
-                        }
-                    },
-
-                    None => {
-                        data_addr += 1;
-                        if data_addr % page_size == 0 && data_addr != 0 {
-                            text_addr += page_size;
-                        }
-                    }
-                }
-            },
-
-            FileTokens::TextTokens(t) => {
-                if mode != 't' {
-                    text_addr += page_size;
-                    mode = 't';
-                }
-
-                match &t.label {
-                    Some(label) => {
-                        if label_table.contains_key(label) {
-                            return Err(AsmValidationError(format!("Duplicate label \"{}\" detected!", label)));
-                        }
-
-                        let num_bytes:i64 = t.bytes.len().try_into().unwrap();
-                        label_table.insert(label.to_owned(), text_addr);
-                        text_addr += num_bytes;
-                    },
-
-                    None => text_addr += 1
-                }
-            },
-
-            FileTokens::InstrTokens(t) => {
-                match &t.label {
-                    Some(label) => {
-                        if label_table.contains_key(label) {
-                            return Err(AsmValidationError(format!("Duplicate label \"{}\" detected!", label)));
-                        }
-
-                        label_table.insert(label.to_owned(), instr_addr);
-                        instr_addr += 1;
-                        if instr_addr % page_size == 0 && instr_addr != 0 {
-                            data_addr += page_size;
-                            text_addr += page_size;
-                        } 
-                    },
-
-                    None => {
-                        instr_addr += 1;
-                        if instr_addr % page_size == 0 && instr_addr != 0 {
-                            data_addr += page_size;
-                            text_addr += page_size;
-                        } 
-                    }
-                }
+        let section = get_token_extent(tokens, 0).0;
+        let index = SECTION_ORDER.iter().position(|s| *s == section).unwrap();
+
+        // entering a section moves it, and every section after it, onto a new page, which an alias, a
+        // deprecation, or a trace point which has not been expanded does not do as it takes up no words
+        let entering = !tokens.is_zero_width() && match section {
+            'd' => mode == 'c',
+            't' | 'b' => mode != section,
+            _ => false
+        };
+
+        if entering {
+            for addr in &mut section_addrs[index..] {
+                *addr += page_size;
             }
+
+            mode = section;
+        }
+
+        addresses.push(section_addrs[index]);
+        let num_words = get_token_extent(tokens, section_addrs[index]).1;
+        let prev_page = section_addrs[index] / page_size;
+        section_addrs[index] += num_words;
+
+        // every page the section spills onto pushes the sections after it back by a page
+        let pages_crossed = section_addrs[index] / page_size - prev_page;
+        for addr in &mut section_addrs[index + 1..] {
+            *addr += pages_crossed * page_size;
+        }
+    }
+
+    addresses
+}
+
+
+/// Represents where a section of the program was placed, and the extents of the labels in it, where each
+/// label extends up to the next label in the section or to the end of the section.
+#[derive(Debug, Clone)]
+pub struct SectionLayout {
+    pub name: String,
+    pub start: i64,
+    pub end: i64,
+    pub labels: Vec<(String, i64, i64)>,
+    pub page_size: i64
+}
+
+
+impl SectionLayout {
+    /// Returns the number of pages the section occupies, counting a page it only partly fills, and the page
+    /// it starts on even if it is empty.
+    pub fn page_count(&self) -> i64 {
+        (self.end - 1).max(self.start) / self.page_size - self.start / self.page_size + 1
+    }
+}
+
+
+/// Takes a stream of tokens and returns the layout of each section as given by
+/// `generate_section_layouts_with_page_size`, with pages of `PAGE_SIZE` words.
+pub fn generate_section_layouts(tokens_stream:&[FileTokens]) -> Vec<SectionLayout> {
+    generate_section_layouts_with_page_size(tokens_stream, PAGE_SIZE)
+}
+
+
+/// Takes a stream of tokens and the number of words in a page, and returns the layout of each section (code,
+/// data, text, and bss) that contains at least one token, in address order.
+pub fn generate_section_layouts_with_page_size(tokens_stream:&[FileTokens], page_size:i64) -> Vec<SectionLayout> {
+    let mut layouts:Vec<SectionLayout> = Vec::new();
+    for (tokens, addr) in tokens_stream.iter().zip(generate_token_addresses_with_page_size(tokens_stream, page_size)) {
+        if tokens.is_zero_width() {
+            continue;
+        }
+
+        let (section, size) = get_token_extent(tokens, addr);
+        let name = match section {
+            'c' => "code",
+            'd' => "data",
+            't' => "text",
+            _ => "bss"
         };
+
+        let label = tokens.label();
+
+        if layouts.last().map(|layout| layout.name != name).unwrap_or(true) {
+            layouts.push(SectionLayout { name: name.to_owned(), start: addr, end: addr, labels: Vec::new(), page_size });
+        }
+
+        let layout = layouts.last_mut().unwrap();
+        if let Some(label) = label {
+            layout.labels.push((label.to_owned(), addr, 0));
+        }
+
+        layout.end = addr + size;
+        if let Some(last_label) = layout.labels.last_mut() {
+            last_label.2 = layout.end - last_label.1;
+        }
+    }
+
+    layouts
+}
+
+
+/// Takes a stream of tokens and returns the label table given by `generate_label_table_with_page_size`, with
+/// pages of `PAGE_SIZE` words.
+pub fn generate_label_table(tokens_stream:&[FileTokens]) -> Result<HashMap<String, i64>, AsmValidationError> {
+    generate_label_table_with_page_size(tokens_stream, PAGE_SIZE)
+}
+
+
+/// Takes a stream of tokens and the number of words in a page, and generates a `HashMap<String, i64>` of all
+/// labels in the instructions and data section and returns it. Will include paging to ensure data is on a
+/// different page to instructions, along with every alias defined with `.set`. Will return an `AsmValidationError` if a label is
+/// defined twice, if an `.org` tries to move back to an address which has already been used, if an alias
+/// cannot be resolved, or if a label marked with `.deprecated` does not exist, holding every such problem
+/// with one on each line.
+pub fn generate_label_table_with_page_size(tokens_stream:&[FileTokens], page_size:i64) -> Result<HashMap<String, i64>, AsmValidationError> {
+    let mut label_table:HashMap<String, i64> = HashMap::new();
+    let mut errors:Vec<String> = Vec::new();
+    for (tokens, addr) in tokens_stream.iter().zip(generate_token_addresses_with_page_size(tokens_stream, page_size)) {
+        let label = tokens.label();
+
+        if let FileTokens::OrgTokens(t) = tokens {
+            if t.target(addr) < addr {
+                errors.push(match &t.pinned_label {
+                    Some(label) => format!(
+                        "{} cannot be placed at 0x{:08X} as the section has already reached 0x{:08X}", label, t.address, addr
+                    ),
+                    None => format!(
+                        ".org 0x{:08X} would move backwards, as the section has already reached 0x{:08X}", t.address, addr
+                    )
+                });
+            }
+        }
+
+        if let Some(label) = label {
+            if label_table.contains_key(label) {
+                errors.push(format!("{}Duplicate label \"{}\" detected!", located(tokens.pos()), label));
+                continue;
+            }
+
+            label_table.insert(label.to_owned(), addr);
+        }
+    }
+
+    errors.extend(resolve_aliases(tokens_stream, &mut label_table));
+    errors.extend(check_deprecated_labels_exist(tokens_stream, &label_table));
+    errors.extend(check_vector_targets(tokens_stream, &label_table));
+    if !errors.is_empty() {
+        return Err(AsmValidationError(errors.join("\n")));
     }
 
     Ok(label_table)
 }
 
 
+/// Takes a stream of tokens, before or after pseudo-instructions are substituted, and gives an unused-label
+/// warning for every label that no instruction refers to. The label of the first item is the entry point
+/// of the program, so it is never reported. Each warning starts with the position of the labelled item, if
+/// it came from a source file.
+pub fn check_unused_labels(tokens_stream:&[FileTokens], warnings:&mut WarningSink) {
+    // a label operand is written @label, or once substituted ul@label for one of the halves of its address
+    let referenced:HashSet<&str> = tokens_stream.iter()
+        .flat_map(|tokens| match tokens {
+            FileTokens::InstrTokens(t) => t.op_label.iter().map(|label| label.as_str()).collect(),
+            FileTokens::DataTokens(t) => t.op_labels.iter().map(|label| label.as_str()).collect(),
+            FileTokens::AliasTokens(t) => vec![t.target.as_str()],
+            _ => Vec::new()
+        })
+        .filter_map(|label| label.rsplit('@').next())
+        .map(|label| split_label_offset(label).0)
+        .collect();
+
+    for tokens in tokens_stream.iter().skip(1) {
+        let label = tokens.label();
+
+        // a numeric local label is often only there to be jumped over, and a slot of an export table is there
+        // to be called from outside the program, so neither is worth a warning
+        let is_export_slot = |label:&str| label.contains(EXPORT_SLOT_PREFIX);
+        if let Some(label) = label.filter(|label| !referenced.contains(label) && !is_local_label(label) && !is_export_slot(label)) {
+            warnings.push(AsmWarning::new(WarningKind::UnusedLabel, format!("{}The label {} is never used", located(tokens.pos()), label)));
+        }
+    }
+}
+
+
+/// Takes a stream of tokens and returns it with every data, text, and bss item which has no label given one
+/// made of `ANONYMOUS_DATA_PREFIX` and the number of the item among those without one, such as `__data_17`,
+/// skipping any number whose label is already defined. This puts every item in the label table, so that the
+/// map file, listing, dump, and disassembly can refer to it and an item which is not at the address it was
+/// expected to be can be seen. As the labels are not in the source, this should be run after the lints,
+/// which would report them as unused, and after the layout is shuffled, which would move an item without a
+/// label away from the labelled item before it.
+pub fn label_anonymous_data(mut tokens_stream:Vec<FileTokens>) -> Vec<FileTokens> {
+    let defined:HashSet<String> = tokens_stream.iter()
+        .filter_map(|tokens| match tokens {
+            FileTokens::AliasTokens(t) => Some(t.alias.clone()),
+            _ => tokens.label().map(str::to_owned)
+        })
+        .collect();
+
+    let mut names = (0..).map(|number| format!("{}{}", ANONYMOUS_DATA_PREFIX, number)).filter(|name| !defined.contains(name));
+    for tokens in &mut tokens_stream {
+        let label = match tokens {
+            FileTokens::DataTokens(t) => &mut t.label,
+            FileTokens::TextTokens(t) => &mut t.label,
+            FileTokens::BssTokens(t) => &mut t.label,
+            _ => continue
+        };
+
+        if label.is_none() {
+            *label = names.next();
+        }
+    }
+
+    tokens_stream
+}
+
+
 #[cfg(test)]
 mod tests {
-    use crate::process_file_into_tokens;
+    use crate::process_source_into_tokens;
     use crate::pseudo_substitution;
     use crate::label_table;
+    use crate::warnings::{WarningFlag, WarningSettings, WarningSink};
+    use crate::testing::{tokens_from_str, FIBONACCI_PROGRAM};
+    use crate::token_types::FileTokens;
 
 
     #[test]
     fn test_label_table_generation() {
-        let tokens = process_file_into_tokens("test_files/test_label_table_gen.asm");
+        let tokens = tokens_from_str(FIBONACCI_PROGRAM);
         let tokens = pseudo_substitution::substitute_pseudo_instrs(tokens);
         let label_table = label_table::generate_label_table(&tokens).unwrap();
 
@@ -127,7 +302,10 @@ mod tests {
 
     #[test]
     fn test_label_paging() {
-        let tokens = process_file_into_tokens("test_files/test_large_prog.asm");
+        // the code fills its first page and runs onto the next, which the data must start after
+        let source = format!("start: ADDI $g0, $zero, 1\n{}pg_end: ADDI $g0, $zero, 1\npg_start: ADDI $g0, $zero, 1\n{}\n\
+            data:\n    some_data: .int 10\n    some_other_data: .float 0.255", "ADDI $g0, $zero, 1\n".repeat(4094), "ADDI $g0, $zero, 1\n".repeat(1196));
+        let tokens = tokens_from_str(&source);
         let tokens = pseudo_substitution::substitute_pseudo_instrs(tokens);
         let label_table = label_table::generate_label_table(&tokens).unwrap();
 
@@ -141,27 +319,151 @@ mod tests {
 
 
     #[test]
-    #[should_panic]
+    fn test_label_paging_with_page_size() {
+        let source = format!("start: ADDI $g0, $zero, 1\n{}last: ADDI $g0, $zero, 1\n\
+            data:\n    some_data: .int 10", "ADDI $g0, $zero, 1\n".repeat(0x100));
+        let tokens = tokens_from_str(&source);
+        let tokens = pseudo_substitution::substitute_pseudo_instrs(tokens);
+        let label_table = label_table::generate_label_table_with_page_size(&tokens, 0x100).unwrap();
+
+        assert_eq!(label_table["start"], 0);
+        assert_eq!(label_table["last"], 0x101);
+        assert_eq!(label_table["some_data"], 0x200);
+    }
+
+
+    #[test]
+    fn test_unlabelled_data_addresses() {
+        let tokens = tokens_from_str("init:\n    HALT\ndata:\nfirst: .int 1\n    .long 0x12345678\nsecond: .int 2\n    .section 4091 [0]\n\
+            crossing: .long 0x12345678\ntext:\n    .text 4 \"abc\"\ngreeting: .text 3 \"hi\"\n");
+        let tokens = pseudo_substitution::substitute_pseudo_instrs(tokens);
+        let label_table = label_table::generate_label_table(&tokens).unwrap();
+
+        // an item without a label takes up all of its words, where it used to take up 1, which put second
+        // at 0x1002 inside the .long and crossing at 0x1004
+        assert_eq!(label_table["first"], 0x1000);
+        assert_eq!(label_table["second"], 0x1003);
+        assert_eq!(label_table["crossing"], 0x1FFF);
+
+        // the .long crosses onto the page at 0x2000 without ending on it, so the text starts on the page after
+        // at 0x3000, and the 4 words of the unlabelled .text come first, where greeting used to be at 0x2001
+        assert_eq!(label_table["greeting"], 0x3004);
+    }
+
+
+    #[test]
+    fn test_section_layouts() {
+        let tokens = tokens_from_str(FIBONACCI_PROGRAM);
+        let tokens = pseudo_substitution::substitute_pseudo_instrs(tokens);
+        let layouts = label_table::generate_section_layouts(&tokens);
+
+        assert_eq!(layouts.len(), 3);
+        assert_eq!(layouts[0].name, "code");
+        assert_eq!((layouts[0].start, layouts[0].end), (0x0000, 0x0015));
+        assert_eq!(layouts[0].labels[1], ("loop".to_owned(), 0x0005, 15));
+        assert_eq!(layouts[1].name, "data");
+        assert_eq!((layouts[1].start, layouts[1].end), (0x1000, 0x1011));
+        assert_eq!(layouts[1].labels.last().unwrap(), &("list".to_owned(), 0x1007, 10));
+        assert_eq!((layouts[2].start, layouts[2].end), (0x2000, 0x2014));
+    }
+
+
+    #[test]
+    #[should_panic(expected = "Duplicate label \\\"start\\\" detected!")]
     fn test_duplicate_label() {
-        let tokens = process_file_into_tokens("test_files/test_duplicate_label.asm");
+        let tokens = tokens_from_str("start:\n    MOVLI $g0, @start\n    MOVLI $g1, @end\nloop_start:\n    ADDI $g0, $g0, 1\n    CMP $g0, $g1\n\
+            \x20   BEQ $g2, $g3, @end\n    JUMP $g2, $g3, @loop_start\nend: HALT\n\ndata:\n    start: .int 100\n    end: .int 300\n");
         let tokens = pseudo_substitution::substitute_pseudo_instrs(tokens);
         let _ = label_table::generate_label_table(&tokens).unwrap();
     }
 
 
     #[test]
-    #[should_panic]
+    fn test_every_duplicate_label_reported() {
+        let tokens = process_source_into_tokens("start: NOP\nend: NOP\nstart: NOP\nend: HALT\n", None).unwrap();
+        let err = label_table::generate_label_table(&tokens).unwrap_err();
+        assert_eq!(err.0, "<source>:3:1: Duplicate label \"start\" detected!\n<source>:4:1: Duplicate label \"end\" detected!");
+    }
+
+
+    #[test]
+    #[should_panic(expected = "<source>:5:5: [E0501] bad: .text 20 \"hello\" is text, yet is not in the text section")]
     fn test_text_outside_text_section() {
-        let _ = process_file_into_tokens("test_files/test_text_outside_section.asm");
+        tokens_from_str("start: HALT\n\ndata:\n    count: .int 50\n    bad: .text 20 \"hello\"\n\ntext:\n    good: .text 20 \"world\"\n");
     }
 
 
     #[test]
     fn test_text_without_data_section() {
-        let tokens = process_file_into_tokens("test_files/test_text_without_data.asm");
+        let tokens = tokens_from_str("init:\n\tLOAD $zero, $g8, $g9, @directory\n\tsyscall 8\n\tHALT\n\ntext:\n\tdirectory: .text 23 \"Novels/Mirrormarch.txt\"");
         let tokens = pseudo_substitution::substitute_pseudo_instrs(tokens);
         let label_table = label_table::generate_label_table(&tokens).unwrap();
 
         assert_eq!(label_table.get("directory").unwrap(), &0x1000);
     }
+
+
+    #[test]
+    fn test_bss_section() {
+        let tokens = tokens_from_str("init:\n    LOAD $g0, $g8, $g9, @count\n    STORE $g0, $g8, $g9, @buffer\n    HALT\n\ndata:\n    count: .int 3\n\n\
+            text:\n    name: .text 4 \"bss\"\n\nbss:\n    buffer: .space 0x1000\n    scratch: .space 16\n");
+        let tokens = pseudo_substitution::substitute_pseudo_instrs(tokens);
+        let label_table = label_table::generate_label_table(&tokens).unwrap();
+
+        assert_eq!(label_table["count"], 0x1000);
+        assert_eq!(label_table["name"], 0x2000);
+        assert_eq!(label_table["buffer"], 0x3000);
+        assert_eq!(label_table["scratch"], 0x4000);
+    }
+
+
+    #[test]
+    fn test_data_lists() {
+        let tokens = process_source_into_tokens("init:\n    HALT\ndata:\ntable: .int 1, 2, 3, 4\nlongs: .long 1, 2\nend: .int 0\n", None).unwrap();
+        let label_table = label_table::generate_label_table(&tokens).unwrap();
+        assert_eq!(label_table["longs"], 0x1004);
+        assert_eq!(label_table["end"], 0x1008);
+    }
+
+
+    #[test]
+    fn test_unused_labels() {
+        let tokens = tokens_from_str(FIBONACCI_PROGRAM);
+        let mut warnings = WarningSink::default();
+        label_table::check_unused_labels(&pseudo_substitution::substitute_pseudo_instrs(tokens), &mut warnings);
+
+        let settings = WarningSettings::from_flags(&[WarningFlag::All], false);
+        let messages:Vec<&str> = warnings.enabled(&settings).iter().map(|warning| warning.message.as_str()).collect();
+        assert_eq!(messages, vec![
+            "<source>:20:5: The label int_long is never used",
+            "<source>:21:5: The label half_float is never used",
+            "<source>:23:9: The label float is never used",
+            "<source>:24:5: The label eszet is never used",
+            "<source>:25:5: The label list is never used",
+            "The label text_data is never used"
+        ]);
+    }
+
+
+    #[test]
+    fn test_label_anonymous_data() {
+        let tokens = tokens_from_str("init:\n    HALT\ndata:\n    table: .int 1\n    .int 2\n    .int 3\ntext:\n    .text 3 \"hi\"\n\
+            bss:\n    __data_1: .space 2\n    .space 4\n");
+        let tokens = label_table::label_anonymous_data(tokens);
+        let labels:Vec<Option<String>> = tokens.iter().map(|tokens| match tokens {
+            FileTokens::DataTokens(t) => t.label.clone(),
+            FileTokens::TextTokens(t) => t.label.clone(),
+            FileTokens::BssTokens(t) => t.label.clone(),
+            _ => None
+        }).collect();
+        assert_eq!(labels, vec![
+            None, Some("table".to_owned()), Some("__data_0".to_owned()), Some("__data_2".to_owned()),
+            Some("__data_3".to_owned()), Some("__data_1".to_owned()), Some("__data_4".to_owned())
+        ]);
+
+        let label_table = label_table::generate_label_table(&tokens).unwrap();
+        assert_eq!(label_table["__data_0"], label_table["table"] + 1);
+        assert_eq!(label_table["__data_2"], label_table["table"] + 2);
+        assert_eq!(label_table["__data_4"], label_table["__data_1"] + 2);
+    }
 }
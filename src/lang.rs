@@ -0,0 +1,166 @@
+use std::fmt;
+use half::f16;
+use crate::token_types::{located, DataTokens, SourcePos};
+use crate::validation::remove_label;
+use crate::warnings::{AsmWarning, WarningKind, WarningSink};
+
+
+/// Represents a level of the assembly language, chosen with `--lang`. A later level may change the meaning
+/// of a construct which was already valid, so a source is always assembled the same way at the level it was
+/// written for. Level 1 is the default, so that existing sources keep assembling byte for byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum LanguageLevel {
+    #[default]
+    One,
+    /// A `.half` is rounded straight from its decimal value rather than through a 32-bit float first, which
+    /// could round it twice and store the wrong one of the two nearest 16-bit floats
+    Two
+}
+
+
+impl LanguageLevel {
+    /// Every level, from the oldest to the latest
+    pub const ALL:[LanguageLevel; 2] = [LanguageLevel::One, LanguageLevel::Two];
+
+
+    /// Takes the number of a level as given to `--lang`, such as `2`, and returns the level, or `None` if
+    /// there is no such level.
+    pub fn from_name(name:&str) -> Option<LanguageLevel> {
+        LanguageLevel::ALL.into_iter().find(|level| level.to_string() == name)
+    }
+
+
+    /// Takes a decimal value of a `.half` and returns the bits of the 16-bit float it is stored as at this
+    /// level.
+    pub fn encode_half(&self, value:f64) -> u16 {
+        match self {
+            LanguageLevel::One => f16::from_f32(value as f32).to_bits(),
+            LanguageLevel::Two => round_half(value)
+        }
+    }
+}
+
+
+/// Takes a decimal value and returns the bits of the 16-bit float nearest to it, choosing the one with an even
+/// significand if it is exactly halfway between two. Rounding to a 32-bit float first can land exactly halfway
+/// between two 16-bit floats when the value was not, so the neighbours of that result are compared against the
+/// value itself.
+fn round_half(value:f64) -> u16 {
+    let bits = f16::from_f32(value as f32).to_bits();
+    if !f16::from_bits(bits).is_finite() {
+        return bits;
+    }
+
+    [bits.wrapping_sub(1), bits, bits.wrapping_add(1)].into_iter()
+        .filter(|bits| f16::from_bits(*bits).is_finite())
+        .min_by(|a, b| {
+            let distance = |bits:u16| (f16::from_bits(bits).to_f64() - value).abs();
+            distance(*a).total_cmp(&distance(*b)).then((a & 1).cmp(&(b & 1)))
+        })
+        .unwrap_or(bits)
+}
+
+
+impl fmt::Display for LanguageLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LanguageLevel::One => write!(f, "1"),
+            LanguageLevel::Two => write!(f, "2")
+        }
+    }
+}
+
+
+/// Takes a line of data which has already been validated, and returns the value of any item in it whose
+/// meaning depends on the language level, which is the value of a `.half`.
+fn get_level_dependent_value(line:&str) -> Option<(&str, f64)> {
+    let tokens:Vec<&str> = remove_label(line).split_whitespace().collect();
+    match tokens[..] {
+        [".half", literal] => literal.parse().ok().map(|value| (literal, value)),
+        _ => None
+    }
+}
+
+
+/// Takes a line of data, the tokens generated from it, which always follow level 1, and the level being
+/// assembled at, and returns the tokens with any item whose meaning is different at that level encoded as
+/// that level describes.
+pub fn apply_language_level(line:&str, mut tokens:DataTokens, level:LanguageLevel) -> DataTokens {
+    if let Some((_, value)) = get_level_dependent_value(line) {
+        tokens.bytes = vec![level.encode_half(value)];
+    }
+
+    tokens
+}
+
+
+/// Takes a line of data which has already been validated and its position in the source, and gives a
+/// migration warning if it would be assembled into different words at one language level than at another,
+/// giving the words at each level, so that a source can be checked before moving it to a later level.
+pub fn check_migration(line:&str, pos:Option<&SourcePos>, warnings:&mut WarningSink) {
+    let (literal, value) = match get_level_dependent_value(line) {
+        Some(item) => item,
+        None => return
+    };
+
+    let encodings:Vec<u16> = LanguageLevel::ALL.iter().map(|level| level.encode_half(value)).collect();
+    if encodings.windows(2).any(|pair| pair[0] != pair[1]) {
+        let levels:Vec<String> = LanguageLevel::ALL.iter().zip(&encodings)
+            .map(|(level, bits)| format!("0x{:04X} at level {}", bits, level))
+            .collect();
+
+        warnings.push(AsmWarning::new(WarningKind::Migration, format!(
+            "{}.half {} is stored differently between language levels: {}", located(pos), literal, levels.join(", ")
+        )));
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::*;
+    use crate::token_generator::generate_data_tokens;
+    use crate::warnings::{WarningFlag, WarningSettings};
+
+
+    // exactly halfway between two 16-bit floats once rounded to 32 bits, but just above halfway before
+    const DOUBLE_ROUNDED:&str = "1.00048831105232";
+
+
+    #[test]
+    fn test_language_levels() {
+        assert_eq!(LanguageLevel::from_name("1"), Some(LanguageLevel::One));
+        assert_eq!(LanguageLevel::from_name("2"), Some(LanguageLevel::Two));
+        assert_eq!(LanguageLevel::from_name("3"), None);
+        assert_eq!(LanguageLevel::default(), LanguageLevel::One);
+
+        assert_eq!(LanguageLevel::One.encode_half(5.25), LanguageLevel::Two.encode_half(5.25));
+        assert_eq!(LanguageLevel::One.encode_half(DOUBLE_ROUNDED.parse().unwrap()), 0x3C00);
+        assert_eq!(LanguageLevel::Two.encode_half(DOUBLE_ROUNDED.parse().unwrap()), 0x3C01);
+    }
+
+
+    #[test]
+    fn test_apply_language_level() {
+        let line = format!("scale: .half {}", DOUBLE_ROUNDED);
+        let tokens = generate_data_tokens(&line, None, 'd').unwrap();
+        assert_eq!(apply_language_level(&line, tokens.clone(), LanguageLevel::One).bytes, vec![0x3C00]);
+        assert_eq!(apply_language_level(&line, tokens, LanguageLevel::Two).bytes, vec![0x3C01]);
+    }
+
+
+    #[test]
+    fn test_check_migration() {
+        let mut warnings = WarningSink::default();
+        check_migration("exact: .half 5.25", None, &mut warnings);
+        check_migration(&format!("scale: .half {}", DOUBLE_ROUNDED), None, &mut warnings);
+        check_migration("count: .int 5", None, &mut warnings);
+
+        assert!(warnings.enabled(&WarningSettings::default()).is_empty());
+        let settings = WarningSettings::from_flags(&[WarningFlag::Enable(WarningKind::Migration)], false);
+        let messages:Vec<&str> = warnings.enabled(&settings).iter().map(|warning| warning.message.as_str()).collect();
+        assert_eq!(messages, vec![
+            ".half 1.00048831105232 is stored differently between language levels: 0x3C00 at level 1, 0x3C01 at level 2"
+        ]);
+    }
+}
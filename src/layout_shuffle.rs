@@ -0,0 +1,164 @@
+use crate::random::SplitMix64;
+use crate::token_types::FileTokens;
+
+
+/// Gets the section a token is placed in, being 'c', 'd', 't', or 'b'
+fn section_of(token:&FileTokens) -> char {
+    match token {
+        FileTokens::InstrTokens(_) => 'c',
+        FileTokens::DataTokens(_) => 'd',
+        FileTokens::TextTokens(_) => 't',
+        FileTokens::BssTokens(_) => 'b',
+        FileTokens::OrgTokens(t) => t.section,
+        FileTokens::AliasTokens(t) => t.section,
+        FileTokens::DeprecatedTokens(t) => t.section,
+        FileTokens::TraceTokens(_) | FileTokens::ExportTableTokens(_) => 'c'
+    }
+}
+
+
+/// Returns true if the token is an instruction which never continues to the one after it
+fn is_terminator(token:&FileTokens) -> bool {
+    matches!(token, FileTokens::InstrTokens(t) if t.opcode == "HALT" || t.opcode == "JUMP")
+}
+
+
+/// Takes the tokens of a single section and splits them into the blocks which can be moved independently.
+/// A block starts at each label, except in the code section, where a label only starts a block if the
+/// instruction before it is a `HALT` or a `JUMP`, as otherwise the code before it runs on into it.
+fn split_blocks(tokens:Vec<FileTokens>) -> Vec<Vec<FileTokens>> {
+    let mut blocks:Vec<Vec<FileTokens>> = Vec::new();
+    for token in tokens {
+        let starts_block = match blocks.last().and_then(|block| block.last()) {
+            Some(previous) => token.label().is_some() && (section_of(&token) != 'c' || is_terminator(previous)),
+            None => true
+        };
+
+        if starts_block {
+            blocks.push(vec![token]);
+        } else {
+            blocks.last_mut().unwrap().push(token);
+        }
+    }
+
+    blocks
+}
+
+
+/// Takes the tokens of a single section and the random number generator, and returns the tokens with their
+/// blocks in a random order. The first block of the code section stays first as it is where the program
+/// starts, as does anything before the first label of any section, and the last block of the code section
+/// stays last if it does not end in a `HALT` or a `JUMP`, as it runs on into the next section. A section
+/// with an `.org` is left as it is, as its items are placed at fixed addresses.
+fn shuffle_section(tokens:Vec<FileTokens>, rng:&mut SplitMix64) -> Vec<FileTokens> {
+    if tokens.iter().any(|token| matches!(token, FileTokens::OrgTokens(_))) {
+        return tokens;
+    }
+
+    let is_code = tokens.first().map(section_of) == Some('c');
+    let mut blocks = split_blocks(tokens);
+    let start = match blocks.first() {
+        Some(block) if is_code || block[0].label().is_none() => 1,
+        _ => 0
+    };
+
+    let end = match blocks.last().and_then(|block| block.last()) {
+        Some(last) if is_code && !is_terminator(last) => blocks.len() - 1,
+        _ => blocks.len()
+    };
+
+    // Fisher-Yates over only the blocks which can move
+    for index in (start + 1..end).rev() {
+        let other = start + rng.below(index - start + 1);
+        blocks.swap(index, other);
+    }
+
+    blocks.into_iter().flatten().collect()
+}
+
+
+/// Takes the tokens of a program and a seed, and returns the tokens with the functions of the code section,
+/// and the labelled items of the data, text, and bss sections, in an order chosen by the seed, so that
+/// builds with different seeds have different layouts while behaving the same. This must be done before
+/// pseudo-instructions are substituted, as every reference between blocks is then still a label which
+/// follows the block to its new address.
+///
+/// A function is a block of code starting with a label which the code before it cannot run on into, and a
+/// labelled data item keeps any unlabelled items after it, so code which relies on two separately labelled
+/// items being next to each other will not work with a shuffled layout. The same seed always gives the same
+/// layout.
+pub fn shuffle_layout(tokens:Vec<FileTokens>, seed:u64) -> Vec<FileTokens> {
+    let mut rng = SplitMix64::new(seed);
+    let mut sections:Vec<Vec<FileTokens>> = Vec::new();
+    for token in tokens {
+        match sections.last_mut() {
+            Some(section) if section_of(&section[0]) == section_of(&token) => section.push(token),
+            _ => sections.push(vec![token])
+        }
+    }
+
+    sections.into_iter().flat_map(|section| shuffle_section(section, &mut rng)).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::assemble_tokens;
+    use crate::layout_shuffle::*;
+    use crate::testing::{tokens_from_str, ORG_PROGRAM, SUBROUTINES_PROGRAM};
+
+
+    fn labels(tokens:&[FileTokens]) -> Vec<String> {
+        tokens.iter().filter_map(|token| match token {
+            FileTokens::InstrTokens(t) => t.label.clone(),
+            FileTokens::DataTokens(t) => t.label.clone(),
+            _ => None
+        }).collect()
+    }
+
+
+    #[test]
+    fn test_shuffle_layout() {
+        let tokens = tokens_from_str(SUBROUTINES_PROGRAM);
+        let original = labels(&tokens);
+        let shuffled = labels(&shuffle_layout(tokens.clone(), 1));
+        assert_eq!(shuffled, labels(&shuffle_layout(tokens.clone(), 1)));
+
+        // every seed keeps the entry point first and the code which runs on into the next label with it
+        let mut layouts:Vec<Vec<String>> = Vec::new();
+        for seed in 0..8 {
+            let shuffled = labels(&shuffle_layout(tokens.clone(), seed));
+            let mut sorted = shuffled.clone();
+            sorted.sort();
+            let mut expected = original.clone();
+            expected.sort();
+            assert_eq!(sorted, expected);
+
+            assert_eq!(shuffled[0], "init");
+            let half = shuffled.iter().position(|label| label == "half").unwrap();
+            assert_eq!(shuffled[half + 1], "half_done");
+            assert!(shuffled[shuffled.len() - 3..].contains(&"first".to_owned()));
+            layouts.push(shuffled);
+        }
+
+        layouts.dedup();
+        assert!(layouts.len() > 1);
+    }
+
+
+    #[test]
+    fn test_shuffled_program_assembles() {
+        let tokens = tokens_from_str(SUBROUTINES_PROGRAM);
+        let original = assemble_tokens(tokens.clone()).unwrap();
+        let shuffled = assemble_tokens(shuffle_layout(tokens.clone(), 3)).unwrap();
+        assert_eq!(original.len(), shuffled.len());
+        assert_ne!(original, shuffled);
+    }
+
+
+    #[test]
+    fn test_org_section_not_shuffled() {
+        let tokens = tokens_from_str(ORG_PROGRAM);
+        assert_eq!(labels(&shuffle_layout(tokens.clone(), 5)), labels(&tokens));
+    }
+}
@@ -0,0 +1,503 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use errors::{AsmValidationError, AssembleError, TokenGenerationError};
+use lang::LanguageLevel;
+use limits::{ResourceBudget, ResourceLimits};
+use warnings::WarningSink;
+use preprocessor::Configuration;
+use token_types::{AliasTokens, DataTokens, ExportTableTokens, FileTokens, SourcePos};
+use stats::AssemblyStats;
+use trace::TraceOutput;
+
+pub mod errors;
+pub mod validation;
+pub mod token_generator;
+pub mod label_table;
+pub mod pseudo_substitution;
+pub mod token_types;
+pub mod generate_code;
+pub mod cmd_args;
+pub mod map_file;
+pub mod partial_assembly;
+pub mod lints;
+pub mod warnings;
+pub mod constants;
+pub mod test_vectors;
+pub mod preprocessor;
+pub mod listing;
+pub mod memmap;
+pub mod concatenation;
+pub mod namespaces;
+pub mod version;
+pub mod dump;
+pub mod memory_fit;
+pub mod rom_split;
+pub mod org;
+pub mod privilege;
+pub mod watch;
+pub mod expressions;
+pub mod config;
+pub mod isa;
+pub mod code_buffer;
+pub mod serve;
+pub mod hardening;
+pub mod layout_shuffle;
+pub mod random;
+pub mod determinism_audit;
+pub mod diagnostics;
+pub mod batch;
+pub mod suggestions;
+pub mod limits;
+pub mod content_name;
+pub mod immediate_parts;
+pub mod fixed_point;
+pub mod aliases;
+pub mod deprecation;
+pub mod lang;
+pub mod switch;
+pub mod interrupts;
+pub mod escapes;
+pub mod coverage;
+pub mod includes;
+pub mod trace;
+pub mod stats;
+pub mod macros;
+pub mod demangle;
+pub mod constant_folding;
+pub mod encoding;
+pub mod local_labels;
+pub mod api_freeze;
+pub mod exports;
+pub mod byte_access;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+
+/// Takes a reader of the source of a program, such as a file or stdin, the name of the source to use in
+/// diagnostics, such as its filename, and the target being assembled for, and returns the tokens of all
+/// the lines of assembly in it which are included for that target by any `.if` blocks. If `harden` is
+/// true, the checks described in `hardening::expand_functions` are inserted into every function.
+///
+/// Any line ending and a byte order mark are accepted, as described for
+/// `preprocessor::normalize_line_endings`. Every instruction and data item records its position in the
+/// source. Will return an `AsmValidationError` if any line is not valid, holding every invalid line found
+/// with one on each line of the message, each starting with the position of the problem such as
+/// `prog.asm:12:9: `, or naming the source if it cannot be read.
+pub fn process_reader_into_tokens<R:BufRead>(reader:R, name:&str, target:Option<&str>, harden:bool) -> Result<Vec<FileTokens>, AsmValidationError> {
+    process_reader_into_tokens_with_warnings(reader, name, &Configuration::for_target(target), harden, LanguageLevel::default(),
+        &mut ResourceBudget::new(ResourceLimits::UNLIMITED), &mut WarningSink::default())
+}
+
+
+/// Takes a reader of the source of a program and returns its tokens in the same way as
+/// `process_reader_into_tokens`, but for a whole configuration, whose constants are defined before the first
+/// line as described for `preprocessor::Configuration`, assembling any construct whose meaning depends on the
+/// language level as the given level describes, nesting `.include` and macros no deeper than the limits of the
+/// budget allow, counting the macros, `.rept` blocks, includes, and byte accesses it expands in the budget,
+/// and pushing any warnings found while validating the lines, such as a `.half` which cannot be stored
+/// exactly, into the sink.
+pub fn process_reader_into_tokens_with_warnings<R:BufRead>(reader:R, name:&str, config:&Configuration, harden:bool, lang:LanguageLevel,
+        budget:&mut ResourceBudget, warnings:&mut WarningSink) -> Result<Vec<FileTokens>, AsmValidationError> {
+    tokenize_reader(reader, name, config, harden, lang, budget, warnings)
+}
+
+
+/// Takes a reader of the source of a program and returns its tokens in the same way as
+/// `process_reader_into_tokens`, but stops with a `ResourceLimitExceeded` as soon as the program reaches one
+/// of the limits of the budget, so that source from untrusted users can be assembled safely.
+pub fn process_reader_into_tokens_with_limits<R:BufRead>(reader:R, name:&str, target:Option<&str>, harden:bool, budget:&mut ResourceBudget)
+        -> Result<Vec<FileTokens>, Box<dyn Error>> {
+    tokenize_reader(reader, name, &Configuration::for_target(target), harden, LanguageLevel::default(), budget, &mut WarningSink::default()).map_err(|e| match budget.take_exceeded() {
+        Some(exceeded) => exceeded.into(),
+        None => e.into()
+    })
+}
+
+
+/// Takes a reader of the source of a program and returns its tokens as described for
+/// `process_reader_into_tokens`, reading no more lines than the budget allows. A limit being reached is
+/// returned as an `AsmValidationError` with its message, and is kept in the budget.
+fn tokenize_reader<R:BufRead>(reader:R, name:&str, config:&Configuration, harden:bool, lang:LanguageLevel, budget:&mut ResourceBudget,
+        warnings:&mut WarningSink) -> Result<Vec<FileTokens>, AsmValidationError> {
+    let lines = encoding::read_lines(reader, budget.limits.max_lines.saturating_add(1))
+        .map_err(|e| AsmValidationError(format!("could not read {}: {}", name, e)))?;
+    budget.check_lines(lines.len()).map_err(|e| AsmValidationError(e.to_string()))?;
+    let lines = encoding::decode_lines(lines, config.encoding).map_err(|e| AsmValidationError(format!("{}:{}", name, e.0)))?;
+    let lines = preprocessor::normalize_line_endings(lines);
+
+    // every error found in the lines starts with the line and column, so only the name is added here, unless
+    // the line was included from another file, which its error already names
+    tokenize_lines(lines, name, config, harden, lang, budget, warnings).map_err(|e| {
+        AsmValidationError(e.0.split('\n').map(|error| match includes::is_located_in_included_file(error) {
+            true => error.to_owned(),
+            false => format!("{}:{}", name, error)
+        }).collect::<Vec<String>>().join("\n"))
+    })
+}
+
+
+/// Takes the lines of the source of a program and returns their tokens as described for
+/// `process_reader_into_tokens`, where every error starts with the line and column it is about. An invalid
+/// line is skipped so that the lines after it are still checked, and an error is only returned once every
+/// line has been, unless the structure of the source, such as its `.if` blocks, is not valid.
+fn tokenize_lines(lines:Vec<String>, name:&str, config:&Configuration, harden:bool, lang:LanguageLevel, budget:&mut ResourceBudget,
+        warnings:&mut WarningSink) -> Result<Vec<FileTokens>, AsmValidationError> {
+    let mut mode = 'c';
+    let source_lines = preprocessor::join_continuation_lines(lines)?;
+    let source_lines = preprocessor::apply_conditionals(source_lines, config)?;
+    let source_lines = includes::expand_includes(source_lines, name, config, budget)?;
+    let source_lines = macros::expand_macros(source_lines, budget)?;
+    let source_lines = macros::expand_repeats(source_lines, budget)?;
+    lints::check_forbidden_opcodes(&source_lines, name, &config.forbidden)?;
+    let (source_lines, exported) = exports::take_exported_functions(source_lines, name)?;
+    let source_lines = hardening::expand_functions(source_lines, harden)?;
+    let source_lines = byte_access::expand_byte_accesses(source_lines, budget)?;
+    let source_lines = local_labels::resolve_local_labels(source_lines, name)?;
+    let source_lines = local_labels::resolve_file_labels(source_lines, name)?;
+
+    let mut tokens:Vec<FileTokens> = Vec::new();
+    let mut next_label:Option<String> = None;
+    let mut next_label_pos:Option<SourcePos> = None;
+    let mut constants:HashMap<String, i64> = config.definitions.iter().cloned().collect();
+    let mut namespace:Option<String> = None;
+    let mut privilege:Option<privilege::Privilege> = None;
+    let mut switch_tables:Vec<DataTokens> = Vec::new();
+    let mut exported = Some(exported);
+    let mut errors:Vec<String> = Vec::new();
+    for source_line in source_lines {
+        budget.check_tokens(&tokens).map_err(|e| AsmValidationError(e.to_string()))?;
+
+        // records an error at this line and moves on to the next one
+        macro_rules! check {
+            ($result:expr) => {
+                match $result {
+                    Ok(value) => value,
+                    Err(e) => {
+                        errors.push(source_line.locate(e).0);
+                        continue;
+                    }
+                }
+            };
+        }
+
+        let line = source_line.text.trim().to_string();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line == "data:" {
+            mode = 'd';
+            continue;
+        } else if line == "text:" {
+            mode = 't';
+            continue;
+        } else if line == "bss:" {
+            mode = 'b';
+            continue;
+        }
+
+        if namespaces::is_namespace_directive(&line) {
+            namespace = Some(check!(namespaces::parse_namespace_directive(&line)));
+            continue;
+        }
+
+        if privilege::is_privilege_directive(&line) {
+            privilege = Some(check!(privilege::parse_privilege_directive(&line)));
+            continue;
+        }
+
+        // the gap an .org or .align leaves cannot have a label, so any label before it stays with the next item
+        if org::is_org_directive(&line) {
+            tokens.push(FileTokens::OrgTokens(check!(org::parse_org_directive(&line, mode))));
+            continue;
+        }
+
+        if aliases::is_alias_directive(&line) {
+            let pos = Some(source_line.pos(name));
+            tokens.push(FileTokens::AliasTokens(check!(aliases::parse_alias_directive(&line, mode, &namespace, pos))));
+            continue;
+        }
+
+        if deprecation::is_deprecated_directive(&line) {
+            let pos = Some(source_line.pos(name));
+            tokens.push(FileTokens::DeprecatedTokens(check!(deprecation::parse_deprecated_directive(&line, mode, &namespace, pos))));
+            continue;
+        }
+
+        if org::is_align_directive(&line) {
+            tokens.push(FileTokens::OrgTokens(check!(org::parse_align_directive(&line, mode))));
+            continue;
+        }
+
+        if mode == 'c' && switch::is_switch_directive(&line) {
+            let pos = Some(source_line.pos(name));
+            let switch = check!(switch::parse_switch_directive(&namespaces::qualify_line_label(&line, &namespace)));
+            let (instrs, tables) = check!(switch::expand_switch(&switch, next_label.take(), source_line.file.as_deref().unwrap_or(name), pos));
+            tokens.extend(instrs.into_iter().map(FileTokens::InstrTokens));
+            switch_tables.extend(tables);
+            continue;
+        }
+
+        if includes::is_incbin_directive(&line) {
+            let pos = Some(source_line.pos(name));
+            let line = namespaces::qualify_line_label(&line, &namespace);
+            let file = source_line.file.as_deref().unwrap_or(name);
+            tokens.push(FileTokens::DataTokens(check!(includes::parse_incbin_directive(&line, mode, next_label.take(), file,
+                budget.limits.max_include_depth, pos))));
+            continue;
+        }
+
+        // the label of a trace point moves to its first instruction once it is expanded
+        if trace::is_trace_directive(&line) {
+            let pos = Some(source_line.pos(name));
+            tokens.push(FileTokens::TraceTokens(check!(trace::parse_trace_directive(&line, mode, &namespace, next_label.take(), pos))));
+            continue;
+        }
+
+        // the functions the file exports go with its table, and are only placed once every file is tokenized
+        if exports::is_export_table_directive(&line) {
+            let pos = Some(source_line.pos(name));
+            let functions = exported.take().unwrap_or_default();
+            tokens.push(FileTokens::ExportTableTokens(check!(exports::parse_export_table_directive(&line, mode, functions, pos))));
+            continue;
+        }
+
+        // a pinned label is an .org followed by the labelled item
+        let line = if org::is_at_directive(&line) {
+            let (org_tokens, line) = check!(org::split_at_directive(&line, mode));
+            tokens.push(FileTokens::OrgTokens(org_tokens));
+            line
+        } else {
+            line
+        };
+
+        // constants must be defined before they are used so they can be substituted in a single pass
+        if constants::is_constant_definition(&line) {
+            let (constant, value) = check!(constants::parse_constant_definition(&line));
+            constants.insert(constant, value);
+            continue;
+        }
+
+        // a part operator is replaced first, as its brackets and comma would otherwise be read as an expression
+        let line = check!(immediate_parts::substitute_immediate_parts(&line, &constants));
+        let line = check!(constants::substitute_constants(&line, &constants));
+        check!(validation::validate_asm_line(&line, mode));
+
+        let line = namespaces::qualify_line_label(&line, &namespace);
+        // a label on its own line followed by another, such as `other:` then `1:`, binds to the same item, so
+        // the earlier label becomes an alias of the later one rather than being replaced by it
+        if line.ends_with(":") {
+            let label = line[..line.len() - 1].to_owned();
+            if let Some(earlier) = next_label.replace(label.clone()) {
+                tokens.push(FileTokens::AliasTokens(AliasTokens::new(mode, earlier, label, next_label_pos.take())));
+            }
+
+            next_label_pos = Some(source_line.pos(name));
+            continue;
+        }
+
+        let pos = Some(source_line.pos(name));
+        let invalid = |e:TokenGenerationError| AsmValidationError(e.0);
+        match mode {
+            'c' => {
+                let instr_tokens = check!(token_generator::generate_instr_tokens(&line, next_label.take()).map_err(invalid)).with_pos(pos);
+                check!(privilege::check_privilege(&instr_tokens.opcode, privilege));
+                tokens.push(FileTokens::InstrTokens(instr_tokens));
+            },
+            'd' => {
+                let data_tokens = check!(token_generator::generate_data_tokens(&line, next_label.take(), mode).map_err(invalid));
+                validation::check_data_precision(&line, pos.as_ref(), warnings);
+                lang::check_migration(&line, pos.as_ref(), warnings);
+                let data_tokens = lang::apply_language_level(&line, data_tokens, lang);
+                tokens.push(FileTokens::DataTokens(data_tokens.with_pos(pos)));
+            },
+            't' => {
+                let text_tokens = check!(token_generator::generate_text_tokens(&line, next_label.take(), mode).map_err(invalid));
+                tokens.push(FileTokens::TextTokens(text_tokens));
+            },
+            'b' => {
+                let bss_tokens = check!(token_generator::generate_bss_tokens(&line, next_label.take()).map_err(invalid));
+                tokens.push(FileTokens::BssTokens(bss_tokens));
+            },
+            _ => panic!("Invalid section mode '{}'", mode)
+        }
+
+        next_label = None;
+    }
+
+    // the functions of a file without a table still go in the table of another file
+    if let Some(functions) = exported.filter(|functions| !functions.is_empty()) {
+        tokens.push(FileTokens::ExportTableTokens(ExportTableTokens::new(None, functions, None)));
+    }
+
+    switch::place_switch_tables(&mut tokens, switch_tables);
+    budget.check_tokens(&tokens).map_err(|e| AsmValidationError(e.to_string()))?;
+    if !errors.is_empty() {
+        return Err(AsmValidationError(errors.join("\n")));
+    }
+
+    Ok(tokens)
+}
+
+
+/// Takes the source of a program and the target being assembled for, and returns its tokens using
+/// `process_reader_into_tokens`, where the source is named `<source>` in diagnostics.
+pub fn process_source_into_tokens(source:&str, target:Option<&str>) -> Result<Vec<FileTokens>, AsmValidationError> {
+    process_reader_into_tokens(source.as_bytes(), "<source>", target, false)
+}
+
+
+/// Takes a filename and returns a `Vec<FileTokens>` representing the tokens of all the lines of assembly in the file
+/// which can be either `DataTokens` or `InstrTokens`.
+pub fn process_file_into_tokens(input_file:&str) -> Vec<FileTokens> {
+    process_file_into_tokens_for_target(input_file, None)
+}
+
+
+/// Takes a filename and the target being assembled for, and returns the tokens of all the lines of assembly in
+/// the file which are included for that target by any `.if` blocks.
+pub fn process_file_into_tokens_for_target(input_file:&str, target:Option<&str>) -> Vec<FileTokens> {
+    process_reader_into_tokens(BufReader::new(File::open(input_file).unwrap()), input_file, target, false).unwrap()
+}
+
+
+/// Takes any error raised while assembling and returns it as an `AssembleError` with the same message.
+fn to_assemble_error<E:Error>(e:E) -> AssembleError {
+    AssembleError(e.to_string())
+}
+
+
+/// Takes the source of a program and assembles it into the bytes of a program binary, in the same way as
+/// running the assembler on a file with no options. This runs the tokenizer, the expansion of trace points,
+/// the fallthrough lint, pseudo substitution, the label table, label substitution, and code generation in
+/// turn without writing any files.
+///
+/// Will return an `AssembleError` describing the first problem found if the program cannot be assembled.
+pub fn assemble_source(source:&str) -> Result<Vec<u8>, AssembleError> {
+    assemble_tokens(process_source_into_tokens(source, None).map_err(to_assemble_error)?)
+}
+
+
+/// Takes the tokens of a program and assembles them into the bytes of a program binary, running every stage
+/// of `assemble_source` after the tokenizer. Will return an `AssembleError` if the program cannot be
+/// assembled.
+pub fn assemble_tokens(tokens:Vec<FileTokens>) -> Result<Vec<u8>, AssembleError> {
+    // every stage's error is already an AssembleError, as no limit can be reached here
+    assemble_tokens_with_limits(tokens, &mut ResourceBudget::new(ResourceLimits::UNLIMITED)).map_err(|e| match e.downcast::<AssembleError>() {
+        Ok(e) => *e,
+        Err(e) => AssembleError(e.to_string())
+    })
+}
+
+
+/// Takes the tokens of a program and assembles them in the same way as `assemble_tokens`, but returns a
+/// `ResourceLimitExceeded` if the pseudo-instructions expand into more tokens than the budget allows, the
+/// binary would hold more words than it allows, or the budget runs out of time between stages.
+pub fn assemble_tokens_with_limits(tokens:Vec<FileTokens>, budget:&mut ResourceBudget) -> Result<Vec<u8>, Box<dyn Error>> {
+    assemble_tokens_with_stats(tokens, budget).map(|(binary, _)| binary)
+}
+
+
+/// Takes the tokens of a program and assembles them in the same way as `assemble_tokens_with_limits`, and
+/// returns the `AssemblyStats` of how much work each stage did along with the binary.
+pub fn assemble_tokens_with_stats(tokens:Vec<FileTokens>, budget:&mut ResourceBudget) -> Result<(Vec<u8>, AssemblyStats), Box<dyn Error>> {
+    let tokens_processed = tokens.len();
+    let (tokens, trace_points) = trace::expand_traces(tokens, TraceOutput::default()).map_err(to_assemble_error)?;
+    budget.count_expansions(trace_points.len());
+    let tokens = exports::expand_export_tables(tokens).map_err(to_assemble_error)?;
+    lints::check_code_fallthrough(&tokens).map_err(to_assemble_error)?;
+
+    let tokens = pseudo_substitution::substitute_pseudo_instrs_with_budget(tokens, budget);
+    budget.check_expansions(tokens.len())?;
+    budget.check_time()?;
+    let label_table:HashMap<String, i64> = label_table::generate_label_table(&tokens).map_err(to_assemble_error)?;
+    let tokens = pseudo_substitution::substitute_labels(tokens, &label_table).map_err(to_assemble_error)?;
+
+    // the gaps left by .org are only known once every token has an address
+    budget.check_layout(&tokens)?;
+    let stats = AssemblyStats::new(tokens_processed, budget.expansions(), &tokens, label_table::PAGE_SIZE);
+    Ok((generate_code::generate_binary_bytes(&tokens, true, 0x0000, label_table::PAGE_SIZE).map_err(to_assemble_error)?, stats))
+}
+
+
+/// Takes the source of a program, the name to use for it in diagnostics, and the limits on the resources it
+/// may use, and assembles it into the bytes of a program binary in the same way as `assemble_source`. Will
+/// return a `ResourceLimitExceeded` if it reaches any of the limits, or any other error if it cannot be
+/// assembled, which lets a service such as `serve` assemble source from untrusted users.
+pub fn assemble_source_with_limits(source:&str, name:&str, limits:ResourceLimits) -> Result<Vec<u8>, Box<dyn Error>> {
+    assemble_source_with_stats(source, name, limits).map(|(binary, _)| binary)
+}
+
+
+/// Takes the source of a program, the name to use for it in diagnostics, and the limits on the resources it
+/// may use, and assembles it in the same way as `assemble_source_with_limits`, returning the `AssemblyStats`
+/// of the tokens it was processed into, the expansions performed, and the words emitted along with the
+/// binary, so that a tool embedding the assembler can monitor the programs its users give it.
+pub fn assemble_source_with_stats(source:&str, name:&str, limits:ResourceLimits) -> Result<(Vec<u8>, AssemblyStats), Box<dyn Error>> {
+    let mut budget = ResourceBudget::new(limits);
+    let tokens = process_reader_into_tokens_with_limits(source.as_bytes(), name, None, false, &mut budget)?;
+    assemble_tokens_with_stats(tokens, &mut budget)
+}
+
+
+/// Takes a filename and assembles the program in it into the bytes of a program binary using
+/// `assemble_source`. Will return an `AssembleError` if the file cannot be read or assembled.
+pub fn assemble_file(input_file:&str) -> Result<Vec<u8>, AssembleError> {
+    let source = fs::read_to_string(input_file)
+        .map_err(|e| AssembleError(format!("could not read {}: {}", input_file, e)))?;
+    assemble_source(&source)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{assemble_file, assemble_source, process_source_into_tokens};
+    use crate::label_table::PAGE_SIZE;
+    use crate::version::{output_header, VERSION};
+
+
+    #[test]
+    fn test_assemble_source() {
+        let binary = assemble_source("init:\n    ADD $g0, $g1, $g2\n    HALT\n").unwrap();
+        let header = output_header(None, PAGE_SIZE);
+        assert!(header.starts_with(format!("info:iridium_assembler {}\0", VERSION).as_bytes()));
+        assert_eq!(binary[..header.len()], header[..]);
+        assert_eq!(binary[header.len()..], [0x23, 0x11, 0xFF, 0xFF]);
+    }
+
+
+    #[test]
+    fn test_assemble_source_errors() {
+        let err = assemble_source("init:\n    ADDQ $g0, 80\n    HALT\n").unwrap_err();
+        assert!(err.0.contains("<source>:2:5: "));
+
+        let err = assemble_source("    JUMP $g8, $g9, @nowhere\n    HALT\n").unwrap_err();
+        assert!(err.0.contains("<source>:1:5: [E0404] The label nowhere was not found!"));
+        assert!(assemble_file("test_files/does_not_exist.asm").is_err());
+    }
+
+
+    #[test]
+    fn test_every_error_reported() {
+        let err = process_source_into_tokens("init:\n    ADDQ $g0, 80\n    ADD $g0, $g1, $g2\n    BAD\n    HALT\n", None).unwrap_err();
+        let errors:Vec<&str> = err.0.split('\n').collect();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("<source>:2:5: ") && errors[1].starts_with("<source>:4:5: "));
+        assert_eq!(err.to_string().matches("Found invalid instruction: ").count(), 2);
+
+        let err = assemble_source("    JUMP $g8, $g9, @first\n    JUMP $g8, $g9, @second\n    HALT\n").unwrap_err();
+        assert!(err.0.contains("<source>:1:5: [E0404] The label first was not found!\n"));
+        assert!(err.0.ends_with("<source>:2:5: [E0404] The label second was not found!"));
+    }
+
+
+    #[test]
+    fn test_assemble_file() {
+        let binary = assemble_file("test_files/test_bss.asm").unwrap();
+        assert!(binary.starts_with(b"info:iridium_assembler"));
+        assert!(binary.windows(6).any(|bytes| bytes == b"data:\0"));
+        assert!(binary.ends_with(b"text:\0b\0s\0s\0\0\0"));
+    }
+}
@@ -0,0 +1,216 @@
+use std::time::{Duration, Instant};
+use crate::errors::ResourceLimitExceeded;
+use crate::label_table::PAGE_SIZE;
+use crate::stats::count_words;
+use crate::token_types::FileTokens;
+
+
+/// Represents the most of each resource that assembling a single program may use, so that a service which
+/// assembles programs from untrusted users, such as `serve` or `batch`, cannot be made to run out of
+/// memory or time by a hostile one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// The most lines of source which are read, which also limits the lines that macros and `.rept` expand into
+    pub max_lines: usize,
+    /// The most instructions, data items, and `.org` gaps the source may expand into, including those
+    /// added by pseudo-instructions
+    pub max_expansions: usize,
+    /// The most words the binary may hold, including the gaps left by `.org`
+    pub max_output_words: usize,
+    /// The longest assembling may take, which is checked between lines and between stages
+    pub max_time: Duration,
+    /// The deepest `.include` may be nested, where 0 means no file may be included at all, either with
+    /// `.include` or `.incbin`
+    pub max_include_depth: usize,
+    /// The deepest macros may be invoked inside the bodies of other macros, where 0 means no macro may be
+    /// invoked at all
+    pub max_macro_expansion: usize
+}
+
+
+impl ResourceLimits {
+    /// No limits at all, which is used when assembling trusted source from the command line
+    pub const UNLIMITED:ResourceLimits = ResourceLimits {
+        max_lines: usize::MAX,
+        max_expansions: usize::MAX,
+        max_output_words: usize::MAX,
+        max_time: Duration::MAX,
+        max_include_depth: usize::MAX,
+        max_macro_expansion: usize::MAX
+    };
+}
+
+
+/// The default limits are far larger than any real program needs, but small enough that a server can
+/// assemble many programs at once. They do not allow `.include` or `.incbin`, so that a program cannot read the files of
+/// the server.
+impl Default for ResourceLimits {
+    fn default() -> ResourceLimits {
+        ResourceLimits {
+            max_lines: 100_000,
+            max_expansions: 1_000_000,
+            max_output_words: 1 << 20,
+            max_time: Duration::from_secs(10),
+            max_include_depth: 0,
+            max_macro_expansion: 16
+        }
+    }
+}
+
+
+/// Represents the resources used so far while assembling a program under some `ResourceLimits`
+#[derive(Debug, Clone)]
+pub struct ResourceBudget {
+    pub limits: ResourceLimits,
+    start: Instant,
+    counted_tokens: usize,
+    output_words: usize,
+    expansions: usize,
+    exceeded: Option<ResourceLimitExceeded>
+}
+
+
+impl ResourceBudget {
+    pub fn new(limits:ResourceLimits) -> ResourceBudget {
+        ResourceBudget { limits, start: Instant::now(), counted_tokens: 0, output_words: 0, expansions: 0, exceeded: None }
+    }
+
+
+    /// Takes the name of a limit and its maximum and returns the error for it, which is kept so that it can
+    /// be recovered with `take_exceeded` when it has to pass through a stage which only returns messages.
+    fn exceed(&mut self, limit:&'static str, maximum:u64) -> ResourceLimitExceeded {
+        let error = ResourceLimitExceeded { limit, maximum };
+        self.exceeded = Some(error.clone());
+        error
+    }
+
+
+    /// Takes the number of macros, `.rept` blocks, includes, or other constructs which have just been expanded
+    /// and adds them to the expansions performed so far.
+    pub fn count_expansions(&mut self, expansions:usize) {
+        self.expansions += expansions;
+    }
+
+
+    /// Returns the number of expansions performed so far, as counted by `count_expansions`.
+    pub fn expansions(&self) -> usize {
+        self.expansions
+    }
+
+
+    /// Takes the error of the limit reached since this was last called, if any.
+    pub fn take_exceeded(&mut self) -> Option<ResourceLimitExceeded> {
+        self.exceeded.take()
+    }
+
+
+    /// Returns a `ResourceLimitExceeded` if assembling has taken longer than the limit.
+    pub fn check_time(&mut self) -> Result<(), ResourceLimitExceeded> {
+        if self.start.elapsed() > self.limits.max_time {
+            return Err(self.exceed("milliseconds", self.limits.max_time.as_millis().min(u64::MAX as u128) as u64));
+        }
+
+        Ok(())
+    }
+
+
+    /// Takes the number of lines read and returns a `ResourceLimitExceeded` if it is more than the limit.
+    pub fn check_lines(&mut self, lines:usize) -> Result<(), ResourceLimitExceeded> {
+        if lines > self.limits.max_lines {
+            return Err(self.exceed("lines", self.limits.max_lines as u64));
+        }
+
+        Ok(())
+    }
+
+
+    /// Takes the tokens generated so far, which only ever grow between calls, and returns a
+    /// `ResourceLimitExceeded` if there are more of them than the expansion limit, if the instructions and
+    /// data in them need more words than the output limit, or if assembling has taken too long.
+    pub fn check_tokens(&mut self, tokens:&[FileTokens]) -> Result<(), ResourceLimitExceeded> {
+        for token in &tokens[self.counted_tokens.min(tokens.len())..] {
+            self.output_words += match token {
+                FileTokens::InstrTokens(_) => 1,
+                FileTokens::DataTokens(t) => t.bytes.len(),
+                FileTokens::TextTokens(t) => t.bytes.len(),
+                FileTokens::BssTokens(_) | FileTokens::OrgTokens(_) | FileTokens::AliasTokens(_) | FileTokens::DeprecatedTokens(_) | FileTokens::TraceTokens(_)
+                    | FileTokens::ExportTableTokens(_) => 0
+            };
+        }
+
+        self.counted_tokens = tokens.len();
+        self.check_expansions(tokens.len())?;
+        if self.output_words > self.limits.max_output_words {
+            return Err(self.exceed("output words", self.limits.max_output_words as u64));
+        }
+
+        self.check_time()
+    }
+
+
+    /// Takes the number of tokens a program has expanded into and returns a `ResourceLimitExceeded` if it is
+    /// more than the limit.
+    pub fn check_expansions(&mut self, expansions:usize) -> Result<(), ResourceLimitExceeded> {
+        if expansions > self.limits.max_expansions {
+            return Err(self.exceed("expansions", self.limits.max_expansions as u64));
+        }
+
+        Ok(())
+    }
+
+
+    /// Takes the final tokens of a program, laid out on pages of `PAGE_SIZE` words, and returns a
+    /// `ResourceLimitExceeded` if the binary would hold more words than the limit, counting the gaps which
+    /// `.org` leaves in every section but bss.
+    pub fn check_layout(&mut self, tokens:&[FileTokens]) -> Result<(), ResourceLimitExceeded> {
+        if count_words(tokens, PAGE_SIZE) > self.limits.max_output_words {
+            return Err(self.exceed("output words", self.limits.max_output_words as u64));
+        }
+
+        self.check_time()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::assemble_source_with_limits;
+    use crate::errors::ResourceLimitExceeded;
+    use crate::limits::*;
+
+
+    /// Takes a program and the limits to assemble it within, and returns the limit it reached
+    fn exceeded(source:&str, limits:ResourceLimits) -> ResourceLimitExceeded {
+        *assemble_source_with_limits(source, "<source>", limits).unwrap_err().downcast::<ResourceLimitExceeded>().unwrap()
+    }
+
+
+    #[test]
+    fn test_resource_limits() {
+        let program = "init:\n    ADD $g0, $g1, $g2\n    NOP\n    HALT\n";
+        assert!(assemble_source_with_limits(program, "<source>", ResourceLimits::default()).is_ok());
+
+        let limits = ResourceLimits { max_lines: 3, ..ResourceLimits::default() };
+        assert_eq!(exceeded(program, limits), ResourceLimitExceeded { limit: "lines", maximum: 3 });
+
+        let limits = ResourceLimits { max_expansions: 2, ..ResourceLimits::default() };
+        assert_eq!(exceeded(program, limits), ResourceLimitExceeded { limit: "expansions", maximum: 2 });
+
+        let limits = ResourceLimits { max_output_words: 100, ..ResourceLimits::default() };
+        assert_eq!(exceeded("init:\n    HALT\ndata:\n    .rand 1000, seed=1\n", limits).limit, "output words");
+        assert_eq!(exceeded("init:\n    HALT\ndata:\n    .org 0x7FFFFFFF\n    .int 1\n", limits).limit, "output words");
+
+        let limits = ResourceLimits { max_time: Duration::ZERO, ..ResourceLimits::default() };
+        assert_eq!(exceeded(program, limits).limit, "milliseconds");
+    }
+
+
+    #[test]
+    fn test_pseudo_instruction_expansions() {
+        // each jump to a label becomes five instructions once the pseudo-instructions are substituted
+        let program = "init:\n    NOP\n    JUMP $g8, $g9, @init\n";
+        let limits = ResourceLimits { max_expansions: 4, ..ResourceLimits::default() };
+        assert_eq!(exceeded(program, limits).limit, "expansions");
+    }
+}
@@ -0,0 +1,299 @@
+use std::path::Path;
+use crate::errors::AsmValidationError;
+use crate::hardening::{split_instr, split_label, strip_comment};
+use crate::namespaces::{is_namespace_directive, parse_namespace_directive};
+use crate::preprocessor::SourceLine;
+use crate::token_types::{located, FileTokens};
+use crate::warnings::{AsmWarning, WarningKind};
+
+
+/// Takes a stream of tokens and checks that the code section does not fall through into the data or text
+/// section, which would cause the CPU to execute data as if it were instructions. The last instruction
+/// before any data must be a `HALT` or an unconditional `JUMP`, otherwise an `AsmValidationError` is
+/// returned.
+///
+/// Programs without a data or text section are not checked, as there is nothing to fall through into. An
+/// `.org` or `.align` after the code, or a bss section, is not data the CPU could run into, so it is ignored.
+pub fn check_code_fallthrough(tokens:&[FileTokens]) -> Result<(), AsmValidationError> {
+    let last_instr_index = match tokens.iter().rposition(|token| matches!(token, FileTokens::InstrTokens(_))) {
+        Some(index) => index,
+        None => return Ok(())
+    };
+
+    let has_data = tokens[last_instr_index + 1..].iter().any(|token| {
+        matches!(token, FileTokens::DataTokens(_) | FileTokens::TextTokens(_))
+    });
+    if !has_data {
+        return Ok(());
+    }
+
+    let last_instr = tokens[last_instr_index].try_get_instr_tokens().unwrap();
+    if last_instr.opcode != "HALT" && last_instr.opcode != "JUMP" {
+        return Err(AsmValidationError(format!(
+            "{}The code section ends with {} which falls through into the data section - end it with HALT or JUMP, or use --allow-fallthrough",
+            located(last_instr.pos.as_ref()), last_instr.opcode
+        )));
+    }
+
+    Ok(())
+}
+
+
+
+/// Represents opcodes forbidden by one `--forbid`, either everywhere or only within its scope
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForbiddenOpcodes {
+    /// A file or directory as given on the command line, whose lines are covered, or a namespace such as
+    /// `@app`, whose lines are covered in any file. Every line is covered if it is `None`.
+    pub scope: Option<String>,
+    pub opcodes: Vec<String>
+}
+
+
+impl ForbiddenOpcodes {
+    /// Takes the file a line is in and the namespace it is in, and returns true if the opcodes are forbidden
+    /// on that line
+    pub fn applies_to(&self, file:&str, namespace:Option<&str>) -> bool {
+        match self.scope.as_deref() {
+            None => true,
+            Some(scope) => match scope.strip_prefix('@') {
+                Some(scope) => namespace == Some(scope),
+                None => Path::new(file).starts_with(scope)
+            }
+        }
+    }
+}
+
+
+/// Takes the lines of a program once its macros and `.rept` blocks are expanded, the name of the file being
+/// assembled, and the opcodes which are forbidden, and returns an `AsmValidationError` for every line which
+/// uses one where it is forbidden, naming the label it comes after. The opcodes are checked as they are
+/// written, before any pseudo-instruction such as `LOADB` is expanded, so a pseudo-instruction can be
+/// forbidden by its own name, and the instructions the assembler adds for one are not checked.
+pub fn check_forbidden_opcodes(source_lines:&[SourceLine], name:&str, forbidden:&[ForbiddenOpcodes]) -> Result<(), AsmValidationError> {
+    let mut errors:Vec<String> = Vec::new();
+    let mut namespace:Option<String> = None;
+    let mut last_label:Option<String> = None;
+    for source_line in source_lines {
+        let line = strip_comment(&source_line.text);
+        if is_namespace_directive(line) {
+            namespace = parse_namespace_directive(line).ok();
+            continue;
+        }
+
+        let (label, instr) = split_label(line);
+        if let Some(label) = label {
+            last_label = Some(match &namespace {
+                Some(namespace) => format!("{}.{}", namespace, label),
+                None => label.to_owned()
+            });
+        }
+
+        let opcode = split_instr(instr).0;
+        let file = source_line.file.as_deref().unwrap_or(name);
+        let rule = forbidden.iter().find(|rule| {
+            rule.applies_to(file, namespace.as_deref()) && rule.opcodes.iter().any(|forbidden| forbidden.eq_ignore_ascii_case(opcode))
+        });
+
+        if let Some(rule) = rule {
+            let scope = rule.scope.as_ref().map(|scope| format!(" in {}", scope)).unwrap_or_default();
+            let used_in = last_label.as_deref().map(|label| format!("in {}", label)).unwrap_or("before the first label".to_owned());
+            errors.push(source_line.locate(AsmValidationError(format!(
+                "{} is forbidden{} by --forbid, but is used {}", opcode, scope, used_in
+            ))).0);
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(AsmValidationError(errors.join("\n")));
+    }
+
+    Ok(())
+}
+
+
+
+/// Takes a stream of tokens and gives a warning for every branch or jump whose two registers cannot form
+/// a sensible 32-bit address, which is when both registers are the same or one of them is `$zero`. Each
+/// warning starts with the position of the branch, if it came from a source file.
+pub fn check_branch_pairs(tokens:&[FileTokens]) -> Vec<AsmWarning> {
+    let branch_opcodes = ["JUMP", "JAL", "BEQ", "BNE", "BLT", "BGT"];
+    let mut warnings:Vec<AsmWarning> = Vec::new();
+    for token in tokens {
+        let t = match token {
+            FileTokens::InstrTokens(t) if branch_opcodes.contains(&t.opcode.as_str()) => t,
+            _ => continue
+        };
+
+        // single-register branches use a 32-bit register so are always a valid pair
+        let (upper, lower) = match (&t.operand_a, &t.operand_b) {
+            (Some(upper), Some(lower)) => (upper, lower),
+            _ => continue
+        };
+
+        if upper == lower {
+            warnings.push(AsmWarning::new(WarningKind::BranchPair, format!(
+                "{}{} {}, {} uses the same register for the upper and lower halves of the address", located(t.pos.as_ref()), t.opcode, upper, lower
+            )));
+        } else if upper == "$zero" || lower == "$zero" {
+            warnings.push(AsmWarning::new(WarningKind::BranchPair, format!(
+                "{}{} {}, {} uses $zero for half of the address", located(t.pos.as_ref()), t.opcode, upper, lower
+            )));
+        }
+    }
+
+    warnings
+}
+
+
+/// Takes a stream of tokens and gives a warning for every instruction which directly follows an
+/// unconditional `JUMP` or a `HALT` with no label in between, as nothing can branch to it so it can never
+/// run. Only the first instruction of each such run is reported. This should be run before
+/// pseudo-instructions are substituted, so that only the instructions written in the source are checked.
+pub fn check_unreachable_code(tokens:&[FileTokens]) -> Vec<AsmWarning> {
+    let mut warnings:Vec<AsmWarning> = Vec::new();
+    let mut unreachable_after:Option<&str> = None;
+    let mut reported = false;
+    for token in tokens {
+        let t = match token {
+            FileTokens::InstrTokens(t) => t,
+            _ => continue
+        };
+
+        if t.label.is_some() {
+            unreachable_after = None;
+        }
+
+        if let Some(terminator) = unreachable_after {
+            if !reported {
+                warnings.push(AsmWarning::new(WarningKind::UnreachableCode, format!(
+                    "{}{} can never run, as it follows {} without a label in between", located(t.pos.as_ref()), t.opcode, terminator
+                )));
+                reported = true;
+            }
+
+            continue;
+        }
+
+        if t.opcode == "JUMP" || t.opcode == "HALT" {
+            unreachable_after = Some(&t.opcode);
+            reported = false;
+        }
+    }
+
+    warnings
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{process_reader_into_tokens_with_warnings, process_source_into_tokens};
+    use crate::lang::LanguageLevel;
+    use crate::limits::{ResourceBudget, ResourceLimits};
+    use crate::lints::{check_code_fallthrough, check_branch_pairs, check_unreachable_code, ForbiddenOpcodes};
+    use crate::preprocessor::Configuration;
+    use crate::token_types::{FileTokens, InstrTokens};
+    use crate::testing::{tokens_from_str, FIBONACCI_PROGRAM, PSEUDO_INSTR_PROGRAM, SINGLE_OPERAND_BRANCH_PROGRAM};
+    use crate::warnings::WarningSink;
+
+
+    #[test]
+    fn test_code_ends_with_halt() {
+        let tokens = tokens_from_str(FIBONACCI_PROGRAM);
+        check_code_fallthrough(&tokens).unwrap();
+    }
+
+
+    #[test]
+    fn test_code_without_data() {
+        let tokens = tokens_from_str(PSEUDO_INSTR_PROGRAM);
+        check_code_fallthrough(&tokens).unwrap();
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_code_falls_through_into_data() {
+        let tokens = tokens_from_str("init:\n    ADDI $g0, $zero, 1\n    ADDI $g1, $zero, 1\n\nloop:\n    ADD $g0, $g0, $g1\n    CMP $g0, $g5\n    BLT $g8, $g9, @loop\n\ndata:\n    limit: .int 100\n");
+        check_code_fallthrough(&tokens).unwrap();
+    }
+
+
+    #[test]
+    fn test_code_without_data_ends_with_org() {
+        // an .align, .org, or bss section after the code is not data the code can fall through into
+        let tokens = tokens_from_str("start:\n    ADDI $g0, $zero, 1\n    ADDI $g1, $zero, 1\n.align 4\n");
+        check_code_fallthrough(&tokens).unwrap();
+
+        let tokens = tokens_from_str("start:\n    ADDI $g0, $zero, 1\n.org 0x100\n");
+        check_code_fallthrough(&tokens).unwrap();
+
+        let tokens = tokens_from_str("start:\n    ADDI $g0, $zero, 1\n\nbss:\n    buffer: .space 16\n");
+        check_code_fallthrough(&tokens).unwrap();
+
+        let tokens = tokens_from_str("start:\n    ADDI $g0, $zero, 1\n.align 4\ndata:\n    limit: .int 100\n");
+        assert!(check_code_fallthrough(&tokens).is_err());
+    }
+
+
+    #[test]
+    fn test_forbidden_opcodes() {
+        let tokenize = |forbidden:Vec<ForbiddenOpcodes>| {
+            let config = Configuration { forbidden, ..Configuration::default() };
+            process_reader_into_tokens_with_warnings(SOURCE.as_bytes(), "app/main.asm", &config, false, LanguageLevel::default(),
+                &mut ResourceBudget::new(ResourceLimits::UNLIMITED), &mut WarningSink::default())
+        };
+
+        const SOURCE:&str = "init:\n    LOADB $g0, $zero, $g1, 1\n.namespace io\nread:\n    IN $g1, 3\n    HALT\n";
+        let everywhere = |opcodes:&[&str]| ForbiddenOpcodes { scope: None, opcodes: opcodes.iter().map(|opcode| opcode.to_string()).collect() };
+        tokenize(vec![everywhere(&["OUT"])]).unwrap();
+
+        // the pseudo-instruction is checked as written, and LOAD is only in its expansion
+        let err = tokenize(vec![everywhere(&["LOADB", "LOAD", "halt"])]).unwrap_err();
+        assert_eq!(err.0, "app/main.asm:2:5: LOADB is forbidden by --forbid, but is used in init\n\
+            app/main.asm:6:5: HALT is forbidden by --forbid, but is used in io.read");
+
+        let in_file = |scope:&str| ForbiddenOpcodes { scope: Some(scope.to_owned()), opcodes: vec!["IN".to_owned()] };
+        assert!(tokenize(vec![in_file("app")]).unwrap_err().0.ends_with("IN is forbidden in app by --forbid, but is used in io.read"));
+        assert!(tokenize(vec![in_file("app/main.asm")]).is_err());
+        assert!(tokenize(vec![in_file("@io")]).is_err());
+        tokenize(vec![in_file("lib"), in_file("@kernel"), in_file("ap")]).unwrap();
+    }
+
+
+    #[test]
+    fn test_branch_pairs() {
+        let tokens = tokens_from_str(FIBONACCI_PROGRAM);
+        assert!(check_branch_pairs(&tokens).is_empty());
+
+        let tokens = tokens_from_str(SINGLE_OPERAND_BRANCH_PROGRAM);
+        assert!(check_branch_pairs(&tokens).is_empty());
+    }
+
+
+    #[test]
+    fn test_suspicious_branch_pairs() {
+        let tokens = vec![
+            FileTokens::InstrTokens(InstrTokens::new(None, "JUMP".to_string(), Some("$g8".to_string()), Some("$g8".to_string()), None, None, None)),
+            FileTokens::InstrTokens(InstrTokens::new(None, "BEQ".to_string(), Some("$zero".to_string()), Some("$g1".to_string()), None, None, None)),
+            FileTokens::InstrTokens(InstrTokens::new(None, "CMP".to_string(), Some("$g1".to_string()), Some("$g1".to_string()), None, None, None))
+        ];
+
+        let warnings = check_branch_pairs(&tokens);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].message.starts_with("JUMP"));
+        assert!(warnings[1].message.starts_with("BEQ"));
+    }
+
+
+    #[test]
+    fn test_unreachable_code() {
+        let tokens = tokens_from_str(FIBONACCI_PROGRAM);
+        assert!(check_unreachable_code(&tokens).is_empty());
+
+        let source = "init:\n    JUMP $g8, $g9, @end\n    ADD $g0, $g1, $g2\n    NOP\n    HALT\n    NOP\nend:\n    HALT\n";
+        let warnings = check_unreachable_code(&process_source_into_tokens(source, None).unwrap());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].message, "<source>:3:5: ADD can never run, as it follows JUMP without a label in between");
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use half::f16;
+use crate::demangle::replace_names;
+use crate::fixed_point::FixedPointFormat;
+use crate::generate_code::get_binary_from_tokens;
+use crate::label_table::generate_token_addresses_with_page_size;
+use crate::token_types::{FileTokens, InstrTokens};
+
+
+/// Takes the tokens of an instruction and returns it as it would be written in assembly, with any label
+/// operand or immediate after the registers.
+fn format_instr(t:&InstrTokens) -> String {
+    let mut operands:Vec<String> = [&t.operand_a, &t.operand_b, &t.operand_c].into_iter()
+        .filter_map(|operand| operand.clone())
+        .collect();
+
+    if let Some(immediate) = t.immediate {
+        operands.push(immediate.to_string());
+    }
+
+    if let Some(op_label) = &t.op_label {
+        operands.push(op_label.to_owned());
+    }
+
+    format!("{} {}", t.opcode, operands.join(", ")).trim_end().to_owned()
+}
+
+
+/// Takes the category of a data instruction and the words it was converted into, and returns a description
+/// of the item in the source that produced each word, such as which element of a `.section` array or which
+/// character of a `.text` string it holds.
+pub fn describe_data_words(category:&str, bytes:&[u16]) -> Vec<String> {
+    let upper_lower = |item:String| vec![format!("{} (upper half)", item), format!("{} (lower half)", item)];
+    match category {
+        "int" if bytes.len() == 1 => vec![format!(".int {}", bytes[0] as i16)],
+        "int" => bytes.iter().enumerate().map(|(index, word)| format!(".int [{}] {}", index, *word as i16)).collect(),
+        "long" if bytes.len() == 2 => upper_lower(format!(".long {}", ((bytes[0] as u32) << 16 | bytes[1] as u32) as i32)),
+        "long" => bytes.chunks(2).enumerate().flat_map(|(index, pair)| {
+            upper_lower(format!(".long [{}] {}", index, ((pair[0] as u32) << 16 | *pair.get(1).unwrap_or(&0) as u32) as i32))
+        }).collect(),
+        "half" => vec![format!(".half {}", f16::from_bits(bytes[0]))],
+        "float" => upper_lower(format!(".float {}", f32::from_bits((bytes[0] as u32) << 16 | bytes[1] as u32))),
+        "byte" | "incbin" => bytes.iter().enumerate().map(|(index, word)| {
+            format!(".{} [{}] 0x{:02X}, [{}] 0x{:02X}", category, index * 2, word & 0xFF, index * 2 + 1, word >> 8)
+        }).collect(),
+        "le32" | "be32" => {
+            let bytes:Vec<u8> = bytes.iter().flat_map(|word| word.to_le_bytes()).collect();
+            let value = if category == "be32" {
+                u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+            } else {
+                u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+            };
+
+            vec![format!(".{} 0x{:08X} (bytes 0-1)", category, value), format!(".{} 0x{:08X} (bytes 2-3)", category, value)]
+        },
+        "q8_8" | "q16_16" => {
+            let format = FixedPointFormat::from_data_type(category).unwrap();
+            let item = format!(".{} {}", category, format.from_words(bytes));
+            if bytes.len() == 1 { vec![item] } else { upper_lower(item) }
+        },
+        "char" => vec![format!(".char {:?}", char::from_u32(bytes[0] as u32).unwrap_or(char::REPLACEMENT_CHARACTER))],
+        "text" => bytes.iter().enumerate().map(|(index, word)| {
+            format!(".text [{}] {:?}", index, char::from_u32(*word as u32).unwrap_or(char::REPLACEMENT_CHARACTER))
+        }).collect(),
+        "rand" => bytes.iter().enumerate().map(|(index, word)| format!(".rand [{}] {}", index, word)).collect(),
+        _ => (0..bytes.len()).map(|index| format!(".{} [{}]", category, index)).collect()
+    }
+}
+
+
+/// Takes a stream of tokens which have had their labels substituted and the number of words in a page, and
+/// writes a listing of the program to the given file. Each line holds the address and value of a single word, any label at that address, and
+/// the instruction or data item that produced the word. The bss section is left out, as it has no words in
+/// the binary. Any mangled label in `names` is shown by its readable name.
+pub fn write_listing(filename:&str, tokens:&[FileTokens], names:&HashMap<String, String>, page_size:i64) -> Result<(), Box<dyn Error>> {
+    let mut listing_file = BufWriter::new(
+        OpenOptions::new().create(true).write(true).truncate(true).open(filename)?);
+
+    writeln!(listing_file, "{:<8}  {:<4}  {:<16} SOURCE", "ADDRESS", "WORD", "LABEL")?;
+    for (token, addr) in tokens.iter().zip(generate_token_addresses_with_page_size(tokens, page_size)) {
+        let descriptions = match token {
+            FileTokens::InstrTokens(t) => vec![format_instr(t)],
+            FileTokens::DataTokens(t) => describe_data_words(&t.category, &t.bytes),
+            FileTokens::TextTokens(t) => describe_data_words("text", &t.bytes),
+            FileTokens::BssTokens(_) | FileTokens::OrgTokens(_) | FileTokens::AliasTokens(_) | FileTokens::DeprecatedTokens(_) | FileTokens::TraceTokens(_)
+                | FileTokens::ExportTableTokens(_) => continue
+        };
+
+        let words = get_binary_from_tokens(token.clone())?;
+        for (index, (word, description)) in words.iter().zip(descriptions).enumerate() {
+            let label = if index == 0 { token.label().unwrap_or("") } else { "" };
+            writeln!(listing_file, "{:08X}  {:04X}  {:<16} {}", addr + index as i64, word, replace_names(label, names), replace_names(&description, names))?;
+        }
+    }
+
+    listing_file.flush()?;
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::listing::describe_data_words;
+
+
+    #[test]
+    fn test_describe_numeric_data() {
+        assert_eq!(describe_data_words("int", &[0xFFFF]), vec![".int -1"]);
+        assert_eq!(describe_data_words("long", &[0x26BE, 0x3680]),
+            vec![".long 650000000 (upper half)", ".long 650000000 (lower half)"]);
+        assert_eq!(describe_data_words("half", &[0x4540]), vec![".half 5.25"]);
+        assert_eq!(describe_data_words("int", &[1, 0xFFFD]), vec![".int [0] 1", ".int [1] -3"]);
+        assert_eq!(describe_data_words("long", &[0, 1, 0xFFFF, 0xFFFF])[3], ".long [1] -1 (lower half)");
+        assert_eq!(describe_data_words("q8_8", &[0xFE80]), vec![".q8_8 -1.5"]);
+        assert_eq!(describe_data_words("byte", &[0x3412, 0x0056])[1], ".byte [2] 0x56, [3] 0x00");
+        assert_eq!(describe_data_words("be32", &[0x3412, 0x7856]), vec![".be32 0x12345678 (bytes 0-1)", ".be32 0x12345678 (bytes 2-3)"]);
+        assert_eq!(describe_data_words("le32", &[0x5678, 0x1234])[1], ".le32 0x12345678 (bytes 2-3)");
+        assert_eq!(describe_data_words("q16_16", &[0x0003, 0x243F])[0], ".q16_16 3.1415863037109375 (upper half)");
+        assert_eq!(describe_data_words("float", &[0xC542, 0x0C30])[1], ".float -3104.7617 (lower half)");
+    }
+
+
+    #[test]
+    fn test_describe_array_data() {
+        assert_eq!(describe_data_words("section", &[1, 1, 2]), vec![".section [0]", ".section [1]", ".section [2]"]);
+        assert_eq!(describe_data_words("text", &[0x0048, 0x0069, 0x0000]),
+            vec![".text [0] 'H'", ".text [1] 'i'", ".text [2] '\\0'"]);
+        assert_eq!(describe_data_words("char", &[0x00DF]), vec![".char 'ß'"]);
+        assert_eq!(describe_data_words("rand", &[0xE220, 0x6E78]), vec![".rand [0] 57888", ".rand [1] 28280"]);
+    }
+}
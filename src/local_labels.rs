@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+use std::path::Path;
+use crate::constants::is_constant_definition;
+use crate::errors::AsmValidationError;
+use crate::hardening::split_label;
+use crate::namespaces::{is_namespace_directive, parse_namespace_directive};
+use crate::preprocessor::SourceLine;
+use crate::token_types::SourcePos;
+
+
+/// The start of the labels local labels are renamed to, which are followed by the name of the source and
+/// either the number of the label and the number of the definition in the source, such as `__local_prog_1_3`,
+/// or the name of a file-local label, such as `__local_prog_Lloop`
+pub const LOCAL_LABEL_PREFIX:&str = "__local_";
+
+
+/// The start of a file-local label, such as `.Lloop`, which can only be referred to from its own source file
+pub const FILE_LABEL_PREFIX:&str = ".L";
+
+
+/// Represents a numeric local label defined on a line, with the global labels it is between and the label
+/// it is renamed to, which is qualified by the namespace it is in for the references to it
+struct Definition {
+    number: String,
+    scope: usize,
+    label: String,
+    qualified: String
+}
+
+
+/// Takes a line of assembly and returns its label, if it has one
+fn line_label(line:&str) -> Option<&str> {
+    split_label(line).0
+}
+
+
+/// Takes a label and returns true if it is a numeric local label, such as `1`
+fn is_numeric_label(label:&str) -> bool {
+    !label.is_empty() && label.chars().all(|c| c.is_ascii_digit())
+}
+
+
+/// Takes a label and returns true if it is a file-local label, being `.L` followed by at least one character
+/// which can be in a label, such as `.Lloop`
+fn is_file_label(label:&str) -> bool {
+    label.strip_prefix(FILE_LABEL_PREFIX).is_some_and(|name| !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_'))
+}
+
+
+/// Takes the name of a source and returns it as it is written in the labels local labels are renamed to, with
+/// its extension removed, every `_` doubled, and every other character which cannot be in a label replaced by
+/// `_` and the hex of its bytes, so that `lib/math.asm` becomes `lib_2Fmath` and `lib_math.asm` becomes
+/// `lib__math`. No two sources have the same tag, so their local labels never clash.
+fn source_tag(name:&str) -> String {
+    let path = Path::new(name).with_extension("").to_string_lossy().into_owned();
+    let mut tag = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            '_' => tag.push_str("__"),
+            c if c.is_ascii_alphanumeric() => tag.push(c),
+            c => tag.extend(c.to_string().bytes().map(|byte| format!("_{:02X}", byte)))
+        }
+    }
+
+    tag
+}
+
+
+/// Takes a label and returns true if it was given to a numeric or file-local label by `resolve_local_labels`
+/// or `resolve_file_labels`, with or without the namespace it is in
+pub fn is_local_label(label:&str) -> bool {
+    label.rsplit('.').next().is_some_and(|name| name.starts_with(LOCAL_LABEL_PREFIX))
+}
+
+
+/// Takes a line of assembly and returns the start of every label operand in it which is not inside double
+/// quotes, and the label it refers to, which is everything after the `@` up to the first character which
+/// cannot be in a label, so `@table+8` refers to `table`
+fn find_label_operands(line:&str) -> Vec<(usize, &str)> {
+    let mut operands:Vec<(usize, &str)> = Vec::new();
+    let mut quoted = false;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '\\' if quoted && !escaped => { escaped = true; continue; },
+            '"' if !escaped => quoted = !quoted,
+            '@' if !quoted => {
+                let rest = &line[index + 1..];
+                let end = rest.find(|c:char| !(c.is_alphanumeric() || c == '_' || c == '.')).unwrap_or(rest.len());
+                operands.push((index, &rest[..end]));
+            },
+            _ => ()
+        }
+
+        escaped = false;
+    }
+
+    operands
+}
+
+
+/// Takes a line of assembly and returns the start, the number, and the direction of every reference to a
+/// numeric local label in it, such as `@1f` or `@2b`, which is not inside double quotes
+fn find_references(line:&str) -> Vec<(usize, &str, char)> {
+    find_label_operands(line).into_iter().filter_map(|(start, label)| match label.split_at(label.len().saturating_sub(1)) {
+        (number, direction @ ("f" | "b")) if is_numeric_label(number) => Some((start, number, direction.chars().next().unwrap())),
+        _ => None
+    }).collect()
+}
+
+
+/// Takes the lines of a source file after its macros and functions are expanded, and the name of the source,
+/// and returns them with every numeric local label, such as `1:`, renamed to a label which is unique in the
+/// program, and every reference to one changed to refer to that label. A reference is written `@1f` for the
+/// first definition of `1` after the line, or `@1b` for the last definition of `1` on or before it, and only
+/// finds definitions between the same two global labels, so the same numbers can be used again in every
+/// routine, and in every expansion of a macro. File-local labels do not separate routines, as they are not
+/// global labels.
+///
+/// Will return an `AsmValidationError` holding every reference which has no definition to refer to, with
+/// one on each line, each starting with the line and column it is on.
+pub fn resolve_local_labels(source_lines:Vec<SourceLine>, name:&str) -> Result<Vec<SourceLine>, AsmValidationError> {
+    let tag = source_tag(name);
+    let mut scope = 0;
+    let mut count = 0;
+    let mut namespace:Option<String> = None;
+    let mut definitions:Vec<Option<Definition>> = Vec::with_capacity(source_lines.len());
+    let mut scopes:Vec<usize> = Vec::with_capacity(source_lines.len());
+    for source_line in &source_lines {
+        let text = source_line.text.trim();
+        if is_namespace_directive(text) {
+            namespace = parse_namespace_directive(text).ok();
+        }
+
+        definitions.push(match line_label(text) {
+            Some(number) if is_numeric_label(number) => {
+                let label = format!("{}{}_{}_{}", LOCAL_LABEL_PREFIX, tag, number, count);
+                count += 1;
+                let qualified = namespace.as_ref().map_or(label.clone(), |namespace| format!("{}.{}", namespace, label));
+                Some(Definition { number: number.to_owned(), scope, label, qualified })
+            },
+
+            Some(label) if !is_constant_definition(text) && !is_file_label(label) => {
+                scope += 1;
+                None
+            },
+
+            _ => None
+        });
+
+        scopes.push(scope);
+    }
+
+    let mut errors:Vec<String> = Vec::new();
+    let mut resolved:Vec<SourceLine> = Vec::with_capacity(source_lines.len());
+    for (index, mut source_line) in source_lines.into_iter().enumerate() {
+        let mut text = source_line.text.clone();
+        for (start, number, direction) in find_references(&source_line.text).into_iter().rev() {
+            let in_scope = |definition:&&Definition| definition.number == number && definition.scope == scopes[index];
+            let target = match direction {
+                'f' => definitions[index + 1..].iter().flatten().find(in_scope),
+                _ => definitions[..=index].iter().flatten().rfind(in_scope)
+            };
+
+            match target {
+                Some(definition) => text.replace_range(start + 1..start + 1 + number.len() + 1, &definition.qualified),
+                None => {
+                    let place = if direction == 'f' { "after it before the next" } else { "before it since the last" };
+                    errors.push(source_line.locate(AsmValidationError(format!(
+                        "@{}{} refers to the local label {}, but there is no {}: {} global label", number, direction, number, number, place
+                    ))).0);
+                }
+            }
+        }
+
+        if let Some(definition) = &definitions[index] {
+            let start = text.find(definition.number.as_str()).unwrap();
+            text.replace_range(start..start + definition.number.len(), &definition.label);
+        }
+
+        source_line.text = text;
+        resolved.push(source_line);
+    }
+
+    if !errors.is_empty() {
+        return Err(AsmValidationError(errors.join("\n")));
+    }
+
+    Ok(resolved)
+}
+
+
+/// Takes the lines of a source file after its numeric local labels are resolved, and the name of the source,
+/// and returns them with every file-local label, such as `.Lloop:`, renamed to a label which is unique to the
+/// source, and every reference to one, such as `@.Lloop`, changed to refer to that label. Each source file
+/// given to the assembler can then use the same file-local labels without them clashing when the files are
+/// assembled into one program, and other files cannot refer to them. The files a source includes are part of
+/// it, so they share its file-local labels.
+///
+/// Will return an `AsmValidationError` holding every file-local label defined more than once and every
+/// reference to one which is not defined in the source, with one on each line, each starting with the line
+/// and column it is on.
+pub fn resolve_file_labels(source_lines:Vec<SourceLine>, name:&str) -> Result<Vec<SourceLine>, AsmValidationError> {
+    let tag = source_tag(name);
+    let rename = |label:&str| format!("{}{}_{}", LOCAL_LABEL_PREFIX, tag, &label[1..]);
+    let mut errors:Vec<String> = Vec::new();
+    let mut namespace:Option<String> = None;
+    let mut definitions:HashMap<String, (SourcePos, String)> = HashMap::new();
+    for source_line in &source_lines {
+        let text = source_line.text.trim();
+        if is_namespace_directive(text) {
+            namespace = parse_namespace_directive(text).ok();
+        }
+
+        match line_label(text).filter(|label| is_file_label(label)) {
+            Some(label) if definitions.contains_key(label) => errors.push(source_line.locate(AsmValidationError(format!(
+                "Duplicate file-local label \"{}\", which is already defined at {}", label, definitions[label].0
+            ))).0),
+
+            Some(label) => {
+                let renamed = rename(label);
+                let qualified = namespace.as_ref().map_or(renamed.clone(), |namespace| format!("{}.{}", namespace, renamed));
+                definitions.insert(label.to_owned(), (source_line.pos(name), qualified));
+            },
+
+            None => ()
+        }
+    }
+
+    let mut resolved:Vec<SourceLine> = Vec::with_capacity(source_lines.len());
+    for mut source_line in source_lines {
+        let mut text = source_line.text.clone();
+        for (start, label) in find_label_operands(&source_line.text).into_iter().rev().filter(|(_, label)| is_file_label(label)) {
+            match definitions.get(label) {
+                Some((_, qualified)) => text.replace_range(start + 1..start + 1 + label.len(), qualified),
+                None => errors.push(source_line.locate(AsmValidationError(format!(
+                    "@{} refers to the file-local label {}, but there is no {}: in the file", label, label, label
+                ))).0)
+            }
+        }
+
+        if let Some(label) = line_label(source_line.text.trim()).filter(|label| is_file_label(label)) {
+            let start = text.find(label).unwrap();
+            text.replace_range(start..start + label.len(), &rename(label));
+        }
+
+        source_line.text = text;
+        resolved.push(source_line);
+    }
+
+    if !errors.is_empty() {
+        return Err(AsmValidationError(errors.join("\n")));
+    }
+
+    Ok(resolved)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::local_labels::*;
+    use crate::label_table::generate_label_table;
+    use crate::pseudo_substitution::{substitute_labels, substitute_pseudo_instrs};
+    use crate::testing::tokens_from_str;
+    use crate::process_source_into_tokens;
+
+
+    #[test]
+    fn test_find_references() {
+        assert_eq!(find_references("JUMP $g8, $g9, @1f"), vec![(15, "1", 'f')]);
+        assert_eq!(find_references("MOVLI $g0, @12b+2"), vec![(11, "12", 'b')]);
+        assert!(find_references("JUMP $g8, $g9, @1fa").is_empty());
+        assert!(find_references("msg: .text 4 \"@1f\"").is_empty());
+        assert!(find_references("JUMP $g8, $g9, @loop").is_empty());
+    }
+
+
+    #[test]
+    fn test_source_tag() {
+        assert_eq!(source_tag("lib/math.asm"), "lib_2Fmath");
+        assert_eq!(source_tag("lib_math.asm"), "lib__math");
+        assert_eq!(source_tag("a-b.asm"), "a_2Db");
+        assert_eq!(source_tag("a_b.asm"), "a__b");
+        assert_eq!(source_tag("prog.asm"), "prog");
+    }
+
+
+    #[test]
+    fn test_resolve_local_labels() {
+        let source = "init:\n    ADDI $g0, $zero, 3\n1:\n    SUBI $g0, $g0, 1\n    CMP $g0, $zero\n    BNE $g8, $g9, @1b\n\
+            \n    JUMP $g8, $g9, @1f\n    NOP\n1:  HALT\nnext:\n1:\n    BEQ $g8, $g9, @1b\n    HALT\n";
+        let tokens = substitute_pseudo_instrs(tokens_from_str(source));
+        let label_table = generate_label_table(&tokens).unwrap();
+        let definitions = ["__local__3Csource_3E_1_0", "__local__3Csource_3E_1_1", "__local__3Csource_3E_1_2"].map(|label| label_table[label] as u64);
+        assert_eq!(definitions[0], 1);
+
+        // each reference loads the address of the definition it refers to into $g9
+        let tokens = substitute_labels(tokens, &label_table).unwrap();
+        let addresses:Vec<u64> = tokens.iter().filter_map(|token| token.try_get_instr_tokens().ok())
+            .filter(|t| t.opcode == "MOVLI" && t.operand_a.as_deref() == Some("$g9"))
+            .map(|t| t.immediate.unwrap())
+            .collect();
+        assert_eq!(addresses, definitions);
+    }
+
+
+    #[test]
+    fn test_local_labels_in_namespace() {
+        let tokens = tokens_from_str(".namespace math\nsquare:\n1:\n    JUMP $g8, $g9, @1b\n");
+        let jump = tokens[1].try_get_instr_tokens().unwrap();
+        assert_eq!(jump.label.unwrap(), "math.__local__3Csource_3E_1_0");
+        assert_eq!(jump.op_label.unwrap(), "@math.__local__3Csource_3E_1_0");
+    }
+
+
+    #[test]
+    fn test_global_label_before_local_label() {
+        let source = "init:\n    JUMP $g8, $g9, @other\nother:\n1:\n    ADDI $g0, $g0, 1\n    JUMP $g8, $g9, @1b\n";
+        let tokens = substitute_pseudo_instrs(tokens_from_str(source));
+        let label_table = generate_label_table(&tokens).unwrap();
+        assert_eq!(label_table["other"], 5);
+        assert_eq!(label_table["__local__3Csource_3E_1_0"], 5);
+        assert!(substitute_labels(tokens, &label_table).is_ok());
+    }
+
+
+    #[test]
+    fn test_missing_local_label() {
+        let err = process_source_into_tokens("init:\n1:\n    NOP\nnext:\n    JUMP $g8, $g9, @1b\n    JUMP $g8, $g9, @2f\n    HALT\n", None).unwrap_err();
+        assert_eq!(err.0, "<source>:5:20: @1b refers to the local label 1, but there is no 1: before it since the last global label\n\
+            <source>:6:20: @2f refers to the local label 2, but there is no 2: after it before the next global label");
+    }
+
+
+    #[test]
+    fn test_resolve_file_labels() {
+        let source = "init:\n    NOP\n1:\n    NOP\n.Lloop:\n    JUMP $g8, $g9, @.Lloop\n    BEQ $g8, $g9, @1b\n.namespace io\n.Ldone: HALT\n";
+        let tokens = tokens_from_str(source);
+        assert_eq!(tokens[2].try_get_instr_tokens().unwrap().label.unwrap(), "__local__3Csource_3E_Lloop");
+        assert_eq!(tokens[2].try_get_instr_tokens().unwrap().op_label.unwrap(), "@__local__3Csource_3E_Lloop");
+        assert_eq!(tokens[4].try_get_instr_tokens().unwrap().label.unwrap(), "io.__local__3Csource_3E_Ldone");
+        assert!(is_local_label("io.__local__3Csource_3E_Ldone"));
+
+        // a file-local label is not a global label, so the numeric local label before it is still found
+        let label_table = generate_label_table(&substitute_pseudo_instrs(tokens)).unwrap();
+        assert_eq!(label_table["__local__3Csource_3E_1_0"], 1);
+        assert_eq!(label_table["__local__3Csource_3E_Lloop"], 2);
+    }
+
+
+    #[test]
+    fn test_invalid_file_labels() {
+        let err = process_source_into_tokens("init:\n.Lloop:\n    NOP\n.Lloop:\n    JUMP $g8, $g9, @.Lend\n    HALT\n", None).unwrap_err();
+        assert_eq!(err.0, "<source>:4:1: Duplicate file-local label \".Lloop\", which is already defined at <source>:2:1\n\
+            <source>:5:20: @.Lend refers to the file-local label .Lend, but there is no .Lend: in the file");
+    }
+}
@@ -0,0 +1,474 @@
+use std::collections::HashMap;
+use crate::errors::AsmValidationError;
+use crate::hardening::split_label;
+use crate::isa::Isa;
+use crate::limits::ResourceBudget;
+use crate::preprocessor::SourceLine;
+use crate::validation::{validate_int_immediate, validate_label};
+
+
+/// Represents a macro defined by a `.macro` block, with the names of its parameters in order, the lines of its
+/// body, and the position of its `.macro` line for diagnostics
+#[derive(Debug, Clone)]
+struct Macro {
+    params: Vec<String>,
+    body: Vec<SourceLine>,
+    location: String
+}
+
+
+/// Takes the operands of a line, after its opcode or the name of a macro, and returns each of them, split on
+/// the commas which are not inside double quotes
+fn split_args(operands:&str) -> Vec<String> {
+    if operands.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let mut args:Vec<String> = vec![String::new()];
+    let mut quoted = false;
+    let mut escaped = false;
+    for c in operands.chars() {
+        match c {
+            ',' if !quoted => args.push(String::new()),
+            '"' if !escaped => {
+                quoted = !quoted;
+                args.last_mut().unwrap().push(c);
+            },
+            _ => args.last_mut().unwrap().push(c)
+        }
+
+        escaped = quoted && c == '\\' && !escaped;
+    }
+
+    args.iter().map(|arg| arg.trim().to_owned()).collect()
+}
+
+
+/// Takes a line of assembly and returns true if it starts the definition of a macro, such as `.macro SAVE reg`
+pub fn is_macro_directive(line:&str) -> bool {
+    let line = line.trim();
+    line.starts_with(".macro ") || line == ".macro"
+}
+
+
+/// Takes a line of assembly and returns true if it ends the definition of a macro
+pub fn is_endm_directive(line:&str) -> bool {
+    line.trim() == ".endm"
+}
+
+
+/// Takes a line of assembly starting a macro in the form `.macro <name> [<param>, ...]` and returns the name of
+/// the macro and of its parameters, which may be separated by commas or spaces. Will return an
+/// `AsmValidationError` if there is no name, the name or a parameter is not a valid label, the name is an
+/// opcode, or two parameters have the same name.
+fn parse_macro_directive(line:&str) -> Result<(String, Vec<String>), AsmValidationError> {
+    let line = line.trim();
+    let mut words = line[".macro".len()..].split(|c:char| c.is_whitespace() || c == ',').filter(|word| !word.is_empty());
+    let name = words.next().ok_or_else(|| AsmValidationError(format!(
+        "Incorrect format for .macro on line {}, which should be .macro <name> [<param>, ...]", line
+    )))?;
+
+    validate_label(line, name)?;
+    if Isa::lookup(name).is_some() {
+        return Err(AsmValidationError(format!("The macro {} on line {} cannot have the name of an instruction", name, line)));
+    }
+
+    let mut params:Vec<String> = Vec::new();
+    for param in words {
+        validate_label(line, param)?;
+        if params.iter().any(|other| other == param) {
+            return Err(AsmValidationError(format!("The macro {} on line {} has more than one parameter named {}", name, line, param)));
+        }
+
+        params.push(param.to_owned());
+    }
+
+    Ok((name.to_owned(), params))
+}
+
+
+/// Takes a line of the body of a macro or `.rept`, the names of its parameters, the arguments they are given,
+/// and the number of the expansion if it has one, and returns the line with every `\<param>` replaced by its
+/// argument and every `\@` replaced by the number. Any other backslash, such as one starting an escape
+/// sequence, is kept as it is.
+fn substitute(line:&str, params:&[String], args:&[String], expansion:Option<usize>) -> String {
+    let mut substituted = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(index) = rest.find('\\') {
+        substituted.push_str(&rest[..index]);
+        rest = &rest[index + 1..];
+        if let (Some(after), Some(expansion)) = (rest.strip_prefix('@'), expansion) {
+            substituted.push_str(&expansion.to_string());
+            rest = after;
+            continue;
+        }
+
+        let end = rest.find(|c:char| !c.is_alphanumeric() && c != '_').unwrap_or(rest.len());
+        match params.iter().position(|param| *param == rest[..end]) {
+            Some(param) => {
+                substituted.push_str(&args[param]);
+                rest = &rest[end..];
+            },
+            None => substituted.push('\\')
+        }
+    }
+
+    substituted.push_str(rest);
+    substituted
+}
+
+
+/// Takes the chain of macros which invoke each other, starting with the outermost, and returns it as it is
+/// written in diagnostics, such as `PUSH2 -> SAVE`
+fn render_chain(chain:&[&str]) -> String {
+    chain.join(" -> ")
+}
+
+
+/// Takes the logical lines of a source file, which have already had their includes expanded, and the budget
+/// it is assembled under, and returns the lines with every macro definition removed and every invocation of a
+/// macro replaced by the lines of its body. A macro is defined by the lines between `.macro <name> [<param>,
+/// ...]` and `.endm`, and is invoked by a line which has its name where the opcode would be, followed by one
+/// argument for each parameter separated by commas, such as `SAVE $g3`. In the body, `\<param>` is replaced by
+/// its argument and `\@` by a number which is different for every expansion, so that a macro can define its
+/// own labels such as `loop\@:`. A label on an invocation is put on a line of its own before the body.
+///
+/// A macro must be defined before it is invoked, and may invoke other macros in its body. Every line of an
+/// expansion keeps the position of the invocation so that diagnostics point at it, the lines of every
+/// expansion count towards the limit on the lines of the budget, and every invocation is counted as an
+/// expansion of the budget.
+///
+/// Will return an `AsmValidationError` if a definition is malformed, nested in another, or never closed, if
+/// a macro is defined twice, if an `.endm` has no `.macro`, or if an invocation has the wrong number of
+/// arguments. A macro which invokes itself through any chain of macros, or macros invoked inside each other
+/// more than `max_macro_expansion` deep, is also an error which gives the whole chain, such as
+/// `PUSH2 -> SAVE -> PUSH2`.
+pub fn expand_macros(source_lines:Vec<SourceLine>, budget:&mut ResourceBudget) -> Result<Vec<SourceLine>, AsmValidationError> {
+    if !source_lines.iter().any(|source_line| is_macro_directive(&source_line.text) || is_endm_directive(&source_line.text)) {
+        return Ok(source_lines);
+    }
+
+    let mut macros:HashMap<String, Macro> = HashMap::new();
+    let mut defining:Option<(String, Macro)> = None;
+    let mut expanded:Vec<SourceLine> = Vec::with_capacity(source_lines.len());
+    let mut expansions = 0;
+    for source_line in source_lines {
+        if is_macro_directive(&source_line.text) {
+            if let Some((outer, definition)) = &defining {
+                return Err(AsmValidationError(format!(
+                    "{}: a macro cannot be defined inside the macro {} from {}, which has no .endm", source_line.location(), outer, definition.location
+                )));
+            }
+
+            let (name, params) = parse_macro_directive(&source_line.text).map_err(|e| source_line.locate(e))?;
+            if let Some(previous) = macros.get(&name) {
+                return Err(AsmValidationError(format!(
+                    "{}: the macro {} is already defined at {}", source_line.location(), name, previous.location
+                )));
+            }
+
+            defining = Some((name, Macro { params, body: Vec::new(), location: source_line.location() }));
+        } else if is_endm_directive(&source_line.text) {
+            let (name, definition) = defining.take()
+                .ok_or_else(|| AsmValidationError(format!("{}: .endm must close a .macro", source_line.location())))?;
+            macros.insert(name, definition);
+        } else if let Some((_, definition)) = &mut defining {
+            definition.body.push(source_line);
+        } else {
+            expand_line(source_line, &macros, &mut Vec::new(), &mut expansions, &mut expanded, budget)?;
+        }
+    }
+
+    if let Some((name, definition)) = defining {
+        return Err(AsmValidationError(format!("{}: the macro {} is never closed with .endm", definition.location, name)));
+    }
+
+    Ok(expanded)
+}
+
+
+/// Takes a line outside any macro definition, the macros defined so far, the chain of macros being expanded,
+/// and the number of expansions so far, and pushes the line onto the expanded lines, or the lines it expands
+/// into if it invokes a macro, as described for `expand_macros`.
+fn expand_line<'a>(source_line:SourceLine, macros:&'a HashMap<String, Macro>, chain:&mut Vec<&'a str>, expansions:&mut usize,
+        expanded:&mut Vec<SourceLine>, budget:&mut ResourceBudget) -> Result<(), AsmValidationError> {
+    let (label, instr) = split_label(source_line.text.trim());
+    let (name, operands) = instr.split_once(char::is_whitespace).unwrap_or((instr, ""));
+    let Some((name, definition)) = macros.get_key_value(name) else {
+        expanded.push(source_line);
+        budget.check_lines(expanded.len()).map_err(|e| AsmValidationError(e.to_string()))?;
+        return Ok(());
+    };
+
+    let mut invoked_chain = chain.clone();
+    invoked_chain.push(name);
+    if chain.contains(&name.as_str()) {
+        return Err(AsmValidationError(format!(
+            "{}: the macro {} invokes itself through {}", source_line.location(), name, render_chain(&invoked_chain)
+        )));
+    } else if chain.len() >= budget.limits.max_macro_expansion {
+        return Err(AsmValidationError(format!(
+            "{}: macros are expanded more than {} deep, through {}", source_line.location(), budget.limits.max_macro_expansion, render_chain(&invoked_chain)
+        )));
+    }
+
+    let args = split_args(operands);
+    if args.len() != definition.params.len() || args.iter().any(|arg| arg.is_empty()) {
+        return Err(source_line.locate(AsmValidationError(format!(
+            "The macro {} takes {} arguments, but is given {} on line {}", name, definition.params.len(), args.len(), instr
+        ))));
+    }
+
+    let expansion = *expansions;
+    *expansions += 1;
+    budget.count_expansions(1);
+    let at_invocation = |text:String| SourceLine { line_num: source_line.line_num, text, file: source_line.file.clone() };
+    if let Some(label) = label {
+        expand_line(at_invocation(format!("{}:", label)), macros, chain, expansions, expanded, budget)?;
+    }
+
+    chain.push(name);
+    for body_line in &definition.body {
+        let text = substitute(&body_line.text, &definition.params, &args, Some(expansion));
+        expand_line(at_invocation(text), macros, chain, expansions, expanded, budget)?;
+    }
+
+    chain.pop();
+    Ok(())
+}
+
+
+/// Takes a line of assembly and returns true if it starts a block which is repeated, such as `.rept 4, i`
+pub fn is_rept_directive(line:&str) -> bool {
+    let line = line.trim();
+    line.starts_with(".rept ") || line == ".rept"
+}
+
+
+/// Takes a line of assembly and returns true if it ends a block which is repeated
+pub fn is_endr_directive(line:&str) -> bool {
+    line.trim() == ".endr"
+}
+
+
+/// Takes a line of assembly starting a repeated block in the form `.rept <count>[, <counter>]` and returns the
+/// number of times it is repeated and the name of its counter, if it has one. Will return an
+/// `AsmValidationError` if the count is not a 16-bit unsigned integer or the counter is not a valid label.
+fn parse_rept_directive(line:&str) -> Result<(u16, Option<String>), AsmValidationError> {
+    let line = line.trim();
+    let rest = line[".rept".len()..].trim();
+    let (count, counter) = match rest.split_once(',') {
+        Some((count, counter)) => (count.trim(), Some(counter.trim())),
+        None => (rest, None)
+    };
+
+    if count.is_empty() || counter.is_some_and(|counter| counter.is_empty()) {
+        return Err(AsmValidationError(format!(
+            "Incorrect format for .rept on line {}, which should be .rept <count>[, <counter>]", line
+        )));
+    }
+
+    let count = validate_int_immediate(count, 16, false)? as u16;
+    if let Some(counter) = counter {
+        validate_label(line, counter)?;
+    }
+
+    Ok((count, counter.map(|counter| counter.to_owned())))
+}
+
+
+/// Takes the logical lines of a source file, which have already had their macros expanded, and the budget it
+/// is assembled under, and returns the lines with every block between `.rept <count>[, <counter>]` and
+/// `.endr` replaced by its lines repeated the given number of times, so that an unrolled loop or a large table
+/// does not have to be written out by hand. If the block has a counter, `\<counter>` in its lines is replaced
+/// by the number of the repetition, counting from 0, such as in `entry\i: .int \i`. Blocks may be nested, and
+/// the count of an inner block may use the counter of an outer one. The repeated lines keep their own
+/// positions, and count towards the limit on the lines of the budget, and every block, however many times it
+/// is repeated, is counted as an expansion of the budget.
+///
+/// Will return an `AsmValidationError` if a `.rept` is malformed or never closed, or if an `.endr` has no
+/// `.rept`.
+pub fn expand_repeats(source_lines:Vec<SourceLine>, budget:&mut ResourceBudget) -> Result<Vec<SourceLine>, AsmValidationError> {
+    if !source_lines.iter().any(|source_line| is_rept_directive(&source_line.text) || is_endr_directive(&source_line.text)) {
+        return Ok(source_lines);
+    }
+
+    let mut expanded:Vec<SourceLine> = Vec::with_capacity(source_lines.len());
+    expand_repeated_lines(source_lines, &mut expanded, budget)?;
+    Ok(expanded)
+}
+
+
+/// Takes some lines and pushes them onto the expanded lines with their repeated blocks expanded, as described
+/// for `expand_repeats`.
+fn expand_repeated_lines(source_lines:Vec<SourceLine>, expanded:&mut Vec<SourceLine>, budget:&mut ResourceBudget)
+        -> Result<(), AsmValidationError> {
+    let mut lines = source_lines.into_iter();
+    while let Some(source_line) = lines.next() {
+        if is_endr_directive(&source_line.text) {
+            return Err(AsmValidationError(format!("{}: .endr must close a .rept", source_line.location())));
+        } else if !is_rept_directive(&source_line.text) {
+            expanded.push(source_line);
+            budget.check_lines(expanded.len()).map_err(|e| AsmValidationError(e.to_string()))?;
+            continue;
+        }
+
+        let (count, counter) = parse_rept_directive(&source_line.text).map_err(|e| source_line.locate(e))?;
+        budget.count_expansions(1);
+        let mut body:Vec<SourceLine> = Vec::new();
+        let mut depth = 0;
+        loop {
+            let line = lines.next()
+                .ok_or_else(|| AsmValidationError(format!("{}: the .rept is never closed with .endr", source_line.location())))?;
+            if is_rept_directive(&line.text) {
+                depth += 1;
+            } else if is_endr_directive(&line.text) {
+                if depth == 0 {
+                    break;
+                }
+
+                depth -= 1;
+            }
+
+            body.push(line);
+        }
+
+        for index in 0..count {
+            let repetition = body.iter().map(|line| match &counter {
+                Some(counter) => SourceLine { text: substitute(&line.text, std::slice::from_ref(counter), &[index.to_string()], None), ..line.clone() },
+                None => line.clone()
+            }).collect();
+
+            expand_repeated_lines(repetition, expanded, budget)?;
+        }
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::macros::*;
+    use crate::limits::ResourceLimits;
+    use crate::token_types::InstrTokens;
+    use crate::{assemble_source, process_source_into_tokens};
+
+
+    const SOURCE:&str = ".macro SAVE reg\n    SUBI $sp, $sp, 1\n    STORE \\reg, $sp, $zero\n.endm\n\
+        .macro COUNTDOWN reg, from\n    ADDI \\reg, $zero, \\from\nloop\\@:\n    SUBI \\reg, \\reg, 1\n    CMP \\reg, $zero\n\
+        \x20   BNE $g8, $g9, @loop\\@\n.endm\ninit:\n    SAVE $g3\n    COUNTDOWN $g0, 3\nagain: COUNTDOWN $g1, 5\n    HALT\n";
+
+
+    /// Takes source lines as they are written and returns them numbered from 1
+    fn to_lines(source:&str) -> Vec<SourceLine> {
+        source.lines().enumerate().map(|(index, text)| SourceLine { line_num: index + 1, text: text.to_owned(), file: None }).collect()
+    }
+
+
+    /// Takes source lines and returns the text of the lines they expand into under the given limits, or the
+    /// message of the error
+    fn expand(source:&str, limits:ResourceLimits) -> Result<Vec<String>, String> {
+        expand_macros(to_lines(source), &mut ResourceBudget::new(limits))
+            .map(|lines| lines.into_iter().map(|line| line.text.trim().to_owned()).collect())
+            .map_err(|e| e.0)
+    }
+
+
+    #[test]
+    fn test_parse_macro_directive() {
+        assert!(is_macro_directive(".macro SAVE reg"));
+        assert!(!is_macro_directive("macro: .int 5"));
+        assert!(is_endm_directive("    .endm"));
+
+        assert_eq!(parse_macro_directive(".macro SAVE reg").unwrap(), ("SAVE".to_owned(), vec!["reg".to_owned()]));
+        assert_eq!(parse_macro_directive(".macro PAIR a, b").unwrap().1, vec!["a".to_owned(), "b".to_owned()]);
+        assert_eq!(parse_macro_directive(".macro PAIR a b").unwrap().1, vec!["a".to_owned(), "b".to_owned()]);
+        assert!(parse_macro_directive(".macro RET").unwrap().1.is_empty());
+        assert!(parse_macro_directive(".macro").is_err());
+        assert!(parse_macro_directive(".macro ADDI reg").is_err());
+        assert!(parse_macro_directive(".macro 1SAVE reg").is_err());
+        assert!(parse_macro_directive(".macro PAIR a, a").is_err());
+    }
+
+
+    #[test]
+    fn test_substitute() {
+        let params = vec!["reg".to_owned(), "r".to_owned()];
+        let args = vec!["$g3".to_owned(), "$g4".to_owned()];
+        assert_eq!(substitute("ADD \\reg, \\r, \\reg", &params, &args, Some(0)), "ADD $g3, $g4, $g3");
+        assert_eq!(substitute("loop\\@: .text \"a\\n\"", &params, &args, Some(7)), "loop7: .text \"a\\n\"");
+        assert_eq!(substitute("loop\\@:", &params, &args, None), "loop\\@:");
+        assert_eq!(split_args("$g3, \"a, \\\"b\\\"\", 5"), vec!["$g3", "\"a, \\\"b\\\"\"", "5"]);
+    }
+
+
+    #[test]
+    fn test_expand_macros() {
+        let lines = expand(SOURCE, ResourceLimits::UNLIMITED).unwrap();
+        assert_eq!(lines[..5], ["init:", "SUBI $sp, $sp, 1", "STORE $g3, $sp, $zero", "ADDI $g0, $zero, 3", "loop1:"]);
+        assert_eq!(lines[7..11], ["BNE $g8, $g9, @loop1", "again:", "ADDI $g1, $zero, 5", "loop2:"]);
+
+        let tokens = process_source_into_tokens(SOURCE, None).unwrap();
+        let instrs:Vec<InstrTokens> = tokens.iter().filter_map(|token| token.try_get_instr_tokens().ok()).collect();
+        assert_eq!(instrs[3].pos.as_ref().unwrap().line, 14);
+        assert_eq!(instrs[3].label.as_deref(), Some("loop1"));
+        assert!(assemble_source(SOURCE).is_ok());
+    }
+
+
+    #[test]
+    fn test_expand_repeats() {
+        let repeat = |source:&str, limits:ResourceLimits| expand_repeats(to_lines(source), &mut ResourceBudget::new(limits))
+            .map(|lines| lines.into_iter().map(|line| (line.line_num, line.text.trim().to_owned())).collect::<Vec<(usize, String)>>())
+            .map_err(|e| e.0);
+
+        assert_eq!(parse_rept_directive(".rept 4").unwrap(), (4, None));
+        assert_eq!(parse_rept_directive(".rept 0x10, i").unwrap(), (16, Some("i".to_owned())));
+        assert!(parse_rept_directive(".rept").is_err());
+        assert!(parse_rept_directive(".rept -1").is_err());
+        assert!(parse_rept_directive(".rept 4,").is_err());
+        assert!(parse_rept_directive(".rept 4, 1i").is_err());
+
+        let lines = repeat("table:\n.rept 2, i\n    .rept \\i, j\n    entry\\i\\j: .int \\j\n    .endr\n.endr\n", ResourceLimits::UNLIMITED).unwrap();
+        assert_eq!(lines, vec![(1, "table:".to_owned()), (4, "entry10: .int 0".to_owned())]);
+
+        let lines = repeat(".rept 3\n    NOP\n.endr\n    HALT\n", ResourceLimits::UNLIMITED).unwrap();
+        assert_eq!(lines.iter().map(|(_, text)| text.as_str()).collect::<Vec<&str>>(), vec!["NOP", "NOP", "NOP", "HALT"]);
+
+        assert_eq!(repeat(".rept 3\n    NOP\n", ResourceLimits::UNLIMITED).unwrap_err(), "1:1: the .rept is never closed with .endr");
+        assert_eq!(repeat("    NOP\n.endr\n", ResourceLimits::UNLIMITED).unwrap_err(), "2:1: .endr must close a .rept");
+        assert_eq!(repeat(".rept 4\n    NOP\n.endr\n", ResourceLimits { max_lines: 3, ..ResourceLimits::UNLIMITED }).unwrap_err(),
+            "Resource limit exceeded: the program needs more than 3 lines");
+
+        let source = "init:\n.rept 3, i\n    ADDI $g\\i, $zero, \\i\n.endr\n    HALT\ndata:\n.rept 2, i\n    value\\i: .int \\i\n.endr\n";
+        let tokens = process_source_into_tokens(source, None).unwrap();
+        let instrs:Vec<InstrTokens> = tokens.iter().filter_map(|token| token.try_get_instr_tokens().ok()).collect();
+        assert_eq!((instrs[2].operand_a.as_deref(), instrs[2].immediate), (Some("$g2"), Some(2)));
+        assert!(assemble_source(source).is_ok());
+    }
+
+
+    #[test]
+    fn test_macro_errors() {
+        let error = expand(".macro A\n    B\n.endm\n.macro B\n    A\n.endm\n    A\n", ResourceLimits::UNLIMITED).unwrap_err();
+        assert_eq!(error, "7:5: the macro A invokes itself through A -> B -> A");
+
+        let source = ".macro C\n    NOP\n.endm\n.macro B\n    C\n.endm\n.macro A\n    B\n.endm\n    A\n";
+        assert!(expand(source, ResourceLimits { max_macro_expansion: 3, ..ResourceLimits::UNLIMITED }).is_ok());
+        let error = expand(source, ResourceLimits { max_macro_expansion: 2, ..ResourceLimits::UNLIMITED }).unwrap_err();
+        assert_eq!(error, "10:5: macros are expanded more than 2 deep, through A -> B -> C");
+
+        assert_eq!(expand(".macro A\n    NOP\n", ResourceLimits::UNLIMITED).unwrap_err(), "1:1: the macro A is never closed with .endm");
+        assert_eq!(expand(".endm\n", ResourceLimits::UNLIMITED).unwrap_err(), "1:1: .endm must close a .macro");
+        assert!(expand(".macro A\n.macro B\n.endm\n", ResourceLimits::UNLIMITED).unwrap_err().contains("inside the macro A from 1:1"));
+        assert!(expand(".macro A\n.endm\n.macro A\n.endm\n", ResourceLimits::UNLIMITED).unwrap_err().contains("already defined at 1:1"));
+
+        let error = process_source_into_tokens(SOURCE.replace("SAVE $g3", "SAVE $g3, $g4").as_str(), None).unwrap_err().0;
+        assert!(error.contains("The macro SAVE takes 1 arguments, but is given 2"), "{}", error);
+
+        let doubling = ".macro D1\n    NOP\n    NOP\n.endm\n.macro D2\n    D1\n    D1\n.endm\n    D2\n";
+        let error = expand(doubling, ResourceLimits { max_lines: 3, ..ResourceLimits::UNLIMITED }).unwrap_err();
+        assert_eq!(error, "Resource limit exceeded: the program needs more than 3 lines");
+    }
+}
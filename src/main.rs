@@ -1,112 +1,512 @@
+use std::collections::HashMap;
 use std::env;
-use std::fs::OpenOptions;
-use std::io::prelude::*;
-use std::io::BufReader;
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::error::Error;
+use std::fs;
 use std::time::Instant;
 
-mod errors;
-mod validation;
-mod token_generator;
-mod label_table;
-mod pseudo_substitution;
-mod token_types;
-mod generate_code;
-
-
-/// Takes a filename and returns a `Vec<FileTokens>` representing the tokens of all the lines of assembly in the file
-/// which can be either `DataTokens` or `InstrTokens`.
-pub fn process_file_into_tokens(input_file:&str) -> Vec<token_types::FileTokens> {
-    let mut mode = 'c';
-    let input_file = BufReader::new(OpenOptions::new().read(true).open(input_file.to_owned()).unwrap())
-        .lines()
-        .map(|l| l.unwrap().trim().to_string())
-        .filter(|l| !l.is_empty())
-        .collect::<Vec<String>>();
-
-    let mut tokens:Vec<token_types::FileTokens> = Vec::new();
-    let mut next_label:Option<String> = None;
-    for line in input_file {
-        if line == "data:" {
-            mode = 'd';
-            continue;
-        } else if line == "text:" {
-            mode = 't';
-            continue;
-        }
+use iridium_assembler::{
+    api_freeze, batch, cmd_args, concatenation, config, constant_folding, content_name, coverage, deprecation, determinism_audit, diagnostics, dump, exports, generate_code, includes, label_table, layout_shuffle, lints, listing,
+    map_file, memmap, memory_fit, org, partial_assembly, pseudo_substitution, rom_split, serve, stats, test_vectors, trace, validation, version, watch
+};
+use iridium_assembler::cmd_args::{AsmArgs, BatchArgs, BinaryArgs, Command, LinkAddress, LinkArgs, MapDiffArgs, SharedArgs, Verbosity};
+use iridium_assembler::demangle::{self, Demangler};
+use iridium_assembler::diagnostics::{DiagnosticFormat, Severity};
+use iridium_assembler::errors::DeniedWarningsError;
+use iridium_assembler::generate_code::OutputFormat;
+use iridium_assembler::limits::{ResourceBudget, ResourceLimits};
+use iridium_assembler::preprocessor::Configuration;
+use iridium_assembler::warnings::WarningSink;
+
+
+/// Prints a message to stdout, or to stderr if the first argument is true, such as when the binary is
+/// being written to stdout and the two must not mix.
+macro_rules! status {
+    ($to_stderr:expr, $($arg:tt)*) => {
+        if $to_stderr { eprintln!($($arg)*) } else { println!($($arg)*) }
+    };
+}
+
 
-        validation::validate_asm_line(&line, mode).unwrap();
-        
-        if line.ends_with(":") {
-            next_label = Some(line[..line.len() - 1].to_owned());
-            continue;
+/// Takes the options the assembler was invoked with and the warnings given by every pass, and prints each
+/// warning the options turn on unless `--quiet` is given, to stderr if the binary is being written to
+/// stdout. Will return a `DeniedWarningsError` if any were turned on while warnings are denied.
+fn report_warnings(asm_args:&AsmArgs, shared:&SharedArgs, warnings:&WarningSink, to_stdout:bool) -> Result<(), DeniedWarningsError> {
+    let warning_settings = asm_args.warning_settings();
+    let enabled = warnings.enabled(&warning_settings);
+    for warning in &enabled {
+        if shared.verbosity() == Verbosity::Quiet {
+            break;
         }
 
-        match mode {
-            'c' => tokens.push(token_types::FileTokens::InstrTokens(token_generator::generate_instr_tokens(&line, next_label))),
-            'd' => tokens.push(token_types::FileTokens::DataTokens(token_generator::generate_data_tokens(&line, next_label, mode))),
-            't' => tokens.push(token_types::FileTokens::TextTokens(token_generator::generate_text_tokens(&line, next_label, mode))),
-            _ => panic!("Invalid section mode '{}'", mode)
+        match asm_args.diagnostic_format {
+            DiagnosticFormat::Human => status!(to_stdout, "{}", demangle_message(shared.demangler().as_ref(), &warning.to_string())),
+            DiagnosticFormat::Json => eprint!("{}", diagnostics::render_json_diagnostics(
+                &demangle_message(shared.demangler().as_ref(), &warning.message), Severity::Warning, warning.kind.name()
+            ))
         }
+    }
 
-        next_label = None;
+    if warning_settings.deny && !enabled.is_empty() {
+        let given = if enabled.len() == 1 { "warning was" } else { "warnings were" };
+        return Err(DeniedWarningsError(format!("{} {} given with --deny warnings", enabled.len(), given)));
     }
 
-    tokens
+    Ok(())
+}
+
+
+/// Takes the demangler the assembler was invoked with, if any, and a label table, and returns the readable
+/// name of every mangled label in it, which is empty without a demangler.
+fn demangled_names(demangler:Option<&Demangler>, label_table:&HashMap<String, i64>) -> Result<HashMap<String, String>, Box<dyn Error>> {
+    match demangler {
+        Some(demangler) => Ok(demangler.demangle_names(label_table.keys().map(|label| label.as_str()))?),
+        None => Ok(HashMap::new())
+    }
+}
+
+
+/// Takes the demangler the assembler was invoked with, if any, and the message of an error or warning, and
+/// returns it with its mangled labels demangled. A demangler which fails leaves the message as it was, as
+/// the message matters more than how its labels are shown.
+fn demangle_message(demangler:Option<&Demangler>, message:&str) -> String {
+    demangler.and_then(|demangler| demangler.demangle_text(message).ok()).unwrap_or_else(|| message.to_owned())
 }
 
 
 /// Runs the assebler through the process of assembling the input file into the output file.
 ///
-/// Iterates through each line of the input file and validates and tokensizes the lines then:
+/// Iterates through each line of the input files and validates and tokensizes the lines then:
 ///  - Converts any lines with label operands into several instructions which load the
 ///    necessary values into registers
 ///  - Builds a table of labels and what address they point to
 ///  - Substitutes labels for immediates
 ///  - Converts each set of tokens rperesenting an instruction into bytes
 ///  - Writes the bytes to the output file
-fn main() -> Result<(), errors::CmdArgsError> {
-    // Check that the command line arguments supplies are correct
-    let cmd_args: Vec<String> = env::args().collect();
-    if cmd_args.len() != 3 || !cmd_args[1].ends_with(".asm") {
-        return Err(errors::CmdArgsError);
+///
+/// If `--only` is given, only the given label range is assembled against the map file of a previous
+/// build, and the fixups for the fragment are written alongside it. If `--layout-only` is given, the final
+/// layout of the sections and labels is printed and nothing is written, and if `--check` is given, the
+/// program is checked as far as the memory fit but nothing is written. With `--audit-determinism`, the
+/// report of `determinism_audit::audit_determinism` is printed instead and nothing is written.
+///
+/// Warnings are printed unless `--quiet` is given, and the time taken by each stage, the label table, and
+/// every token only with `--verbose`. If `watching` is true, only warnings are printed, as the watch loop
+/// prints its own line for each build.
+fn assemble(asm_args:&AsmArgs, shared:&SharedArgs, watching:bool) -> Result<(), Box<dyn Error>> {
+    // the binary is written to stdout when the target is `-`, so progress goes to stderr instead
+    let to_stdout = asm_args.output_file == "-";
+    macro_rules! progress {
+        ($($arg:tt)*) => {
+            if !watching && shared.verbosity() >= Verbosity::Normal { status!(to_stdout, $($arg)*) }
+        };
     }
 
-    println!("Assembling {} into {}", cmd_args[1], cmd_args[2]);
+    macro_rules! verbose {
+        ($($arg:tt)*) => {
+            if !watching && shared.verbosity() >= Verbosity::Verbose { status!(to_stdout, $($arg)*) }
+        };
+    }
+
+    if asm_args.check_only() || asm_args.audit_only {
+        progress!("Checking {}", asm_args.input_files.join(", "));
+    } else if asm_args.layout_only {
+        progress!("Computing layout of {}", asm_args.input_files.join(", "));
+    } else {
+        progress!("Assembling {} into {}", asm_args.input_files.join(", "), asm_args.output_file);
+    }
 
     let now = Instant::now();
 
     let since = Instant::now();
-    let tokens = process_file_into_tokens(&cmd_args[1]);
-    println!("Tokenizer: {:?}", since.elapsed());
+    let mut warnings = WarningSink::default();
+    let limits = ResourceLimits {
+        max_include_depth: asm_args.max_include_depth,
+        max_macro_expansion: asm_args.max_macro_expansion,
+        ..ResourceLimits::UNLIMITED
+    };
+    let config = Configuration {
+        target: asm_args.target.clone(),
+        definitions: asm_args.definitions.clone(),
+        encoding: asm_args.input_encoding,
+        forbidden: asm_args.forbidden_opcodes.clone()
+    };
+    let mut budget = ResourceBudget::new(limits);
+    let tokens = concatenation::process_files_into_tokens(&asm_args.input_files, &config, asm_args.harden, asm_args.lang, &mut budget,
+        &mut warnings)?;
+    verbose!("Tokenizer: {:?}", since.elapsed());
+    let tokens_processed = tokens.len();
+
+    // the trace points are numbered and the exports given slots across every file, so they can only be expanded once
+    // all are tokenized
+    let (tokens, trace_points) = trace::expand_traces(tokens, asm_args.trace_output())?;
+    budget.count_expansions(trace_points.len());
+    let tokens = exports::expand_export_tables(tokens)?;
+
+    if !asm_args.allow_fallthrough {
+        lints::check_code_fallthrough(&tokens)?;
+    }
+
+    warnings.extend(lints::check_branch_pairs(&tokens));
+    warnings.extend(lints::check_unreachable_code(&tokens));
+    validation::check_zero_destinations(&tokens, &mut warnings);
+    label_table::check_unused_labels(&tokens, &mut warnings);
+    deprecation::check_deprecated_references(&tokens, &mut warnings);
+    report_warnings(asm_args, shared, &warnings, to_stdout)?;
+
+    // the beacons go in after the lints, which would otherwise see them as part of the program
+    let (tokens, coverage_blocks) = if asm_args.coverage_file.is_some() {
+        coverage::instrument_coverage(tokens)?
+    } else {
+        (tokens, Vec::new())
+    };
+    budget.count_expansions(coverage_blocks.len());
+
+    // the blocks are moved while they still refer to each other by label, so every reference follows them
+    let tokens = match asm_args.shuffle_seed {
+        Some(seed) => layout_shuffle::shuffle_layout(tokens, seed),
+        None => tokens
+    };
+
+    let tokens = org::place_sections(tokens, &asm_args.section_starts);
 
     let since = Instant::now();
-    let tokens = pseudo_substitution::substitute_pseudo_instrs(tokens);
-    println!("Pseudo Substitution: {:?}", since.elapsed());
+    let tokens = pseudo_substitution::substitute_pseudo_instrs_with_budget(tokens, &mut budget);
+    verbose!("Pseudo Substitution: {:?}", since.elapsed());
+
+    // the folded program is shorter, so it must be folded before any address is worked out
+    let tokens = if asm_args.fold_constants {
+        let since = Instant::now();
+        let tokens = constant_folding::fold_constants(tokens);
+        verbose!("Constant Folding: {:?}", since.elapsed());
+        tokens
+    } else {
+        tokens
+    };
+
+    if asm_args.audit_only {
+        let differences = determinism_audit::audit_determinism(&tokens, asm_args.page_size)?;
+        println!("{}", determinism_audit::render_audit_report(&differences)?);
+        return Ok(());
+    }
 
+    // assemble only the requested region against the addresses of a previous build
+    if let Some((start, end)) = &asm_args.only_range {
+        let base_map = map_file::read_map_file(asm_args.map_file.as_ref().unwrap())?;
+        let (fragment, fixups) = partial_assembly::assemble_label_range(tokens, start, end, &base_map)?;
+        generate_code::generate_binary(&asm_args.output_file, &fragment, OutputFormat::Ird, false, asm_args.fill_word, asm_args.page_size)?;
+        partial_assembly::write_fixups_file(&format!("{}.fix", asm_args.output_file), &fixups)?;
+
+        progress!("Assembled {}..{} into {} words with {} fixups", start, end, fragment.len(), fixups.len());
+        return Ok(());
+    }
+
+    // every data item is labelled so that the map file and the dump of the binary can refer to it
+    let tokens = label_table::label_anonymous_data(tokens);
     let since = Instant::now();
-    let label_table = label_table::generate_label_table(&tokens).unwrap();
-    println!("Label table: {:?}", since.elapsed());
-    // println!("{:#?}", label_table);
+    let label_table = label_table::generate_label_table_with_page_size(&tokens, asm_args.page_size)?;
+    verbose!("Label table: {:?}", since.elapsed());
 
     let since = Instant::now();
-    let tokens = pseudo_substitution::substitute_labels(tokens, &label_table).unwrap();
-    println!("Label substitution: {:?}", since.elapsed());
+    let tokens = pseudo_substitution::substitute_labels(tokens, &label_table)?;
+    verbose!("Label substitution: {:?}", since.elapsed());
+
+    // stop before anything is written so the layout can be checked without touching the output
+    if asm_args.layout_only {
+        print!("{}", memmap::render_memory_map(&label_table::generate_section_layouts_with_page_size(&tokens, asm_args.page_size)));
+        return Ok(());
+    }
+
+    if let Some(memory_filename) = &asm_args.memory_file {
+        let region_sizes = memory_fit::read_memory_description(memory_filename)?;
+        memory_fit::check_memory_fit(&label_table::generate_section_layouts_with_page_size(&tokens, asm_args.page_size), &region_sizes)?;
+    }
+
+    if let Some(max_pages) = asm_args.max_pages {
+        memory_fit::check_page_limit(&label_table::generate_section_layouts_with_page_size(&tokens, asm_args.page_size), max_pages)?;
+    }
+
+    // a check never writes the lock file, so that new addresses are only recorded by a build
+    if let Some(lock_filename) = &asm_args.freeze_api_file {
+        let frozen = api_freeze::read_lock_file(lock_filename)?;
+        let addresses = api_freeze::freeze_addresses(&frozen, &label_table, asm_args.refreeze)?;
+        if !asm_args.check_only() && api_freeze::write_lock_file(lock_filename, &frozen, &addresses)? {
+            progress!("Recorded the addresses of {} frozen labels in {}", addresses.len(), lock_filename);
+        }
+    }
+
+    if asm_args.show_stats && !watching {
+        status!(to_stdout, "{}", stats::render_stats(&stats::AssemblyStats::new(tokens_processed, budget.expansions(), &tokens, asm_args.page_size)).trim_end());
+    }
+
+    // everything that can fail has been checked, so there is nothing left to do but write the output
+    if asm_args.check_only() {
+        progress!("{} are valid, with {} labels", asm_args.input_files.join(", "), label_table.len());
+        return Ok(());
+    }
 
     let since = Instant::now();
-    generate_code::generate_binary(&cmd_args[2], &tokens).unwrap();
-    println!("Binary Generation: {:?}", since.elapsed());
+    generate_code::generate_binary(&asm_args.output_file, &tokens, asm_args.output_format, true, asm_args.fill_word, asm_args.page_size)?;
+    verbose!("Binary Generation: {:?}", since.elapsed());
+
+    if let Some((rom_filenames, split)) = &asm_args.split_rom() {
+        rom_split::write_split_rom(&tokens, asm_args.fill_word, asm_args.page_size, rom_filenames, *split)?;
+    }
+
+    if asm_args.output_hash_name {
+        let hashed_name = content_name::rename_to_content_name(&asm_args.output_file)?;
+        if !watching {
+            println!("{} -> {}", asm_args.output_file, hashed_name);
+        }
+    }
+
+    let names = demangled_names(shared.demangler().as_ref(), &label_table)?;
+    if let Some(map_filename) = &asm_args.map_file {
+        map_file::write_map_file(map_filename, &label_table, &tokens, &names, asm_args.page_size)?;
+    }
+
+    if let Some(coverage_filename) = &asm_args.coverage_file {
+        coverage::write_coverage_map(coverage_filename, &coverage_blocks, &label_table)?;
+    }
+
+    if let Some(trace_filename) = &asm_args.trace_file {
+        trace::write_trace_table(trace_filename, &trace_points)?;
+    }
+
+    if let Some(listing_filename) = &asm_args.listing_file {
+        listing::write_listing(listing_filename, &tokens, &names, asm_args.page_size)?;
+    }
+
+    if let Some(memmap_filename) = &asm_args.memmap_file {
+        memmap::write_memory_map(memmap_filename, &tokens, asm_args.page_size)?;
+    }
 
     let mut sorted_vec:Vec<_> = label_table.iter().collect();
     sorted_vec.sort_by(|a, b| a.1.cmp(b.1));
     for (label, line) in sorted_vec {
-        println!("{:<16} {:06X}", label, line);
+        verbose!("{:<16} {:06X}", label, line);
     }
-    
+
     for token in &tokens {
-        println!("{:?}", token);
+        verbose!("{:?}", token);
     }
 
-    println!("Assembly successful! Took {:?} to process {} lines", now.elapsed(), tokens.len());
+    verbose!("Took {:?} to process {} lines", now.elapsed(), tokens.len());
+    progress!("Assembly successful!");
 
     Ok(())
 }
+
+
+/// Takes the command line arguments and an error from assembling the program, and prints it to stderr in the
+/// format given by `--diagnostics`, with each of the problems it holds rendered separately with the line it is
+/// about, and with its labels demangled.
+fn print_error(asm_args:&AsmArgs, shared:&SharedArgs, e:&(dyn Error + 'static)) {
+    let message = demangle_message(shared.demangler().as_ref(), &e.to_string());
+    match asm_args.diagnostic_format {
+        DiagnosticFormat::Human => eprint!("{}", diagnostics::render_diagnostics(&message, diagnostics::use_color())),
+        DiagnosticFormat::Json => eprint!("{}", diagnostics::render_json_diagnostics(&message, Severity::Error, diagnostics::error_kind(e)))
+    }
+}
+
+
+/// Takes the command line arguments and returns the files `--watch` should watch, which are the source files
+/// and every file they include or embed, as found for the same configuration the program is assembled for.
+fn watched_files(asm_args:&AsmArgs) -> Vec<String> {
+    let config = Configuration {
+        target: asm_args.target.clone(),
+        definitions: asm_args.definitions.clone(),
+        encoding: asm_args.input_encoding,
+        forbidden: asm_args.forbidden_opcodes.clone()
+    };
+    let mut files = asm_args.input_files.clone();
+    for input_file in &asm_args.input_files {
+        for included in includes::find_included_files(input_file, &config, asm_args.max_include_depth) {
+            if !files.contains(&included) {
+                files.push(included);
+            }
+        }
+    }
+
+    files
+}
+
+
+/// Takes the options of the `dump` subcommand and prints every section of the binary, decoding the data
+/// using the types in the map file if one is given.
+fn dump_binary(binary_args:&BinaryArgs, shared:&SharedArgs) -> Result<(), Box<dyn Error>> {
+    let (label_table, data_types) = match &binary_args.map_file {
+        Some(map_filename) => (map_file::read_map_file(map_filename)?, map_file::read_map_data_types(map_filename)?),
+        None => (HashMap::new(), HashMap::new())
+    };
+
+    let label_table = demangle::rename_labels(&label_table, &demangled_names(shared.demangler().as_ref(), &label_table)?);
+    print!("{}", dump::render_dump(&fs::read(&binary_args.binary_file)?, &label_table, &data_types)?);
+    Ok(())
+}
+
+
+/// Takes the options of the `disasm` subcommand and prints the code section of the binary as assembly, with
+/// the labels in the map file if one is given.
+fn disassemble_binary(binary_args:&BinaryArgs, shared:&SharedArgs) -> Result<(), Box<dyn Error>> {
+    let label_table = match &binary_args.map_file {
+        Some(map_filename) => map_file::read_map_file(map_filename)?,
+        None => HashMap::new()
+    };
+
+    let label_table = demangle::rename_labels(&label_table, &demangled_names(shared.demangler().as_ref(), &label_table)?);
+    print!("{}", dump::render_disassembly(&fs::read(&binary_args.binary_file)?, &label_table)?);
+    Ok(())
+}
+
+
+/// Takes the options of the `link` subcommand and writes the base binary with the fragment patched over it
+/// at the given address, or at the address of the given label in the map file.
+fn link_fragment(link_args:&LinkArgs, shared:&SharedArgs) -> Result<(), Box<dyn Error>> {
+    let address = match &link_args.address {
+        LinkAddress::Address(address) => *address,
+        LinkAddress::Label(label) => match map_file::read_map_file(link_args.map_file.as_ref().unwrap())?.get(label) {
+            Some(address) => *address,
+            None => return Err(format!("The label {} is not in the map file", label).into())
+        }
+    };
+
+    let patched = partial_assembly::patch_fragment(&fs::read(&link_args.base_file)?, &fs::read(&link_args.fragment_file)?, address)?;
+    fs::write(&link_args.output_file, patched)?;
+    if shared.verbosity() > Verbosity::Quiet {
+        println!("Patched {} into {} at {:#06X}, writing {}", link_args.fragment_file, link_args.base_file, address, link_args.output_file);
+    }
+
+    Ok(())
+}
+
+
+/// Takes the options of the `mapdiff` subcommand and prints the labels which changed between the two map
+/// files, exiting with 1 if any change breaks code built against the old one.
+fn diff_maps(map_diff_args:&MapDiffArgs, shared:&SharedArgs) -> Result<(), Box<dyn Error>> {
+    let changes = map_file::diff_map_files(&map_diff_args.old_file, &map_diff_args.new_file)?;
+    let names = match shared.demangler() {
+        Some(demangler) => demangler.demangle_names(changes.iter().map(|change| change.label()))?,
+        None => HashMap::new()
+    };
+
+    print!("{}", map_file::render_map_diff(&changes, &names));
+    // a moved or removed label breaks code built against the old map file, which a script can check for
+    if changes.iter().any(|change| change.is_breaking()) {
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+
+/// Takes the options of the `batch` subcommand and assembles every source file in the archive, writing
+/// whether each passed to the report.
+fn assemble_batch(batch_args:&BatchArgs, shared:&SharedArgs) -> Result<(), Box<dyn Error>> {
+    let (archive, report) = (&batch_args.archive, &batch_args.report_file);
+    let results = batch::assemble_archive(archive)?;
+    batch::write_report(report, archive, &results)?;
+    if shared.verbosity() > Verbosity::Quiet {
+        let passed = results.iter().filter(|result| result.passed).count();
+        status!(report == "-", "Assembled {} files from {}, of which {} passed and {} failed", results.len(), archive, passed, results.len() - passed);
+    }
+
+    Ok(())
+}
+
+
+/// Takes the options of the `asm` subcommand and writes the test vectors if they were asked for, then
+/// assembles the program unless only the test vectors were asked for. With `--watch`, the program is
+/// assembled again every time a source file, or a file it includes or embeds, changes.
+fn run_assembler(asm_args:&AsmArgs, shared:&SharedArgs) -> Result<(), Box<dyn Error>> {
+    if let Some(vectors_filename) = &asm_args.test_vectors_file {
+        let vectors = test_vectors::generate_test_vectors()?;
+        test_vectors::write_test_vectors(vectors_filename, &vectors)?;
+        if shared.verbosity() > Verbosity::Quiet {
+            status!(asm_args.output_file == "-", "Wrote {} test vectors to {}", vectors.len(), vectors_filename);
+        }
+
+        if asm_args.input_files.is_empty() {
+            return Ok(());
+        }
+    }
+
+    // keep watching after a failed build, including one which panics, so it can be fixed and saved again
+    if asm_args.watch {
+        let quiet = shared.verbosity() == Verbosity::Quiet;
+        if !quiet {
+            println!("Watching {} for changes", asm_args.input_files.join(", "));
+        }
+
+        watch::watch_files(&asm_args.input_files, watch::WATCH_INTERVAL, || {
+            match panic::catch_unwind(AssertUnwindSafe(|| assemble(asm_args, shared, true))) {
+                Ok(Ok(())) if quiet => (),
+                Ok(Ok(())) => println!("Assembled {} successfully", asm_args.input_files.join(", ")),
+                Ok(Err(e)) => print_error(asm_args, shared, e.as_ref()),
+                Err(_) => print_error(asm_args, shared, Box::<dyn Error>::from("the assembler stopped unexpectedly").as_ref())
+            }
+
+            watched_files(asm_args)
+        });
+    }
+
+    // several errors can be found together, so each is rendered separately with the line it is about
+    if let Err(e) = assemble(asm_args, shared, false) {
+        print_error(asm_args, shared, e.as_ref());
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+
+/// Parses the command line arguments and runs the subcommand they ask for, which is assembling a program
+/// unless another was given, or prints the help or the version of the assembler.
+fn main() -> Result<(), Box<dyn Error>> {
+    // the options in the configuration file go first, so that any given on the command line override them
+    let config_args = match config::read_config(config::CONFIG_FILENAME) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        }
+    };
+
+    let args = config::insert_config_args(config_args, env::args().skip(1).collect());
+
+    // Check that the command line arguments supplies are correct
+    let cmd_args = match cmd_args::parse_cmd_args(&args) {
+        Ok(cmd_args) => cmd_args,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        }
+    };
+
+    if let Some(help_text) = &cmd_args.help_text {
+        print!("{}", help_text);
+        return Ok(());
+    }
+
+    if cmd_args.version {
+        println!("{}", version::version_report());
+        return Ok(());
+    }
+
+    let shared = &cmd_args.shared;
+    match &cmd_args.command {
+        Some(Command::Asm(asm_args)) => run_assembler(asm_args, shared),
+        Some(Command::Disasm(binary_args)) => disassemble_binary(binary_args, shared),
+        Some(Command::Dump(binary_args)) => dump_binary(binary_args, shared),
+        Some(Command::Link(link_args)) => link_fragment(link_args, shared),
+        Some(Command::Mapdiff(map_diff_args)) => diff_maps(map_diff_args, shared),
+        Some(Command::Explain(explain_args)) => {
+            print!("{}", diagnostics::render_explanation(&explain_args.code).unwrap());
+            Ok(())
+        },
+        Some(Command::Batch(batch_args)) => assemble_batch(batch_args, shared),
+        Some(Command::Serve(serve_args)) => serve::serve(&serve_args.host, serve_args.port),
+        None => Ok(())
+    }
+}
@@ -0,0 +1,387 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use crate::errors::AsmValidationError;
+use crate::label_table::{generate_section_layouts_with_page_size, generate_token_addresses_with_page_size, ANONYMOUS_DATA_PREFIX};
+use crate::local_labels::is_local_label;
+use crate::token_types::FileTokens;
+
+
+/// Represents a single line of a map file, being a label, its address, and, for labels in the data and
+/// text sections, the directive type and number of words of the item it labels.
+type MapEntry = (String, i64, Option<(String, usize)>);
+
+
+/// Represents how a single label differs between the map files of two builds, as found by `diff_map_files`.
+/// A label which is both moved and resized has a change for each.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapChange {
+    /// A label at a different address in the new build, with its old and new address
+    Moved(String, i64, i64),
+    /// A label only in the new build, with its address
+    Added(String, i64),
+    /// A label only in the old build, with its old address
+    Removed(String, i64),
+    /// A data or text label whose item is a different number of words in the new build, with its new address,
+    /// its directive type, and its old and new number of words
+    Resized(String, i64, String, usize, usize)
+}
+
+
+impl MapChange {
+    /// Returns the label which has changed
+    pub fn label(&self) -> &str {
+        match self {
+            MapChange::Moved(label, ..) | MapChange::Added(label, _) | MapChange::Removed(label, _) | MapChange::Resized(label, ..) => label
+        }
+    }
+
+
+    /// Returns true if the change breaks code which was built against the old map file, which is a label
+    /// moving or being removed, as adding a label or resizing an item leaves every old address in place
+    pub fn is_breaking(&self) -> bool {
+        matches!(self, MapChange::Moved(..) | MapChange::Removed(..))
+    }
+}
+
+
+/// Represents the totals of a single section of the program, being where it starts and ends, how many
+/// words in it are used by instructions or data, and how many are lost to `.org` gaps within the section and
+/// to padding out its last page so that the next section starts on a new page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionStats {
+    pub name: String,
+    pub start: i64,
+    pub end: i64,
+    pub used: i64,
+    pub org_gaps: i64,
+    pub page_padding: i64,
+    pub pages: i64
+}
+
+
+impl SectionStats {
+    /// Returns the percentage of the words reserved for the section, up to the end of its last page, which
+    /// are used by instructions or data.
+    pub fn fill_percent(&self) -> f64 {
+        let reserved = self.end - self.start + self.page_padding;
+        if reserved == 0 {
+            return 100.0;
+        }
+
+        self.used as f64 * 100.0 / reserved as f64
+    }
+}
+
+
+/// Takes a label table and the tokens it was generated from and writes it to the given map file, with one
+/// label and its address in hex per line, sorted by address so that the file can be read as a layout of the
+/// program. Labels of data and text are followed by the type and size in words of the item they label, so
+/// that the data can be decoded when the binary is dumped, and any mangled label in `names` is followed by a
+/// comment giving its readable name.
+pub fn write_map_file(filename:&str, label_table:&HashMap<String, i64>, tokens:&[FileTokens], names:&HashMap<String, String>,
+        page_size:i64) -> Result<(), Box<dyn Error>> {
+    let mut map_file = BufWriter::new(
+        OpenOptions::new().create(true).write(true).truncate(true).open(filename)?);
+
+    let mut data_types:HashMap<&str, (&str, usize)> = HashMap::new();
+    for (token, addr) in tokens.iter().zip(generate_token_addresses_with_page_size(tokens, page_size)) {
+        let data_type = match token {
+            FileTokens::DataTokens(t) => (t.category.as_str(), t.bytes.len()),
+            FileTokens::TextTokens(t) => ("text", t.bytes.len()),
+            FileTokens::BssTokens(t) => ("space", t.size as usize),
+            FileTokens::InstrTokens(_) | FileTokens::OrgTokens(_) | FileTokens::AliasTokens(_) | FileTokens::DeprecatedTokens(_) | FileTokens::TraceTokens(_)
+                | FileTokens::ExportTableTokens(_) => continue
+        };
+
+        // only the first item with a label is the one at the label's address
+        if let Some(label) = token.label() {
+            if label_table.get(label) == Some(&addr) {
+                data_types.entry(label).or_insert(data_type);
+            }
+        }
+    }
+
+    let mut sorted_vec:Vec<_> = label_table.iter().collect();
+    sorted_vec.sort_by(|a, b| a.1.cmp(b.1).then(a.0.cmp(b.0)));
+    for (label, addr) in sorted_vec {
+        let entry = match data_types.get(label.as_str()) {
+            Some((category, words)) => format!("{:<16} {:08X} {} {}", label, addr, category, words),
+            None => format!("{:<16} {:08X}", label, addr)
+        };
+
+        match names.get(label) {
+            Some(name) => writeln!(map_file, "{}  ; {}", entry, name)?,
+            None => writeln!(map_file, "{}", entry)?
+        }
+    }
+
+    write!(map_file, "{}", render_section_stats(&generate_section_stats(tokens, page_size)))?;
+    map_file.flush()?;
+    Ok(())
+}
+
+
+/// Takes a stream of tokens and the number of words in a page, and returns the totals of each section (code,
+/// data, text, and bss) that contains at least one token, in address order.
+pub fn generate_section_stats(tokens:&[FileTokens], page_size:i64) -> Vec<SectionStats> {
+    let layouts = generate_section_layouts_with_page_size(tokens, page_size);
+    let mut stats:Vec<SectionStats> = layouts.iter().map(|layout| {
+        let page_end = (layout.end + page_size - 1) / page_size * page_size;
+        SectionStats {
+            name: layout.name.to_owned(),
+            start: layout.start,
+            end: layout.end,
+            used: 0,
+            org_gaps: 0,
+            page_padding: page_end - layout.end,
+            pages: layout.page_count()
+        }
+    }).collect();
+
+    for (token, addr) in tokens.iter().zip(generate_token_addresses_with_page_size(tokens, page_size)) {
+        let size = match token {
+            FileTokens::InstrTokens(_) => 1,
+            FileTokens::DataTokens(t) => t.bytes.len() as i64,
+            FileTokens::TextTokens(t) => t.bytes.len() as i64,
+            FileTokens::BssTokens(t) => t.size as i64,
+            FileTokens::OrgTokens(_) | FileTokens::AliasTokens(_) | FileTokens::DeprecatedTokens(_) | FileTokens::TraceTokens(_)
+                | FileTokens::ExportTableTokens(_) => continue
+        };
+
+        if let Some(section) = stats.iter_mut().find(|section| section.start <= addr && addr < section.end) {
+            section.used += size;
+        }
+    }
+
+    for section in stats.iter_mut() {
+        section.org_gaps = section.end - section.start - section.used;
+    }
+
+    stats
+}
+
+
+/// Takes the totals of each section and returns them as a table of comments to go at the end of a map file,
+/// so that the file can still be read back by `read_map_file`. Each line gives the words used by the section,
+/// the words lost to `.org` gaps and page padding, how much of the pages reserved for it are used, and how
+/// many 4K pages it occupies.
+pub fn render_section_stats(stats:&[SectionStats]) -> String {
+    let mut lines:Vec<String> = vec![
+        String::new(),
+        format!("; {:<8} {:<8}  {:<8}  {:>6}  {:>8}  {:>8}  {:>6}  {:>5}", "SECTION", "START", "END", "USED", "ORG GAPS", "PADDING", "FILL", "PAGES")
+    ];
+
+    for section in stats {
+        lines.push(format!("; {:<8} {:08X}  {:08X}  {:>6}  {:>8}  {:>8}  {:>5.1}%  {:>5}", section.name, section.start,
+            section.end, section.used, section.org_gaps, section.page_padding, section.fill_percent(), section.pages));
+    }
+
+    let wasted:i64 = stats.iter().map(|section| section.org_gaps + section.page_padding).sum();
+    lines.push(format!("; {} words lost to .org gaps and page padding", wasted));
+    lines.push(format!("; {} pages used", stats.iter().map(|section| section.pages).sum::<i64>()));
+    lines.join("\n") + "\n"
+}
+
+
+/// Takes the filename of a map file written by `write_map_file` and returns every entry in it. Everything
+/// after a ';' is a comment, blank lines are ignored, and any other malformed line gives an
+/// `AsmValidationError`.
+fn read_map_entries(filename:&str) -> Result<Vec<MapEntry>, Box<dyn Error>> {
+    let map_file = BufReader::new(OpenOptions::new().read(true).open(filename)?);
+    let mut entries:Vec<MapEntry> = Vec::new();
+    for line in map_file.lines() {
+        let line = line?;
+        let line = line[..line.find(';').unwrap_or(line.len())].trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let tokens:Vec<&str> = line.split_whitespace().collect();
+        if tokens.len() != 2 && tokens.len() != 4 {
+            return Err(Box::new(AsmValidationError(format!("{} is not a valid map file entry", line))));
+        }
+
+        let addr = match i64::from_str_radix(tokens[1], 16) {
+            Ok(addr) => addr,
+            Err(_) => {
+                return Err(Box::new(AsmValidationError(format!("{} is not a valid address in the map file", tokens[1]))));
+            }
+        };
+
+        let data_type = match tokens.get(2..4) {
+            Some([category, words]) => match words.parse::<usize>() {
+                Ok(words) => Some((category.to_string(), words)),
+                Err(_) => {
+                    return Err(Box::new(AsmValidationError(format!("{} is not a valid size in the map file", words))));
+                }
+            },
+            _ => None
+        };
+
+        entries.push((tokens[0].to_owned(), addr, data_type));
+    }
+
+    Ok(entries)
+}
+
+
+/// Takes the filename of a map file written by `write_map_file` and reads it back into a label table.
+pub fn read_map_file(filename:&str) -> Result<HashMap<String, i64>, Box<dyn Error>> {
+    Ok(read_map_entries(filename)?.into_iter().map(|(label, addr, _)| (label, addr)).collect())
+}
+
+
+/// Takes the filename of a map file written by `write_map_file` and returns the directive type and number
+/// of words of the item at the address of each data and text label.
+pub fn read_map_data_types(filename:&str) -> Result<HashMap<i64, (String, usize)>, Box<dyn Error>> {
+    Ok(read_map_entries(filename)?.into_iter()
+        .filter_map(|(_, addr, data_type)| data_type.map(|data_type| (addr, data_type)))
+        .collect())
+}
+
+
+/// Takes the filenames of the map files of an old and a new build and returns every label which was moved,
+/// added, removed, or resized between them, sorted by their new address, or old address if they were removed,
+/// and then by label. The labels the assembler gives to anonymous data and local labels are left out, as
+/// their names can change whenever one is added before them, and code in other files cannot refer to them.
+pub fn diff_map_files(old_filename:&str, new_filename:&str) -> Result<Vec<MapChange>, Box<dyn Error>> {
+    Ok(diff_map_entries(read_map_entries(old_filename)?, read_map_entries(new_filename)?))
+}
+
+
+/// Takes the entries of the map files of an old and a new build and returns every change between them, as
+/// described for `diff_map_files`.
+fn diff_map_entries(old:Vec<MapEntry>, new:Vec<MapEntry>) -> Vec<MapChange> {
+    let is_named = |(label, _, _):&MapEntry| !label.starts_with(ANONYMOUS_DATA_PREFIX) && !is_local_label(label);
+    let old:HashMap<String, (i64, Option<(String, usize)>)> = old.into_iter().filter(is_named)
+        .map(|(label, addr, data_type)| (label, (addr, data_type)))
+        .collect();
+
+    let mut changes:Vec<(i64, MapChange)> = Vec::new();
+    let mut kept:Vec<&str> = Vec::new();
+    for (label, addr, data_type) in new.iter().filter(|entry| is_named(entry)) {
+        let Some((old_addr, old_data_type)) = old.get(label) else {
+            changes.push((*addr, MapChange::Added(label.clone(), *addr)));
+            continue;
+        };
+
+        kept.push(label);
+        if old_addr != addr {
+            changes.push((*addr, MapChange::Moved(label.clone(), *old_addr, *addr)));
+        }
+
+        if let (Some((_, old_words)), Some((category, words))) = (old_data_type, data_type) {
+            if old_words != words {
+                changes.push((*addr, MapChange::Resized(label.clone(), *addr, category.clone(), *old_words, *words)));
+            }
+        }
+    }
+
+    for (label, (addr, _)) in &old {
+        if !kept.contains(&label.as_str()) {
+            changes.push((*addr, MapChange::Removed(label.clone(), *addr)));
+        }
+    }
+
+    changes.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.label().cmp(b.1.label())));
+    changes.into_iter().map(|(_, change)| change).collect()
+}
+
+
+/// Takes the changes between the map files of two builds and returns them as a report, with one change on
+/// each line followed by a count of each kind of change. Any label in `names` is shown by its readable name.
+pub fn render_map_diff(changes:&[MapChange], names:&HashMap<String, String>) -> String {
+    let name = |label:&str| names.get(label).map_or(label, |name| name.as_str()).to_owned();
+    let mut lines:Vec<String> = changes.iter().map(|change| match change {
+        MapChange::Moved(label, old_addr, addr) => format!("moved    {:<16} {:08X} -> {:08X}", name(label), old_addr, addr),
+        MapChange::Added(label, addr) => format!("added    {:<16} {:08X}", name(label), addr),
+        MapChange::Removed(label, addr) => format!("removed  {:<16} {:08X}", name(label), addr),
+        MapChange::Resized(label, addr, category, old_words, words) => {
+            format!("resized  {:<16} {:08X} {} {} -> {}", name(label), addr, category, old_words, words)
+        }
+    }).collect();
+
+    let count = |kind:fn(&MapChange) -> bool| changes.iter().filter(|change| kind(change)).count();
+    lines.push(format!("; {} moved, {} added, {} removed, {} resized",
+        count(|change| matches!(change, MapChange::Moved(..))), count(|change| matches!(change, MapChange::Added(..))),
+        count(|change| matches!(change, MapChange::Removed(..))), count(|change| matches!(change, MapChange::Resized(..)))));
+
+    lines.join("\n") + "\n"
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::map_file::*;
+    use crate::label_table::PAGE_SIZE;
+    use crate::pseudo_substitution::substitute_pseudo_instrs;
+    use crate::testing::{tokens_from_str, ORG_PROGRAM};
+
+
+    #[test]
+    fn test_read_map_file() {
+        let label_table = read_map_file("test_files/test_partial_assembly.map").unwrap();
+        assert_eq!(label_table.len(), 5);
+        assert_eq!(label_table["init"], 0x0000);
+        assert_eq!(label_table["double"], 0x0003);
+        assert_eq!(label_table["double_end"], 0x0010);
+        assert_eq!(label_table["value"], 0x1000);
+    }
+
+
+    #[test]
+    fn test_read_map_data_types() {
+        let data_types = read_map_data_types("test_files/test_partial_assembly.map").unwrap();
+        assert_eq!(data_types.len(), 1);
+        assert_eq!(data_types[&0x1000], ("int".to_owned(), 1));
+        assert_eq!(read_map_file("test_files/test_partial_assembly.map").unwrap()["value"], 0x1000);
+    }
+
+
+    #[test]
+    fn test_read_demangled_map_file() {
+        let label_table = read_map_file("test_files/test_demangled.map").unwrap();
+        assert_eq!(label_table.len(), 3);
+        assert_eq!(label_table["_Zmath_sqrt_ii"], 0x0009);
+        assert_eq!(read_map_data_types("test_files/test_demangled.map").unwrap()[&0x1000], ("int".to_owned(), 4));
+    }
+
+
+    #[test]
+    fn test_diff_map_files() {
+        let changes = diff_map_files("test_files/test_partial_assembly.map", "test_files/test_partial_assembly_patched.map").unwrap();
+        assert_eq!(changes, vec![
+            MapChange::Removed("exit".to_owned(), 0x0011),
+            MapChange::Moved("double_end".to_owned(), 0x0010, 0x0012),
+            MapChange::Added("triple".to_owned(), 0x0012),
+            MapChange::Resized("value".to_owned(), 0x1000, "int".to_owned(), 1, 2)
+        ]);
+        assert_eq!(changes.iter().filter(|change| change.is_breaking()).count(), 2);
+
+        let rendered = render_map_diff(&changes, &HashMap::from([("triple".to_owned(), "math.triple(int)".to_owned())]));
+        assert_eq!(rendered, "removed  exit             00000011\n\
+            moved    double_end       00000010 -> 00000012\n\
+            added    math.triple(int) 00000012\n\
+            resized  value            00001000 int 1 -> 2\n\
+            ; 1 moved, 1 added, 1 removed, 1 resized\n");
+
+        assert!(diff_map_files("test_files/test_partial_assembly.map", "test_files/test_partial_assembly.map").unwrap().is_empty());
+    }
+
+
+    #[test]
+    fn test_generate_section_stats() {
+        let stats = generate_section_stats(&substitute_pseudo_instrs(tokens_from_str(ORG_PROGRAM)), PAGE_SIZE);
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats[0].name.as_str(), stats[0].used, stats[0].org_gaps), ("code", 4, 14));
+        assert_eq!(stats[1], SectionStats {
+            name: "data".to_owned(), start: 0x1000, end: 0x1024, used: 5, org_gaps: 0x1F, page_padding: 0xFDC, pages: 1
+        });
+
+        let rendered = render_section_stats(&stats);
+        assert!(rendered.contains("; data     00001000  00001024       5        31      4060    0.1%      1\n"));
+        assert!(rendered.ends_with("; 2 pages used\n"));
+    }
+}
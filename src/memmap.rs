@@ -0,0 +1,97 @@
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use crate::label_table::{generate_section_layouts_with_page_size, SectionLayout};
+use crate::token_types::FileTokens;
+
+
+/// The number of characters in the bar showing how full a page is
+const BAR_WIDTH:i64 = 32;
+
+/// The percentage of a page which, once used, marks the page as nearly full
+const NEARLY_FULL_PERCENT:i64 = 90;
+
+
+/// Takes the number of words used in a page and the number of words in a page, and returns a bar showing how
+/// full the page is, such as `[########........................]`. Any page with at least one word used
+/// shows at least one '#'.
+fn usage_bar(used:i64, page_size:i64) -> String {
+    let filled = ((used * BAR_WIDTH + page_size - 1) / page_size).min(BAR_WIDTH);
+    format!("[{}{}]", "#".repeat(filled as usize), ".".repeat((BAR_WIDTH - filled) as usize))
+}
+
+
+/// Takes the layout of each section of a program and returns an ASCII memory map of it. Each section lists
+/// the pages it occupies with a bar showing how much of each page is used, followed by the extent of every
+/// label in the section. Sections which spill over into more than one page, and pages which are nearly
+/// full, are marked with a '!' so they stand out.
+pub fn render_memory_map(layouts:&[SectionLayout]) -> String {
+    let mut lines:Vec<String> = vec![format!("{:<8} {:<8}  {:<8}  {:>6}  {:>5}", "SECTION", "START", "END", "WORDS", "PAGES")];
+    for layout in layouts {
+        let page_size = layout.page_size;
+        let first_page = layout.start / page_size;
+        let page_count = layout.page_count();
+        let last_page = first_page + page_count - 1;
+
+        lines.push(String::new());
+        lines.push(format!("{:<8} {:08X}  {:08X}  {:>6}  {:>5}",
+            layout.name, layout.start, layout.end, layout.end - layout.start, page_count));
+
+        if page_count > 1 {
+            lines.push(format!("  ! {} section spills over {} pages", layout.name, page_count));
+        }
+
+        for page in first_page..=last_page {
+            let used = layout.end.min((page + 1) * page_size) - layout.start.max(page * page_size);
+            let percent = used * 100 / page_size;
+            let marker = if percent >= NEARLY_FULL_PERCENT { "  ! nearly full" } else { "" };
+            lines.push(format!("  page {:04X}  {} {:>4}/{} {:>3}%{}",
+                page, usage_bar(used, page_size), used, page_size, percent, marker));
+        }
+
+        for (label, start, size) in &layout.labels {
+            lines.push(format!("    {:<16} {:08X}  {:>6}", label, start, size));
+        }
+    }
+
+    let pages:i64 = layouts.iter().map(|layout| layout.page_count()).sum();
+    lines.push(String::new());
+    lines.push(format!("{} pages in total", pages));
+    lines.join("\n") + "\n"
+}
+
+
+/// Takes a stream of tokens which have had their pseudo-instructions substituted and the number of words in
+/// a page, and writes an ASCII memory map of the program to the given file.
+pub fn write_memory_map(filename:&str, tokens:&[FileTokens], page_size:i64) -> Result<(), Box<dyn Error>> {
+    let mut memmap_file = BufWriter::new(
+        OpenOptions::new().create(true).write(true).truncate(true).open(filename)?);
+
+    write!(memmap_file, "{}", render_memory_map(&generate_section_layouts_with_page_size(tokens, page_size)))?;
+    memmap_file.flush()?;
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::label_table::{SectionLayout, PAGE_SIZE};
+    use crate::memmap::render_memory_map;
+
+
+    #[test]
+    fn test_render_memory_map() {
+        let layouts = vec![
+            SectionLayout { name: "code".to_owned(), start: 0, end: 0x15, labels: vec![("init".to_owned(), 0, 0x15)], page_size: PAGE_SIZE },
+            SectionLayout { name: "data".to_owned(), start: 0x1000, end: 0x2F80, labels: vec![], page_size: PAGE_SIZE }
+        ];
+
+        let memmap = render_memory_map(&layouts);
+        assert!(memmap.contains("code     00000000  00000015      21      1"));
+        assert!(memmap.contains("  page 0000  [#...............................]   21/4096   0%\n"));
+        assert!(memmap.contains("    init             00000000      21"));
+        assert!(memmap.contains("  ! data section spills over 2 pages"));
+        assert!(memmap.contains("  page 0002  [###############################.] 3968/4096  96%  ! nearly full"));
+        assert!(!memmap.contains("code section spills"));
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader};
+use crate::errors::{AsmValidationError, MemoryFitError};
+use crate::label_table::SectionLayout;
+use crate::validation::validate_int_immediate;
+
+
+/// The regions of memory a target description can give the size of, one for each section
+const MEMORY_REGIONS:[&str; 4] = ["code", "data", "text", "bss"];
+
+/// The number of labels listed in the breakdown of a section which does not fit
+const BREAKDOWN_LABELS:usize = 5;
+
+
+/// Takes the filename of a target description and returns the size in words of each region of memory it
+/// gives. Each line holds the name of a region (`code`, `data`, `text`, or `bss`) and its size, such as
+/// `data 0x2000`. Blank lines and lines starting with ';' are ignored, and any other malformed line gives an
+/// `AsmValidationError`. Regions which are not given have no limit.
+pub fn read_memory_description(filename:&str) -> Result<HashMap<String, i64>, Box<dyn Error>> {
+    let description_file = BufReader::new(OpenOptions::new().read(true).open(filename)?);
+    let mut region_sizes:HashMap<String, i64> = HashMap::new();
+    for line in description_file.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+
+        let tokens:Vec<&str> = line.split_whitespace().collect();
+        if tokens.len() != 2 || !MEMORY_REGIONS.contains(&tokens[0]) {
+            return Err(Box::new(AsmValidationError(format!(
+                "{} is not a valid memory region, which should be one of {} followed by its size", line, MEMORY_REGIONS.join(", ")
+            ))));
+        }
+
+        region_sizes.insert(tokens[0].to_owned(), validate_int_immediate(tokens[1], 32, false)?);
+    }
+
+    Ok(region_sizes)
+}
+
+
+/// Takes the layout of each section of a program and the size of each region of memory on the target, and
+/// returns a `MemoryFitError` if any section is larger than its region. The error lists every section which
+/// does not fit along with the largest labels in it, as these are the most likely to need moving or
+/// shrinking.
+pub fn check_memory_fit(layouts:&[SectionLayout], region_sizes:&HashMap<String, i64>) -> Result<(), MemoryFitError> {
+    let mut overflows:Vec<String> = Vec::new();
+    for layout in layouts {
+        let available = match region_sizes.get(&layout.name) {
+            Some(available) => *available,
+            None => continue
+        };
+
+        let used = layout.end - layout.start;
+        if used <= available {
+            continue;
+        }
+
+        let mut labels:Vec<&(String, i64, i64)> = layout.labels.iter().collect();
+        labels.sort_by(|a, b| b.2.cmp(&a.2).then(a.1.cmp(&b.1)));
+        let largest:Vec<String> = labels.iter().take(BREAKDOWN_LABELS)
+            .map(|(label, _, size)| format!("{} ({} words)", label, size))
+            .collect();
+
+        let mut overflow = format!("the {} section uses {} words but the target only has {}, so is over by {} words",
+            layout.name, used, available, used - available);
+        if !largest.is_empty() {
+            overflow.push_str(&format!(" - the largest labels are {}", largest.join(", ")));
+        }
+
+        overflows.push(overflow);
+    }
+
+    if !overflows.is_empty() {
+        return Err(MemoryFitError(overflows.join("; ")));
+    }
+
+    Ok(())
+}
+
+
+/// Takes the layout of each section of a program and the most pages of memory the target can map for it,
+/// and returns a `MemoryFitError` if its sections occupy more pages than that in total. The error gives the
+/// pages each section occupies, so that the largest can be shrunk.
+pub fn check_page_limit(layouts:&[SectionLayout], max_pages:i64) -> Result<(), MemoryFitError> {
+    let pages:i64 = layouts.iter().map(|layout| layout.page_count()).sum();
+    if pages <= max_pages {
+        return Ok(());
+    }
+
+    let breakdown:Vec<String> = layouts.iter().map(|layout| format!("{} {}", layout.name, layout.page_count())).collect();
+    Err(MemoryFitError(format!(
+        "the program uses {} pages but the target can only map {}, so is over by {} pages - the sections use {}",
+        pages, max_pages, pages - max_pages, breakdown.join(", ")
+    )))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::label_table::{SectionLayout, PAGE_SIZE};
+    use crate::memory_fit::*;
+
+
+    #[test]
+    fn test_read_memory_description() {
+        let region_sizes = read_memory_description("test_files/test_memory_fit.mem").unwrap();
+        assert_eq!(region_sizes.len(), 3);
+        assert_eq!(region_sizes["code"], 0x1000);
+        assert_eq!(region_sizes["data"], 0x0010);
+        assert!(!region_sizes.contains_key("bss"));
+    }
+
+
+    #[test]
+    fn test_check_memory_fit() {
+        let layouts = vec![
+            SectionLayout { name: "code".to_owned(), start: 0, end: 0x20, labels: vec![("init".to_owned(), 0, 0x20)], page_size: PAGE_SIZE },
+            SectionLayout { name: "data".to_owned(), start: 0x1000, end: 0x1018, labels: vec![
+                ("count".to_owned(), 0x1000, 1), ("table".to_owned(), 0x1001, 0x10), ("name".to_owned(), 0x1011, 7)
+            ], page_size: PAGE_SIZE }
+        ];
+
+        let region_sizes = HashMap::from([("code".to_owned(), 0x1000), ("data".to_owned(), 0x10)]);
+        let err = check_memory_fit(&layouts, &region_sizes).unwrap_err();
+        assert_eq!(err.0, "the data section uses 24 words but the target only has 16, so is over by 8 words - the largest labels are table (16 words), name (7 words), count (1 words)");
+
+        let region_sizes = HashMap::from([("data".to_owned(), 0x18)]);
+        check_memory_fit(&layouts, &region_sizes).unwrap();
+    }
+
+
+    #[test]
+    fn test_check_page_limit() {
+        let layouts = vec![
+            SectionLayout { name: "code".to_owned(), start: 0, end: 0x1001, labels: Vec::new(), page_size: PAGE_SIZE },
+            SectionLayout { name: "data".to_owned(), start: 0x2000, end: 0x2010, labels: Vec::new(), page_size: PAGE_SIZE }
+        ];
+
+        assert_eq!(layouts.iter().map(|layout| layout.page_count()).collect::<Vec<i64>>(), vec![2, 1]);
+        check_page_limit(&layouts, 3).unwrap();
+        assert_eq!(check_page_limit(&layouts, 2).unwrap_err().0,
+            "the program uses 3 pages but the target can only map 2, so is over by 1 pages - the sections use code 2, data 1");
+    }
+}
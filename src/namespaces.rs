@@ -0,0 +1,78 @@
+use crate::errors::AsmValidationError;
+use crate::validation::validate_label;
+
+
+/// Takes a line of assembly and returns true if it opens a namespace, such as `.namespace math`
+pub fn is_namespace_directive(line:&str) -> bool {
+    line.starts_with(".namespace ") || line == ".namespace"
+}
+
+
+/// Takes a line of assembly opening a namespace in the form `.namespace <name>` and returns the name of
+/// the namespace, or an `AsmValidationError` if the name is missing or is not a valid label.
+pub fn parse_namespace_directive(line:&str) -> Result<String, AsmValidationError> {
+    let tokens:Vec<&str> = line.split_whitespace().collect();
+    if tokens.len() != 2 {
+        return Err(AsmValidationError(format!("Incorrect format for namespace on line {}, which should be .namespace <name>", line)));
+    }
+
+    validate_label(line, tokens[1])?;
+    Ok(tokens[1].to_owned())
+}
+
+
+/// Takes a line of assembly and the namespace it is in, and returns the line with its label, if it has one,
+/// prefixed by the namespace, so `sqrt: NOP` in the namespace `math` becomes `math.sqrt: NOP`. Label operands
+/// are left alone, as they must already be written with their qualified name.
+pub fn qualify_line_label(line:&str, namespace:&Option<String>) -> String {
+    match (namespace, line.find(':')) {
+        (Some(namespace), Some(_)) => format!("{}.{}", namespace, line),
+        _ => line.to_owned()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::namespaces::*;
+    use crate::testing::tokens_from_str;
+
+
+    #[test]
+    fn test_parse_namespace_directive() {
+        assert!(is_namespace_directive(".namespace math"));
+        assert!(!is_namespace_directive("namespace: NOP"));
+        assert_eq!(parse_namespace_directive(".namespace math").unwrap(), "math");
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_namespace() {
+        parse_namespace_directive(".namespace 2d").unwrap();
+    }
+
+
+    #[test]
+    fn test_qualify_line_label() {
+        let namespace = Some("math".to_owned());
+        assert_eq!(qualify_line_label("sqrt: ADD $g0, $g0, $g1", &namespace), "math.sqrt: ADD $g0, $g0, $g1");
+        assert_eq!(qualify_line_label("loop:", &namespace), "math.loop:");
+        assert_eq!(qualify_line_label("NOP", &namespace), "NOP");
+        assert_eq!(qualify_line_label("sqrt: NOP", &None), "sqrt: NOP");
+    }
+
+
+    #[test]
+    fn test_namespaces_in_source() {
+        let tokens = tokens_from_str("main:\n    JAL $g8, $g9, @math.square\n    HALT\n\n.namespace math\nsquare:\n    ADD $g0, $g0, $g0\n\
+            \x20   BEQ $g8, $g9, @math.done\ndone: JUMP $ra\n\ndata:\n    scale: .int 4\n");
+        let main = tokens[0].try_get_instr_tokens().unwrap();
+        assert_eq!(main.label.unwrap(), "main");
+        assert_eq!(main.op_label.unwrap(), "@math.square");
+
+        assert_eq!(tokens[2].try_get_instr_tokens().unwrap().label.unwrap(), "math.square");
+        assert_eq!(tokens[4].try_get_instr_tokens().unwrap().label.unwrap(), "math.done");
+        assert_eq!(tokens[5].try_get_data_tokens().unwrap().label.unwrap(), "math.scale");
+    }
+}
@@ -0,0 +1,278 @@
+use crate::errors::AsmValidationError;
+use crate::token_types::{FileTokens, OrgTokens};
+use crate::validation::{remove_label, validate_int_immediate};
+
+
+/// Takes a line of assembly and returns true if it is an `.org` directive, such as `.org 0x0100`
+pub fn is_org_directive(line:&str) -> bool {
+    remove_label(line).split_whitespace().next() == Some(".org")
+}
+
+
+/// Takes a line of assembly holding an `.org` directive in the form `.org <address>` and the section it is
+/// in, and returns its tokens, or an `AsmValidationError` if the address is missing or invalid. An `.org`
+/// cannot have a label, as the label would be ambiguous between the start and end of the gap, so a label
+/// should go on the line after it instead.
+pub fn parse_org_directive(line:&str, mode:char) -> Result<OrgTokens, AsmValidationError> {
+    if line.contains(':') {
+        return Err(AsmValidationError(format!(
+            "The .org on line {} cannot have a label - put the label on the line after it instead", line
+        )));
+    }
+
+    let tokens:Vec<&str> = line.split_whitespace().collect();
+    if tokens.len() != 2 {
+        return Err(AsmValidationError(format!("Incorrect format for .org on line {}, which should be .org <address>", line)));
+    }
+
+    Ok(OrgTokens::new(mode, validate_int_immediate(tokens[1], 32, false)?, None))
+}
+
+
+/// Takes a line of assembly and returns true if it is an `.align` directive, such as `.align 2`
+pub fn is_align_directive(line:&str) -> bool {
+    remove_label(line).split_whitespace().next() == Some(".align")
+}
+
+
+/// Takes a line of assembly holding an `.align` directive in the form `.align <words>` and the section it is
+/// in, and returns its tokens, or an `AsmValidationError` if the number of words is missing, is 0, or does
+/// not fit in 16 bits. Like an `.org`, an `.align` cannot have a label.
+pub fn parse_align_directive(line:&str, mode:char) -> Result<OrgTokens, AsmValidationError> {
+    if line.contains(':') {
+        return Err(AsmValidationError(format!(
+            "The .align on line {} cannot have a label - put the label on the line after it instead", line
+        )));
+    }
+
+    let tokens:Vec<&str> = line.split_whitespace().collect();
+    if tokens.len() != 2 {
+        return Err(AsmValidationError(format!("Incorrect format for .align on line {}, which should be .align <words>", line)));
+    }
+
+    let alignment = validate_int_immediate(tokens[1], 16, false)?;
+    if alignment == 0 {
+        return Err(AsmValidationError(format!("The .align on line {} must be to a multiple of at least 1 word", line)));
+    }
+
+    Ok(OrgTokens::align(mode, alignment))
+}
+
+
+/// Takes a line of assembly and returns true if it pins its label to an address with `.at`, such as
+/// `status: .at 0x2000 .int 0`
+pub fn is_at_directive(line:&str) -> bool {
+    remove_label(line).split_whitespace().next() == Some(".at")
+}
+
+
+/// Takes a line of assembly pinning its label to an address in the form `<label>: .at <address> <item>` and
+/// the section it is in, and returns the tokens of an `.org` which moves the item to that address, along
+/// with the line without the `.at` so that the item can be assembled as normal. The item can be left out,
+/// in which case the label is given to the next item. Returns an `AsmValidationError` if there is no label
+/// or the address is invalid.
+pub fn split_at_directive(line:&str, mode:char) -> Result<(OrgTokens, String), AsmValidationError> {
+    let label = match line.find(':') {
+        Some(index) => line[..index].trim(),
+        None => {
+            return Err(AsmValidationError(format!("The .at on line {} must have a label to pin, such as status: .at 0x2000 .int 0", line)));
+        }
+    };
+
+    let mut tokens = remove_label(line).splitn(3, char::is_whitespace).skip(1);
+    let address = match tokens.next() {
+        Some(address) => validate_int_immediate(address.trim(), 32, false)?,
+        None => {
+            return Err(AsmValidationError(format!("Incorrect format for .at on line {}, which should be <label>: .at <address> <item>", line)));
+        }
+    };
+
+    let item = tokens.next().unwrap_or("").trim();
+    let line = if item.is_empty() { format!("{}:", label) } else { format!("{}: {}", label, item) };
+    Ok((OrgTokens::new(mode, address, Some(label.to_owned())), line))
+}
+
+
+/// The name of each section as given to `--section-start`, with the character it is held as in the tokens
+pub const SECTION_NAMES:[(&str, char); 4] = [("code", 'c'), ("data", 'd'), ("text", 't'), ("bss", 'b')];
+
+
+/// Takes the tokens of a program and the address each section given to `--section-start` should start at,
+/// with each section given once, and returns the tokens with an `.org` to that address in front of the first
+/// item of each of those sections, so the section and every section after it are moved as if the `.org` were
+/// in the source. The `.org` names the section, so a start before the address the section would otherwise
+/// reach is reported as the section not being placeable. A section with no items is left alone.
+pub fn place_sections(tokens:Vec<FileTokens>, starts:&[(char, i64)]) -> Vec<FileTokens> {
+    let mut placed:Vec<char> = Vec::new();
+    let mut result:Vec<FileTokens> = Vec::with_capacity(tokens.len() + starts.len());
+    for token in tokens {
+        if token.is_zero_width() {
+            result.push(token);
+            continue;
+        }
+
+        let section = match &token {
+            FileTokens::DataTokens(_) => 'd',
+            FileTokens::TextTokens(_) => 't',
+            FileTokens::BssTokens(_) => 'b',
+            FileTokens::OrgTokens(t) => t.section,
+            _ => 'c'
+        };
+
+        let start = starts.iter().find(|(start, _)| *start == section);
+        if let Some((_, address)) = start.filter(|_| !placed.contains(&section)) {
+            let name = SECTION_NAMES.iter().find(|(_, s)| *s == section).unwrap().0;
+            result.push(FileTokens::OrgTokens(OrgTokens::new(section, *address, Some(format!("The {} section", name)))));
+            placed.push(section);
+        }
+
+        result.push(token);
+    }
+
+    result
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::generate_code::generate_binary_bytes;
+    use crate::label_table::{generate_label_table, PAGE_SIZE};
+    use crate::org::*;
+    use crate::pseudo_substitution::substitute_pseudo_instrs;
+    use crate::testing::{tokens_from_str, ORG_PROGRAM};
+    use crate::assemble_source;
+
+
+    #[test]
+    fn test_parse_org_directive() {
+        assert!(is_org_directive(".org 0x100"));
+        assert!(!is_org_directive("origin: .int 5"));
+
+        let tokens = parse_org_directive(".org 0x100", 'd').unwrap();
+        assert_eq!(tokens.section, 'd');
+        assert_eq!(tokens.address, 0x100);
+    }
+
+
+    #[test]
+    fn test_parse_align_directive() {
+        assert!(is_align_directive(".align 2"));
+        assert!(!is_align_directive("aligned: .int 5"));
+
+        let tokens = parse_align_directive(".align 4", 'd').unwrap();
+        assert_eq!((tokens.section, tokens.alignment), ('d', Some(4)));
+        assert_eq!(tokens.target(0x1001), 0x1004);
+        assert_eq!(tokens.target(0x1004), 0x1004);
+
+        assert!(parse_align_directive(".align 0", 'd').is_err());
+        assert!(parse_align_directive("start: .align 2", 'd').is_err());
+        assert!(parse_align_directive(".align", 'd').is_err());
+    }
+
+
+    #[test]
+    fn test_align_in_file() {
+        let tokens = substitute_pseudo_instrs(tokens_from_str("init:\n    ADDI $g0, $zero, 1\n    HALT\n.align 4\nhandler:\n    NOP\n    JUMP $ra\n\n\
+            data:\n    flag: .int 1\n.align 2\n    big: .long 100000\n.align 2\n    pi: .float 3.14159265359\n"));
+        let label_table = generate_label_table(&tokens).unwrap();
+        assert_eq!(label_table["handler"], 0x0004);
+        assert_eq!(label_table["flag"], 0x1000);
+        assert_eq!(label_table["big"], 0x1002);
+        assert_eq!(label_table["pi"], 0x1004);
+
+        let binary = generate_binary_bytes(&tokens, false, 0xFFFF, PAGE_SIZE).unwrap();
+        let data = binary.windows(6).position(|window| window == b"data:\0").unwrap() + 6;
+        assert_eq!(binary[data..data + 8], [0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0xA0, 0x86]);
+    }
+
+
+    #[test]
+    fn test_code_ending_with_align() {
+        // the padding of an .align at the end of a program with no data is not data the code falls through into
+        let binary = assemble_source("init:\n    ADDI $g0, $zero, 1\n    ADDI $g1, $zero, 2\n    ADDI $g2, $zero, 3\n.align 4\n").unwrap();
+        assert_eq!(binary[binary.len() - 4..], [0x03, 0x33, 0x00, 0x00]);
+    }
+
+
+    #[test]
+    fn test_split_at_directive() {
+        assert!(is_at_directive("status: .at 0x2000 .int 5"));
+        assert!(!is_at_directive("status: .int 5"));
+
+        let (tokens, line) = split_at_directive("status: .at 0x2000 .int 5", 'd').unwrap();
+        assert_eq!((tokens.section, tokens.address), ('d', 0x2000));
+        assert_eq!(tokens.pinned_label.unwrap(), "status");
+        assert_eq!(line, "status: .int 5");
+
+        let (_, line) = split_at_directive("handler: .at 0x10", 'c').unwrap();
+        assert_eq!(line, "handler:");
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_at_without_label() {
+        split_at_directive(".at 0x2000 .int 5", 'd').unwrap();
+    }
+
+
+    #[test]
+    fn test_at_in_file() {
+        let tokens = substitute_pseudo_instrs(tokens_from_str("init:\n    ADDI $g0, $zero, 1\n    HALT\nhandler: .at 0x20 NOP\n    JUMP $ra\n\n\
+            data:\n    count: .int 3\n    status: .at 0x1010 .int 0\n    control: .int 0xFF\n"));
+        let label_table = generate_label_table(&tokens).unwrap();
+        assert_eq!(label_table["handler"], 0x0020);
+        assert_eq!(label_table["status"], 0x1010);
+        assert_eq!(label_table["control"], 0x1011);
+    }
+
+
+    #[test]
+    #[should_panic(expected = "status")]
+    fn test_at_cannot_be_placed() {
+        let tokens = substitute_pseudo_instrs(tokens_from_str("init: HALT\n\ndata:\n    table: .section 32 [1, 2, 3]\n    status: .at 0x1010 .int 0\n"));
+        generate_label_table(&tokens).unwrap();
+    }
+
+
+    #[test]
+    fn test_place_sections() {
+        let source = "init:\n    HALT\ndata:\ncount: .int 5\ntext:\ngreeting: .asciiz \"hi\"\n";
+        let tokens = place_sections(substitute_pseudo_instrs(tokens_from_str(source)), &[('d', 0x4000), ('c', 0x0100)]);
+        let label_table = generate_label_table(&tokens).unwrap();
+        assert_eq!((label_table["init"], label_table["count"], label_table["greeting"]), (0x0100, 0x4000, 0x5000));
+
+        // there is no bss section to move
+        assert_eq!(place_sections(tokens_from_str(source), &[('b', 0x8000)]).len(), tokens_from_str(source).len());
+
+        let tokens = place_sections(substitute_pseudo_instrs(tokens_from_str(source)), &[('d', 0x0800)]);
+        assert_eq!(generate_label_table(&tokens).unwrap_err().0,
+            "The data section cannot be placed at 0x00000800 as the section has already reached 0x00001000");
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_org_with_label() {
+        parse_org_directive("start: .org 0x100", 'c').unwrap();
+    }
+
+
+    #[test]
+    fn test_org_in_file() {
+        let tokens = substitute_pseudo_instrs(tokens_from_str(ORG_PROGRAM));
+        let label_table = generate_label_table(&tokens).unwrap();
+        assert_eq!(label_table["init"], 0x0000);
+        assert_eq!(label_table["handler"], 0x0010);
+        assert_eq!(label_table["first"], 0x1000);
+        assert_eq!(label_table["table"], 0x1020);
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_org_moving_backwards() {
+        let tokens = substitute_pseudo_instrs(tokens_from_str("init:\n    ADDI $g0, $zero, 1\n    ADDI $g1, $zero, 2\n.org 0x1\n    HALT\n"));
+        generate_label_table(&tokens).unwrap();
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use crate::dump::{header_length, split_binary};
+use crate::errors::{BinaryFormatError, PartialAssemblyError};
+use crate::pseudo_substitution::substitute_labels;
+use crate::token_types::FileTokens;
+
+
+/// Represents a word in an assembled fragment whose immediate was derived from a label, so that the
+/// fragment can be patched again if the label moves.
+#[derive(Debug, Clone)]
+pub struct Fixup {
+    pub address: i64,
+    pub opcode: String,
+    pub label: String
+}
+
+
+/// Takes a stream of tokens which has already had pseudo-instructions substituted and returns the index
+/// of the instruction carrying the given label, or a `PartialAssemblyError` if there is no such
+/// instruction.
+fn find_labelled_instr(tokens:&[FileTokens], label:&str) -> Result<usize, PartialAssemblyError> {
+    for (index, token) in tokens.iter().enumerate() {
+        if let FileTokens::InstrTokens(t) = token {
+            if t.label.as_deref() == Some(label) {
+                return Ok(index);
+            }
+        }
+    }
+
+    Err(PartialAssemblyError(format!("The label {} does not mark an instruction in the source file", label)))
+}
+
+
+/// Takes a stream of tokens which has already had pseudo-instructions substituted, the start and end
+/// labels of a region, and the label table read from the map file of a previous full build, and returns
+/// the region's tokens with all labels substituted along with the fixups needed to relocate them.
+///
+/// The region runs from the instruction labelled `start` up to, but not including, the instruction
+/// labelled `end`, and is placed at the address `start` has in the map file. Labels inside the region
+/// take their new addresses, whilst all other labels are resolved from the map file. Will return a
+/// `PartialAssemblyError` if the region contains data or is larger than the region in the map file.
+pub fn assemble_label_range(tokens:Vec<FileTokens>, start:&str, end:&str,
+    base_map:&HashMap<String, i64>) -> Result<(Vec<FileTokens>, Vec<Fixup>), Box<dyn Error>> {
+        let start_index = find_labelled_instr(&tokens, start)?;
+        let end_index = find_labelled_instr(&tokens, end)?;
+        if end_index <= start_index {
+            return Err(Box::new(PartialAssemblyError(format!("The label {} does not come after {}", end, start))));
+        }
+
+        let (start_addr, end_addr) = match (base_map.get(start), base_map.get(end)) {
+            (Some(start_addr), Some(end_addr)) => (*start_addr, *end_addr),
+            _ => {
+                return Err(Box::new(PartialAssemblyError(format!(
+                    "The labels {} and {} must both be in the map file", start, end))));
+            }
+        };
+
+        let fragment:Vec<FileTokens> = tokens[start_index..end_index].to_vec();
+        let fragment_len:i64 = fragment.len().try_into().unwrap();
+        if fragment_len > end_addr - start_addr {
+            return Err(Box::new(PartialAssemblyError(format!(
+                "The region {}..{} is now {} words long, but only {} words are available in the map file",
+                start, end, fragment_len, end_addr - start_addr))));
+        }
+
+        // labels inside the region are relative to its start, all others come from the previous build
+        let mut label_table = base_map.clone();
+        let mut fixups:Vec<Fixup> = Vec::new();
+        for (addr, token) in (start_addr..).zip(&fragment) {
+            let t = match token.try_get_instr_tokens() {
+                Ok(t) => t,
+                Err(_) => {
+                    return Err(Box::new(PartialAssemblyError(format!(
+                        "The region {}..{} contains data, only instructions can be assembled on their own", start, end))));
+                }
+            };
+
+            if let Some(label) = t.label {
+                label_table.insert(label, addr);
+            }
+
+            if let Some(op_label) = t.op_label {
+                fixups.push(Fixup { address: addr, opcode: t.opcode, label: op_label });
+            }
+        }
+
+        let fragment = substitute_labels(fragment, &label_table)?;
+        Ok((fragment, fixups))
+}
+
+
+/// Takes a list of fixups and writes them to the given file, one per line, as the address of the word
+/// to patch, its opcode, and the label operand it was generated from.
+pub fn write_fixups_file(filename:&str, fixups:&[Fixup]) -> Result<(), Box<dyn Error>> {
+    let mut fixups_file = BufWriter::new(
+        OpenOptions::new().create(true).write(true).truncate(true).open(filename)?);
+
+    for fixup in fixups {
+        writeln!(fixups_file, "{:08X} {:<5} {}", fixup.address, fixup.opcode, fixup.label)?;
+    }
+
+    fixups_file.flush()?;
+    Ok(())
+}
+
+
+/// Takes the bytes of the binary of a full build, the bytes of a fragment assembled with `--only`, and the
+/// address the fragment starts at, and returns the binary with the words of the fragment written over the
+/// words of the code section from that address. Will return a `BinaryFormatError` if the fragment has a
+/// header or is not a whole number of words, or if it does not fit in the code section of the binary.
+pub fn patch_fragment(base:&[u8], fragment:&[u8], address:i64) -> Result<Vec<u8>, BinaryFormatError> {
+    if header_length(fragment)? != 0 || !fragment.len().is_multiple_of(2) {
+        return Err(BinaryFormatError("the fragment should be the words of the instructions alone, as written by --only".to_owned()));
+    }
+
+    let code_len = split_binary(base)?.code.len() as i64;
+    let fragment_len = (fragment.len() / 2) as i64;
+    if address < 0 || address + fragment_len > code_len {
+        return Err(BinaryFormatError(format!(
+            "the fragment of {} words at {:#06X} does not fit in the code section, which is {} words long",
+            fragment_len, address, code_len
+        )));
+    }
+
+    let start = header_length(base)? + address as usize * 2;
+    let mut patched = base.to_vec();
+    patched[start..start + fragment.len()].copy_from_slice(fragment);
+    Ok(patched)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::pseudo_substitution::substitute_pseudo_instrs;
+    use crate::map_file::read_map_file;
+    use crate::partial_assembly::{assemble_label_range, patch_fragment};
+    use crate::testing::tokens_from_str;
+
+
+    /// The program whose map is `test_files/test_partial_assembly.map`
+    const SOURCE:&str = "init:\n    ADDI $g0, $zero, 1\n    ADDI $g1, $zero, 2\n    ADD $g2, $g0, $g1\n    HALT\n\n\
+        double:\n    ADD $g0, $g0, $g0\n    LOAD $g1, $g8, $g9, @value\n    JUMP $g8, $g9, @double\ndouble_end:\n    HALT\n\
+        exit: HALT\n\ndata:\n    value: .int 5\n";
+
+    #[test]
+    fn test_assemble_label_range() {
+        let tokens = tokens_from_str(SOURCE);
+        let tokens = substitute_pseudo_instrs(tokens);
+        let base_map = read_map_file("test_files/test_partial_assembly.map").unwrap();
+        let (fragment, fixups) = assemble_label_range(tokens, "double", "double_end", &base_map).unwrap();
+
+        assert_eq!(fragment.len(), 9);
+        assert_eq!(fragment[2].try_get_instr_tokens().unwrap().immediate, Some(0x10));
+        assert_eq!(fragment[6].try_get_instr_tokens().unwrap().immediate, Some(0x03));
+
+        assert_eq!(fixups.len(), 6);
+        assert_eq!(fixups[0].address, 0x0004);
+        assert_eq!(fixups[0].opcode, "MOVLI");
+        assert_eq!(fixups[0].label, "l@value");
+        assert_eq!(fixups[5].address, 0x000A);
+        assert_eq!(fixups[5].label, "l@double");
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_label_range_too_large() {
+        let tokens = tokens_from_str(SOURCE);
+        let tokens = substitute_pseudo_instrs(tokens);
+        let base_map = read_map_file("test_files/test_partial_assembly.map").unwrap();
+        let _ = assemble_label_range(tokens, "init", "double", &base_map).unwrap();
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_label_range_backwards() {
+        let tokens = tokens_from_str(SOURCE);
+        let tokens = substitute_pseudo_instrs(tokens);
+        let base_map = read_map_file("test_files/test_partial_assembly.map").unwrap();
+        let _ = assemble_label_range(tokens, "double_end", "double", &base_map).unwrap();
+    }
+
+
+    #[test]
+    fn test_patch_fragment() {
+        let base = crate::assemble_source("init:\n    NOP\n    NOP\n    HALT\ndata:\n    value: .int 5\n").unwrap();
+        let patched = patch_fragment(&base, &[0x12, 0x13, 0x23, 0x11], 1).unwrap();
+        let code = crate::dump::split_binary(&patched).unwrap();
+        assert_eq!(code.code, vec![0x0000, 0x1312, 0x1123]);
+        assert_eq!(code.data, vec![5]);
+
+        assert!(patch_fragment(&base, &[0x12, 0x13, 0x23, 0x11], 2).is_err());
+        assert!(patch_fragment(&base, &[0x12, 0x13, 0x23], 0).is_err());
+        assert!(patch_fragment(&base, &base, 0).is_err());
+    }
+}
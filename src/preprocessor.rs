@@ -0,0 +1,516 @@
+use std::collections::HashMap;
+use crate::constants::{is_constant_definition, is_constant_name, parse_constant_definition};
+use crate::encoding::InputEncoding;
+use crate::errors::AsmValidationError;
+use crate::lints::ForbiddenOpcodes;
+use crate::suggestions::SUGGESTION_PREFIX;
+use crate::token_types::SourcePos;
+use crate::validation::validate_int_immediate;
+
+
+/// Represents a logical line of assembly, which may be made up of several lines of the source file joined
+/// by continuations, along with the number of the line in the source file that it starts on, and the file
+/// it was spliced in from by an `.include`, which is `None` for a line of the file being assembled.
+#[derive(Debug, Clone)]
+pub struct SourceLine {
+    pub line_num: usize,
+    pub text: String,
+    pub file: Option<String>
+}
+
+
+impl SourceLine {
+    /// Returns the column the statement on the line starts at, counting from 1
+    pub fn column(&self) -> usize {
+        self.text.chars().take_while(|c| c.is_whitespace()).count() + 1
+    }
+
+
+    /// Returns the line number and the column the statement on the line starts at, in the form `12:5`, with
+    /// the file in front if the line was included from another file, such as `macros.asm:12:5`
+    pub fn location(&self) -> String {
+        match &self.file {
+            Some(file) => format!("{}:{}:{}", file, self.line_num, self.column()),
+            None => format!("{}:{}", self.line_num, self.column())
+        }
+    }
+
+
+    /// Takes the name of the file being assembled and returns the position of the statement on the line,
+    /// which is in the file it was included from if it was spliced in by an `.include`
+    pub fn pos(&self, name:&str) -> SourcePos {
+        SourcePos { file: self.file.as_deref().unwrap_or(name).to_owned(), line: self.line_num, column: self.column() }
+    }
+
+
+    /// Takes the message of an error about the line and returns the column of the first word of the line
+    /// which the message names, or of the start of the statement if it names none of them. A label operand is
+    /// named by its label without the `@`. As many messages quote the whole line, the line itself is removed
+    /// from the message before it is searched.
+    pub fn error_column(&self, message:&str) -> usize {
+        let code = &self.text[..self.text.find(';').unwrap_or(self.text.len())];
+
+        // a suggested replacement may be another word of the line, which is not what the error is about
+        let message = message.split(SUGGESTION_PREFIX).next().unwrap_or_default();
+        let message = message.replace(code.trim(), "");
+        let is_word_char = |c:char| c.is_alphanumeric() || c == '_';
+        for word in code.split(|c:char| c.is_whitespace() || c == ',') {
+            let start = word.as_ptr() as usize - code.as_ptr() as usize;
+            let word = word.trim_end_matches(':').trim_start_matches('@');
+            let named = !word.is_empty() && message.match_indices(word).any(|(index, _)| {
+                !message[..index].ends_with(is_word_char) && !message[index + word.len()..].starts_with(is_word_char)
+            });
+
+            if named {
+                return code[..start].chars().count() + 1;
+            }
+        }
+
+        self.column()
+    }
+
+
+    /// Takes an error about the line and returns it with the line and column it is about in front, such as
+    /// `12:9: $g12 is not a valid register`, and the file as well if the line was included from another one
+    pub fn locate(&self, e:AsmValidationError) -> AsmValidationError {
+        let file = self.file.as_ref().map(|file| format!("{}:", file)).unwrap_or_default();
+        AsmValidationError(format!("{}{}:{}: {}", file, self.line_num, self.error_column(&e.0), e.0))
+    }
+}
+
+
+/// Takes the lines of a source file as read by `encoding::read_lines`, which already removes `\n` and
+/// `\r\n` endings, and returns them with the UTF-8 byte order mark some Windows editors save at the start
+/// of a file removed, and with any line ending in a lone `\r`, as saved by old Mac editors, split into
+/// separate lines.
+/// This lets files saved on any platform, or edited on several, be read the same way.
+pub fn normalize_line_endings(lines:Vec<String>) -> Vec<String> {
+    let mut normalized:Vec<String> = Vec::with_capacity(lines.len());
+    for (index, line) in lines.into_iter().enumerate() {
+        let line = match line.strip_prefix('\u{FEFF}') {
+            Some(line) if index == 0 => line,
+            _ => &line
+        };
+
+        normalized.extend(line.split('\r').map(|line| line.to_owned()));
+    }
+
+    normalized
+}
+
+
+/// Takes the lines of a source file and joins any line ending with a backslash onto the line after it, so
+/// that long directives such as `.section` arrays, and invocations of macros with many arguments, can be
+/// wrapped over several lines, as the lines are joined before macros are expanded. The backslash itself
+/// is removed, along with any indentation on the next line, but whitespace before the backslash is kept so
+/// that strings can be split between words.
+///
+/// Every logical line keeps the line number it starts on so that diagnostics point at the right place. Will
+/// return an `AsmValidationError` if the last line of the file ends with a continuation.
+pub fn join_continuation_lines(lines:Vec<String>) -> Result<Vec<SourceLine>, AsmValidationError> {
+    let mut source_lines:Vec<SourceLine> = Vec::new();
+    let mut continued:Option<SourceLine> = None;
+    for (index, line) in lines.into_iter().enumerate() {
+        let line = line.trim_end();
+        let (text, continues) = match line.strip_suffix('\\') {
+            Some(text) => (text, true),
+            None => (line, false)
+        };
+
+        let source_line = match continued.take() {
+            Some(mut source_line) => {
+                source_line.text.push_str(text.trim_start());
+                source_line
+            },
+            None => SourceLine { line_num: index + 1, text: text.to_owned(), file: None }
+        };
+
+        if continues {
+            continued = Some(source_line);
+        } else {
+            source_lines.push(source_line);
+        }
+    }
+
+    if let Some(source_line) = continued {
+        return Err(AsmValidationError(format!(
+            "{}: the line is continued with a backslash, but the file ends before the next line", source_line.location()
+        )));
+    }
+
+    Ok(source_lines)
+}
+
+
+/// Represents the configuration a program is assembled in, made up of the target given by `--target` and the
+/// constants given by `-D`, which its `.if` blocks can test, the encoding given by `--input-encoding`, and
+/// the opcodes forbidden by `--forbid`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Configuration {
+    /// The name of the board or configuration being assembled for, which `TARGET` is compared with
+    pub target: Option<String>,
+    /// The constants defined before the source is read, in the order they were given, which are defined in
+    /// every file as if with `.equ` on its first line
+    pub definitions: Vec<(String, i64)>,
+    /// The encoding every source file is read in, including any it includes
+    pub encoding: InputEncoding,
+    /// The opcodes which may not be used, which are checked in every file as it is written
+    pub forbidden: Vec<ForbiddenOpcodes>
+}
+
+
+impl Configuration {
+    /// Takes the target being assembled for and returns its configuration, with no constants defined
+    pub fn for_target(target:Option<&str>) -> Configuration {
+        Configuration { target: target.map(|target| target.to_owned()), ..Configuration::default() }
+    }
+}
+
+
+/// The operators a condition can compare a constant with a value by
+const COMPARISONS:[&str; 6] = ["==", "!=", "<=", ">=", "<", ">"];
+
+
+/// Takes an operand of a condition, which is an integer or the name of a constant defined earlier in the file,
+/// and the constants defined so far, and returns its value, or an `AsmValidationError` if it is neither.
+fn condition_value(operand:&str, constants:&HashMap<String, i64>) -> Result<i64, AsmValidationError> {
+    if is_constant_name(operand) {
+        return constants.get(operand).copied()
+            .ok_or_else(|| AsmValidationError(format!("{} in the condition is not a defined constant", operand)));
+    }
+
+    validate_int_immediate(operand, 32, true)
+}
+
+
+/// Takes the condition of an `.if` directive, the target being assembled for, and the constants defined so
+/// far, and returns whether the condition holds. A condition compares the `TARGET` symbol with a name using
+/// `==` or `!=`, compares a constant with an integer or another constant using `==`, `!=`, `<`, `>`, `<=`, or
+/// `>=`, or is just the name of a constant, which holds if its value is not 0. Several comparisons can be
+/// joined with `||`, such as `TARGET == board-a || LOG_LEVEL >= 2`. If no target was given, `TARGET` does not
+/// equal any name.
+fn evaluate_condition(condition:&str, target:Option<&str>, constants:&HashMap<String, i64>) -> Result<bool, AsmValidationError> {
+    let mut result = false;
+    for comparison in condition.split("||") {
+        let tokens:Vec<&str> = comparison.split_whitespace().collect();
+        let invalid = || AsmValidationError(format!(
+            "{} is not a valid condition, which should be in the form TARGET == <name>, TARGET != <name>, \
+            <constant> <comparison> <value>, or <constant>", comparison.trim()
+        ));
+
+        result |= match tokens[..] {
+            ["TARGET", operator @ ("==" | "!="), name] => (target == Some(name)) == (operator == "=="),
+            [constant] if is_constant_name(constant) => condition_value(constant, constants)? != 0,
+            [constant, operator, value] if is_constant_name(constant) && COMPARISONS.contains(&operator) => {
+                let (constant, value) = (condition_value(constant, constants)?, condition_value(value, constants)?);
+                match operator {
+                    "==" => constant == value,
+                    "!=" => constant != value,
+                    "<=" => constant <= value,
+                    ">=" => constant >= value,
+                    "<" => constant < value,
+                    _ => constant > value
+                }
+            },
+            _ => return Err(invalid())
+        };
+    }
+
+    Ok(result)
+}
+
+
+/// Takes the name given to an `.ifdef` or `.ifndef` directive and the constants defined so far, and returns
+/// whether a constant with that name is defined, or an `AsmValidationError` if it is not a single name.
+fn evaluate_defined(directive:&str, name:&str, constants:&HashMap<String, i64>) -> Result<bool, AsmValidationError> {
+    if !is_constant_name(name) {
+        return Err(AsmValidationError(format!(
+            "Incorrect format for {} {}, which should be {} <constant>", directive, name, directive
+        )));
+    }
+
+    Ok(constants.contains_key(name))
+}
+
+
+/// Takes the logical lines of a source file and the configuration being assembled for, and returns only the
+/// lines which should be assembled in that configuration. Lines between `.if <condition>` and the matching `.else` or
+/// `.endif` are only kept if the condition holds, and lines between `.else` and `.endif` only if it does
+/// not. `.ifdef <constant>` and `.ifndef <constant>` open a block in the same way, which is kept if the
+/// constant is or is not defined. Conditional blocks can be nested, and the directives themselves are removed.
+///
+/// A condition can use the constants of the configuration and those defined with `.equ` or `.const` on the
+/// lines kept before it, so that a single definition such as `DEBUG: .equ 1` can switch between variants of
+/// a program. The definitions
+/// themselves are kept, and any which are invalid are reported when the lines are tokenized.
+///
+/// Will return an `AsmValidationError` if a condition is invalid, or if an `.else` or `.endif` does not
+/// match an `.if`, or if an `.if` is never closed.
+pub fn apply_conditionals(source_lines:Vec<SourceLine>, config:&Configuration) -> Result<Vec<SourceLine>, AsmValidationError> {
+    // each open block records where it started, whether it is active, and whether it has had an .else
+    let mut blocks:Vec<(String, bool, bool)> = Vec::new();
+    let mut constants:HashMap<String, i64> = config.definitions.iter().cloned().collect();
+    let mut kept_lines:Vec<SourceLine> = Vec::new();
+    for source_line in source_lines {
+        let text = source_line.text.trim();
+        let enclosing_active = blocks.last().map(|block| block.1).unwrap_or(true);
+        let condition = match text.split_once(' ') {
+            Some((".if", condition)) => Some(evaluate_condition(condition, config.target.as_deref(), &constants)),
+            Some((".ifdef", name)) => Some(evaluate_defined(".ifdef", name.trim(), &constants)),
+            Some((".ifndef", name)) => Some(evaluate_defined(".ifndef", name.trim(), &constants).map(|defined| !defined)),
+            _ => None
+        };
+
+        if let Some(condition) = condition {
+            // a condition inside a block which is not assembled is not checked, as its constants may never be defined
+            let active = enclosing_active && condition.map_err(|e| source_line.locate(e))?;
+            blocks.push((source_line.location(), active, false));
+        } else if text == ".else" {
+            let parent_active = blocks.len() < 2 || blocks[blocks.len() - 2].1;
+            match blocks.last_mut() {
+                Some(block) if !block.2 => {
+                    block.1 = parent_active && !block.1;
+                    block.2 = true;
+                },
+                _ => {
+                    return Err(AsmValidationError(format!("{}: .else does not match an .if", source_line.location())));
+                }
+            }
+        } else if text == ".endif" {
+            if blocks.pop().is_none() {
+                return Err(AsmValidationError(format!("{}: .endif does not match an .if", source_line.location())));
+            }
+        } else if enclosing_active {
+            if is_constant_definition(text) {
+                if let Ok((name, value)) = parse_constant_definition(text) {
+                    constants.insert(name, value);
+                }
+            }
+
+            kept_lines.push(source_line);
+        }
+    }
+
+    if let Some((location, _, _)) = blocks.last() {
+        return Err(AsmValidationError(format!("{}: .if is never closed with an .endif", location)));
+    }
+
+    Ok(kept_lines)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::preprocessor::*;
+    use crate::lang::LanguageLevel;
+    use crate::limits::{ResourceBudget, ResourceLimits};
+    use crate::warnings::WarningSink;
+    use crate::{process_reader_into_tokens_with_warnings, process_source_into_tokens};
+    use crate::testing::{tokens_from_str, FIBONACCI_PROGRAM};
+
+
+    fn to_lines(lines:&[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+
+    #[test]
+    fn test_join_continuation_lines() {
+        let lines = to_lines(&["data:", "list: .section 4 [1, 2, \\", "    3, 4]", "", "msg: .int 5"]);
+        let source_lines = join_continuation_lines(lines).unwrap();
+
+        assert_eq!(source_lines.len(), 4);
+        assert_eq!(source_lines[1].text, "list: .section 4 [1, 2, 3, 4]");
+        assert_eq!(source_lines[1].line_num, 2);
+        assert_eq!(source_lines[2].line_num, 4);
+        assert_eq!(source_lines[3].line_num, 5);
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_continuation_at_end_of_file() {
+        join_continuation_lines(to_lines(&["list: .section 4 [1, 2, \\"])).unwrap();
+    }
+
+
+    #[test]
+    fn test_continuation_in_source() {
+        let tokens = tokens_from_str("init: HALT\n\ndata:\n    list: .section 8 [1, 1, 2, 3, \\\n                      5, 8, 13, 21]\n\n\
+            text:\n    greeting: .text 20 \"Hello \\\n        world, again!\"\n");
+        assert_eq!(tokens.len(), 3);
+
+        let list = tokens[1].try_get_data_tokens().unwrap();
+        assert_eq!(list.label.unwrap(), "list");
+        assert_eq!(list.bytes, vec![1, 1, 2, 3, 5, 8, 13, 21]);
+
+        let text = tokens[2].try_get_text_tokens().unwrap();
+        assert_eq!(text.bytes.len(), 20);
+        assert_eq!(text.bytes[5], ' ' as u16);
+        assert_eq!(text.bytes[6], 'w' as u16);
+    }
+
+
+    #[test]
+    fn test_continued_macro_invocation() {
+        let source = ".macro FILL reg, a, b, c\n    ADDI \\reg, $zero, \\a\n    ADDI \\reg, \\reg, \\b\n    ADDI \\reg, \\reg, \\c\n.endm\n\
+            init:\n    FILL $g0, \\\n         1, \\\n         2, 3\n    FILL $g1, 4, 5,\\\n         BAD\n";
+        let err = process_source_into_tokens(source, None).unwrap_err();
+        // an error in the expansion points at the line the continued invocation starts on
+        assert!(err.0.starts_with("<source>:10:") && err.0.ends_with("BAD is not a defined constant"), "{}", err.0);
+
+        let tokens = process_source_into_tokens(&source.replace("BAD", "6"), None).unwrap();
+        let instrs:Vec<_> = tokens.iter().filter_map(|token| token.try_get_instr_tokens().ok()).collect();
+        assert_eq!(instrs.iter().map(|instr| instr.immediate.unwrap()).collect::<Vec<u64>>(), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(instrs[2].pos.as_ref().unwrap().line, 7);
+    }
+
+
+    #[test]
+    fn test_normalize_line_endings() {
+        let lines = to_lines(&["\u{FEFF}init:", "    NOP\r    HALT", "data:\r"]);
+        assert_eq!(normalize_line_endings(lines), to_lines(&["init:", "    NOP", "    HALT", "data:", ""]));
+        assert_eq!(normalize_line_endings(to_lines(&["init:", "\u{FEFF}NOP"]))[1], "\u{FEFF}NOP");
+    }
+
+
+    #[test]
+    fn test_mixed_line_endings_in_source() {
+        let endings = ["\r\n", "\n", "\r"];
+        let source:String = FIBONACCI_PROGRAM.lines().enumerate().map(|(index, line)| format!("{}{}", line, endings[index % 3])).collect();
+        let expected = tokens_from_str(FIBONACCI_PROGRAM);
+        let tokens = tokens_from_str(&format!("\u{FEFF}{}", source));
+        assert_eq!(tokens.len(), expected.len());
+        for (token, expected) in tokens.iter().zip(expected) {
+            assert!(token.compare_label(expected));
+        }
+
+        let instr = tokens[3].try_get_instr_tokens().unwrap();
+        assert_eq!(instr.label.as_deref(), Some("loop"));
+        assert_eq!(instr.pos.unwrap().to_string(), "<source>:7:5");
+    }
+
+
+    #[test]
+    fn test_apply_conditionals() {
+        let lines = to_lines(&[
+            ".if TARGET == board-a", "a", ".if TARGET != board-b", "a_not_b", ".endif", ".else", "other", ".endif",
+            ".if TARGET == board-b || TARGET == board-c", "b_or_c", ".endif", "always"
+        ]);
+
+        let kept = |target| -> Vec<String> {
+            apply_conditionals(join_continuation_lines(lines.clone()).unwrap(), &Configuration::for_target(target)).unwrap()
+                .into_iter().map(|line| line.text).collect()
+        };
+
+        assert_eq!(kept(Some("board-a")), vec!["a", "a_not_b", "always"]);
+        assert_eq!(kept(Some("board-c")), vec!["other", "b_or_c", "always"]);
+        assert_eq!(kept(None), vec!["other", "always"]);
+    }
+
+
+    #[test]
+    fn test_unbalanced_conditionals() {
+        let apply = |lines:&[&str]| apply_conditionals(join_continuation_lines(to_lines(lines)).unwrap(), &Configuration::default());
+        assert_eq!(apply(&["NOP", ".if TARGET == a", "NOP"]).unwrap_err().0, "2:1: .if is never closed with an .endif");
+        assert_eq!(apply(&[".endif"]).unwrap_err().0, "1:1: .endif does not match an .if");
+        assert!(apply(&[".if TARGET == a", ".else", ".else", ".endif"]).is_err());
+        assert!(apply(&[".if BOARD == a", ".endif"]).is_err());
+    }
+
+
+    #[test]
+    fn test_constant_conditionals() {
+        let kept = |lines:&[&str]| -> Vec<String> {
+            apply_conditionals(join_continuation_lines(to_lines(lines)).unwrap(), &Configuration::default()).unwrap()
+                .into_iter().map(|line| line.text).collect()
+        };
+
+        let lines = ["DEBUG: .equ 1", "LEVEL: .const 0x2", ".ifdef DEBUG", "debug", ".endif", ".ifndef RELEASE", "not_release",
+            ".else", "release", ".endif", ".if LEVEL >= 2 || TARGET == board-a", "verbose", ".endif", ".if LEVEL == DEBUG", "one",
+            ".else", "two", ".endif", ".if DEBUG", "on", ".endif"];
+        assert_eq!(kept(&lines), vec!["DEBUG: .equ 1", "LEVEL: .const 0x2", "debug", "not_release", "verbose", "two", "on"]);
+
+        // a constant defined inside a block which is not assembled is not defined
+        let lines = [".ifdef RELEASE", "DEBUG: .equ 0", ".endif", ".ifdef DEBUG", "debug", ".endif", "DEBUG: .equ 0", ".if DEBUG",
+            "on", ".else", "off", ".endif"];
+        assert_eq!(kept(&lines), vec!["DEBUG: .equ 0", "off"]);
+    }
+
+
+    #[test]
+    fn test_configuration_definitions() {
+        let config = Configuration { definitions: vec![("DEBUG".to_owned(), 1), ("PORT".to_owned(), 3)], ..Configuration::default() };
+        let source = "init:\n.ifdef DEBUG\n    OUT $g0, PORT\n.endif\n.if PORT == 4\n    HALT\n.endif\nPORT: .equ 4\n.if PORT == 4\n    OUT $g1, PORT\n.endif\n";
+        let tokens = process_reader_into_tokens_with_warnings(source.as_bytes(), "<source>", &config, false, LanguageLevel::default(),
+            &mut ResourceBudget::new(ResourceLimits::UNLIMITED), &mut WarningSink::default()).unwrap();
+
+        // a definition in the source replaces one from the configuration for the lines after it
+        let instrs:Vec<(String, Option<u64>)> = tokens.iter().filter_map(|token| token.try_get_instr_tokens().ok())
+            .map(|t| (t.opcode, t.immediate)).collect();
+        assert_eq!(instrs, vec![("OUT".to_owned(), Some(3)), ("OUT".to_owned(), Some(4))]);
+    }
+
+
+    #[test]
+    fn test_invalid_constant_conditionals() {
+        let apply = |lines:&[&str]| apply_conditionals(join_continuation_lines(to_lines(lines)).unwrap(), &Configuration::default());
+        assert_eq!(apply(&[".if DEBUG", ".endif"]).unwrap_err().0, "1:5: DEBUG in the condition is not a defined constant");
+        assert_eq!(apply(&[".ifdef DEBUG RELEASE", ".endif"]).unwrap_err().0,
+            "1:1: Incorrect format for .ifdef DEBUG RELEASE, which should be .ifdef <constant>");
+        assert!(apply(&["LEVEL: .equ 1", ".if LEVEL =< 2", ".endif"]).is_err());
+        assert!(apply(&["LEVEL: .equ 1", ".if LEVEL == two", ".endif"]).is_err());
+        assert!(apply(&["LEVEL: .equ 1", ".if 2 == LEVEL", ".endif"]).is_err());
+
+        // a condition inside a block which is not assembled is not checked
+        assert!(apply(&[".ifdef DEBUG", ".if LEVEL > 1", ".endif", ".endif"]).is_ok());
+    }
+
+
+    #[test]
+    fn test_debug_tracing_is_compiled_out() {
+        let source = |debug| format!("DEBUG: .equ {}\ninit:\n.if DEBUG\n    OUT $g0, 1\n.endif\n    HALT\n", debug);
+        let opcodes = |debug| -> Vec<String> {
+            process_source_into_tokens(&source(debug), None).unwrap().iter()
+                .filter_map(|token| token.try_get_instr_tokens().ok()).map(|t| t.opcode).collect()
+        };
+
+        assert_eq!(opcodes(1), vec!["OUT", "HALT"]);
+        assert_eq!(opcodes(0), vec!["HALT"]);
+    }
+
+
+    #[test]
+    fn test_conditionals_in_source() {
+        let source = "init:\n.if TARGET == board-a\n    OUT $g0, 2\n.else\n    OUT $g0, 5\n.endif\n    HALT\n\ndata:\n\
+            .if TARGET == board-a || TARGET == board-b\n    uart_base: .long 0x00A00000\n.endif\n    baud: .int 96\n";
+        let tokens = process_source_into_tokens(source, Some("board-a")).unwrap();
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(tokens[0].try_get_instr_tokens().unwrap().immediate, Some(2));
+        assert_eq!(tokens[2].try_get_data_tokens().unwrap().label.unwrap(), "uart_base");
+
+        let tokens = tokens_from_str(source);
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[0].try_get_instr_tokens().unwrap().immediate, Some(5));
+        assert_eq!(tokens[2].try_get_data_tokens().unwrap().label.unwrap(), "baud");
+    }
+
+
+    #[test]
+    fn test_error_column() {
+        let line = SourceLine { line_num: 12, text: "loop:   ADDI $g0, $g12, 4 ; add".to_owned(), file: None };
+        assert_eq!(line.error_column("$g12 is not a valid register"), 19);
+        assert_eq!(line.error_column("Unexpected number of operands on line loop:   ADDI $g0, $g12, 4"), 1);
+        assert_eq!(line.error_column("The label loop is already defined"), 1);
+        assert_eq!(line.error_column("$g1 is not a valid register"), 1);
+        assert_eq!(line.error_column("$g12 is not a valid register - did you mean $g0?"), 19);
+        assert_eq!(line.locate(AsmValidationError("4 is too large".to_owned())).0, "12:25: 4 is too large");
+
+        let line = SourceLine { line_num: 3, text: "    BAD $g0".to_owned(), file: None };
+        assert_eq!(line.location(), "3:5");
+
+        let line = SourceLine { file: Some("lib/macros.asm".to_owned()), ..line };
+        assert_eq!(line.location(), "lib/macros.asm:3:5");
+        assert_eq!(line.locate(AsmValidationError("BAD is not a valid opcode".to_owned())).0, "lib/macros.asm:3:5: BAD is not a valid opcode");
+    }
+}
@@ -0,0 +1,102 @@
+use crate::errors::AsmValidationError;
+
+
+/// The opcodes which can only be used in kernel code, as they access the IO ports directly
+const KERNEL_ONLY_OPCODES:[&str; 2] = ["IN", "OUT"];
+
+
+/// The privilege level of a section of code, which decides the instructions it is allowed to use. User code
+/// must go through the operating system with `syscall` to do any IO, while kernel code can use anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Privilege {
+    User,
+    Kernel
+}
+
+
+impl Privilege {
+    /// Gets the name of the privilege level as used in the `.privilege` directive
+    pub fn name(&self) -> &'static str {
+        match self {
+            Privilege::User => "user",
+            Privilege::Kernel => "kernel"
+        }
+    }
+}
+
+
+/// Takes a line of assembly and returns true if it sets the privilege level, such as `.privilege user`
+pub fn is_privilege_directive(line:&str) -> bool {
+    line.starts_with(".privilege ") || line == ".privilege"
+}
+
+
+/// Takes a line of assembly setting the privilege level in the form `.privilege <user|kernel>` and returns
+/// the privilege level, or an `AsmValidationError` if it is missing or is not a privilege level.
+pub fn parse_privilege_directive(line:&str) -> Result<Privilege, AsmValidationError> {
+    match line.split_whitespace().collect::<Vec<&str>>()[..] {
+        [_, "user"] => Ok(Privilege::User),
+        [_, "kernel"] => Ok(Privilege::Kernel),
+        _ => Err(AsmValidationError(format!(
+            "Incorrect format for privilege level on line {}, which should be .privilege user or .privilege kernel", line
+        )))
+    }
+}
+
+
+/// Takes the opcode of an instruction and the privilege level of the code it is in, and returns an
+/// `AsmValidationError` naming the privilege level if the instruction is not allowed at that level. Code
+/// which has not been given a privilege level can use any instruction.
+pub fn check_privilege(opcode:&str, privilege:Option<Privilege>) -> Result<(), AsmValidationError> {
+    if privilege == Some(Privilege::User) && KERNEL_ONLY_OPCODES.contains(&opcode) {
+        return Err(AsmValidationError(format!(
+            "{} is not allowed in {} code, as only kernel code can use {} - use syscall instead",
+            opcode, Privilege::User.name(), KERNEL_ONLY_OPCODES.join(" or ")
+        )));
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::privilege::*;
+    use crate::testing::tokens_from_str;
+
+
+    #[test]
+    fn test_parse_privilege_directive() {
+        assert!(is_privilege_directive(".privilege user"));
+        assert!(!is_privilege_directive("privilege: NOP"));
+        assert_eq!(parse_privilege_directive(".privilege user").unwrap(), Privilege::User);
+        assert_eq!(parse_privilege_directive(".privilege  kernel").unwrap(), Privilege::Kernel);
+        assert!(parse_privilege_directive(".privilege root").is_err());
+        assert!(parse_privilege_directive(".privilege").is_err());
+    }
+
+
+    #[test]
+    fn test_check_privilege() {
+        check_privilege("OUT", None).unwrap();
+        check_privilege("OUT", Some(Privilege::Kernel)).unwrap();
+        check_privilege("syscall", Some(Privilege::User)).unwrap();
+        assert_eq!(check_privilege("IN", Some(Privilege::User)).unwrap_err().0,
+            "IN is not allowed in user code, as only kernel code can use IN or OUT - use syscall instead");
+    }
+
+
+    #[test]
+    fn test_privilege_in_source() {
+        let tokens = tokens_from_str(".privilege kernel\nputc:\n    OUT $g0, 2\n    JUMP $ra\n\n.privilege user\nmain:\n    syscall 2\n\
+            \x20   ADDI $g0, $g0, 1\n    HALT\n\ndata:\n    count: .int 0\n");
+        assert_eq!(tokens.len(), 6);
+    }
+
+
+    #[test]
+    #[should_panic(expected = "<source>:8:5: OUT is not allowed in user code")]
+    fn test_privilege_violation() {
+        tokens_from_str(".privilege kernel\nputc:\n    OUT $g0, 2\n    JUMP $ra\n\n.privilege user\nmain:\n    OUT $g0, 2\n    HALT\n");
+    }
+}
@@ -1,5 +1,8 @@
-use crate::token_types::{FileTokens, InstrTokens};
+use crate::token_types::{located, FileTokens, InstrTokens, SourcePos};
 use crate::errors::LabelNotFoundError;
+use crate::limits::{ResourceBudget, ResourceLimits};
+use crate::suggestions::did_you_mean;
+use crate::validation::{parse_label_offset, split_label_offset};
 use std::collections::HashMap;
 
 
@@ -8,6 +11,14 @@ use std::collections::HashMap;
 /// `substitute_labels` function. If any single-operand branch instructions are found, then the 
 /// 1st operand is swapped to be the 2nd, and the 1st is turned into `None`.
 pub fn substitute_pseudo_instrs(tokens: Vec<FileTokens>) -> Vec<FileTokens> {
+    substitute_pseudo_instrs_with_budget(tokens, &mut ResourceBudget::new(ResourceLimits::UNLIMITED))
+}
+
+
+/// Takes the tokens of a program and the budget it is assembled under, and substitutes its pseudo-instructions
+/// as described for `substitute_pseudo_instrs`, counting every `LOAD`, `STORE`, or branch to a label which is
+/// expanded into several instructions as an expansion of the budget.
+pub fn substitute_pseudo_instrs_with_budget(tokens: Vec<FileTokens>, budget:&mut ResourceBudget) -> Vec<FileTokens> {
     let mut new_tokens:Vec<FileTokens> = Vec::new();
     for token in &tokens {
         match token {
@@ -15,15 +26,17 @@ pub fn substitute_pseudo_instrs(tokens: Vec<FileTokens>) -> Vec<FileTokens> {
                 match &t.op_label {
                     Some(operand) => {
                         if t.opcode == "LOAD" || t.opcode == "STORE" {
-                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(None, "MOVLI".to_owned(), t.operand_b.clone(), None, None, None, Some("l".to_string() + &*operand.clone()))));
-                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(None, "MOVUI".to_owned(), t.operand_b.clone(), None, None, None, Some("l".to_string() + &*operand.clone()))));
-                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(None, t.opcode.clone(), t.operand_a.clone(), t.operand_b.clone(), t.operand_c.clone(), None, None)));
+                            budget.count_expansions(1);
+                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(t.label.clone(), "MOVLI".to_owned(), t.operand_b.clone(), None, None, None, Some("l".to_string() + &*operand.clone())).with_pos(t.pos.clone())));
+                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(None, "MOVUI".to_owned(), t.operand_b.clone(), None, None, None, Some("l".to_string() + &*operand.clone())).with_pos(t.pos.clone())));
+                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(None, t.opcode.clone(), t.operand_a.clone(), t.operand_b.clone(), t.operand_c.clone(), None, None).with_pos(t.pos.clone())));
                         } else if t.opcode != "MOVLI" && t.opcode != "MOVUI" { // Branch opcodes
-                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(t.label.clone(), "MOVLI".to_owned(), t.operand_a.clone(), None, None, None, Some("u".to_string() + &*operand.clone()))));
-                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(None, "MOVUI".to_owned(), t.operand_a.clone(), None, None, None, Some("u".to_string() + &*operand.clone()))));
-                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(None, "MOVLI".to_owned(), t.operand_b.clone(), None, None, None, Some("l".to_string() + &*operand.clone()))));
-                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(None, "MOVUI".to_owned(), t.operand_b.clone(), None, None, None, Some("l".to_string() + &*operand.clone()))));
-                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(None, t.opcode.clone(), t.operand_a.clone(), t.operand_b.clone(), t.operand_c.clone(), None, None)));
+                            budget.count_expansions(1);
+                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(t.label.clone(), "MOVLI".to_owned(), t.operand_a.clone(), None, None, None, Some("u".to_string() + &*operand.clone())).with_pos(t.pos.clone())));
+                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(None, "MOVUI".to_owned(), t.operand_a.clone(), None, None, None, Some("u".to_string() + &*operand.clone())).with_pos(t.pos.clone())));
+                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(None, "MOVLI".to_owned(), t.operand_b.clone(), None, None, None, Some("l".to_string() + &*operand.clone())).with_pos(t.pos.clone())));
+                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(None, "MOVUI".to_owned(), t.operand_b.clone(), None, None, None, Some("l".to_string() + &*operand.clone())).with_pos(t.pos.clone())));
+                            new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(None, t.opcode.clone(), t.operand_a.clone(), t.operand_b.clone(), t.operand_c.clone(), None, None).with_pos(t.pos.clone())));
                         } else {
                             new_tokens.push(token.clone());
                         }
@@ -32,7 +45,7 @@ pub fn substitute_pseudo_instrs(tokens: Vec<FileTokens>) -> Vec<FileTokens> {
                         if t.opcode == "JUMP" || t.opcode == "BEQ" || t.opcode == "BNE" || t.opcode == "BLT" || t.opcode == "BGT" || t.opcode == "JAL" {
                             match &t.operand_b {
                                 Some(_) => new_tokens.push(token.clone()),
-                                None => new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(t.label.clone(), t.opcode.clone(), None, t.operand_a.clone(), None, None, None))),
+                                None => new_tokens.push(FileTokens::InstrTokens(InstrTokens::new(t.label.clone(), t.opcode.clone(), None, t.operand_a.clone(), None, None, None).with_pos(t.pos.clone()))),
                             }
                         } else {
                             new_tokens.push(FileTokens::InstrTokens(t.clone()));
@@ -47,6 +60,11 @@ pub fn substitute_pseudo_instrs(tokens: Vec<FileTokens>) -> Vec<FileTokens> {
 
             FileTokens::TextTokens(_) => {
                 new_tokens.push(token.clone());
+            },
+
+            FileTokens::BssTokens(_) | FileTokens::OrgTokens(_) | FileTokens::AliasTokens(_) | FileTokens::DeprecatedTokens(_) | FileTokens::TraceTokens(_)
+                | FileTokens::ExportTableTokens(_) => {
+                new_tokens.push(token.clone());
             }
         }
     }
@@ -55,20 +73,94 @@ pub fn substitute_pseudo_instrs(tokens: Vec<FileTokens>) -> Vec<FileTokens> {
 }
 
 
+/// Takes the position of an instruction with a label operand which is not in the label table, the label, and
+/// the table, and returns the error saying so, which suggests the closest label in the table if it looks
+/// like a typo
+fn missing_label_error(pos:Option<&SourcePos>, label:&str, label_table:&HashMap<String, i64>) -> String {
+    match did_you_mean(label, label_table.keys().map(|label| label.as_str())) {
+        suggestion if suggestion.is_empty() => format!("{}[E0404] The label {} was not found!", located(pos), label),
+        suggestion => format!("{}[E0404] The label {} was not found{}", located(pos), label, suggestion)
+    }
+}
+
+
+/// Takes the position of an instruction or data item, one of its label operands without the `@`, such as
+/// `table+8`, and the label table, and returns the address of the label with any offset added. Will return
+/// the error to give if the label is not in the table, or if the offset is not valid or makes the address
+/// negative.
+fn resolve_label_operand(pos:Option<&SourcePos>, operand:&str, label_table:&HashMap<String, i64>) -> Result<i64, String> {
+    let (label, offset) = split_label_offset(operand);
+    let addr = *label_table.get(label).ok_or_else(|| missing_label_error(pos, label, label_table))?;
+    let offset = match offset {
+        Some(offset) => parse_label_offset(offset).ok_or_else(|| {
+            format!("{}The offset of the label operand @{} is not an integer which fits into 32 bits", located(pos), operand)
+        })?,
+        None => 0
+    };
+
+    match addr + offset {
+        addr if addr < 0 => Err(format!("{}The label operand @{} is at the negative address {}", located(pos), operand, addr)),
+        addr => Ok(addr)
+    }
+}
+
+
 /// Takes a label table and a vector of `FileTokens` as arguments and returns a new vector which has,
-/// where appropriate, converted the label operands into immediates.
+/// where appropriate, converted the label operands into immediates. Will return a `LabelNotFoundError`
+/// giving the position of the instruction if a label operand is not in the label table, holding every
+/// missing label with one on each line.
 pub fn substitute_labels(tokens:Vec<FileTokens>, label_table:&HashMap<String, i64>) -> Result<Vec<FileTokens>, LabelNotFoundError> {
     let mut new_tokens:Vec<FileTokens> = Vec::new();
+    let mut errors:Vec<String> = Vec::new();
     for token in tokens {
         match token {
-            FileTokens::DataTokens(t) => {
-                new_tokens.push(FileTokens::DataTokens(t.clone()));
+            FileTokens::DataTokens(mut t) => {
+                // a word holding half of an address, such as in the table of a .switch, is 16 bits of it
+                for (word, op_label) in t.bytes.iter_mut().zip(&t.op_labels) {
+                    let (prefix, label) = op_label.split_once('@').unwrap_or(("", op_label));
+                    match resolve_label_operand(t.pos.as_ref(), label, label_table) {
+                        Ok(addr) if prefix == "u" => *word = (addr as u64 >> 16) as u16,
+                        Ok(addr) => *word = addr as u16,
+                        Err(error) => {
+                            if !errors.contains(&error) {
+                                errors.push(error);
+                            }
+                        }
+                    }
+                }
+
+                t.op_labels.clear();
+                new_tokens.push(FileTokens::DataTokens(t));
             },
 
             FileTokens::TextTokens(t) => {
                 new_tokens.push(FileTokens::TextTokens(t.clone()));
             },
 
+            FileTokens::BssTokens(t) => {
+                new_tokens.push(FileTokens::BssTokens(t.clone()));
+            },
+
+            FileTokens::OrgTokens(t) => {
+                new_tokens.push(FileTokens::OrgTokens(t.clone()));
+            },
+
+            FileTokens::AliasTokens(t) => {
+                new_tokens.push(FileTokens::AliasTokens(t.clone()));
+            },
+
+            FileTokens::DeprecatedTokens(t) => {
+                new_tokens.push(FileTokens::DeprecatedTokens(t.clone()));
+            },
+
+            FileTokens::TraceTokens(t) => {
+                new_tokens.push(FileTokens::TraceTokens(t.clone()));
+            },
+
+            FileTokens::ExportTableTokens(t) => {
+                new_tokens.push(FileTokens::ExportTableTokens(t.clone()));
+            },
+
             FileTokens::InstrTokens(mut t) => {
                 match t.op_label {
                     Some(label) => {
@@ -83,51 +175,30 @@ pub fn substitute_labels(tokens:Vec<FileTokens>, label_table:&HashMap<String, i6
                             label = label[1..].to_string();
                         }
 
-                        let new_imm:u64;
-                        if t.opcode == "MOVLI" {
-                            new_imm = match label_table.get(&label) {
-                                Some(addr) => {
-                                    let address;
-                                    if prefix == 'u' {
-                                        address = (*addr as u64 & 0x00FF_0000) >> 16;
-                                    } else {
-                                        address = *addr as u64 & 0x0000_00FF;
-                                    }
-
-                                    address
-                                },
-
-                                None => {
-                                    return Err(LabelNotFoundError(format!(
-                                        "The label {} was not found!", label))); 
-                                }
-                            }
+                        if t.opcode != "MOVLI" && t.opcode != "MOVUI" {
+                            errors.push(format!(
+                                "{}The instruction {} cannot take label operands!", located(t.pos.as_ref()), t.opcode));
+                            continue;
                         }
 
-                        else if t.opcode == "MOVUI" {
-                            new_imm = match label_table.get(&label) {
-                                Some(addr) => {
-                                    let address;
-                                    if prefix == 'u' {
-                                        address = (*addr as u64 & 0xFF00_0000) >> 24;
-                                    } else {
-                                        address = (*addr as u64 & 0x0000_FF00) >> 8;
-                                    }
-
-                                    address
-                                },
-
-                                None => {
-                                    return Err(LabelNotFoundError(format!(
-                                        "The label {} was not found!", label))); 
+                        let addr = match resolve_label_operand(t.pos.as_ref(), &label, label_table) {
+                            Ok(addr) => addr as u64,
+                            Err(error) => {
+                                // both halves of the address come from the same label, so it is only reported once
+                                if !errors.contains(&error) {
+                                    errors.push(error);
                                 }
+
+                                continue;
                             }
-                        }
+                        };
 
-                        else {
-                            return Err(LabelNotFoundError(format!(
-                                "The instruction {} cannot take label operands!", t.opcode)));
-                        }
+                        let new_imm = match (t.opcode.as_str(), prefix) {
+                            ("MOVLI", 'u') => (addr & 0x00FF_0000) >> 16,
+                            ("MOVLI", _) => addr & 0x0000_00FF,
+                            ("MOVUI", 'u') => (addr & 0xFF00_0000) >> 24,
+                            _ => (addr & 0x0000_FF00) >> 8
+                        };
 
                         t.immediate = Option::from(new_imm);
                         t.op_label = None;
@@ -141,14 +212,19 @@ pub fn substitute_labels(tokens:Vec<FileTokens>, label_table:&HashMap<String, i6
         }
     }
 
+    if !errors.is_empty() {
+        return Err(LabelNotFoundError(errors.join("\n")));
+    }
+
     Ok(new_tokens)
 }
 
 
 #[cfg(test)]
 mod tests {
-    use crate::process_file_into_tokens;
+    use crate::process_source_into_tokens;
     use crate::pseudo_substitution::{substitute_pseudo_instrs, substitute_labels};
+    use crate::testing::{tokens_from_str, FIBONACCI_PROGRAM, PSEUDO_INSTR_PROGRAM, SINGLE_OPERAND_BRANCH_PROGRAM};
     use crate::token_types::InstrTokens;
     use crate::label_table::generate_label_table;
 
@@ -166,7 +242,7 @@ mod tests {
 
     #[test]
     fn test_load_substitution() {
-        let tokens = process_file_into_tokens("test_files/test_expand_pseudoinstrs.asm");
+        let tokens = tokens_from_str(PSEUDO_INSTR_PROGRAM);
         let subbed_tokens = substitute_pseudo_instrs(tokens);
 
         let mut token = subbed_tokens[0].try_get_instr_tokens().unwrap();
@@ -187,7 +263,7 @@ mod tests {
 
     #[test]
     fn test_store_substitution() {
-        let tokens = process_file_into_tokens("test_files/test_expand_pseudoinstrs.asm");
+        let tokens = tokens_from_str(PSEUDO_INSTR_PROGRAM);
         let subbed_tokens = substitute_pseudo_instrs(tokens);
 
         let mut token = subbed_tokens[5].try_get_instr_tokens().unwrap();
@@ -203,7 +279,7 @@ mod tests {
 
     #[test]
     fn test_beq_substitution() {
-        let tokens = process_file_into_tokens("test_files/test_expand_pseudoinstrs.asm");
+        let tokens = tokens_from_str(PSEUDO_INSTR_PROGRAM);
         let subbed_tokens = substitute_pseudo_instrs(tokens);
 
         let mut token = subbed_tokens[9].try_get_instr_tokens().unwrap();
@@ -225,7 +301,7 @@ mod tests {
 
     #[test]
     fn test_bgt_substitution() {
-        let tokens = process_file_into_tokens("test_files/test_expand_pseudoinstrs.asm");
+        let tokens = tokens_from_str(PSEUDO_INSTR_PROGRAM);
         let subbed_tokens = substitute_pseudo_instrs(tokens);
 
         let mut token = subbed_tokens[14].try_get_instr_tokens().unwrap();
@@ -248,16 +324,25 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_non_existant_label() {
-        let tokens = process_file_into_tokens("test_files/test_detect_bad_label.asm");
+        let tokens = tokens_from_str(&FIBONACCI_PROGRAM.replace("@target", "@invalid"));
         let tokens = substitute_pseudo_instrs(tokens);
         let label_table = generate_label_table(&tokens).unwrap();
         let _tokens = substitute_labels(tokens, &label_table).unwrap();
     }
 
 
+    #[test]
+    fn test_missing_label_suggestion() {
+        let tokens = substitute_pseudo_instrs(process_source_into_tokens("init:\n    JUMP $g8, $g9, @lop\nloop:\n    JUMP $g8, $g9, @far\n    HALT\n", None).unwrap());
+        let label_table = generate_label_table(&tokens).unwrap();
+        let err = substitute_labels(tokens, &label_table).unwrap_err();
+        assert_eq!(err.0, "<source>:2:5: [E0404] The label lop was not found - did you mean loop?\n<source>:4:5: [E0404] The label far was not found!");
+    }
+
+
     #[test]
     fn test_label_substitution() {
-        let tokens = process_file_into_tokens("test_files/test_sub_label_addrs.asm");
+        let tokens = tokens_from_str(FIBONACCI_PROGRAM);
         let tokens = substitute_pseudo_instrs(tokens);
 
         let label_table = generate_label_table(&tokens).unwrap();
@@ -296,9 +381,32 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_label_offset_substitution() {
+        let source = "init:\n    MOVLI $g0, @table+0x102\n    MOVUI $g0, @table+0x102\n    MOVLI $g1, @table - 1\n    HALT\n\
+            data:\n    table: .int 1\n";
+        let tokens = substitute_pseudo_instrs(process_source_into_tokens(source, None).unwrap());
+        let label_table = generate_label_table(&tokens).unwrap();
+        let table = label_table["table"] as u64;
+        let tokens = substitute_labels(tokens, &label_table).unwrap();
+        assert_eq!(tokens[0].try_get_instr_tokens().unwrap().immediate, Some((table + 0x102) & 0xFF));
+        assert_eq!(tokens[1].try_get_instr_tokens().unwrap().immediate, Some(((table + 0x102) >> 8) & 0xFF));
+        assert_eq!(tokens[2].try_get_instr_tokens().unwrap().immediate, Some((table - 1) & 0xFF));
+
+        let tokens = substitute_pseudo_instrs(process_source_into_tokens("init:\n    MOVLI $g0, @init-1\n    MOVLI $g1, @tabl+8\n    HALT\n\
+            data:\n    table: .int 1\n", None).unwrap());
+        let err = substitute_labels(tokens.clone(), &generate_label_table(&tokens).unwrap()).unwrap_err();
+        assert_eq!(err.0, "<source>:2:5: The label operand @init-1 is at the negative address -1\n\
+            <source>:3:5: [E0404] The label tabl was not found - did you mean table?");
+
+        let err = process_source_into_tokens("init:\n    MOVLI $g0, @init+x\n    HALT\n", None).unwrap_err();
+        assert_eq!(err.0, "<source>:2:16: [E0403] The offset of the label operand @init+x on line MOVLI $g0, @init+x is not an integer which fits into 32 bits");
+    }
+
+
     #[test]
     fn test_single_operand_branch_substitution() {
-        let tokens = process_file_into_tokens("test_files/test_single_operand_branch_sub.asm");
+        let tokens = tokens_from_str(SINGLE_OPERAND_BRANCH_PROGRAM);
         let tokens = substitute_pseudo_instrs(tokens);
 
         let label_table = generate_label_table(&tokens).unwrap();
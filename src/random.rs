@@ -0,0 +1,27 @@
+/// Generates pseudo-random numbers with SplitMix64, which is used wherever the assembler needs randomness,
+/// so that the same seed always gives the same numbers on every platform
+pub struct SplitMix64(u64);
+
+
+impl SplitMix64 {
+    /// Takes a seed and returns a generator started from it
+    pub fn new(seed:u64) -> SplitMix64 {
+        SplitMix64(seed)
+    }
+
+
+    /// Returns the next number from the generator
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+
+    /// Returns a number from 0 up to, but not including, `bound`
+    pub fn below(&mut self, bound:usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
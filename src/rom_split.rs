@@ -0,0 +1,90 @@
+use std::error::Error;
+use std::fs;
+use crate::generate_code::generate_memory_image;
+use crate::token_types::FileTokens;
+
+
+/// Represents how a program binary is divided between two ROM chips
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RomSplit {
+    /// The low byte of every word goes to the first chip and the high byte to the second, for a pair of
+    /// 8-bit chips which together make up the 16-bit data bus
+    Bytes,
+
+    /// Words at even addresses go to the first chip and words at odd addresses go to the second, for a
+    /// pair of 16-bit chips which are interleaved on the address bus
+    Words
+}
+
+
+/// Takes the words of a program laid out at their addresses and splits them between two ROM images in the
+/// given way, returning the first and second image, each of which holds little-endian bytes.
+pub fn split_rom_image(words:&[u16], split:RomSplit) -> (Vec<u8>, Vec<u8>) {
+    let mut first:Vec<u8> = Vec::with_capacity(words.len());
+    let mut second:Vec<u8> = Vec::with_capacity(words.len());
+    for (index, word) in words.iter().enumerate() {
+        let [lo, hi] = word.to_le_bytes();
+        match split {
+            RomSplit::Bytes => {
+                first.push(lo);
+                second.push(hi);
+            },
+            RomSplit::Words if index % 2 == 0 => first.extend([lo, hi]),
+            RomSplit::Words => second.extend([lo, hi])
+        }
+    }
+
+    (first, second)
+}
+
+
+/// Takes the tokens of a program and splits its words, laid out at their addresses on pages of `page_size`
+/// words as given by `generate_memory_image`, between the two given ROM image files in the given way, so that
+/// each chip of a pair can be programmed separately. The header and section markers of the program binary are left out, so
+/// the first word of each pair of images is the word at address 0, and gaps are filled with `fill_word`.
+pub fn write_split_rom(tokens:&[FileTokens], fill_word:u16, page_size:i64, rom_filenames:&(String, String), split:RomSplit)
+        -> Result<(), Box<dyn Error>> {
+    let (first, second) = split_rom_image(&generate_memory_image(tokens, fill_word, page_size)?, split);
+    fs::write(&rom_filenames.0, first)?;
+    fs::write(&rom_filenames.1, second)?;
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::label_table::PAGE_SIZE;
+    use crate::pseudo_substitution::substitute_pseudo_instrs;
+    use crate::rom_split::*;
+    use crate::testing::tokens_from_str;
+
+
+    #[test]
+    fn test_split_rom_bytes() {
+        let (lo, hi) = split_rom_image(&[0x1123, 0xFFFF, 0x3905], RomSplit::Bytes);
+        assert_eq!(lo, vec![0x23, 0xFF, 0x05]);
+        assert_eq!(hi, vec![0x11, 0xFF, 0x39]);
+    }
+
+
+    #[test]
+    fn test_split_rom_words() {
+        let (even, odd) = split_rom_image(&[0x1123, 0xFFFF, 0x3905], RomSplit::Words);
+        assert_eq!(even, vec![0x23, 0x11, 0x05, 0x39]);
+        assert_eq!(odd, vec![0xFF, 0xFF]);
+    }
+
+
+    #[test]
+    fn test_split_rom_starts_with_first_instruction() {
+        let tokens = substitute_pseudo_instrs(tokens_from_str("init:\n    ADD $g0, $zero, $g1\n    HALT\ndata:\nvalue: .int 0x1234\n"));
+        let words = generate_memory_image(&tokens, 0xFFFF, PAGE_SIZE).unwrap();
+        assert_eq!(&words[..2], &[0x1102, 0xFFFF]);
+        assert_eq!(words.len(), PAGE_SIZE as usize + 1);
+        assert_eq!(words[PAGE_SIZE as usize], 0x1234);
+
+        let (lo, hi) = split_rom_image(&words, RomSplit::Bytes);
+        assert_eq!((lo[0], hi[0]), (0x02, 0x11));
+        assert_eq!((lo[PAGE_SIZE as usize], hi[PAGE_SIZE as usize]), (0x34, 0x12));
+    }
+}
@@ -0,0 +1,377 @@
+use std::error::Error;
+use std::io::{self, BufRead, BufReader, Read, Take, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::assemble_source_with_limits;
+use crate::diagnostics::{json_string, parse_diagnostic};
+use crate::errors::{AssembleError, ResourceLimitExceeded};
+use crate::limits::ResourceLimits;
+
+
+/// The largest request body the server accepts, which is far larger than any real program
+const MAX_BODY_SIZE:usize = 1024 * 1024;
+
+/// The largest number of header lines the server reads before giving up on a request
+const MAX_HEADERS:usize = 64;
+
+/// The largest number of bytes the request line and headers together may take up
+const MAX_HEAD_SIZE:usize = 16 * 1024;
+
+/// How long a client has to send the whole of its request, or to read the response, before the server drops
+/// the connection
+const REQUEST_TIMEOUT:Duration = Duration::from_secs(10);
+
+/// The largest number of connections the server handles at once, each on its own thread, beyond which a
+/// connection is answered with a 503 straight away
+const MAX_CONNECTIONS:usize = 16;
+
+
+/// Represents an HTTP response, with its status code, content type, and body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>
+}
+
+
+impl Response {
+    fn json(status:u16, body:String) -> Response {
+        Response { status, content_type: "application/json", body: body.into_bytes() }
+    }
+
+
+    /// Gets the reason phrase which goes after the status code in the status line
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            431 => "Request Header Fields Too Large",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error"
+        }
+    }
+}
+
+
+/// Takes a single error from the message of an assembler error and returns it as a JSON diagnostic. Errors
+/// which say where they are with a position such as `<source>:12:5: ` give the line and column separately,
+/// followed by the rest of the message, and others have a line and column of null.
+fn json_diagnostic(message:&str) -> String {
+    let diagnostic = parse_diagnostic(message);
+    match diagnostic.pos {
+        Some(pos) => format!(
+            "{{\"line\": {}, \"column\": {}, \"message\": {}}}", pos.line, pos.column, json_string(&diagnostic.message)
+        ),
+        None => format!("{{\"line\": null, \"column\": null, \"message\": {}}}", json_string(&diagnostic.message))
+    }
+}
+
+
+/// Takes the message of an assembler error, which has one error on each line when several are found
+/// together, and returns the JSON diagnostics of all of them separated by commas.
+fn json_diagnostics(message:&str) -> String {
+    message.split('\n').map(json_diagnostic).collect::<Vec<String>>().join(", ")
+}
+
+
+/// Takes the source of a program and assembles it within the default `ResourceLimits`, returning the bytes
+/// of the binary or the error. A panic while assembling is caught and returned as an error, so one bad
+/// submission cannot stop the server.
+fn try_assemble(source:&str) -> Result<Vec<u8>, Box<dyn Error>> {
+    match panic::catch_unwind(|| assemble_source_with_limits(source, "<source>", ResourceLimits::default())) {
+        Ok(result) => result,
+        Err(_) => Err("the assembler stopped unexpectedly".into())
+    }
+}
+
+
+/// Takes the method, path, and body of a request and returns the response. `POST /assemble` assembles the
+/// source in the body and returns the binary, or a 422 with the diagnostics as JSON if it is not valid, and
+/// `POST /check` returns whether the source is valid along with the diagnostics as JSON. Either returns a 413
+/// naming the limit if the source reaches any of the default `ResourceLimits`.
+pub fn handle_request(method:&str, path:&str, body:&[u8]) -> Response {
+    if path != "/assemble" && path != "/check" {
+        return Response::json(404, format!("{{\"error\": {}}}", json_string(&format!("{} is not an endpoint", path))));
+    }
+
+    if method != "POST" {
+        return Response::json(405, format!("{{\"error\": {}}}", json_string(&format!("{} only accepts POST", path))));
+    }
+
+    let source = match std::str::from_utf8(body) {
+        Ok(source) => source,
+        Err(_) => return Response::json(400, "{\"error\": \"the source must be UTF-8\"}".to_owned())
+    };
+
+    let result = try_assemble(source);
+    if let Some(e) = result.as_ref().err().and_then(|e| e.downcast_ref::<ResourceLimitExceeded>()) {
+        return Response::json(413, format!(
+            "{{\"error\": {}, \"limit\": {}, \"maximum\": {}}}", json_string(&e.to_string()), json_string(e.limit), e.maximum
+        ));
+    }
+
+    let result = result.map_err(|e| match e.downcast_ref::<AssembleError>() {
+        Some(e) => e.0.clone(),
+        None => e.to_string()
+    });
+
+    match (path, result) {
+        ("/assemble", Ok(binary)) => Response { status: 200, content_type: "application/octet-stream", body: binary },
+        ("/assemble", Err(e)) => Response::json(422, format!("{{\"valid\": false, \"errors\": [{}]}}", json_diagnostics(&e))),
+        (_, Ok(_)) => Response::json(200, "{\"valid\": true, \"errors\": []}".to_owned()),
+        (_, Err(e)) => Response::json(200, format!("{{\"valid\": false, \"errors\": [{}]}}", json_diagnostics(&e)))
+    }
+}
+
+
+/// Takes a message and returns the response to a request which is malformed in the way it describes
+fn bad_request(message:&str) -> Response {
+    Response::json(400, format!("{{\"error\": {}}}", json_string(message)))
+}
+
+
+/// Takes the error from reading part of a request and the message to give if it is not because the client
+/// took too long, and returns the response to send for it
+fn read_failed(e:io::Error, message:&str) -> Response {
+    match e.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => {
+            Response::json(408, format!("{{\"error\": \"the request must be sent within {} seconds\"}}", REQUEST_TIMEOUT.as_secs()))
+        },
+        _ => bad_request(message)
+    }
+}
+
+
+/// Takes the reader of the request line and headers, which stops once they reach `MAX_HEAD_SIZE`, and returns
+/// the next line, or the response to send if it cannot be read or the line goes past the limit.
+fn read_head_line<R:BufRead>(head:&mut Take<R>, message:&str) -> Result<String, Response> {
+    let mut line = String::new();
+    head.read_line(&mut line).map_err(|e| read_failed(e, message))?;
+    if !line.ends_with('\n') && head.limit() == 0 {
+        return Err(Response::json(431, format!(
+            "{{\"error\": \"the request line and headers must be at most {} bytes\"}}", MAX_HEAD_SIZE
+        )));
+    }
+
+    Ok(line)
+}
+
+
+/// Takes a reader holding an HTTP request and returns its method, path, and body, or the response to send
+/// if the request is malformed, it or its body is too large, or the client took too long to send it.
+pub fn read_request<R:BufRead>(reader:&mut R) -> Result<(String, String, Vec<u8>), Response> {
+    let mut head = reader.by_ref().take(MAX_HEAD_SIZE as u64);
+    let request_line = read_head_line(&mut head, "the request could not be read")?;
+
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_owned(), path.to_owned()),
+        _ => return Err(bad_request("the request line is malformed"))
+    };
+
+    let mut content_length:usize = 0;
+    for _ in 0..MAX_HEADERS {
+        let header = read_head_line(&mut head, "the headers could not be read")?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            let mut body = vec![0; content_length];
+            head.into_inner().read_exact(&mut body).map_err(|e| read_failed(e, "the body is shorter than its Content-Length"))?;
+            return Ok((method, path, body));
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().map_err(|_| bad_request("the Content-Length is not a number"))?;
+                if content_length > MAX_BODY_SIZE {
+                    return Err(Response::json(413, format!("{{\"error\": \"the body must be at most {} bytes\"}}", MAX_BODY_SIZE)));
+                }
+            }
+        }
+    }
+
+    Err(bad_request("the request has too many headers"))
+}
+
+
+/// Represents a connection being read from, which fails with a timeout once the deadline for the whole of
+/// the request has passed, so that a client which sends a byte at a time cannot hold it open for longer than
+/// `REQUEST_TIMEOUT` however often it sends one
+struct DeadlineReader {
+    stream: TcpStream,
+    deadline: Instant
+}
+
+
+impl Read for DeadlineReader {
+    fn read(&mut self, buf:&mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "the request took too long to send"));
+        }
+
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+
+/// Takes a response and writes it to the given writer as an HTTP response, closing the connection after it
+pub fn write_response<W:Write>(writer:&mut W, response:&Response) -> Result<(), Box<dyn Error>> {
+    write!(writer, "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status, response.reason(), response.content_type, response.body.len())?;
+    writer.write_all(&response.body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+
+/// Reads a single request from the connection and writes the response to it, giving the client
+/// `REQUEST_TIMEOUT` to send the request and the same again to read the response
+fn handle_connection(stream:TcpStream) -> Result<(), Box<dyn Error>> {
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(DeadlineReader { stream: stream.try_clone()?, deadline: Instant::now() + REQUEST_TIMEOUT });
+    let response = match read_request(&mut reader) {
+        Ok((method, path, body)) => handle_request(&method, &path, &body),
+        Err(response) => response
+    };
+
+    write_response(&mut &stream, &response)
+}
+
+
+/// Takes a listener and handles each connection to it on its own thread, with up to `MAX_CONNECTIONS` at
+/// once, until the process is stopped. A connection which fails is reported and dropped without stopping the
+/// server.
+pub fn serve_listener(listener:TcpListener) {
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                continue;
+            }
+        };
+
+        if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            active.fetch_sub(1, Ordering::SeqCst);
+            let busy = Response::json(503, format!("{{\"error\": \"the server is handling {} requests already\"}}", MAX_CONNECTIONS));
+            let written = stream.set_write_timeout(Some(REQUEST_TIMEOUT)).map_err(|e| e.into())
+                .and_then(|_| write_response(&mut &stream, &busy));
+            if let Err(e) = written {
+                eprintln!("Error: {}", e);
+            }
+
+            continue;
+        }
+
+        let active = Arc::clone(&active);
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream) {
+                eprintln!("Error: {}", e);
+            }
+
+            active.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+
+/// Listens for HTTP requests on the given host and port, handling them as described for `serve_listener`,
+/// until the process is stopped. Will only return an error if the address cannot be listened on.
+pub fn serve(host:&str, port:u16) -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind((host, port))?;
+    println!("Serving on http://{}:{}, with POST /assemble and POST /check", host, port);
+    serve_listener(listener);
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::serve::*;
+
+
+    #[test]
+    fn test_handle_request() {
+        let response = handle_request("POST", "/assemble", b"init:\n    ADD $g0, $g1, $g2\n    HALT\n");
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, "application/octet-stream");
+        assert!(response.body.ends_with(&[0x23, 0x11, 0xFF, 0xFF]));
+
+        let response = handle_request("POST", "/check", b"init:\n    ADD $g0, $g1, $g2\n    HALT\n");
+        assert_eq!(String::from_utf8(response.body).unwrap(), "{\"valid\": true, \"errors\": []}");
+
+        let response = handle_request("POST", "/check", b"init:\n    HALT\n    ADD $g0, $g1\n");
+        assert_eq!(response.status, 200);
+        assert!(String::from_utf8(response.body).unwrap().starts_with("{\"valid\": false, \"errors\": [{\"line\": 3, \"column\": 5, \"message\": "));
+
+        let response = handle_request("POST", "/check", b"init:\n    ADD $g0, $g1\n    BAD\n    HALT\n");
+        let body = String::from_utf8(response.body).unwrap();
+        assert!(body.contains("{\"line\": 2, \"column\": 5, ") && body.contains("{\"line\": 3, \"column\": 5, "));
+
+        assert_eq!(handle_request("POST", "/assemble", b"init:\n    BAD\n").status, 422);
+        assert_eq!(handle_request("GET", "/assemble", b"").status, 405);
+        assert_eq!(handle_request("POST", "/", b"").status, 404);
+        assert_eq!(handle_request("POST", "/check", &[0xFF, 0xFE]).status, 400);
+
+        let response = handle_request("POST", "/assemble", b"init:\n    HALT\ndata:\n    .org 0x7FFFFFFF\n    .int 1\n");
+        assert_eq!(response.status, 413);
+        assert!(String::from_utf8(response.body).unwrap().contains("\"limit\": \"output words\", \"maximum\": 1048576}"));
+    }
+
+
+    #[test]
+    fn test_read_request() {
+        let request = b"POST /check HTTP/1.1\r\nHost: localhost\r\ncontent-length: 5\r\n\r\nHALT\nextra";
+        let (method, path, body) = read_request(&mut &request[..]).unwrap();
+        assert_eq!((method.as_str(), path.as_str(), body.as_slice()), ("POST", "/check", &b"HALT\n"[..]));
+
+        assert_eq!(read_request(&mut &b"POST /check HTTP/1.1\r\nContent-Length: 10\r\n\r\nHALT"[..]).unwrap_err().status, 400);
+        assert_eq!(read_request(&mut &b"POST /check HTTP/1.1\r\nContent-Length: 99999999\r\n\r\n"[..]).unwrap_err().status, 413);
+        assert_eq!(read_request(&mut &b"\r\n"[..]).unwrap_err().status, 400);
+
+        // a line which never ends is only read up to the limit on the size of the headers
+        let request = [&b"POST /check HTTP/1.1\r\nX-Padding: "[..], &vec![b'a'; MAX_HEAD_SIZE]].concat();
+        assert_eq!(read_request(&mut &request[..]).unwrap_err().status, 431);
+        assert_eq!(read_request(&mut &vec![b'a'; MAX_HEAD_SIZE * 2][..]).unwrap_err().status, 431);
+    }
+
+
+    #[test]
+    fn test_slow_client_does_not_block_others() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve_listener(listener));
+
+        let mut slow = TcpStream::connect(address).unwrap();
+        slow.write_all(b"POST /check HTTP/1.1\r\n").unwrap();
+
+        let start = Instant::now();
+        let mut client = TcpStream::connect(address).unwrap();
+        client.write_all(b"POST /check HTTP/1.1\r\nContent-Length: 11\r\n\r\ninit: HALT\n").unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("{\"valid\": true, \"errors\": []}"));
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+
+    #[test]
+    fn test_json_diagnostic() {
+        assert_eq!(json_diagnostic("Found invalid instruction: <source>:12:7: \"BAD\" is not\tvalid"),
+            "{\"line\": 12, \"column\": 7, \"message\": \"\\\"BAD\\\" is not\\tvalid\"}");
+        assert_eq!(json_diagnostic("The label x was not found!"), "{\"line\": null, \"column\": null, \"message\": \"The label x was not found!\"}");
+    }
+}
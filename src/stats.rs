@@ -0,0 +1,89 @@
+use crate::label_table::generate_section_layouts_with_page_size;
+use crate::token_types::FileTokens;
+
+
+/// Represents how much work assembling a program took at each stage, so that a tool which assembles programs
+/// from its users can monitor how they behave
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AssemblyStats {
+    /// The instructions, data items, and directives the tokenizer produced from the source
+    pub tokens: usize,
+    /// The macros, `.rept` blocks, includes, trace points, coverage beacons, and pseudo-instructions which
+    /// were expanded, each counted once however many lines or tokens it expanded into
+    pub expansions: usize,
+    /// The passes needed to settle the address of every label, which is always 1 as every instruction has
+    /// a fixed size before its labels are known
+    pub relaxation_iterations: usize,
+    /// The words of the program, including the gaps left by `.org` but not the bss section, which has no
+    /// words in the binary
+    pub words: usize
+}
+
+
+impl AssemblyStats {
+    /// Takes the number of tokens the tokenizer produced, the number of expansions counted while assembling,
+    /// the final tokens of the program, and the number of words in a page, and returns the statistics of assembling it
+    pub fn new(tokens:usize, expansions:usize, final_tokens:&[FileTokens], page_size:i64) -> AssemblyStats {
+        AssemblyStats {
+            tokens,
+            expansions,
+            relaxation_iterations: 1,
+            words: count_words(final_tokens, page_size)
+        }
+    }
+}
+
+
+/// Takes the final tokens of a program and the number of words in a page, and returns the number of words in
+/// its binary, counting the gaps which `.org` leaves in every section but bss.
+pub fn count_words(tokens:&[FileTokens], page_size:i64) -> usize {
+    let words:i64 = generate_section_layouts_with_page_size(tokens, page_size).iter()
+        .filter(|layout| layout.name != "bss")
+        .map(|layout| layout.end - layout.start)
+        .sum();
+
+    words.max(0) as usize
+}
+
+
+/// Takes the statistics of assembling a program and returns them as they are printed by `--stats`, with one
+/// counter on each line.
+pub fn render_stats(stats:&AssemblyStats) -> String {
+    format!(
+        "Tokens processed:      {}\nExpansions performed:  {}\nRelaxation iterations: {}\nWords emitted:         {}\n",
+        stats.tokens, stats.expansions, stats.relaxation_iterations, stats.words
+    )
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::stats::*;
+    use crate::limits::ResourceLimits;
+    use crate::assemble_source_with_stats;
+
+
+    #[test]
+    fn test_assembly_stats() {
+        let source = "init:\n    ADDI $g0, $zero, 3\nloop:\n    SUBI $g0, $g0, 1\n    BNE $g8, $g9, @loop\n    HALT\ndata:\n    value: .int 5\n";
+        let (binary, stats) = assemble_source_with_stats(source, "<source>", ResourceLimits::UNLIMITED).unwrap();
+        assert!(!binary.is_empty());
+
+        // the branch expands into 4 moves and the branch itself, which is a single expansion
+        assert_eq!(stats, AssemblyStats { tokens: 5, expansions: 1, relaxation_iterations: 1, words: 9 });
+        assert_eq!(render_stats(&stats), "Tokens processed:      5\nExpansions performed:  1\nRelaxation iterations: 1\nWords emitted:         9\n");
+    }
+
+
+    #[test]
+    fn test_counted_expansions() {
+        // 2 invocations of the macro, 1 .rept block, 1 byte access, and 1 jump to a label
+        let source = ".macro CLEAR reg\n    ADD \\reg, $zero, $zero\n.endm\ninit:\n    CLEAR $g0\n    CLEAR $g1\n.rept 3\n    NOP\n.endr\n\
+        \x20   LOADB $g2, $g0, $g1, 0\n    JUMP $g8, $g9, @init\n";
+        let (_, stats) = assemble_source_with_stats(source, "<source>", ResourceLimits::UNLIMITED).unwrap();
+        assert_eq!(stats.expansions, 5);
+
+        let (_, stats) = assemble_source_with_stats("init:\n    NOP\n    HALT\n", "<source>", ResourceLimits::UNLIMITED).unwrap();
+        assert_eq!(stats.expansions, 0);
+    }
+}
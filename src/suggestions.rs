@@ -0,0 +1,73 @@
+/// Goes in front of the suggestion at the end of an error, so that the suggested word can be told apart
+/// from the words the error is about
+pub const SUGGESTION_PREFIX:&str = " - did you mean ";
+
+
+/// Takes two words and returns the number of single character insertions, deletions, and substitutions
+/// needed to turn one into the other, ignoring case, so that `add` is the same as `ADD`
+fn edit_distance(a:&str, b:&str) -> usize {
+    let a:Vec<char> = a.to_lowercase().chars().collect();
+    let b:Vec<char> = b.to_lowercase().chars().collect();
+
+    // only the previous row of the table is needed to fill in the next one
+    let mut previous:Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+
+/// Takes a word which was not recognised, such as a misspelt opcode, and the words it could have been, and
+/// returns the closest of them if it is close enough to be a likely typo, which is within one edit for
+/// every three characters of the word, or one edit for shorter words. Ties go to the candidate which comes
+/// first alphabetically, so the same suggestion is always given.
+pub fn closest_match<'a, I:IntoIterator<Item = &'a str>>(word:&str, candidates:I) -> Option<&'a str> {
+    let max_distance = (word.chars().count() / 3).max(1);
+    candidates.into_iter()
+        .filter(|candidate| *candidate != word)
+        .map(|candidate| (edit_distance(word, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+
+/// Takes a word which was not recognised and the words it could have been, and returns ` - did you mean
+/// <word>?` with the closest of them to go at the end of an error, or nothing if none is close enough
+pub fn did_you_mean<'a, I:IntoIterator<Item = &'a str>>(word:&str, candidates:I) -> String {
+    closest_match(word, candidates).map(|candidate| format!("{}{}?", SUGGESTION_PREFIX, candidate)).unwrap_or_default()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::suggestions::*;
+
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("ADDD", "ADD"), 1);
+        assert_eq!(edit_distance("add", "ADD"), 0);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "HALT"), 4);
+    }
+
+
+    #[test]
+    fn test_closest_match() {
+        assert_eq!(closest_match("ADDD", ["ADD", "ADDI", "SUB"]), Some("ADD"));
+        assert_eq!(closest_match("$g10", ["$g0", "$g1", "$sp"]), Some("$g0"));
+        assert_eq!(closest_match("lop", ["loop", "end"]), Some("loop"));
+        assert_eq!(closest_match("FLY", ["ADD", "SUB"]), None);
+        assert_eq!(did_you_mean("hlat", ["HALT"]), "");
+        assert_eq!(did_you_mean("HALTT", ["HALT"]), " - did you mean HALT?");
+    }
+}
@@ -0,0 +1,219 @@
+use std::path::Path;
+use crate::errors::AsmValidationError;
+use crate::hardening::bounds_check;
+use crate::isa::Isa;
+use crate::token_generator::generate_instr_tokens;
+use crate::token_types::{DataTokens, FileTokens, InstrTokens, SourcePos};
+use crate::validation::{remove_label, validate_label, validate_operand_label};
+
+
+/// The largest number of cases a `.switch` can have, as the index is compared with it as a signed word
+pub const MAX_CASES:usize = 0x7FFF;
+
+
+/// Represents a `.switch` directive, which jumps to the case given by the index register, or to the default
+/// if the index is not the number of a case
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Switch {
+    pub label: Option<String>,
+    pub index: String,
+    pub cases: Vec<String>,
+    pub default: String
+}
+
+
+/// Takes a line of assembly and returns true if it is a jump table, such as
+/// `.switch $g0, [@case0, @case1], default=@other`, which may be labelled
+pub fn is_switch_directive(line:&str) -> bool {
+    let instr = remove_label(line);
+    instr.starts_with(".switch ") || instr == ".switch"
+}
+
+
+/// Takes a line of assembly holding a jump table in the form
+/// `[<label>:] .switch <register>, [@<case>, ...], default=@<label>` and returns it. The index register must
+/// be `$zero` or one of `$g0` to `$g7`, as the jump uses `$g8` and `$g9`. Will return an
+/// `AsmValidationError` if it is not in this form, has no cases or more than `MAX_CASES`, or any label is
+/// invalid.
+pub fn parse_switch_directive(line:&str) -> Result<Switch, AsmValidationError> {
+    let malformed = || AsmValidationError(format!(
+        "Incorrect format for .switch on line {}, which should be .switch <register>, [@<label>, ...], default=@<label>", line
+    ));
+
+    let label = line.find(':').map(|index| line[..index].trim().to_owned());
+    let rest = remove_label(line)[".switch".len()..].trim();
+    let (index, rest) = rest.split_once(',').ok_or_else(malformed)?;
+    let (cases, default) = rest.trim().strip_prefix('[').and_then(|rest| rest.split_once(']')).ok_or_else(malformed)?;
+    let default = default.trim().strip_prefix(',').map(|default| default.trim())
+        .and_then(|default| default.strip_prefix("default=")).ok_or_else(malformed)?;
+
+    let index = index.trim();
+    if !matches!(Isa::register(index), Some(0..=8)) {
+        return Err(AsmValidationError(format!(
+            "The index {} of .switch on line {} must be $zero or one of $g0 to $g7, as the jump uses $g8 and $g9", index, line
+        )));
+    }
+
+    let cases:Vec<&str> = cases.split(',').map(|case| case.trim()).collect();
+    if cases.len() > MAX_CASES || cases == [""] {
+        return Err(AsmValidationError(format!("The .switch on line {} must have between 1 and {} cases", line, MAX_CASES)));
+    }
+
+    if let Some(label) = &label {
+        validate_label(line, label)?;
+    }
+
+    for case in cases.iter().chain([&default]) {
+        validate_operand_label(line, case)?;
+    }
+
+    Ok(Switch {
+        label,
+        index: index.to_owned(),
+        cases: cases.iter().map(|case| case[1..].to_owned()).collect(),
+        default: default[1..].to_owned()
+    })
+}
+
+
+/// Takes the name of the source a `.switch` is in and the line it is on, and returns the label of its table,
+/// such as `prog_switch_12` for line 12 of `src/prog.asm`, so that tables from different files do not clash
+fn table_label(name:&str, line_num:usize) -> String {
+    let stem = Path::new(name).file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    let stem:String = stem.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    let stem = stem.trim_matches('_');
+
+    // a label cannot start with a digit
+    let prefix = if stem.is_empty() || stem.starts_with(|c:char| c.is_ascii_digit()) { "_" } else { "" };
+    format!("{}{}_switch_{}", prefix, stem, line_num)
+}
+
+
+/// Takes a jump table, the label of the item before it if it has none of its own, and its position in the
+/// source, and returns the instructions it expands into and the tables they read, which go in the data
+/// section. The instructions branch to the default unless the index is at least 0 and less than the number
+/// of cases, in the same way as a check of `.bounds`, then load the upper and lower halves of the address of
+/// the case from two tables indexed by the index register and jump to it:
+///
+/// ```text
+/// LOAD $g8, $g8, $g0, @prog_switch_12_upper
+/// LOAD $g9, $g9, $g0, @prog_switch_12_lower
+/// JUMP $g8, $g9
+/// ```
+///
+/// The tables are read with `LOAD`, so `$ua` must hold the upper half of their address, as it does for any
+/// load from a label. The values of `$g8` and `$g9` are not kept.
+pub fn expand_switch(switch:&Switch, prev_label:Option<String>, name:&str, pos:Option<SourcePos>)
+        -> Result<(Vec<InstrTokens>, Vec<DataTokens>), AsmValidationError> {
+    let table = table_label(name, pos.as_ref().map(|pos| pos.line).unwrap_or_default());
+    let mut lines = bounds_check(&switch.index, switch.cases.len() as u16, &switch.default);
+    lines.push(format!("LOAD $g8, $g8, {}, @{}_upper", switch.index, table));
+    lines.push(format!("LOAD $g9, $g9, {}, @{}_lower", switch.index, table));
+    lines.push("JUMP $g8, $g9".to_owned());
+
+    let mut label = switch.label.clone().or(prev_label);
+    let instrs = lines.iter()
+        .map(|line| generate_instr_tokens(line, label.take()).map(|t| t.with_pos(pos.clone())))
+        .collect::<Result<Vec<InstrTokens>, _>>()
+        .map_err(|e| AsmValidationError(e.0))?;
+
+    let halves = [("upper", 'u'), ("lower", 'l')].map(|(half, prefix)| {
+        let op_labels = switch.cases.iter().map(|case| format!("{}@{}", prefix, case)).collect();
+        DataTokens::new(Some(format!("{}_{}", table, half)), "switch".to_owned(), Vec::new())
+            .with_op_labels(op_labels)
+            .with_pos(pos.clone())
+    });
+
+    Ok((instrs, halves.to_vec()))
+}
+
+
+/// Takes the tokens of a source file and the tables of its jump tables, and places the tables at the end of
+/// its data section, which is before any text or bss. If the file has no data section, the tables start one.
+pub fn place_switch_tables(tokens:&mut Vec<FileTokens>, tables:Vec<DataTokens>) {
+    let index = tokens.iter().position(|tokens| match tokens {
+        FileTokens::TextTokens(_) | FileTokens::BssTokens(_) => true,
+        FileTokens::OrgTokens(t) => t.section == 't' || t.section == 'b',
+        FileTokens::AliasTokens(t) => t.section == 't' || t.section == 'b',
+        FileTokens::DeprecatedTokens(t) => t.section == 't' || t.section == 'b',
+        FileTokens::InstrTokens(_) | FileTokens::DataTokens(_) | FileTokens::TraceTokens(_) | FileTokens::ExportTableTokens(_) => false
+    }).unwrap_or(tokens.len());
+
+    tokens.splice(index..index, tables.into_iter().map(FileTokens::DataTokens));
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::switch::*;
+    use crate::{assemble_source, process_source_into_tokens};
+    use crate::label_table::generate_label_table;
+
+
+    #[test]
+    fn test_parse_switch_directive() {
+        assert!(is_switch_directive(".switch $g0, [@case0], default=@other"));
+        assert!(is_switch_directive("dispatch: .switch $g0, [@case0], default=@other"));
+        assert!(!is_switch_directive("switch: ADD $g0, $g1, $g2"));
+
+        let switch = parse_switch_directive("dispatch: .switch $g0, [@case0, @case1, @case2], default=@other").unwrap();
+        assert_eq!(switch, Switch {
+            label: Some("dispatch".to_owned()),
+            index: "$g0".to_owned(),
+            cases: vec!["case0".to_owned(), "case1".to_owned(), "case2".to_owned()],
+            default: "other".to_owned()
+        });
+
+        assert!(parse_switch_directive(".switch $g0, [@case0, @case1]").is_err());
+        assert!(parse_switch_directive(".switch $g0, [], default=@other").is_err());
+        assert!(parse_switch_directive(".switch $g0, [case0], default=@other").is_err());
+        assert!(parse_switch_directive(".switch $g0, @case0, default=@other").is_err());
+        assert!(parse_switch_directive(".switch $g8, [@case0], default=@other").unwrap_err().0.contains("$g8 and $g9"));
+        assert!(parse_switch_directive(".switch $sp, [@case0], default=@other").is_err());
+    }
+
+
+    #[test]
+    fn test_table_label() {
+        assert_eq!(table_label("src/prog.asm", 12), "prog_switch_12");
+        assert_eq!(table_label("<source>", 3), "source_switch_3");
+        assert_eq!(table_label("2024-rom.asm", 3), "_2024_rom_switch_3");
+    }
+
+
+    #[test]
+    fn test_switch_in_source() {
+        let source = "init:\n    .switch $g0, [@zero, @one], default=@other\nzero:\n    HALT\none:\n    HALT\nother:\n    HALT\n\
+            text:\ngreeting: .asciiz \"hi\"\n";
+        let tokens = process_source_into_tokens(source, None).unwrap();
+        let opcodes:Vec<String> = tokens.iter().filter_map(|token| token.try_get_instr_tokens().ok()).map(|t| t.opcode).collect();
+        assert_eq!(opcodes[..10], ["CMP", "BGT", "MOVLI", "MOVUI", "CMP", "BEQ", "BLT", "LOAD", "LOAD", "JUMP"]);
+        assert_eq!(tokens[0].try_get_instr_tokens().unwrap().label.as_deref(), Some("init"));
+
+        // the tables come after the code and before the text
+        let label_table = generate_label_table(&tokens).unwrap();
+        assert_eq!(label_table["source_switch_2_upper"], 0x1000);
+        assert_eq!(label_table["source_switch_2_lower"], 0x1002);
+        assert_eq!(label_table["greeting"], 0x2000);
+    }
+
+
+    #[test]
+    fn test_switch_tables_hold_case_addresses() {
+        let source = "init:\n    .switch $g0, [@zero, @one], default=@other\nzero:\n    HALT\none:\n    HALT\nother:\n    HALT\n";
+        let binary = assemble_source(source).unwrap();
+        let data = binary.windows(6).position(|window| window == b"data:\0").unwrap() + 6;
+        let words:Vec<u16> = binary[data..data + 8].chunks(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect();
+
+        // the case labels follow the 26 words the jump expands into, with the upper halves first
+        assert_eq!(words, vec![0x0000, 0x0000, 0x001A, 0x001B]);
+    }
+
+
+    #[test]
+    fn test_switch_missing_case() {
+        let source = "init:\n    .switch $g0, [@zero, @missing], default=@zero\nzero:\n    HALT\n";
+        let err = assemble_source(source).unwrap_err();
+        assert!(err.0.contains("The label missing was not found"));
+    }
+}
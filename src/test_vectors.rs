@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use crate::byte_access::expand_byte_accesses;
+use crate::generate_code::get_binary_from_tokens;
+use crate::limits::{ResourceBudget, ResourceLimits};
+use crate::preprocessor::SourceLine;
+use crate::pseudo_substitution::{substitute_pseudo_instrs, substitute_labels};
+use crate::token_generator::generate_instr_tokens;
+use crate::token_types::FileTokens;
+use crate::validation::validate_asm_line;
+
+
+/// The address the label `@target` resolves to in the test vectors which use label operands. Every byte
+/// is different so that any mix-up between the upper and lower halves shows in the encoding.
+const TARGET_ADDR:i64 = 0x0012_3456;
+
+/// One line of assembly for every form of every instruction the assembler supports, including the
+/// pseudo-instructions which expand into several words.
+const VECTOR_SOURCES:[&str; 48] = [
+    "NOP",
+    "ADD $g0, $g1, $g2",           "SUB $g3, $g4, $g5",           "ADDI $g6, $zero, 15",
+    "SUBI $g7, $g8, 0x1",          "SLL $g9, $ua, $sp",           "SRL $fp, $ra, $pc",
+    "SRA $g0, $g1, $g2",           "NAND $g3, $g4, $g5",          "OR $g6, $g7, $g8",
+    "LOAD $g0, $g8, $g9",          "STORE $g1, $g8, $g9",         "MOVUI $g5, 0xFF",
+    "MOVLI $g5, 0",                "ADDC $g0, $g1",               "SUBC $g2, $g3",
+    "JUMP $g8, $g9",               "JAL $g8, $g9",                "CMP $g1, $g5",
+    "BEQ $g8, $g9",                "BNE $g8, $g9",                "BLT $g8, $g9",
+    "BGT $g8, $g9",                "JUMP $ra",                    "JAL $sp",
+    "BEQ $fp",                     "BNE $pc",                     "BLT $ra",
+    "BGT $ra",                     "IN $g0, 2",                   "OUT $g9, 15",
+    "syscall 255",                 "ATOM",                        "HALT",
+    "LOAD $g0, $g8, $g9, @target", "STORE $g1, $g8, $g9, @target",
+    "MOVUI $g0, @target",          "MOVLI $g0, @target",
+    "JUMP $g8, $g9, @target",      "JAL $g8, $g9, @target",       "BEQ $g8, $g9, @target",
+    "BNE $g8, $g9, @target",       "BLT $g8, $g9, @target",       "BGT $g8, $g9, @target",
+    "LOADB $g0, $g1, $g2, 0",      "LOADB $g0, $g1, $g2, 1",
+    "STOREB $g0, $g1, $g2, 0",     "STOREB $g0, $g1, $g2, 1"
+];
+
+
+/// Represents a line of assembly and the words it is expected to assemble into
+#[derive(Debug, Clone)]
+pub struct TestVector {
+    pub source: String,
+    pub encoding: Vec<u16>
+}
+
+
+/// Assembles every line in `VECTOR_SOURCES` using the same byte access expansion, validation, tokenization,
+/// substitution, and code generation as a full program, and returns the resulting test vectors. Label
+/// operands all refer to the label `target`, which is placed at `TARGET_ADDR`.
+pub fn generate_test_vectors() -> Result<Vec<TestVector>, Box<dyn Error>> {
+    let label_table:HashMap<String, i64> = HashMap::from([("target".to_owned(), TARGET_ADDR)]);
+    let mut budget = ResourceBudget::new(ResourceLimits::UNLIMITED);
+    let mut vectors:Vec<TestVector> = Vec::new();
+    for source in VECTOR_SOURCES {
+        let source_lines = expand_byte_accesses(vec![SourceLine { line_num: 1, text: source.to_owned(), file: None }], &mut budget)?;
+        let mut tokens:Vec<FileTokens> = Vec::new();
+        for source_line in source_lines {
+            validate_asm_line(&source_line.text, 'c')?;
+            tokens.push(FileTokens::InstrTokens(generate_instr_tokens(&source_line.text, None)?));
+        }
+
+        let tokens = substitute_labels(substitute_pseudo_instrs(tokens), &label_table)?;
+
+        let mut encoding:Vec<u16> = Vec::new();
+        for token in tokens {
+            encoding.append(&mut get_binary_from_tokens(token)?);
+        }
+
+        vectors.push(TestVector { source: source.to_owned(), encoding });
+    }
+
+    Ok(vectors)
+}
+
+
+/// Takes a list of test vectors and writes them to the given file as JSON, with each encoding given as an
+/// array of 16-bit words in hex.
+pub fn write_test_vectors(filename:&str, vectors:&[TestVector]) -> Result<(), Box<dyn Error>> {
+    let mut vectors_file = BufWriter::new(
+        OpenOptions::new().create(true).write(true).truncate(true).open(filename)?);
+
+    writeln!(vectors_file, "{{")?;
+    writeln!(vectors_file, "  \"target_address\": \"0x{:08X}\",", TARGET_ADDR)?;
+    writeln!(vectors_file, "  \"vectors\": [")?;
+    for (index, vector) in vectors.iter().enumerate() {
+        let encoding:Vec<String> = vector.encoding.iter().map(|word| format!("\"0x{:04X}\"", word)).collect();
+        let separator = if index == vectors.len() - 1 { "" } else { "," };
+        writeln!(vectors_file, "    {{ \"source\": \"{}\", \"encoding\": [{}] }}{}",
+            vector.source.replace('\\', "\\\\").replace('"', "\\\""), encoding.join(", "), separator)?;
+    }
+
+    writeln!(vectors_file, "  ]")?;
+    writeln!(vectors_file, "}}")?;
+    vectors_file.flush()?;
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::test_vectors::generate_test_vectors;
+
+
+    #[test]
+    fn test_generate_test_vectors() {
+        let vectors = generate_test_vectors().unwrap();
+        assert_eq!(vectors.len(), 48);
+
+        let encoding_of = |source:&str| vectors.iter().find(|v| v.source == source).unwrap().encoding.clone();
+        assert_eq!(encoding_of("ADD $g0, $g1, $g2"), vec![0x1123]);
+        assert_eq!(encoding_of("SLL $g9, $ua, $sp"), vec![0x5ABC]);
+        assert_eq!(encoding_of("JUMP $ra"), vec![0xF20E]);
+        assert_eq!(encoding_of("OUT $g9, 15"), vec![0xFAAF]);
+        assert_eq!(encoding_of("LOAD $g0, $g8, $g9, @target"), vec![0xD956, 0xC934, 0xA19A]);
+        assert_eq!(encoding_of("JUMP $g8, $g9, @target").len(), 5);
+        assert_eq!(encoding_of("LOADB $g0, $g1, $g2, 0"), vec![0xA123, 0x3A08, 0x511A, 0x611A]);
+        assert_eq!(encoding_of("LOADB $g0, $g1, $g2, 1"), vec![0xA123, 0x3A08, 0x611A]);
+        assert_eq!(encoding_of("STOREB $g0, $g1, $g2, 1").len(), 9);
+    }
+}
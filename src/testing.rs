@@ -0,0 +1,193 @@
+use crate::{assemble_tokens, process_source_into_tokens};
+use crate::token_generator::convert_string_to_bytes;
+use crate::token_types::{BssTokens, DataTokens, FileTokens, InstrTokens, TextTokens};
+use crate::validation::validate_int_immediate;
+
+
+/// A program which counts up a Fibonacci sequence, with a data item of every category and some text, used by
+/// the tests of the label table and the stages around it
+#[cfg(test)]
+pub const FIBONACCI_PROGRAM:&str = "init:\n    ADDI $g0, $zero, 1\n    ADDI $g1, $zero, 1\n    LOAD $g5, $g8, $g9, @target\n\n\
+    loop:\n    ADD $g3, $g0, $g1\n    ADD $g0, $zero, $g1\n    ADD $g1, $zero, $g2\n    ADD $g2, $zero, $g1\n\n\
+    \x20   CMP $g1, $g5\n    BGT $g8, $g9, @end\n    JUMP $g8, $g9, @loop\n\nend: HALT\n\n\
+    data:\n    target: .int 7\n    int_long: .long 650000000\n    half_float:    .half 5.25\n    float:\n\
+    \x20       .float -3104.76171875\n    eszet: .char '\u{DF}'\n    list: .section 10 [1, 1, 2, 3, 5, 8, 13, 21, 34, 55]\n\n\
+    text:\n    text_data: .text 20 \"Some characters!\"";
+
+
+/// A program with no labels whose loads, stores, and branches all take label operands, one after each of 3
+/// plain instructions, used by the tests of pseudo-instruction substitution
+#[cfg(test)]
+pub const PSEUDO_INSTR_PROGRAM:&str = "ADDI $g0, $zero, 10\nLOAD $g5, $g6, $g7, @test_1\nADDI $g0, $zero, 10\n\
+    STORE $g0, $g1, $g2, @test_2\nADDI $g0, $zero, 10\nBEQ $g3, $g4, @test_3\nBGT $g6, $g7, @test_4";
+
+
+/// A program of nothing but branches which each take a single register
+#[cfg(test)]
+pub const SINGLE_OPERAND_BRANCH_PROGRAM:&str = "JUMP $ra\nBNE $sp\nBEQ $fp\nBGT $pc\nBLT $ra\nJAL $ra";
+
+
+/// A program with a `.org` in both its code and its data sections
+#[cfg(test)]
+pub const ORG_PROGRAM:&str = "init:\n    ADDI $g0, $zero, 1\n    HALT\n\n.org 0x10\nhandler:\n    NOP\n    JUMP $ra\n\n\
+    data:\n    first: .int 1\n.org 0x1020\n    table: .section 4 [1, 2, 3, 4]\n";
+
+
+/// A program of several subroutines which call each other and load and store data by label, one of which is
+/// never called, used by the tests of the layout shuffle
+#[cfg(test)]
+pub const SUBROUTINES_PROGRAM:&str = "init:\n    LOAD $g0, $g8, $g9, @first\n    JAL $g8, $g9, @double\n    JAL $g8, $g9, @triple\n\
+    \x20   JAL $g8, $g9, @half\n    STORE $g0, $g8, $g9, @third\n    HALT\n\n\
+    double:\n    ADD $g0, $g0, $g0\n    JUMP $ra\n\n\
+    triple:\n    ADD $g1, $g0, $g0\n    ADD $g0, $g1, $g0\n    JUMP $ra\n\n\
+    half:\n    LOAD $g1, $g8, $g9, @second\n    ADD $g2, $g1, $g1\n\n\
+    half_done:\n    SUB $g0, $g0, $g2\n    JUMP $ra\n\n\
+    negate:\n    SUB $g0, $zero, $g0\n    JUMP $ra\n\n\
+    data:\n    first: .int 3\n    second: .int 1\n    third: .section 2 [0, 0]\n";
+
+
+/// Takes the source of a program held in memory and returns its tokens, so that a test can write the program
+/// it needs in place rather than adding a file under `test_files/`. The source is named `<source>` in
+/// diagnostics.
+///
+/// Will panic with every error found if the source is not valid.
+pub fn tokens_from_str(source:&str) -> Vec<FileTokens> {
+    process_source_into_tokens(source, None).unwrap_or_else(|e| panic!("{}", e))
+}
+
+
+/// Takes the source of a program held in memory and returns its binary, assembled in the same way as
+/// `assemble_source`.
+///
+/// Will panic with the error if the program cannot be assembled.
+pub fn assemble_str(source:&str) -> Vec<u8> {
+    crate::assemble_source(source).unwrap_or_else(|e| panic!("{}", e))
+}
+
+
+/// Builds the tokens of a program one item at a time, in the form the tokenizer would produce them, so that
+/// a test can give a later stage exactly the program it needs without writing out its source. Unlike
+/// `code_buffer::CodeBuffer`, which only builds valid instructions from typed operands, any opcode and
+/// operands can be given, including pseudo-instructions and ones the tokenizer would reject, and items can
+/// be added to the text and bss sections. The tokens stay in the order they were added, so a test can also
+/// build a program in an order the tokenizer would never produce.
+#[derive(Debug, Clone, Default)]
+pub struct ProgramBuilder {
+    tokens: Vec<FileTokens>,
+    next_label: Option<String>
+}
+
+
+impl ProgramBuilder {
+    /// Creates an empty `ProgramBuilder`
+    pub fn new() -> ProgramBuilder {
+        ProgramBuilder::default()
+    }
+
+
+    /// Labels the next item added with the given name, in the same way as a label on the line before it
+    pub fn label(&mut self, name:&str) -> &mut Self {
+        self.next_label = Some(name.to_owned());
+        self
+    }
+
+
+    /// Adds an instruction with the given opcode and operands, written as in the source, such as `"ADDI"` and
+    /// `&["$g0", "$zero", "3"]`. Registers fill the register operands in order, an operand starting with `@`
+    /// is the label operand, and any other operand is the immediate, in binary, decimal, or hexadecimal.
+    ///
+    /// Will panic if an immediate is not an integer, or if there are more than 3 registers.
+    pub fn instr(&mut self, opcode:&str, operands:&[&str]) -> &mut Self {
+        let mut registers:Vec<String> = Vec::new();
+        let mut immediate:Option<u64> = None;
+        let mut op_label:Option<String> = None;
+        for operand in operands {
+            if operand.starts_with('$') {
+                registers.push(operand.to_string());
+            } else if operand.starts_with('@') {
+                op_label = Some(operand.to_string());
+            } else {
+                let value = validate_int_immediate(operand, 32, true).unwrap_or_else(|e| panic!("{}", e));
+                immediate = Some(value as u64);
+            }
+        }
+
+        assert!(registers.len() <= 3, "{} is given {} registers, but an instruction has at most 3", opcode, registers.len());
+        let mut registers = registers.into_iter();
+        self.tokens.push(FileTokens::InstrTokens(InstrTokens::new(
+            self.next_label.take(), opcode.to_owned(), registers.next(), registers.next(), registers.next(), immediate, op_label
+        )));
+
+        self
+    }
+
+
+    /// Adds a data item of the given category, written without its dot, such as `"int"` or `"section"`,
+    /// holding the given words
+    pub fn data(&mut self, category:&str, words:&[u16]) -> &mut Self {
+        self.tokens.push(FileTokens::DataTokens(DataTokens::new(self.next_label.take(), category.to_owned(), words.to_vec())));
+        self
+    }
+
+
+    /// Adds the given text to the text section followed by a null character, in the same way as a `.text`
+    /// just large enough to hold it
+    pub fn text(&mut self, text:&str) -> &mut Self {
+        let bytes = convert_string_to_bytes(text, text.encode_utf16().count() + 1);
+        self.tokens.push(FileTokens::TextTokens(TextTokens::new(self.next_label.take(), bytes)));
+        self
+    }
+
+
+    /// Adds a buffer of the given number of words to the bss section, in the same way as a `.space`
+    pub fn bss(&mut self, size:u64) -> &mut Self {
+        self.tokens.push(FileTokens::BssTokens(BssTokens::new(self.next_label.take(), size)));
+        self
+    }
+
+
+    /// Returns the tokens of the program in the order they were added
+    pub fn tokens(&self) -> Vec<FileTokens> {
+        self.tokens.clone()
+    }
+
+
+    /// Assembles the program into the bytes of a program binary, in the same way as `assemble_tokens`.
+    ///
+    /// Will panic with the error if the program cannot be assembled.
+    pub fn assemble(&self) -> Vec<u8> {
+        assemble_tokens(self.tokens()).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::*;
+
+
+    #[test]
+    fn test_builder_matches_source() {
+        let source = "init:\n    ADDI $g0, $zero, 3\nloop:\n    SUBI $g0, $g0, 1\n    BNE $g8, $g9, @loop\n    HALT\n\
+            data:\n    value: .int 5\ntext:\n    msg: .text 3 \"hi\"\nbss:\n    buffer: .space 4\n";
+        let mut built = ProgramBuilder::new();
+        built.label("init").instr("ADDI", &["$g0", "$zero", "3"])
+            .label("loop").instr("SUBI", &["$g0", "$g0", "0x1"])
+            .instr("BNE", &["$g8", "$g9", "@loop"])
+            .instr("HALT", &[])
+            .label("value").data("int", &[5])
+            .label("msg").text("hi")
+            .label("buffer").bss(4);
+
+        let tokens = tokens_from_str(source);
+        assert_eq!(format!("{:?}", built.tokens()), format!("{:?}", tokens));
+        assert_eq!(built.assemble(), assemble_str(source));
+    }
+
+
+    #[test]
+    #[should_panic(expected = "<source>:2:5: [E0301] BAD is not a valid opcode")]
+    fn test_invalid_fixture() {
+        tokens_from_str("init:\n    BAD $g0\n");
+    }
+}
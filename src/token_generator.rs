@@ -1,4 +1,10 @@
+use std::collections::HashMap;
 use half::f16;
+use crate::errors::TokenGenerationError;
+use crate::escapes::parse_escapes;
+use crate::expressions::{evaluate_int_expression, generate_table_words, is_int_expression};
+use crate::fixed_point::FixedPointFormat;
+use crate::random::SplitMix64;
 use crate::validation::*;
 use crate::token_types::*;
 
@@ -9,80 +15,168 @@ use crate::token_types::*;
 /// as `u16`s.
 ///
 /// Will panic if the vec_size is too small.
-fn convert_string_to_bytes(string:&str, vec_size:usize) -> Vec<u16> {
+pub fn convert_string_to_bytes(string:&str, vec_size:usize) -> Vec<u16> {
     let mut buffer = [0;2];
     let mut bytes:Vec<u16> = Vec::with_capacity(vec_size);
 
     for index in 0..vec_size {
         string.chars().nth(index).unwrap_or('\0').encode_utf16(&mut buffer);
-        bytes.push(((buffer[1] as u16) << 8) | (buffer[0] as u16));
+        bytes.push((buffer[1] << 8) | buffer[0]);
     }
 
     bytes
 }
 
 
+/// Takes the number of words to generate and a seed, and returns that many pseudo-random words. The words
+/// are the upper 16 bits of each output of SplitMix64 started from the seed, so the same seed always gives
+/// the same words.
+fn generate_random_words(count:usize, seed:u64) -> Vec<u16> {
+    let mut rng = SplitMix64::new(seed);
+    (0..count).map(|_| (rng.next_u64() >> 48) as u16).collect()
+}
+
+
+/// Takes a CRC-16 polynomial, such as 0x1021 for CRC-16/CCITT, and returns the 256-entry lookup table for
+/// computing the CRC a byte at a time, most significant bit first.
+fn generate_crc16_table(poly:u16) -> Vec<u16> {
+    (0..256u16).map(|byte| {
+        let mut crc = byte << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ poly } else { crc << 1 };
+        }
+
+        crc
+    }).collect()
+}
+
+
+/// Takes a line of data and returns the operand at the given index after its label, where the directive
+/// is at index 0, or a `TokenGenerationError` if the line does not have that many
+fn get_operand(data:&str, index:usize) -> Result<&str, TokenGenerationError> {
+    data.split_whitespace()
+        .nth(index)
+        .ok_or_else(|| TokenGenerationError(format!("{} is missing an operand", data)))
+}
+
+
+/// Takes an integer and the line it came from, and returns it as the type it is stored in, or a
+/// `TokenGenerationError` if it does not fit
+fn fit_integer<T:TryFrom<i64>>(value:i64, line:&str) -> Result<T, TokenGenerationError> {
+    T::try_from(value).map_err(|_| TokenGenerationError(format!("{} does not fit in {}", value, line)))
+}
+
+
+/// Takes a 32-bit value and whether it is big-endian, and returns the two words which hold its bytes in that
+/// order once the binary is written, as each word is written with its lower byte first.
+pub fn get_byte_ordered_words(value:u32, big_endian:bool) -> Vec<u16> {
+    let bytes = if big_endian { value.to_be_bytes() } else { value.to_le_bytes() };
+    bytes.chunks(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect()
+}
+
+
+/// Takes a list of 8-bit values and packs them two to a word, with the first of each pair in the lower byte
+/// so that the bytes appear in the binary in the order they were given, as each word is written with its
+/// lower byte first. An odd byte at the end is padded with a zero upper byte.
+pub fn pack_bytes(bytes:&[u8]) -> Vec<u16> {
+    bytes.chunks(2).map(|pair| u16::from_le_bytes([pair[0], pair.get(1).copied().unwrap_or(0)])).collect()
+}
+
+
 /// Takes some data in the form of a string which can be any data type (e.g. long, text, integer,
-/// section...) and converts it to an array of bytes
-fn get_bytes_array_from_line(category:&str, data:&str) -> Vec<u16> {
+/// section...) and converts it to an array of bytes. Will return a `TokenGenerationError` if the data is
+/// not in the form its type needs.
+fn get_bytes_array_from_line(category:&str, data:&str) -> Result<Vec<u16>, TokenGenerationError> {
     let data = remove_label(data);
+    let malformed = || TokenGenerationError(format!("{} is not a valid .{}", data, category));
     let mut bytes:Vec<u16> = Vec::new();
     match category {
         "int" => {
-            let integer = data.split(" ").filter(|token| !token.is_empty()).collect::<Vec<&str>>()[1];
-            bytes.push(get_int_immediate_from_string(integer).try_into().unwrap());
+            // a negative value is stored as its two's complement
+            for integer in get_data_list(data, ".int") {
+                let integer = get_int_immediate_from_string(integer)?;
+                bytes.push(fit_integer::<u16>(integer, data).or_else(|_| fit_integer::<i16>(integer, data).map(|i| i as u16))?);
+            }
         },
 
         "long" => {
-            let long_str = data.split(" ").filter(|token| !token.is_empty()).collect::<Vec<&str>>()[1];
-            let long_num:u32 = get_int_immediate_from_string(long_str).try_into().unwrap();
-            bytes.push(((long_num & 0xFFFF_0000) >> 16).try_into().unwrap());
-            bytes.push((long_num & 0x0000_FFFF).try_into().unwrap());
+            for long_str in get_data_list(data, ".long") {
+                let long_num = get_int_immediate_from_string(long_str)?;
+                let long_num = fit_integer::<u32>(long_num, data).or_else(|_| fit_integer::<i32>(long_num, data).map(|l| l as u32))?;
+                bytes.push(((long_num & 0xFFFF_0000) >> 16) as u16);
+                bytes.push((long_num & 0x0000_FFFF) as u16);
+            }
         },
 
         "half" => {
-            let num = data.split(" ").filter(|token| !token.is_empty()).collect::<Vec<&str>>()[1];
-            bytes.push(f16::from_f32(num.parse().unwrap()).to_bits());
+            let num:f32 = get_operand(data, 1)?.parse().map_err(|_| malformed())?;
+            bytes.push(f16::from_f32(num).to_bits());
         },
 
         "float" => {
-            let num = data.split(" ").filter(|token| !token.is_empty()).collect::<Vec<&str>>()[1];
-            bytes.push(((num.parse::<f32>().unwrap().to_bits() & 0xFFFF_0000) >> 16).try_into().unwrap());
-            bytes.push((num.parse::<f32>().unwrap().to_bits() & 0x0000_FFFF).try_into().unwrap());
+            let num:f32 = get_operand(data, 1)?.parse().map_err(|_| malformed())?;
+            bytes.push(((num.to_bits() & 0xFFFF_0000) >> 16) as u16);
+            bytes.push((num.to_bits() & 0x0000_FFFF) as u16);
+        },
+
+        "byte" => {
+            let values = data[".byte".len()..].split(',')
+                .map(|item| Ok(get_int_immediate_from_string(item.trim())? as u8))
+                .collect::<Result<Vec<u8>, TokenGenerationError>>()?;
+            bytes.append(&mut pack_bytes(&values));
+        },
+
+        "le32" | "be32" => {
+            let value:u32 = fit_integer(get_int_immediate_from_string(get_operand(data, 1)?)? & 0xFFFF_FFFF, data)?;
+            bytes.append(&mut get_byte_ordered_words(value, category == "be32"));
+        },
+
+        "q8_8" | "q16_16" => {
+            let format = FixedPointFormat::from_data_type(category).ok_or_else(malformed)?;
+            let raw = format.to_raw(get_operand(data, 1)?, data).map_err(|e| TokenGenerationError(e.0))?;
+            bytes.append(&mut format.to_words(raw));
         },
 
         "char" => {
-            let character_str = data.split(" ").filter(|token| !token.is_empty()).collect::<Vec<&str>>()[1];
-            bytes.append(&mut convert_string_to_bytes(&format!("{}", character_str.chars().nth(1).unwrap()), 1));
+            let literal = data[".char".len()..].trim();
+            let character = literal.strip_prefix('\'').ok_or_else(malformed)?;
+            let character = character.strip_suffix('\'').unwrap_or(character);
+            let character = parse_escapes(data, character).map_err(|e| TokenGenerationError(e.0))?;
+            bytes.append(&mut convert_string_to_bytes(&character, 1));
         },
 
         "text" => {
-            let text_start_index = match data.find("\"") {
-                Some(index) => index,
-                None => panic!("{} dot not contain a valid text string", data)
-            };
-
-            let text = data[text_start_index..].to_owned();
-            let size:usize = data.split(" ").filter(|token| !token.is_empty())
-                                            .collect::<Vec<&str>>()[1]
-                                            .parse().unwrap();
-            bytes.append(&mut convert_string_to_bytes(&text[1..text.len() - 1], size));
+            let text = data.find("\"")
+                .and_then(|index| data[index + 1..].strip_suffix('"'))
+                .ok_or_else(|| TokenGenerationError(format!("{} does not contain a valid text string", data)))?;
+            let text = &parse_escapes(data, text).map_err(|e| TokenGenerationError(e.0))?;
+
+            // sizes are capped at a 16-bit count like .rand and .table, so one line cannot exhaust memory
+            let size:usize = get_operand(data, 1)?.parse::<u16>().map_err(|_| malformed())?.into();
+            bytes.append(&mut convert_string_to_bytes(text, size));
         },
 
-        "section" => {
-            let section_str = match data.find("[") {
-                Some(index) => data[index + 1..data.len() - 1].to_owned(),
-                None => panic!("{} is not a valid section", data)
-            };
+        "ascii" | "asciiz" => {
+            let text = data.find("\"")
+                .and_then(|index| data[index + 1..].strip_suffix('"'))
+                .ok_or_else(|| TokenGenerationError(format!("{} does not contain a valid text string", data)))?;
+            let text = &parse_escapes(data, text).map_err(|e| TokenGenerationError(e.0))?;
+
+            // the size is the length of the string, plus the null terminator of an .asciiz
+            let size = text.chars().count() + usize::from(category == "asciiz");
+            bytes.append(&mut convert_string_to_bytes(text, size));
+        },
 
-            let size:usize = data.split(" ").filter(|token| !token.trim().is_empty())
-                                            .collect::<Vec<&str>>()[1]
-                                            .parse().unwrap();
+        "section" => {
+            let section_str = data.find("[")
+                .and_then(|index| data[index + 1..].strip_suffix(']'))
+                .ok_or_else(|| TokenGenerationError(format!("{} is not a valid section", data)))?;
 
+            let size:usize = get_operand(data, 1)?.parse::<u16>().map_err(|_| malformed())?.into();
             let mut bytes_array:Vec<u16> = section_str.split(",")
                                     .filter(|item| !item.is_empty() && item != &" ")
-                                    .map(|item| get_int_immediate_from_string(item.trim()).try_into().unwrap())
-                                    .collect();
+                                    .map(|item| fit_integer(get_int_immediate_from_string(item.trim())?, data))
+                                    .collect::<Result<Vec<u16>, TokenGenerationError>>()?;
             while bytes_array.len() < size {
                 bytes_array.push(0x0000);
             }
@@ -90,101 +184,139 @@ fn get_bytes_array_from_line(category:&str, data:&str) -> Vec<u16> {
             bytes.append(&mut bytes_array);
         },
 
-        _ => panic!("Invalid or unsupported data type: {}", category)
+        "space" | "zero" => {
+            let size:usize = get_operand(data, 1).and_then(get_int_immediate_from_string)
+                .and_then(|size| fit_integer::<u16>(size, data))?.into();
+            bytes.append(&mut vec![0x0000; size]);
+        },
+
+        "rand" => {
+            let operands:Vec<&str> = data[".rand".len()..].split(',').map(|item| item.trim()).collect();
+            let count:usize = fit_integer(get_int_immediate_from_string(operands[0])?, data)?;
+            let seed = operands.get(1).and_then(|seed| seed.strip_prefix("seed=")).ok_or_else(malformed)?;
+            bytes.append(&mut generate_random_words(count, get_int_immediate_from_string(seed)? as u64));
+        },
+
+        "crc16_table" => {
+            let poly = get_operand(data, 1)?.strip_prefix("poly=").ok_or_else(malformed)?;
+            bytes.append(&mut generate_crc16_table(get_int_immediate_from_string(poly)? as u16));
+        },
+
+        "table" => {
+            bytes.append(&mut generate_table_words(data).map_err(|e| TokenGenerationError(e.0))?);
+        },
+
+        _ => return Err(TokenGenerationError(format!("Invalid or unsupported data type: {}", category)))
     }
 
-    bytes
+    Ok(bytes)
 }
 
 
-/// Takes a line of assembly representing a data instruction and returns its token equivalent.
+/// Takes a line of assembly representing a data instruction and returns its token equivalent. Will return a
+/// `TokenGenerationError` if the line is not valid data.
 ///
 /// Assumes that the line has already been validated and line is an instruction and not blank.
-pub fn generate_data_tokens(line:&str, prev_label:Option<String>, mode:char) -> DataTokens {
+pub fn generate_data_tokens(line:&str, prev_label:Option<String>, mode:char) -> Result<DataTokens, TokenGenerationError> {
     let label:Option<String> = match line.find(":") {
         Some(index) => Some(line[..index].to_owned()),
         None => prev_label
     };
 
-    let category = &validate_data_type(line, mode).unwrap()[1..];
-    DataTokens::new(label, category.to_owned(), get_bytes_array_from_line(category, line))
+    let category = &validate_data_type(line, mode).map_err(|e| TokenGenerationError(e.0))?[1..];
+    if category == "vectors" {
+        // each vector is the upper then the lower half of the address of its label, filled in once it is known
+        let op_labels = remove_label(line)[".vectors".len()..].split(',')
+            .flat_map(|item| ["u", "l"].map(|half| format!("{}{}", half, item.trim())))
+            .collect();
+        return Ok(DataTokens::new(label, category.to_owned(), Vec::new()).with_op_labels(op_labels));
+    }
+
+    Ok(DataTokens::new(label, category.to_owned(), get_bytes_array_from_line(category, line)?))
 }
 
 
-/// Takes a line of assembly representing a text instruction and returns its token equivalent.
+/// Takes a line of assembly representing a text instruction and returns its token equivalent. Will return a
+/// `TokenGenerationError` if the line is not valid text.
 ///
 /// Assumes that the line has been validated and is not blank.
-pub fn generate_text_tokens(line:&str, prev_label:Option<String>, mode:char) -> TextTokens {
+pub fn generate_text_tokens(line:&str, prev_label:Option<String>, mode:char) -> Result<TextTokens, TokenGenerationError> {
     let label:Option<String> = match line.find(":") {
         Some(index) => Some(line[..index].to_owned()),
         None => prev_label
     };
 
-    let category = &validate_data_type(line, mode).unwrap()[1..];
-    TextTokens::new(label, get_bytes_array_from_line(category, line))
+    let category = &validate_data_type(line, mode).map_err(|e| TokenGenerationError(e.0))?[1..];
+    Ok(TextTokens::new(label, get_bytes_array_from_line(category, line)?))
 } 
 
 
-/// Takes a string of an integer in binary, decimal, or hexadecimal and returns it. Assumes that the
-/// input has already been validated.
-fn get_int_immediate_from_string(immediate:&str) -> i64 {
-    let parsed_immediate:i64;
-    if immediate.starts_with("0x") {
-        parsed_immediate = i64::from_str_radix(&immediate[2..], 16).unwrap();
-    } else if immediate.starts_with("0b") {
-        parsed_immediate = i64::from_str_radix(&immediate[2..], 2).unwrap();
-    } else {
-        parsed_immediate = immediate.parse().unwrap();
+/// Takes a line of assembly reserving space in the bss section and returns its token equivalent. Will
+/// return a `TokenGenerationError` if the size is not a positive integer.
+///
+/// Assumes that the line has been validated and is not blank.
+pub fn generate_bss_tokens(line:&str, prev_label:Option<String>) -> Result<BssTokens, TokenGenerationError> {
+    let label:Option<String> = match line.find(":") {
+        Some(index) => Some(line[..index].to_owned()),
+        None => prev_label
+    };
+
+    let data = remove_label(line);
+    let size = get_int_immediate_from_string(get_operand(data, 1)?)?;
+    Ok(BssTokens::new(label, fit_integer(size, data)?))
+}
+
+
+/// Takes a string of an integer in binary, octal, decimal, or hexadecimal, as read by
+/// `validation::parse_int_literal`, or an expression such as `(3*4)+1`, and returns it, or a
+/// `TokenGenerationError` if it is not an integer.
+fn get_int_immediate_from_string(immediate:&str) -> Result<i64, TokenGenerationError> {
+    if is_int_expression(immediate) {
+        return evaluate_int_expression(immediate, &HashMap::new()).map_err(|e| TokenGenerationError(e.0));
     }
 
-    parsed_immediate
+    parse_int_literal(immediate).ok_or_else(|| TokenGenerationError(format!("{} is not an integer", immediate)))
 }
 
 
-/// Takes a line of assembly representing an instruction and generates a `InstrTokens` from it.
+/// Takes a line of assembly representing an instruction and generates a `InstrTokens` from it. Will return
+/// a `TokenGenerationError` if the opcode or number of operands is not valid, or if an immediate is not a
+/// non-negative integer.
 ///
 /// Assumes that the line has already been validated and line is an instruction and not blank.
-pub fn generate_instr_tokens(line:&str, prev_label:Option<String>) -> InstrTokens {
+pub fn generate_instr_tokens(line:&str, prev_label:Option<String>) -> Result<InstrTokens, TokenGenerationError> {
     let label:Option<String> = match line.find(":") {
         Some(index) => Some(line[..index].to_owned()),
-        None => {
-            match prev_label.clone() {
-                Some(l) => Some(l.to_string()),
-                None => None
-            }
-        }
+        None => prev_label
     };
 
-    let opcode = validate_opcode(&line).unwrap();
-    let mut operands:Vec<String> = get_operands_from_line(&line, opcode);
+    let opcode = validate_opcode(line).map_err(|e| TokenGenerationError(e.0))?;
+    let mut operands:Vec<String> = get_operands_from_line(line, opcode);
+    let immediate = |operand:&str| -> Result<Option<u64>, TokenGenerationError> {
+        Ok(Some(fit_integer(get_int_immediate_from_string(operand)?, line)?))
+    };
 
-    match operands.len() {
+    let tokens = match operands.len() {
         0 => InstrTokens::new(label, opcode.to_owned(), None, None, None, None, None),
         1 => {
             if opcode == "syscall" {
-                return InstrTokens::new(label, opcode.to_owned(), None, None, None, 
-                                                Some(get_int_immediate_from_string(&operands[0])
-                                                .try_into().unwrap()), None)
+                return Ok(InstrTokens::new(label, opcode.to_owned(), None, None, None, immediate(&operands[0])?, None));
             }
             
             InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), None, None, None, None)
         },
 
         2 => {
-            let tokens:InstrTokens;
             if operands[1].starts_with("$") {
-                tokens = InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), 
-                                                Some(operands.remove(0)), None, None, None);
+                InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), 
+                                                Some(operands.remove(0)), None, None, None)
             } else if operands[1].starts_with("@") {
-                tokens = InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), None, None, 
-                                                None, Some(operands.remove(0)));
+                InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), None, None, 
+                                                None, Some(operands.remove(0)))
             } else {
-                tokens = InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), None, None, 
-                                                Some(get_int_immediate_from_string(&operands[1])
-                                                        .try_into().unwrap()), None);
+                let immediate = immediate(&operands.remove(1))?;
+                InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), None, None, immediate, None)
             }
-
-            tokens
         },
 
         4 => InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), 
@@ -192,27 +324,24 @@ pub fn generate_instr_tokens(line:&str, prev_label:Option<String>) -> InstrToken
                                                 Some(operands.remove(0)), None, 
                                                 Some(operands.remove(0))),
         3 => { // may or may not contain a label as an operand
-            let tokens:InstrTokens;
             if operands[2].starts_with("@") {
-                tokens = InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), 
+                InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), 
                                                 Some(operands.remove(0)), None, None, 
                                                 Some(operands.remove(0)))
             } else if !operands[2].starts_with("$") {
-                let operand_c = operands.remove(2);
-                tokens = InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), 
-                                                Some(operands.remove(0)), None, 
-                                                Some(get_int_immediate_from_string(&operand_c)
-                                                        .try_into().unwrap()), None)
+                let immediate = immediate(&operands.remove(2))?;
+                InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), 
+                                                Some(operands.remove(0)), None, immediate, None)
             } else {
-                tokens = InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), 
+                InstrTokens::new(label, opcode.to_owned(), Some(operands.remove(0)), 
                                                 Some(operands.remove(0)), Some(operands.remove(0)), 
-                                                None, None);
+                                                None, None)
             }
-            
-            tokens
         },
-        _ => panic!("Invalid number of operands (validation module has failed!)"),
-    }
+        _ => return Err(TokenGenerationError(format!("{} has an invalid number of operands", line)))
+    };
+
+    Ok(tokens)
 }
 
 
@@ -223,7 +352,7 @@ mod tests {
 
     #[test]
     fn test_token_generation_addi() {
-        let tokens = generate_instr_tokens("init: ADDI $g0, $zero, 1", None);
+        let tokens = generate_instr_tokens("init: ADDI $g0, $zero, 1", None).unwrap();
         assert_eq!(tokens.label.as_ref().unwrap(), "init");
         assert_eq!(tokens.opcode, "ADDI");
         assert_eq!(tokens.operand_a.as_ref().unwrap(), "$g0");
@@ -235,13 +364,13 @@ mod tests {
 
     #[test]
     fn test_token_generation_no_operands() {
-        let tokens = generate_instr_tokens("HALT", None);
+        let tokens = generate_instr_tokens("HALT", None).unwrap();
         assert_eq!(tokens.label, None);
         assert_eq!(tokens.opcode, "HALT");
         assert_eq!(tokens.operand_a, None);
         assert_eq!(tokens.op_label.as_ref().unwrap_or(&"none".to_owned()), &"none".to_owned());
 
-        let tokens = generate_instr_tokens("ATOM", None);
+        let tokens = generate_instr_tokens("ATOM", None).unwrap();
         assert_eq!(tokens.label, None);
         assert_eq!(tokens.opcode, "ATOM");
         assert_eq!(tokens.operand_a, None);
@@ -251,36 +380,50 @@ mod tests {
 
     #[test]
     fn test_instr_token_addi_all_bases() {
-        let tokens_decimal = generate_instr_tokens("init: ADDI $g0, $zero, 1", None);
+        let tokens_decimal = generate_instr_tokens("init: ADDI $g0, $zero, 1", None).unwrap();
         assert_eq!(*tokens_decimal.immediate.as_ref().unwrap(), 1);
 
-        let tokens_hex = generate_instr_tokens("init: ADDI $g0, $zero, 0b0010", None);
+        let tokens_hex = generate_instr_tokens("init: ADDI $g0, $zero, 0b0010", None).unwrap();
         assert_eq!(*tokens_hex.immediate.as_ref().unwrap(), 2);
 
-        let tokens_binary = generate_instr_tokens("init: ADDI $g0, $zero, 0x0004", None);
+        let tokens_binary = generate_instr_tokens("init: ADDI $g0, $zero, 0x0004", None).unwrap();
         assert_eq!(*tokens_binary.immediate.as_ref().unwrap(), 4);
     }
 
 
     #[test]
     fn test_syscall_generation_all_bases() {
-        let tokens_decimal = generate_instr_tokens("syscall 20", None);
+        let tokens_decimal = generate_instr_tokens("syscall 20", None).unwrap();
         assert_eq!(*tokens_decimal.immediate.as_ref().unwrap(), 20);
         assert_eq!(tokens_decimal.operand_a, None);
 
-        let tokens_hex = generate_instr_tokens("syscall 0x1F", None);
+        let tokens_hex = generate_instr_tokens("syscall 0x1F", None).unwrap();
         assert_eq!(*tokens_hex.immediate.as_ref().unwrap(), 31);
         assert_eq!(tokens_hex.operand_a, None);
 
-        let tokens_binary = generate_instr_tokens("syscall 0b11001", None);
+        let tokens_binary = generate_instr_tokens("syscall 0b11001", None).unwrap();
         assert_eq!(*tokens_binary.immediate.as_ref().unwrap(), 25);
         assert_eq!(tokens_binary.operand_a, None);
+
+        let tokens_octal = generate_instr_tokens("syscall 0o1_7", None).unwrap();
+        assert_eq!(*tokens_octal.immediate.as_ref().unwrap(), 15);
+    }
+
+
+    #[test]
+    fn test_data_with_underscores_and_octal() {
+        let tokens = generate_data_tokens("big: .long 1_000_000", None, 'd').unwrap();
+        assert_eq!(tokens.bytes, vec![0x000F, 0x4240]);
+
+        let tokens = generate_data_tokens("masks: .section 3 [0o17, 0b1010_1010, 0xFF_00]", None, 'd').unwrap();
+        assert_eq!(tokens.bytes, vec![0o17, 0b1010_1010, 0xFF00]);
+        assert!(generate_data_tokens("bad: .int 1_", None, 'd').is_err());
     }
 
 
     #[test]
     fn test_load_token_generation_with_label_opcode() {
-        let tokens = generate_instr_tokens("LOAD $g5, $g8, $g9, @target", None);
+        let tokens = generate_instr_tokens("LOAD $g5, $g8, $g9, @target", None).unwrap();
         assert_eq!(tokens.label.as_ref().unwrap_or(&"none".to_owned()), "none");
         assert_eq!(tokens.opcode, "LOAD");
         assert_eq!(tokens.operand_a.as_ref().unwrap(), "$g5");
@@ -292,7 +435,7 @@ mod tests {
 
     #[test]
     fn test_movli_with_label_opcode() {
-        let tokens = generate_instr_tokens("MOVLI $g0, @target", None);
+        let tokens = generate_instr_tokens("MOVLI $g0, @target", None).unwrap();
         assert_eq!(tokens.label.as_ref().unwrap_or(&"none".to_owned()), "none");
         assert_eq!(tokens.opcode, "MOVLI");
         assert_eq!(tokens.operand_a.as_ref().unwrap(), "$g0");
@@ -304,7 +447,7 @@ mod tests {
 
     #[test]
     fn test_movui_with_label_opcode() {
-        let tokens = generate_instr_tokens("MOVUI $g0, @target", None);
+        let tokens = generate_instr_tokens("MOVUI $g0, @target", None).unwrap();
         assert_eq!(tokens.label.as_ref().unwrap_or(&"none".to_owned()), "none");
         assert_eq!(tokens.opcode, "MOVUI");
         assert_eq!(tokens.operand_a.as_ref().unwrap(), "$g0");
@@ -314,7 +457,7 @@ mod tests {
 
     #[test]
     fn test_jump_token_generation_with_label_opcode() {
-        let tokens = generate_instr_tokens("JUMP $g8, $g9, @loop", None);
+        let tokens = generate_instr_tokens("JUMP $g8, $g9, @loop", None).unwrap();
         assert_eq!(tokens.label.as_ref().unwrap_or(&"none".to_owned()), &"none".to_owned());
         assert_eq!(tokens.opcode, "JUMP");
         assert_eq!(tokens.operand_a.as_ref().unwrap(), "$g8");
@@ -324,28 +467,38 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_ori_token_generation() {
+        let tokens = generate_instr_tokens("OUT $g3, 0xA", None).unwrap();
+        assert_eq!(tokens.opcode, "OUT");
+        assert_eq!(tokens.operand_a.as_ref().unwrap(), "$g3");
+        assert_eq!(tokens.operand_b, None);
+        assert_eq!(tokens.immediate, Some(10));
+    }
+
+
     #[test]
     fn test_label_on_prev_line() {
-        let tokens = generate_instr_tokens("JUMP $g8, $g9, @loop", Some("prev_label".to_owned())); 
+        let tokens = generate_instr_tokens("JUMP $g8, $g9, @loop", Some("prev_label".to_owned())).unwrap(); 
         assert_eq!(tokens.label.as_ref().unwrap(), "prev_label"); 
     }
 
 
     #[test]
     fn test_data_token_int() {
-        let tokens_decimal = generate_data_tokens("my_data: .int 50", None, 'd');
+        let tokens_decimal = generate_data_tokens("my_data: .int 50", None, 'd').unwrap();
         assert_eq!(tokens_decimal.label.unwrap_or("null".to_string()), "my_data");
         assert_eq!(tokens_decimal.category, "int");
         assert_eq!(tokens_decimal.bytes[0], 50);
         assert_eq!(tokens_decimal.bytes.len(), 1);
 
-        let tokens_hex = generate_data_tokens("my_data: .int 0b0101", None, 'd');
+        let tokens_hex = generate_data_tokens("my_data: .int 0b0101", None, 'd').unwrap();
         assert_eq!(tokens_hex.label.unwrap_or("null".to_string()), "my_data");
         assert_eq!(tokens_hex.category, "int");
         assert_eq!(tokens_hex.bytes[0], 0b0101);
         assert_eq!(tokens_hex.bytes.len(), 1);
 
-        let tokens_binary = generate_data_tokens("init: .int 0x001A", None, 'd');
+        let tokens_binary = generate_data_tokens("init: .int 0x001A", None, 'd').unwrap();
         assert_eq!(tokens_binary.label.unwrap_or("null".to_string()), "init");
         assert_eq!(tokens_binary.category, "int");
         assert_eq!(tokens_binary.bytes[0], 0x001A);
@@ -353,23 +506,34 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_data_token_lists() {
+        let tokens = generate_data_tokens("table: .int 1, 2, -3, 0x4", None, 'd').unwrap();
+        assert_eq!(tokens.label.unwrap(), "table");
+        assert_eq!(tokens.bytes, vec![1, 2, 0xFFFD, 4]);
+
+        let tokens = generate_data_tokens("table: .long 650000000, -1", None, 'd').unwrap();
+        assert_eq!(tokens.bytes, vec![0x26BE, 0x3680, 0xFFFF, 0xFFFF]);
+    }
+
+
     #[test]
     fn test_data_token_long() {
-        let tokens_decimal = generate_data_tokens("my_data: .long 650000000", None, 'd');
+        let tokens_decimal = generate_data_tokens("my_data: .long 650000000", None, 'd').unwrap();
         assert_eq!(tokens_decimal.label.unwrap_or("null".to_string()), "my_data");
         assert_eq!(tokens_decimal.category, "long");
         assert_eq!(tokens_decimal.bytes[0], 0x26BE);
         assert_eq!(tokens_decimal.bytes[1], 0x3680);
         assert_eq!(tokens_decimal.bytes.len(), 2);
 
-        let tokens_hex = generate_data_tokens("my_data: .long 0b01010101010101011010101010101010", None, 'd');
+        let tokens_hex = generate_data_tokens("my_data: .long 0b01010101010101011010101010101010", None, 'd').unwrap();
         assert_eq!(tokens_hex.label.unwrap_or("null".to_string()), "my_data");
         assert_eq!(tokens_hex.category, "long");
         assert_eq!(tokens_hex.bytes[0], 0x5555);
         assert_eq!(tokens_hex.bytes[1], 0xAAAA);
         assert_eq!(tokens_hex.bytes.len(), 2);
 
-        let tokens_binary = generate_data_tokens("init: .long 0xFEDCBA98", None, 'd');
+        let tokens_binary = generate_data_tokens("init: .long 0xFEDCBA98", None, 'd').unwrap();
         assert_eq!(tokens_binary.label.unwrap_or("null".to_string()), "init");
         assert_eq!(tokens_binary.category, "long");
         assert_eq!(tokens_binary.bytes[0], 0xFEDC);
@@ -380,7 +544,7 @@ mod tests {
 
     #[test]
     fn test_data_token_half() {
-        let tokens = generate_data_tokens(".half 5.25", Some("prev_label".to_owned()), 'd');
+        let tokens = generate_data_tokens(".half 5.25", Some("prev_label".to_owned()), 'd').unwrap();
         assert_eq!(tokens.label.unwrap_or("null".to_string()), "prev_label");
         assert_eq!(tokens.category, "half");
         assert_eq!(tokens.bytes[0], 0x4540);
@@ -388,9 +552,60 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_data_token_byte() {
+        let tokens = generate_data_tokens("header: .byte 0x12, 0x34, 0x56", None, 'd').unwrap();
+        assert_eq!(tokens.category, "byte");
+        assert_eq!(tokens.bytes, vec![0x3412, 0x0056]);
+
+        let tokens = generate_data_tokens(".byte -1, 200", None, 'd').unwrap();
+        assert_eq!(tokens.bytes, vec![0xC8FF]);
+    }
+
+
+    #[test]
+    fn test_data_token_byte_order() {
+        let tokens = generate_data_tokens("magic: .be32 0x12345678", None, 'd').unwrap();
+        assert_eq!(tokens.category, "be32");
+        assert_eq!(tokens.bytes, vec![0x3412, 0x7856]);
+
+        let tokens = generate_data_tokens(".le32 0x12345678", None, 'd').unwrap();
+        assert_eq!(tokens.category, "le32");
+        assert_eq!(tokens.bytes, vec![0x5678, 0x1234]);
+
+        let tokens = generate_data_tokens(".le32 -2", None, 'd').unwrap();
+        assert_eq!(tokens.bytes, vec![0xFFFE, 0xFFFF]);
+    }
+
+
+    #[test]
+    fn test_data_token_space() {
+        let tokens = generate_data_tokens("buffer: .space 256", None, 'd').unwrap();
+        assert_eq!(tokens.category, "space");
+        assert_eq!(tokens.bytes, vec![0x0000; 256]);
+
+        let tokens = generate_data_tokens(".zero 0x10", Some("scratch".to_owned()), 'd').unwrap();
+        assert_eq!(tokens.label.unwrap(), "scratch");
+        assert_eq!(tokens.category, "zero");
+        assert_eq!(tokens.bytes, vec![0x0000; 16]);
+    }
+
+
+    #[test]
+    fn test_data_token_fixed_point() {
+        let tokens = generate_data_tokens("gain: .q8_8 -1.5", None, 'd').unwrap();
+        assert_eq!(tokens.category, "q8_8");
+        assert_eq!(tokens.bytes, vec![0xFE80]);
+
+        let tokens = generate_data_tokens(".q16_16 0.75", None, 'd').unwrap();
+        assert_eq!(tokens.category, "q16_16");
+        assert_eq!(tokens.bytes, vec![0x0000, 0xC000]);
+    }
+
+
     #[test]
     fn test_data_token_float() {
-        let tokens = generate_data_tokens(".float -3104.76171875", Some("prev_label".to_owned()), 'd');
+        let tokens = generate_data_tokens(".float -3104.76171875", Some("prev_label".to_owned()), 'd').unwrap();
         assert_eq!(tokens.label.unwrap_or("null".to_string()), "prev_label");
         assert_eq!(tokens.category, "float");
         assert_eq!(tokens.bytes[0], 0xC542);
@@ -401,7 +616,7 @@ mod tests {
 
     #[test]
     fn test_data_token_char() {
-        let tokens = generate_data_tokens("character: .char 'ß", None, 'd');
+        let tokens = generate_data_tokens("character: .char 'ß", None, 'd').unwrap();
         assert_eq!(tokens.label.unwrap_or("null".to_string()), "character");
         assert_eq!(tokens.category, "char");
         assert_eq!(tokens.bytes[0], 0x00DF);
@@ -411,7 +626,7 @@ mod tests {
 
     #[test]
     fn test_text_exact_length() {
-        let tokens = generate_data_tokens("txt: .text 7 \"Hello!\"", None, 't');
+        let tokens = generate_data_tokens("txt: .text 7 \"Hello!\"", None, 't').unwrap();
         assert_eq!(tokens.label.unwrap_or("null".to_string()), "txt");
         assert_eq!(tokens.category, "text");
         assert_eq!(tokens.bytes[0], 0x0048);
@@ -424,7 +639,7 @@ mod tests {
 
     #[test]
     fn test_text_non_exact_length() {
-        let tokens = generate_data_tokens("txt: .text 10 \"Hello!\"", None, 't');
+        let tokens = generate_data_tokens("txt: .text 10 \"Hello!\"", None, 't').unwrap();
         assert_eq!(tokens.label.unwrap_or("null".to_string()), "txt");
         assert_eq!(tokens.category, "text");
         assert_eq!(tokens.bytes[0], 0x0048);
@@ -436,9 +651,35 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_strings() {
+        let tokens = generate_text_tokens("greeting: .asciiz \"Hi!\"", None, 't').unwrap();
+        assert_eq!(tokens.label.as_deref(), Some("greeting"));
+        assert_eq!(tokens.bytes, vec![0x0048, 0x0069, 0x0021, 0x0000]);
+
+        let tokens = generate_text_tokens(".ascii \"Hi!\"", None, 't').unwrap();
+        assert_eq!(tokens.bytes, vec![0x0048, 0x0069, 0x0021]);
+        assert_eq!(generate_text_tokens("empty: .asciiz \"\"", None, 't').unwrap().bytes, vec![0x0000]);
+    }
+
+
+    #[test]
+    fn test_escape_sequences() {
+        assert_eq!(generate_data_tokens("newline: .char '\\n'", None, 'd').unwrap().bytes, vec![0x000A]);
+        assert_eq!(generate_data_tokens("quote: .char '\\''", None, 'd').unwrap().bytes, vec![0x0027]);
+        assert_eq!(generate_data_tokens("letter: .char '\\x41'", None, 'd').unwrap().bytes, vec![0x0041]);
+
+        let tokens = generate_data_tokens("txt: .text 6 \"a\\tb\\\"\\\\\"", None, 't').unwrap();
+        assert_eq!(tokens.bytes, vec![0x0061, 0x0009, 0x0062, 0x0022, 0x005C, 0x0000]);
+
+        let tokens = generate_text_tokens("line: .asciiz \"Hi\\r\\n\"", None, 't').unwrap();
+        assert_eq!(tokens.bytes, vec![0x0048, 0x0069, 0x000D, 0x000A, 0x0000]);
+    }
+
+
     #[test]
     fn test_text_non_latin_text() {
-        let tokens = generate_data_tokens("chinese: .text 6 \"你好世界!\"", None, 't');
+        let tokens = generate_data_tokens("chinese: .text 6 \"你好世界!\"", None, 't').unwrap();
         assert_eq!(tokens.label.unwrap_or("null".to_string()), "chinese");
         assert_eq!(tokens.category, "text");
         assert_eq!(tokens.bytes.len(), 6);
@@ -447,7 +688,7 @@ mod tests {
 
     #[test]
     fn test_section_exact_length() {
-        let tokens = generate_data_tokens("data_pts: .section 4 [0x0100, 0b0011, 10, 0x00A4]", None, 'd');
+        let tokens = generate_data_tokens("data_pts: .section 4 [0x0100, 0b0011, 10, 0x00A4]", None, 'd').unwrap();
         assert_eq!(tokens.label.unwrap_or("null".to_string()), "data_pts");
         assert_eq!(tokens.category, "section");
         assert_eq!(tokens.bytes[0], 0x0100);
@@ -460,7 +701,7 @@ mod tests {
 
     #[test]
     fn test_section_non_exact_length() {
-        let tokens = generate_data_tokens("data_pts: .section 6 [0x0100, 0b0011, 10, 0x00A4]", None, 'd');
+        let tokens = generate_data_tokens("data_pts: .section 6 [0x0100, 0b0011, 10, 0x00A4]", None, 'd').unwrap();
         assert_eq!(tokens.label.unwrap_or("null".to_string()), "data_pts");
         assert_eq!(tokens.category, "section");
         assert_eq!(tokens.bytes[3], 0x00A4);
@@ -468,4 +709,51 @@ mod tests {
         assert_eq!(tokens.bytes[5], 0x0000);
         assert_eq!(tokens.bytes.len(), 6);
     }
+
+
+    #[test]
+    fn test_data_token_rand() {
+        let tokens = generate_data_tokens("noise: .rand 64, seed=42", None, 'd').unwrap();
+        assert_eq!(tokens.label.unwrap(), "noise");
+        assert_eq!(tokens.category, "rand");
+        assert_eq!(tokens.bytes.len(), 64);
+        assert_eq!(tokens.bytes, generate_data_tokens(".rand 64,seed=0x2A", None, 'd').unwrap().bytes);
+        assert_ne!(tokens.bytes, generate_data_tokens(".rand 64, seed=43", None, 'd').unwrap().bytes);
+        assert_eq!(&generate_random_words(3, 0), &[0xE220, 0x6E78, 0x06C4]);
+    }
+
+
+    #[test]
+    fn test_data_token_crc16_table() {
+        let tokens = generate_data_tokens("crc_table: .crc16_table poly=0x1021", None, 'd').unwrap();
+        assert_eq!(tokens.label.unwrap(), "crc_table");
+        assert_eq!(tokens.category, "crc16_table");
+        assert_eq!(tokens.bytes.len(), 256);
+        assert_eq!(&tokens.bytes[..4], &[0x0000, 0x1021, 0x2042, 0x3063]);
+        assert_eq!(tokens.bytes[255], 0x1EF0);
+    }
+
+
+    #[test]
+    fn test_bss_tokens() {
+        let tokens = generate_bss_tokens("buffer: .space 0x100", None).unwrap();
+        assert_eq!(tokens.label.unwrap(), "buffer");
+        assert_eq!(tokens.size, 256);
+
+        let tokens = generate_bss_tokens(".space 16", Some("scratch".to_owned())).unwrap();
+        assert_eq!(tokens.label.unwrap(), "scratch");
+    }
+
+
+    #[test]
+    fn test_invalid_tokens() {
+        assert_eq!(generate_instr_tokens("ADDI $g0, $zero, 99999999999999999999", None).unwrap_err().0,
+            "99999999999999999999 is not an integer");
+        assert!(generate_instr_tokens("syscall -1", None).is_err());
+        assert!(generate_instr_tokens("BOGUS $g0", None).is_err());
+        assert_eq!(generate_data_tokens("big: .int 70000", None, 'd').unwrap_err().0, "70000 does not fit in .int 70000");
+        assert!(generate_data_tokens("txt: .text 7 \"Hello!", None, 't').is_err());
+        assert!(generate_data_tokens("noise: .rand 64", None, 'd').is_err());
+        assert!(generate_bss_tokens("buffer: .space", None).is_err());
+    }
 }
@@ -1,14 +1,53 @@
 use std::fmt;
 use crate::errors::TokenTypeError;
+#[cfg(feature = "serde")]
+use crate::errors::TokenFormatError;
 
 
+/// The version of the format tokens are serialized in, which is increased whenever a change to the tokens
+/// means that tokens serialized by an older assembler can no longer be read correctly
+pub const TOKEN_FORMAT_VERSION:u32 = 1;
+
+
+/// Represents where an item is in the source, as the file, line, and column of the start of its
+/// statement, which is displayed as `file.asm:123:8` in diagnostics
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourcePos {
+    pub file: String,
+    pub line: usize,
+    pub column: usize
+}
+
+
+impl fmt::Display for SourcePos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+
+/// Takes the position of an item in the source and returns it followed by ": ", to go in front of an error
+/// about the item, or nothing if it did not come from a source file
+pub fn located(pos:Option<&SourcePos>) -> String {
+    pos.map(|pos| format!("{}: ", pos)).unwrap_or_default()
+}
+
 
 /// Can contain both types of tokens a line of asm can take
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(clippy::enum_variant_names)]
 pub enum FileTokens {
     InstrTokens(InstrTokens),
     DataTokens(DataTokens),
-    TextTokens(TextTokens)
+    TextTokens(TextTokens),
+    BssTokens(BssTokens),
+    OrgTokens(OrgTokens),
+    AliasTokens(AliasTokens),
+    DeprecatedTokens(DeprecatedTokens),
+    TraceTokens(TraceTokens),
+    ExportTableTokens(ExportTableTokens)
 }
 
 
@@ -19,20 +58,29 @@ impl FileTokens {
     /// TODO: make sure that a `FileTokens` with a label of "null" and one without a label do not return
     /// true when used with this function
     pub fn compare_label(&self, other: FileTokens) -> bool {
-        let null_str = &"null".to_string();
-        let self_label = match self {
-            FileTokens::InstrTokens(t) => t.label.as_ref().unwrap_or(null_str),
-            FileTokens::DataTokens(t) => t.label.as_ref().unwrap_or(null_str),
-            FileTokens::TextTokens(t) => t.label.as_ref().unwrap_or(null_str)
-        };
+        self.label().unwrap_or("null") == other.label().unwrap_or("null")
+    }
+
+
+    /// Gets the label of the instruction, data item, text, bss buffer, or trace point, if it has one. An
+    /// `.org`, alias, deprecation, or export table never has a label of its own.
+    pub fn label(&self) -> Option<&str> {
+        match self {
+            FileTokens::InstrTokens(t) => t.label.as_deref(),
+            FileTokens::DataTokens(t) => t.label.as_deref(),
+            FileTokens::TextTokens(t) => t.label.as_deref(),
+            FileTokens::BssTokens(t) => t.label.as_deref(),
+            FileTokens::TraceTokens(t) => t.label.as_deref(),
+            FileTokens::OrgTokens(_) | FileTokens::AliasTokens(_) | FileTokens::DeprecatedTokens(_) | FileTokens::ExportTableTokens(_) => None
+        }
+    }
 
-        let other_label = match other {
-            FileTokens::InstrTokens(t) => t.label.unwrap_or("null".to_string()),
-            FileTokens::DataTokens(t) => t.label.unwrap_or("null".to_string()),
-            FileTokens::TextTokens(t) => t.label.unwrap_or("null".to_string())
-        };
 
-        self_label == &other_label
+    /// Returns true if the token takes up no words and so never enters or moves a section, which is the case
+    /// for an alias, a deprecation, and a trace point or export table which has not been expanded yet.
+    pub fn is_zero_width(&self) -> bool {
+        matches!(self, FileTokens::AliasTokens(_) | FileTokens::DeprecatedTokens(_) | FileTokens::TraceTokens(_)
+            | FileTokens::ExportTableTokens(_))
     }
 
 
@@ -42,7 +90,8 @@ impl FileTokens {
         match self {
             FileTokens::InstrTokens(t) => Ok(t.clone()),
             FileTokens::DataTokens(_) => Err(TokenTypeError("Invalid token type detected!".to_string())),
-            FileTokens::TextTokens(_) => Err(TokenTypeError("Invalid token type detected!".to_string()))
+            FileTokens::TextTokens(_) => Err(TokenTypeError("Invalid token type detected!".to_string())),
+            _ => Err(TokenTypeError("Invalid token type detected!".to_string()))
         }
     }
 
@@ -53,7 +102,8 @@ impl FileTokens {
         match self {
             FileTokens::DataTokens(t) => Ok(t.clone()),
             FileTokens::InstrTokens(_) => Err(TokenTypeError("Invalid token type detected!".to_string())),
-            FileTokens::TextTokens(_) => Err(TokenTypeError("Invalid token type detected!".to_string()))
+            FileTokens::TextTokens(_) => Err(TokenTypeError("Invalid token type detected!".to_string())),
+            _ => Err(TokenTypeError("Invalid token type detected!".to_string()))
         }
     }
 
@@ -64,15 +114,42 @@ impl FileTokens {
         match self {
             FileTokens::DataTokens(_) => Err(TokenTypeError("Invalid token type detected!".to_string())),
             FileTokens::InstrTokens(_) => Err(TokenTypeError("Invalid token type detected!".to_string())),
-            FileTokens::TextTokens(t) => Ok(t.clone())
+            FileTokens::TextTokens(t) => Ok(t.clone()),
+            _ => Err(TokenTypeError("Invalid token type detected!".to_string()))
+        }
+    }
+
+
+    /// Attempts to get a `BssTokens` from a `FileTokens` enum. Will return a `BssTokens` if the enum
+    /// is of the right type, or a `TokensTypeError` if not.
+    pub fn try_get_bss_tokens(&self) -> Result<BssTokens, TokenTypeError> {
+        match self {
+            FileTokens::BssTokens(t) => Ok(t.clone()),
+            _ => Err(TokenTypeError("Invalid token type detected!".to_string()))
+        }
+    }
+
+
+    /// Gets the position in the source of the instruction or data item, if it came from a source file
+    pub fn pos(&self) -> Option<&SourcePos> {
+        match self {
+            FileTokens::InstrTokens(t) => t.pos.as_ref(),
+            FileTokens::DataTokens(t) => t.pos.as_ref(),
+            FileTokens::AliasTokens(t) => t.pos.as_ref(),
+            FileTokens::DeprecatedTokens(t) => t.pos.as_ref(),
+            FileTokens::TraceTokens(t) => t.pos.as_ref(),
+            FileTokens::ExportTableTokens(t) => t.pos.as_ref(),
+            _ => None
         }
     }
 }
 
 
 /// Represents the core components of an instruction, including the opcode, and the optional label and 
-/// operands, and possible operand label
+/// operands, and possible operand label. The position is that of the line in the source the instruction
+/// came from, which is kept by any instructions it is expanded into.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InstrTokens {
     pub label: Option<String>,
     pub opcode: String,
@@ -80,7 +157,8 @@ pub struct InstrTokens {
     pub operand_b: Option<String>,
     pub operand_c: Option<String>,
     pub immediate: Option<u64>, // used as a set of bytes
-    pub op_label: Option<String>
+    pub op_label: Option<String>,
+    pub pos: Option<SourcePos>
 }
 
 impl InstrTokens {
@@ -89,15 +167,23 @@ impl InstrTokens {
         operand_b:Option<String>, operand_c:Option<String>, immediate:Option<u64>, 
         op_label:Option<String>) -> InstrTokens {
             InstrTokens {
-                label: label,
-                opcode: opcode,
-                operand_a: operand_a,
-                operand_b: operand_b,
-                operand_c: operand_c,
-                immediate: immediate,
-                op_label: op_label
+                label,
+                opcode,
+                operand_a,
+                operand_b,
+                operand_c,
+                immediate,
+                op_label,
+                pos: None
             }
     }
+
+
+    /// Returns the instruction with the given position in the source
+    pub fn with_pos(mut self, pos:Option<SourcePos>) -> InstrTokens {
+        self.pos = pos;
+        self
+    }
 }
 
 impl fmt::Debug for InstrTokens {
@@ -115,23 +201,47 @@ impl fmt::Debug for InstrTokens {
 }
 
 
-/// Represents the components of a data instruction, including the label, category, and value
+/// Represents the components of a data instruction, including the label, category, value, and position
+/// in the source. A word which holds half of the address of a label, such as in the table of a `.switch`,
+/// has its label operand in `op_labels` until the labels are substituted, written `u@label` for the upper
+/// half or `l@label` for the lower.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DataTokens {
     pub label: Option<String>,
     pub category: String,
-    pub bytes: Vec<u16>
+    pub bytes: Vec<u16>,
+    pub op_labels: Vec<String>,
+    pub pos: Option<SourcePos>
 }
 
 
 impl DataTokens {
     pub fn new(label:Option<String>, category:String, bytes:Vec<u16>) -> DataTokens {
         DataTokens {
-            label: label,
-            category: category,
-            bytes: bytes
+            label,
+            category,
+            bytes,
+            op_labels: Vec::new(),
+            pos: None
         }
     }
+
+
+    /// Returns the data item with the given position in the source
+    pub fn with_pos(mut self, pos:Option<SourcePos>) -> DataTokens {
+        self.pos = pos;
+        self
+    }
+
+
+    /// Returns the data item holding a word for each of the given label operands, which are zero until the
+    /// labels are substituted
+    pub fn with_op_labels(mut self, op_labels:Vec<String>) -> DataTokens {
+        self.bytes = vec![0; op_labels.len()];
+        self.op_labels = op_labels;
+        self
+    }
 }
 
 
@@ -144,6 +254,7 @@ impl fmt::Debug for DataTokens {
 
 /// Represents the components of a data instruction, including the label, category, and value
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextTokens {
     pub label: Option<String>,
     pub bytes: Vec<u16>
@@ -153,8 +264,8 @@ pub struct TextTokens {
 impl TextTokens {
     pub fn new(label:Option<String>, bytes:Vec<u16>) -> TextTokens {
         TextTokens {
-            label: label,
-            bytes: bytes
+            label,
+            bytes
         }
     }
 }
@@ -165,3 +276,281 @@ impl fmt::Debug for TextTokens {
         write!(f, "{}\t{:04X?}", self.label.clone().unwrap_or("null".to_string()), self.bytes)
     }
 }
+
+
+/// Represents a buffer in the bss section, which has a label and a size in words but no contents, as it
+/// is filled with zeros when the program is loaded rather than being written to the binary
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BssTokens {
+    pub label: Option<String>,
+    pub size: u64
+}
+
+
+impl BssTokens {
+    pub fn new(label:Option<String>, size:u64) -> BssTokens {
+        BssTokens {
+            label,
+            size
+        }
+    }
+}
+
+
+impl fmt::Debug for BssTokens {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\tspace\t{}", self.label.clone().unwrap_or("null".to_string()), self.size)
+    }
+}
+
+
+/// Represents an `.org` directive, which moves the next item in its section forward to the given address,
+/// leaving a gap which is filled with the fill word when the binary is written. If the `.org` comes from a
+/// label pinned with `.at` or from `--section-start`, the label or section is kept so that it can be named if
+/// it cannot be placed. An `.align`
+/// is held in the same way with the alignment it moves forward to, and its gap is always filled with zeros.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrgTokens {
+    pub section: char,
+    pub address: i64,
+    pub pinned_label: Option<String>,
+    pub alignment: Option<i64>
+}
+
+
+impl OrgTokens {
+    pub fn new(section:char, address:i64, pinned_label:Option<String>) -> OrgTokens {
+        OrgTokens {
+            section,
+            address,
+            pinned_label,
+            alignment: None
+        }
+    }
+
+
+    /// Takes the section and a number of words, and returns the tokens of an `.align` which moves the next
+    /// item forward to the next address which is a multiple of that number.
+    pub fn align(section:char, alignment:i64) -> OrgTokens {
+        OrgTokens {
+            section,
+            address: 0,
+            pinned_label: None,
+            alignment: Some(alignment)
+        }
+    }
+
+
+    /// Takes the address its section has reached and returns the address the next item is moved to, which
+    /// for an `.org` may be before the address reached if it would move backwards.
+    pub fn target(&self, addr:i64) -> i64 {
+        match self.alignment {
+            Some(alignment) => (addr + alignment - 1).div_euclid(alignment) * alignment,
+            None => self.address
+        }
+    }
+}
+
+
+impl fmt::Debug for OrgTokens {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.alignment {
+            Some(alignment) => write!(f, "{}\talign\t{}", self.section, alignment),
+            None => write!(f, "{}\torg\t0x{:08X}", self.section, self.address)
+        }
+    }
+}
+
+
+/// Represents a `.set` directive, which defines another name for a label so that it resolves to the same
+/// address, such as a stable public name for a label which may be renamed. It takes up no words, and is
+/// resolved once the label table has been built.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AliasTokens {
+    pub section: char,
+    pub alias: String,
+    pub target: String,
+    pub pos: Option<SourcePos>
+}
+
+
+impl AliasTokens {
+    pub fn new(section:char, alias:String, target:String, pos:Option<SourcePos>) -> AliasTokens {
+        AliasTokens {
+            section,
+            alias,
+            target,
+            pos
+        }
+    }
+}
+
+
+impl fmt::Debug for AliasTokens {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\tset\t{}\t@{}", self.section, self.alias, self.target)
+    }
+}
+
+
+/// Represents a `.deprecated` directive, which marks a label as deprecated so that every reference to it
+/// gives a warning with the message, if there is one, such as which label to use instead. It takes up no
+/// words.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeprecatedTokens {
+    pub section: char,
+    pub label: String,
+    pub message: Option<String>,
+    pub pos: Option<SourcePos>
+}
+
+
+impl DeprecatedTokens {
+    pub fn new(section:char, label:String, message:Option<String>, pos:Option<SourcePos>) -> DeprecatedTokens {
+        DeprecatedTokens {
+            section,
+            label,
+            message,
+            pos
+        }
+    }
+}
+
+
+impl fmt::Debug for DeprecatedTokens {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\tdeprecated\t{}\t{:?}", self.section, self.label, self.message.clone().unwrap_or_default())
+    }
+}
+
+
+/// Represents a `.trace` directive, which marks a point in the code that outputs a number when it runs, so
+/// that the message written for it can be looked up. The numbers are only given once the whole program has
+/// been tokenized, when `trace::expand_traces` replaces each one with the instructions that output it.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceTokens {
+    pub label: Option<String>,
+    pub message: String,
+    pub pos: Option<SourcePos>
+}
+
+
+impl TraceTokens {
+    pub fn new(label:Option<String>, message:String, pos:Option<SourcePos>) -> TraceTokens {
+        TraceTokens {
+            label,
+            message,
+            pos
+        }
+    }
+}
+
+
+impl fmt::Debug for TraceTokens {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\ttrace\t\"{}\"", self.label.as_ref().unwrap_or(&"none".to_owned()), self.message)
+    }
+}
+
+
+/// Represents a function marked with `.global`, qualified by the namespace it is in, with the slot of the
+/// export table it was given, if any
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExportedFunction {
+    pub name: String,
+    pub slot: Option<u16>,
+    pub pos: Option<SourcePos>
+}
+
+
+/// Represents the functions a source file exports, along with its `.export_table` if it has one, in which
+/// case the address is that of the table. The slots are only given once the whole program has been
+/// tokenized, so that the functions of every file can go in the one table, when `exports::expand_export_tables`
+/// replaces the table with its slots.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExportTableTokens {
+    pub address: Option<i64>,
+    pub functions: Vec<ExportedFunction>,
+    pub pos: Option<SourcePos>
+}
+
+
+impl ExportTableTokens {
+    pub fn new(address:Option<i64>, functions:Vec<ExportedFunction>, pos:Option<SourcePos>) -> ExportTableTokens {
+        ExportTableTokens {
+            address,
+            functions,
+            pos
+        }
+    }
+}
+
+
+impl fmt::Debug for ExportTableTokens {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let address = self.address.map(|address| format!("{:#06X}", address)).unwrap_or_else(|| "none".to_owned());
+        let functions:Vec<&str> = self.functions.iter().map(|function| function.name.as_str()).collect();
+        write!(f, "{}	export_table	{}", address, functions.join(", "))
+    }
+}
+
+
+/// Represents the tokens of a program as they are serialized, with the version of the format they were
+/// serialized in, so that a tool reading them can tell if they were written by an assembler whose tokens
+/// it does not understand rather than reading them wrongly
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SerializedTokens {
+    pub format_version: u32,
+    pub tokens: Vec<FileTokens>
+}
+
+
+#[cfg(feature = "serde")]
+impl SerializedTokens {
+    /// Takes the tokens of a program and returns them ready to be serialized in the current format
+    pub fn new(tokens:Vec<FileTokens>) -> SerializedTokens {
+        SerializedTokens { format_version: TOKEN_FORMAT_VERSION, tokens }
+    }
+
+
+    /// Returns the tokens which were deserialized, or a `TokenFormatError` if they were serialized in a
+    /// different version of the format to `TOKEN_FORMAT_VERSION`
+    pub fn into_tokens(self) -> Result<Vec<FileTokens>, TokenFormatError> {
+        if self.format_version != TOKEN_FORMAT_VERSION {
+            return Err(TokenFormatError(format!(
+                "the tokens are in version {} of the format, but this assembler reads version {}", self.format_version, TOKEN_FORMAT_VERSION
+            )));
+        }
+
+        Ok(self.tokens)
+    }
+}
+
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::token_types::*;
+    use crate::testing::tokens_from_str;
+
+
+    #[test]
+    fn test_serialized_tokens() {
+        let tokens = tokens_from_str("init:\n    ADDI $g0, $zero, 3\n    BNE $g8, $g9, @init\ndata:\n    value: .int 5\n\
+            text:\n    msg: .text 3 \"hi\"\nbss:\n    buffer: .space 4\n");
+        let serialized = toml::to_string(&SerializedTokens::new(tokens.clone())).unwrap();
+        let deserialized:SerializedTokens = toml::from_str(&serialized).unwrap();
+        assert_eq!(format!("{:?}", deserialized.into_tokens().unwrap()), format!("{:?}", tokens));
+
+        let newer = serialized.replacen(&format!("format_version = {}", TOKEN_FORMAT_VERSION), "format_version = 99", 1);
+        let error = toml::from_str::<SerializedTokens>(&newer).unwrap().into_tokens().unwrap_err();
+        assert_eq!(error.0, format!("the tokens are in version 99 of the format, but this assembler reads version {}", TOKEN_FORMAT_VERSION));
+    }
+}
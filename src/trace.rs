@@ -0,0 +1,228 @@
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use crate::errors::AsmValidationError;
+use crate::escapes::parse_escapes;
+use crate::hardening::split_label;
+use crate::token_generator::generate_instr_tokens;
+use crate::token_types::{FileTokens, SourcePos, TraceTokens};
+use crate::validation::validate_label;
+
+
+/// The I/O port a trace point writes its number to unless another output is chosen
+pub const TRACE_PORT:u8 = 0xE;
+
+
+/// Represents how a trace point hands its number to the emulator or operating system, which is in `$g8`
+/// either way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceOutput {
+    /// Writes the number to the I/O port with `OUT`
+    Port(u8),
+    /// Calls the interrupt service routine with `syscall`, with the number as its argument
+    Syscall(u8)
+}
+
+
+/// Trace points write to `TRACE_PORT` by default
+impl Default for TraceOutput {
+    fn default() -> TraceOutput {
+        TraceOutput::Port(TRACE_PORT)
+    }
+}
+
+
+/// Represents a trace point which has been given a number, with the message written for it in the source,
+/// escape sequences and all
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TracePoint {
+    pub id: u16,
+    pub message: String,
+    pub pos: Option<SourcePos>
+}
+
+
+/// Takes a line of assembly and returns true if it is a trace point, such as `.trace "entered loop"`, which
+/// may be labelled
+pub fn is_trace_directive(line:&str) -> bool {
+    let (_, instr) = split_label(line);
+    instr.starts_with(".trace ") || instr == ".trace"
+}
+
+
+/// Takes a line of assembly holding a trace point in the form `[<label>:] .trace "<message>"`, the section and
+/// namespace it is in, the label of the line before it if it has none of its own, and its position in the
+/// source, and returns its tokens. Will return an `AsmValidationError` if it is not in the code section, the
+/// message is not in double quotes or has an invalid escape sequence, or the label is invalid.
+pub fn parse_trace_directive(line:&str, mode:char, namespace:&Option<String>, prev_label:Option<String>, pos:Option<SourcePos>)
+        -> Result<TraceTokens, AsmValidationError> {
+    if mode != 'c' {
+        return Err(AsmValidationError(format!(".trace can only be used in the code section, but is used on line {}", line)));
+    }
+
+    let malformed = || AsmValidationError(format!(
+        "Incorrect format for .trace on line {}, which should be .trace \"<message>\"", line
+    ));
+
+    let (label, instr) = split_label(line);
+    let message = instr[".trace".len()..].trim();
+    let message = message.strip_prefix('"').and_then(|message| message.strip_suffix('"')).ok_or_else(malformed)?;
+    parse_escapes(line, message)?;
+
+    if let Some(label) = label {
+        validate_label(line, label)?;
+    }
+
+    let label = label.map(|label| match namespace {
+        Some(namespace) => format!("{}.{}", namespace, label),
+        None => label.to_owned()
+    });
+
+    Ok(TraceTokens::new(label.or(prev_label), message.to_owned(), pos))
+}
+
+
+/// Takes the number of a trace point and how it is output, and returns the lines it expands into, which
+/// keep the value of `$g8` by holding it on the stack while the number is output.
+fn trace_lines(id:u16, output:TraceOutput) -> Vec<String> {
+    let emit = match output {
+        TraceOutput::Port(port) => format!("OUT $g8, {}", port),
+        TraceOutput::Syscall(service) => format!("syscall {}", service)
+    };
+
+    vec![
+        "SUBI $sp, $sp, 1".to_owned(),
+        "STORE $g8, $sp, $zero".to_owned(),
+        format!("MOVLI $g8, {:#04X}", id & 0xFF),
+        format!("MOVUI $g8, {:#04X}", id >> 8),
+        emit,
+        "LOAD $g8, $sp, $zero".to_owned(),
+        "ADDI $sp, $sp, 1".to_owned()
+    ]
+}
+
+
+/// Takes a stream of tokens and how trace points are output, and returns the tokens with every trace point
+/// replaced by the instructions which output its number, along with the trace points in the order they were
+/// numbered. The numbers are given in the order the trace points appear in the program, so they are unique
+/// even when several source files are assembled together, and a label on a trace point moves to its first
+/// instruction.
+///
+/// A trace point keeps the value of every register, but not the flags, and needs one word of stack. Will
+/// return an `AsmValidationError` if there are more trace points than can be numbered in 16 bits.
+pub fn expand_traces(tokens_stream:Vec<FileTokens>, output:TraceOutput) -> Result<(Vec<FileTokens>, Vec<TracePoint>), AsmValidationError> {
+    let mut expanded:Vec<FileTokens> = Vec::with_capacity(tokens_stream.len());
+    let mut points:Vec<TracePoint> = Vec::new();
+    for tokens in tokens_stream {
+        let t = match tokens {
+            FileTokens::TraceTokens(t) => t,
+            tokens => {
+                expanded.push(tokens);
+                continue;
+            }
+        };
+
+        let id = u16::try_from(points.len())
+            .map_err(|_| AsmValidationError("The program has more than 65536 trace points".to_owned()))?;
+        let mut label = t.label;
+        for line in trace_lines(id, output) {
+            let instr_tokens = generate_instr_tokens(&line, label.take()).map_err(|e| AsmValidationError(e.0))?;
+            expanded.push(FileTokens::InstrTokens(instr_tokens.with_pos(t.pos.clone())));
+        }
+
+        points.push(TracePoint { id, message: t.message, pos: t.pos });
+    }
+
+    Ok((expanded, points))
+}
+
+
+/// Takes the trace points of a program and returns the message of each, one per line, as its number, its
+/// position in the source, and the message in double quotes as it was written.
+pub fn render_trace_table(points:&[TracePoint]) -> String {
+    points.iter()
+        .map(|point| {
+            let pos = point.pos.as_ref().map(|pos| pos.to_string()).unwrap_or_else(|| "-".to_owned());
+            format!("{:04X} {} \"{}\"\n", point.id, pos, point.message)
+        })
+        .collect()
+}
+
+
+/// Takes the trace points of a program and writes the table given by `render_trace_table` to the given file.
+pub fn write_trace_table(filename:&str, points:&[TracePoint]) -> Result<(), Box<dyn Error>> {
+    let mut trace_file = BufWriter::new(
+        OpenOptions::new().create(true).write(true).truncate(true).open(filename)?);
+
+    write!(trace_file, "{}", render_trace_table(points))?;
+    trace_file.flush()?;
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::trace::*;
+    use crate::token_types::InstrTokens;
+    use crate::{assemble_source, process_source_into_tokens};
+
+
+    const SOURCE:&str = "init:\n    .trace \"start\"\n    ADDI $g0, $zero, 3\nloop: .trace \"loop \\\"again\\\"\"\n    SUBI $g0, $g0, 1\n\
+        \x20   CMP $g0, $zero\n    BNE $g8, $g9, @loop\n    HALT\n";
+
+
+    #[test]
+    fn test_parse_trace_directive() {
+        assert!(is_trace_directive(".trace \"hello\""));
+        assert!(is_trace_directive("here: .trace \"hello\""));
+        assert!(!is_trace_directive("trace: ADD $g0, $g1, $g2"));
+        assert_eq!(parse_trace_directive(".trace \"x: y\"", 'c', &None, None, None).unwrap().message, "x: y");
+
+        let t = parse_trace_directive("here: .trace \"hi\\n\"", 'c', &None, None, None).unwrap();
+        assert_eq!((t.label.as_deref(), t.message.as_str()), (Some("here"), "hi\\n"));
+        let t = parse_trace_directive(".trace \"hi\"", 'c', &None, Some("prev".to_owned()), None).unwrap();
+        assert_eq!(t.label.as_deref(), Some("prev"));
+        let t = parse_trace_directive("here: .trace \"hi\"", 'c', &Some("uart".to_owned()), None, None).unwrap();
+        assert_eq!(t.label.as_deref(), Some("uart.here"));
+
+        assert!(parse_trace_directive(".trace hi", 'c', &None, None, None).is_err());
+        assert!(parse_trace_directive(".trace \"hi", 'c', &None, None, None).is_err());
+        assert!(parse_trace_directive(".trace \"\\q\"", 'c', &None, None, None).unwrap_err().0.starts_with("[E0503]"));
+        assert!(parse_trace_directive("1bad: .trace \"hi\"", 'c', &None, None, None).is_err());
+    }
+
+
+    #[test]
+    fn test_expand_traces() {
+        let tokens = process_source_into_tokens(SOURCE, None).unwrap();
+        let (expanded, points) = expand_traces(tokens, TraceOutput::default()).unwrap();
+        let messages:Vec<(u16, &str, usize)> = points.iter().map(|point| (point.id, point.message.as_str(), point.pos.as_ref().unwrap().line)).collect();
+        assert_eq!(messages, vec![(0, "start", 2), (1, "loop \\\"again\\\"", 4)]);
+
+        let instrs:Vec<InstrTokens> = expanded.iter().filter_map(|token| token.try_get_instr_tokens().ok()).collect();
+        assert_eq!(instrs[0].label.as_deref(), Some("init"));
+        assert_eq!(instrs[8].label.as_deref(), Some("loop"));
+        assert_eq!((instrs[10].opcode.as_str(), instrs[10].immediate), ("MOVLI", Some(1)));
+        assert_eq!((instrs[12].opcode.as_str(), instrs[12].immediate), ("OUT", Some(TRACE_PORT as u64)));
+
+        let (expanded, _) = expand_traces(process_source_into_tokens(SOURCE, None).unwrap(), TraceOutput::Syscall(7)).unwrap();
+        let opcode = expanded[4].try_get_instr_tokens().unwrap();
+        assert_eq!((opcode.opcode.as_str(), opcode.immediate), ("syscall", Some(7)));
+    }
+
+
+    #[test]
+    fn test_render_trace_table() {
+        let (_, points) = expand_traces(process_source_into_tokens(SOURCE, None).unwrap(), TraceOutput::default()).unwrap();
+        assert_eq!(render_trace_table(&points), "0000 <source>:2:5 \"start\"\n0001 <source>:4:1 \"loop \\\"again\\\"\"\n");
+    }
+
+
+    #[test]
+    fn test_trace_in_source() {
+        assert!(assemble_source(SOURCE).is_ok());
+
+        let error = process_source_into_tokens("init:\n    HALT\ndata:\n    .trace \"no\"\n", None).unwrap_err().0;
+        assert!(error.contains(".trace can only be used in the code section"), "{}", error);
+    }
+}
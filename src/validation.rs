@@ -1,5 +1,13 @@
+use std::collections::HashMap;
 use std::str;
+use half::f16;
 use crate::errors::AsmValidationError;
+use crate::escapes::parse_escapes;
+use crate::expressions::{evaluate_int_expression, generate_table_words, is_int_expression};
+use crate::fixed_point::FixedPointFormat;
+use crate::suggestions::did_you_mean;
+use crate::token_types::{located, FileTokens, SourcePos};
+use crate::warnings::{AsmWarning, WarningKind, WarningSink};
 
 
 /// Takes a line of assembly code, for example `ADD $g0, $zero, $g1`, and returns an `Err` if it is not 
@@ -17,7 +25,7 @@ pub fn validate_asm_line(line:&str, mode:char) -> Result<(), AsmValidationError>
             Err(e) => {
                 match validate_data_type(line, mode) {
                     Ok(_) => {
-                        return Err(AsmValidationError(format!("{} is for data, but is in the instructions section, which is invalid", line)));
+                        return Err(AsmValidationError::coded("E0501", format!("{} is for data, but is in the instructions section, which is invalid", line)));
                     },
 
                     Err(_) => {
@@ -36,7 +44,7 @@ pub fn validate_asm_line(line:&str, mode:char) -> Result<(), AsmValidationError>
         Err(e) => {
             match validate_opcode(line) {
                 Ok(_) => {
-                    return Err(AsmValidationError(format!("{} is an instruction, but is in the data section, which is invalid", line)));
+                    return Err(AsmValidationError::coded("E0501", format!("{} is an instruction, but is in the data section, which is invalid", line)));
                 },
 
                 Err(_) => {
@@ -46,7 +54,7 @@ pub fn validate_asm_line(line:&str, mode:char) -> Result<(), AsmValidationError>
         }
     };
 
-    validate_data_format(line, data_type)?;
+    validate_data_format(line, data_type, mode)?;
     Ok(())
 }
 
@@ -55,7 +63,7 @@ pub fn validate_asm_line(line:&str, mode:char) -> Result<(), AsmValidationError>
 pub fn remove_label(line:&str) -> &str {
     match line.find(":") {
         Some(index) => {
-            &line[index+1..].trim()
+            line[index+1..].trim()
         },
         None => line,
     }
@@ -65,16 +73,22 @@ pub fn remove_label(line:&str) -> &str {
 /// Takes a line of assembly and checks if it is a valid data instruction, such as .text or .float. Returns 
 /// an `AsmValidationErr` if there is no valid data type, and returns the data type if there is.
 pub fn validate_data_type(line:&str, mode:char) -> Result<&str, AsmValidationError> {
-    let valid_data_types:[&str;7] = [".int", ".long", ".half", ".float", ".section", ".char", ".text"];
+    let valid_data_types:[&str;20] = [
+        ".int", ".long", ".half", ".float", ".q8_8", ".q16_16", ".le32", ".be32", ".byte", ".section", ".char",
+        ".text", ".ascii", ".asciiz", ".space", ".zero", ".rand", ".crc16_table", ".table", ".vectors"
+    ];
+    let text_types = [".text", ".ascii", ".asciiz"];
     let data_type = remove_label(line).split(" ").collect::<Vec<&str>>()[0];
     if !valid_data_types.contains(&data_type) {
-        return Err(AsmValidationError(format!("{} is not a valid data type on line {}", data_type, line)));
+        return Err(AsmValidationError::coded("E0502", format!("{} is not a valid data type on line {}", data_type, line)));
     }
 
-    if mode == 't' && data_type != ".text" {
-        return Err(AsmValidationError(format!("{} is not text, yet is in the text section", line)));
-    } else if mode != 't' && data_type == ".text" {
-        return Err(AsmValidationError(format!("{} is text, yet is not in the text section", line)));
+    if mode == 't' && !text_types.contains(&data_type) {
+        return Err(AsmValidationError::coded("E0501", format!("{} is not text, yet is in the text section", line)));
+    } else if mode != 't' && text_types.contains(&data_type) {
+        return Err(AsmValidationError::coded("E0501", format!("{} is text, yet is not in the text section", line)));
+    } else if mode == 'b' && data_type != ".space" && data_type != ".zero" {
+        return Err(AsmValidationError::coded("E0501", format!("{} has contents, yet is in the bss section", line)));
     }
 
     Ok(data_type)
@@ -83,9 +97,35 @@ pub fn validate_data_type(line:&str, mode:char) -> Result<&str, AsmValidationErr
 
 /// Checks that a `Vec<&str>` has a certain number of items and returns an `AsmValidationError` if it 
 /// does not.
-fn validate_token_vec(line:&str, vec:&Vec<&str>, req_len:usize) -> Result<(), AsmValidationError> {
+fn validate_token_vec(line:&str, vec:&[&str], req_len:usize) -> Result<(), AsmValidationError> {
     if vec.len() != req_len {
-        return Err(AsmValidationError(format!("Incorrect format for tokenisation on line {}", line)));
+        return Err(AsmValidationError::coded("E0302", format!("Incorrect format for tokenisation on line {}", line)));
+    }
+
+    Ok(())
+}
+
+
+/// Takes a line holding a list of comma-separated values after its data type, such as `table: .int 1, 2, 3`,
+/// and the data type, and returns each value with any whitespace around it removed.
+pub fn get_data_list<'a>(line:&'a str, data_type:&str) -> Vec<&'a str> {
+    remove_label(line)[data_type.len()..].split(',').map(|item| item.trim()).collect()
+}
+
+
+/// Takes a line holding a list of integers after its data type, such as `table: .int 1, 2, 3`, and checks
+/// that there is at least one and that each fits into the given number of bits. Will return an
+/// `AsmValidationError` if not.
+fn validate_int_list(line:&str, data_type:&str, bits:i16) -> Result<(), AsmValidationError> {
+    let items = get_data_list(line, data_type);
+    if items.iter().any(|item| item.is_empty() || item.contains(char::is_whitespace)) {
+        return Err(AsmValidationError::coded("E0503", format!(
+            "Incorrect format for {} on line {}, which should be {} <value>, <value>, ...", data_type, line, data_type
+        )));
+    }
+
+    for item in items {
+        validate_int_immediate(item, bits, true)?;
     }
 
     Ok(())
@@ -101,14 +141,14 @@ fn validate_float_immediate(line:&str, immediate:&str, short:bool) -> Result<(),
             if short {
                 let min_max_value = 4_293_918_720.0;
                 if val > min_max_value || val < -min_max_value {
-                    return Err(AsmValidationError(format!(
+                    return Err(AsmValidationError::coded("E0104", format!(
                         "{} cannot fit into a 16-bit IEEE 754 format number on line {}", immediate, line
                     ))); 
                 }
             } else {
                 let min_max_value:f32 = f32::MAX;
                 if val > min_max_value || val < -min_max_value {
-                    return Err(AsmValidationError(format!(
+                    return Err(AsmValidationError::coded("E0104", format!(
                         "{} cannot fit into a 32-bit IEEE 754 format number on line {}", immediate, line
                     ))); 
                 }
@@ -116,7 +156,7 @@ fn validate_float_immediate(line:&str, immediate:&str, short:bool) -> Result<(),
         },
 
         Err(_) => {
-            return Err(AsmValidationError(format!("{} is not a valid immediate on line {}", immediate, line)));
+            return Err(AsmValidationError::coded("E0102", format!("{} is not a valid immediate on line {}", immediate, line)));
         }
     };
 
@@ -125,18 +165,18 @@ fn validate_float_immediate(line:&str, immediate:&str, short:bool) -> Result<(),
 
 
 /// Takes a character immediate in the format `'<char>'` and checks that it is a valid UTF-8 character in 
-/// that format. If not, an `AsmValidationError` is returned.
+/// that format, which may be an escape sequence such as `'\n'`. If not, an `AsmValidationError` is returned.
 fn validate_char_immediate(line:&str, immediate:&str) -> Result<(), AsmValidationError> {
-    if !immediate.starts_with("'") || !immediate.ends_with("'") {
-        return Err(AsmValidationError(format!(
+    if immediate.len() < 2 || !immediate.starts_with("'") || !immediate.ends_with("'") {
+        return Err(AsmValidationError::coded("E0503", format!(
             "Immediate {} on line \"{}\" is not in a valid format - should be label: .char '<char>'", 
             immediate, line
         )));
     }
 
-    let imm_char:&str = &immediate[1..immediate.len() - 1];
+    let imm_char = parse_escapes(line, &immediate[1..immediate.len() - 1])?;
     if imm_char.chars().collect::<Vec<char>>().len() != 1 {
-        return Err(AsmValidationError(format!(
+        return Err(AsmValidationError::coded("E0503", format!(
             "Immediate {} on line \"{}\" is not in a valid format - more than 1 character found", 
             immediate, line
         )));
@@ -151,13 +191,13 @@ fn validate_char_immediate(line:&str, immediate:&str) -> Result<(), AsmValidatio
 fn validate_char_instr(line:&str) -> Result<(), AsmValidationError> {
     let mut instr = remove_label(line).trim();
     if !instr.starts_with(".char") {
-        return Err(AsmValidationError(format!("{} is not a valid character data instruction", line)));
+        return Err(AsmValidationError::coded("E0503", format!("{} is not a valid character data instruction", line)));
     }
 
     // checks that the character immediate format is '<character>'
-    instr = &instr[5..].trim();
+    instr = instr[5..].trim();
     if !(instr.starts_with("'") && instr.ends_with("'")) {
-        return Err(AsmValidationError(format!("{} is not a valid character data instruction", line)));
+        return Err(AsmValidationError::coded("E0503", format!("{} is not a valid character data instruction", line)));
     }
 
     match validate_char_immediate(line, instr) {
@@ -181,10 +221,10 @@ fn validate_char_instr(line:&str) -> Result<(), AsmValidationError> {
 /// ASSUMES LABEL HAS ALREADY BEEN REMOVED!
 fn get_valid_array_size(line:&str) -> Result<i64, AsmValidationError> {
     let tokens:Vec<&str> = line.split(" ").collect();
-    match i64::from_str_radix(tokens[1].trim(), 10) {
+    match tokens[1].trim().parse::<i64>() {
         Ok(val) => Ok(val),
         Err(_) => {
-            Err(AsmValidationError(format!(
+            Err(AsmValidationError::coded("E0503", format!(
                 "{} is not a valid size for the array on line {}", tokens[1].trim(), line
             )))
         }
@@ -192,8 +232,9 @@ fn get_valid_array_size(line:&str) -> Result<i64, AsmValidationError> {
 }
 
 
-/// Takes a line of assembly containing a .text data instruction and determines if it is valid or not,
-/// will return an `AsmValidationError` if not.
+/// Takes a line of assembly containing a .text data instruction and determines if it is valid or not, with
+/// any escape sequences in the text counting as the one character they stand for. Will return an
+/// `AsmValidationError` if not.
 fn validate_text_instr(line:&str) -> Result<(), AsmValidationError> {
     let instr = remove_label(line);
     let array_size = get_valid_array_size(instr)?;
@@ -201,32 +242,33 @@ fn validate_text_instr(line:&str) -> Result<(), AsmValidationError> {
     let text_start_index = match instr.find("\"") {
         Some(index) => index,
         None => {
-            return Err(AsmValidationError(format!(
+            return Err(AsmValidationError::coded("E0503", format!(
                 "{} is not a correctly formatted .text data instruction - have you used double quotes?", 
                 line
             )));
         }
     };
     
-    if !instr.ends_with("\"") {
-        return Err(AsmValidationError(format!(
+    let text = &instr[text_start_index..];
+    if text.len() < 2 || !text.ends_with("\"") {
+        return Err(AsmValidationError::coded("E0503", format!(
             "{} is not a correctly formatted .text data instruction - have you used double quotes?", line
         )));
     }
 
-    let text = &instr[text_start_index..];
     match str::from_utf8(instr.as_bytes()) {
         Ok(_) => {},
         Err(_) => {
-            return Err(AsmValidationError(format!(
+            return Err(AsmValidationError::coded("E0503", format!(
                 "Text {} on line \"{}\" is not valid UTF-8", text, line
             )));
         }
     };
 
-    let str_len = text.chars().collect::<Vec<char>>().len() - 1;
+    // one more than the length of the text, for the null terminator
+    let str_len = parse_escapes(line, &text[1..text.len() - 1])?.chars().count() + 1;
     if str_len > array_size.try_into().unwrap() {
-        return Err(AsmValidationError(format!(
+        return Err(AsmValidationError::coded("E0504", format!(
             "Text is too long for {} bytes on line {}. Have you taken the null terminator into account?",
             array_size, line
         )));
@@ -236,6 +278,29 @@ fn validate_text_instr(line:&str) -> Result<(), AsmValidationError> {
 }
 
 
+/// Takes a line of assembly containing a `.ascii` or `.asciiz` data instruction, which is a string in double
+/// quotes without a size, and checks that it is valid. The string, with each escape sequence counting as one
+/// word and along with the null terminator of an `.asciiz`, can be at most 65535 words long, like the size of
+/// a `.text`. Will return an `AsmValidationError` if not.
+fn validate_string_instr(line:&str, data_type:&str) -> Result<(), AsmValidationError> {
+    let text = remove_label(line)[data_type.len()..].trim();
+    if text.len() < 2 || !text.starts_with('"') || !text.ends_with('"') {
+        return Err(AsmValidationError::coded("E0503", format!(
+            "{} is not a correctly formatted {} data instruction - have you used double quotes?", line, data_type
+        )));
+    }
+
+    let words = parse_escapes(line, &text[1..text.len() - 1])?.chars().count() + usize::from(data_type == ".asciiz");
+    if words > u16::MAX.into() {
+        return Err(AsmValidationError::coded("E0504", format!(
+            "Text is {} words long on line {}, which is more than the 65535 a string can take", words, line
+        )));
+    }
+
+    Ok(())
+}
+
+
 /// Takes a line of assembly for a bytes section and checks that it is formatted properly. Will return
 /// an `AsmValidationError` if not.
 fn validate_bytes_section_instr(line:&str) -> Result<(), AsmValidationError> {
@@ -245,14 +310,14 @@ fn validate_bytes_section_instr(line:&str) -> Result<(), AsmValidationError> {
     let array_start_index = match instr.find("[") {
         Some(index) => index,
         None => {
-            return Err(AsmValidationError(format!(
+            return Err(AsmValidationError::coded("E0503", format!(
                 "{} is not a properly formatted array, which requires square brackets []", instr
             )));
         }
     };
 
     if !instr.ends_with("]") {
-        return Err(AsmValidationError(format!(
+        return Err(AsmValidationError::coded("E0503", format!(
             "{} is not a properly formatted array, which requires square brackets []", instr
         ))); 
     }
@@ -267,7 +332,7 @@ fn validate_bytes_section_instr(line:&str) -> Result<(), AsmValidationError> {
     }
 
     if array_contents.len() > array_size.try_into().unwrap() {
-        return Err(AsmValidationError(format!(
+        return Err(AsmValidationError::coded("E0504", format!(
             "Bytes array is too long for section of length {} on line {}.", array_size, line
         )));
     }
@@ -276,19 +341,64 @@ fn validate_bytes_section_instr(line:&str) -> Result<(), AsmValidationError> {
 }
 
 
-/// Takes a line of assembly of a data instruction and its data type and checks that the data provided 
-/// matches that data type
-fn validate_data_format(line:&str, data_type:&str) -> Result<(), AsmValidationError> {
+/// Takes a line of assembly for a list of bytes in the form `.byte <byte>, <byte>, ...` and checks that there
+/// is at least one and that each fits into 8 bits, either as an unsigned number or as a negative decimal. Will
+/// return an `AsmValidationError` if not.
+fn validate_byte_list_instr(line:&str) -> Result<(), AsmValidationError> {
+    let items:Vec<&str> = remove_label(line)[".byte".len()..].split(',').map(|item| item.trim()).collect();
+    if items.iter().any(|item| item.is_empty()) {
+        return Err(AsmValidationError::coded("E0503", format!(
+            "Incorrect format for bytes on line {}, which should be .byte <byte>, <byte>, ...", line
+        )));
+    }
+
+    for item in items {
+        validate_int_immediate(item, 8, item.starts_with('-'))?;
+    }
+
+    Ok(())
+}
+
+
+/// Takes a line of assembly for a block of pseudo-random words in the form `.rand <count>, seed=<seed>`
+/// and checks that the count is a 16-bit number of at least 1 and the seed is a 32-bit number. Will return
+/// an `AsmValidationError` if not.
+fn validate_rand_instr(line:&str) -> Result<(), AsmValidationError> {
+    let operands:Vec<&str> = remove_label(line)[".rand".len()..].split(',').map(|item| item.trim()).collect();
+    let seed = match operands[..] {
+        [_, seed] => seed.strip_prefix("seed="),
+        _ => None
+    };
+
+    let seed = match seed {
+        Some(seed) => seed,
+        None => {
+            return Err(AsmValidationError::coded("E0503", format!(
+                "Incorrect format for random data on line {}, which should be .rand <count>, seed=<seed>", line
+            )));
+        }
+    };
+
+    if validate_int_immediate(operands[0], 16, false)? == 0 {
+        return Err(AsmValidationError::coded("E0505", format!("The random data on line {} must be at least 1 word", line)));
+    }
+
+    validate_int_immediate(seed, 32, false)?;
+    Ok(())
+}
+
+
+/// Takes a line of assembly of a data instruction, its data type, and the section it is in, and checks that
+/// the data provided matches that data type
+fn validate_data_format(line:&str, data_type:&str, mode:char) -> Result<(), AsmValidationError> {
     let tokens:Vec<&str> = remove_label(line).split(" ").collect();
     match data_type {
-        ".int" => { // label: .int <16-bit integer>
-            validate_token_vec(line, &tokens, 2)?;
-            validate_int_immediate(tokens[1], 16, true)?;
+        ".int" => { // label: .int <16-bit integer>, ...
+            validate_int_list(line, data_type, 16)?;
         },
 
-        ".long" => { // label: .long <32-bit integer>
-            validate_token_vec(line, &tokens, 2)?;
-            validate_int_immediate(tokens[1], 32, true)?;
+        ".long" => { // label: .long <32-bit integer>, ...
+            validate_int_list(line, data_type, 32)?;
         },
 
         ".half" => { // label: .half <16-bit IEEE 754 float>
@@ -301,6 +411,22 @@ fn validate_data_format(line:&str, data_type:&str) -> Result<(), AsmValidationEr
             validate_float_immediate(line, tokens[1], false)?;
         },
 
+        ".le32" | ".be32" => { // label: .be32 <32-bit integer>
+            validate_token_vec(line, &tokens, 2)?;
+            validate_int_immediate(tokens[1], 32, true)?;
+        },
+
+        ".q8_8" | ".q16_16" => { // label: .q8_8 <decimal>
+            validate_token_vec(line, &tokens, 2)?;
+            if let Some(format) = FixedPointFormat::from_data_type(data_type) {
+                format.to_raw(tokens[1], line)?;
+            }
+        },
+
+        ".byte" => { // label: .byte <byte>, <byte>, ...
+            validate_byte_list_instr(line)?;
+        },
+
         ".section" => { // label: .section [<bytes>]
             validate_bytes_section_instr(line)?;
         },
@@ -313,8 +439,53 @@ fn validate_data_format(line:&str, data_type:&str) -> Result<(), AsmValidationEr
             validate_text_instr(line)?;
         },
 
+        ".ascii" | ".asciiz" => { // label: .asciiz "<string>"
+            validate_string_instr(line, data_type)?;
+        },
+
+        ".rand" => { // label: .rand <number of words>, seed=<seed>
+            validate_rand_instr(line)?;
+        },
+
+        ".crc16_table" => { // label: .crc16_table poly=<16-bit polynomial>
+            validate_token_vec(line, &tokens, 2)?;
+            match tokens[1].strip_prefix("poly=") {
+                Some(poly) => validate_int_immediate(poly, 16, false)?,
+                None => {
+                    return Err(AsmValidationError::coded("E0503", format!(
+                        "Incorrect format for CRC table on line {}, which should be .crc16_table poly=<polynomial>", line
+                    )));
+                }
+            };
+        },
+
+        ".table" => { // label: .table <count>, <variable> => <expression>
+            generate_table_words(line)?;
+        },
+
+        ".vectors" => { // label: .vectors @<label>, @<label>, ...
+            let items = get_data_list(line, data_type);
+            if items.iter().any(|item| item.is_empty() || item.contains(char::is_whitespace)) {
+                return Err(AsmValidationError::coded("E0503", format!(
+                    "Incorrect format for .vectors on line {}, which should be .vectors @<label>, @<label>, ...", line
+                )));
+            }
+
+            for item in items {
+                validate_operand_label(line, item)?;
+            }
+        },
+
+        ".space" | ".zero" => { // label: .space <number of words>
+            // zeroed words in the data section are written to the binary, so are capped like a .section
+            validate_token_vec(line, &tokens, 2)?;
+            if validate_int_immediate(tokens[1], if mode == 'b' { 32 } else { 16 }, false)? == 0 {
+                return Err(AsmValidationError::coded("E0505", format!("The space reserved on line {} must be at least 1 word", line)));
+            }
+        },
+
         _ => {
-            return Err(AsmValidationError(format!("{} is not a valid data type on line {}", data_type, line)));
+            return Err(AsmValidationError::coded("E0502", format!("{} is not a valid data type on line {}", data_type, line)));
         }
     }
 
@@ -323,7 +494,8 @@ fn validate_data_format(line:&str, data_type:&str) -> Result<(), AsmValidationEr
 
 
 /// Takes a line of assembly, extracts the opcode from it, and checks that it is a valid opcode. If an 
-/// invalid opcode is found, an `AsmValidationError` will be thrown.
+/// invalid opcode is found, an `AsmValidationError` will be thrown, suggesting the closest valid opcode if
+/// it looks like a typo.
 pub fn validate_opcode(line:&str) -> Result<&str, AsmValidationError> {
     let valid_opcodes:[&str;28] = [
         "ADD", "SUB", "ADDI", "SUBI", "SLL", "SRL", "SRA", "NAND", "OR", "ADDC", "SUBC",
@@ -332,9 +504,11 @@ pub fn validate_opcode(line:&str) -> Result<&str, AsmValidationError> {
     ];
 
     // get the opcode and remove any label there may be
-    let opcode:&str = remove_label(line).split(" ").filter(|item| *item != "").collect::<Vec<&str>>()[0];
+    let opcode:&str = remove_label(line).split(" ").filter(|item| !item.is_empty()).collect::<Vec<&str>>()[0];
     if !valid_opcodes.contains(&opcode) {
-        return Err(AsmValidationError(format!("{} is not a valid opcode on line {}", opcode, line)));
+        return Err(AsmValidationError::coded("E0301", format!(
+            "{} is not a valid opcode on line {}{}", opcode, line, did_you_mean(opcode, valid_opcodes)
+        )));
     }
 
     Ok(opcode)
@@ -343,22 +517,23 @@ pub fn validate_opcode(line:&str) -> Result<&str, AsmValidationError> {
 
 /// Gets operands from a string by removing the operand and any comments and labels, and then split it up 
 /// using commas
-pub fn get_operands_from_line<'a>(line:&'a str, opcode:&str) -> Vec<String> {    
-    let opcode_start_index = line.find(opcode).expect(&format!("Could not find opcode {} in line {}", opcode, line));
+pub fn get_operands_from_line(line:&str, opcode:&str) -> Vec<String> {    
+    let opcode_start_index = line.find(opcode).unwrap_or_else(|| panic!("Could not find opcode {} in line {}", opcode, line));
     let opcode_end_index = opcode_start_index + opcode.len();
     let comment_start_index = line.find(";").unwrap_or(line.len());
 
     let operands_section = line[opcode_end_index..comment_start_index].to_owned();
     let operands:Vec<String> = operands_section.split(",")
                                     .map(|operand| operand.trim().to_owned())
-                                    .filter(|operand| operand != "")
+                                    .filter(|operand| !operand.is_empty())
                                     .collect();
 
     operands
 }
 
 
-/// Checks that a given register string is a valid register and returns an `AsmValidationError` if not
+/// Checks that a given register string is a valid register and returns an `AsmValidationError` if not,
+/// suggesting the closest valid register if it looks like a typo
 fn validate_register(register:&str) -> Result<(), AsmValidationError> {
     let valid_registers:[&str;16] = [
         "$zero", "$g0", "$g1", "$g2", "$g3", "$g4", "$g5", "$g6", "$g7", "$g8", "$g9",
@@ -366,46 +541,62 @@ fn validate_register(register:&str) -> Result<(), AsmValidationError> {
     ];
 
     if !valid_registers.contains(&register) {
-        return Err(AsmValidationError(format!("{} is not a valid register", register)));
+        return Err(AsmValidationError::coded("E0201", format!(
+            "{} is not a valid register{}", register, did_you_mean(register, valid_registers)
+        )));
     }
 
     Ok(())
 }
 
 
+/// Takes an integer literal and returns the radix it is written in, being 2 for a `0b` prefix, 8 for `0o`,
+/// 16 for `0x`, and 10 for no prefix, along with its digits after the prefix
+fn split_int_radix(literal:&str) -> (u32, &str) {
+    [("0b", 2), ("0o", 8), ("0x", 16)].iter()
+        .find_map(|(prefix, radix)| literal.strip_prefix(prefix).map(|digits| (*radix, digits)))
+        .unwrap_or((10, literal))
+}
+
+
+/// Takes an integer literal in binary (`0b`), octal (`0o`), hexadecimal (`0x`), or decimal, and returns its
+/// value, or `None` if it is not a valid literal. Underscores may separate the digits to make long literals
+/// readable, such as `0b1010_1010` or `1_000_000`, but may not come first or last.
+pub fn parse_int_literal(literal:&str) -> Option<i64> {
+    let (radix, digits) = split_int_radix(literal);
+    let unsigned = digits.strip_prefix(['-', '+']).unwrap_or(digits);
+    if unsigned.starts_with('_') || unsigned.ends_with('_') {
+        return None;
+    }
+
+    i64::from_str_radix(&digits.replace('_', ""), radix).ok()
+}
+
+
 /// Checks that a given immediate is a valid immediate and returns it or an `AsmValidationError` if not. 
 /// Will ensure that immediate is within the range the given number of bits can handle, and is in a valid 
-/// format given the prefix (0x for hexadecimal and 0b for binary, no prefix for decimal).
-fn validate_int_immediate(operand:&str, bits:i16, signed:bool) -> Result<i64, AsmValidationError> {
+/// format given the prefix (0x for hexadecimal, 0o for octal, and 0b for binary, no prefix for decimal), with
+/// any underscores between the digits ignored. An expression such as `(3*4)+1` is evaluated as described for
+/// `expressions::evaluate_int_expression`, and its value is range checked in the same way as a decimal
+/// immediate.
+pub fn validate_int_immediate(operand:&str, bits:i16, signed:bool) -> Result<i64, AsmValidationError> {
     let immediate:i64;
     let decimal:bool;
-    if operand.starts_with("0b") {
-        immediate = match i64::from_str_radix(&operand[2..], 2) {
-            Ok(val) => val,
-            Err(_) => {
-                return Err(AsmValidationError(format!("Could not parse binary immediate {}", operand)));
-            }
-        };
-
-        decimal = false;
-    } else if operand.starts_with("0x") {
-        immediate = match i64::from_str_radix(&operand[2..], 16) {
-            Ok(val) => val,
-            Err(_) => {
-                return Err(AsmValidationError(format!("Could not parse hexadecimal immediate {}", operand)));
-            }
-        };
-
-        decimal = false;
+    if is_int_expression(operand) {
+        immediate = evaluate_int_expression(operand, &HashMap::new())
+            .map_err(|e| AsmValidationError::coded("E0102", e.0))?;
+        decimal = true;
     } else {
-        immediate = match operand.parse() {
-            Ok(val) => val,
-            Err(_) => {
-                return Err(AsmValidationError(format!("Could not parse immediate {}", operand)));
+        let radix = split_int_radix(operand).0;
+        immediate = match parse_int_literal(operand) {
+            Some(val) => val,
+            None => {
+                let kind = match radix { 2 => "binary ", 8 => "octal ", 16 => "hexadecimal ", _ => "" };
+                return Err(AsmValidationError::coded("E0102", format!("Could not parse {}immediate {}", kind, operand)));
             }
         };
 
-        decimal = true;
+        decimal = radix == 10;
     }
 
     let max_immediate:i64;
@@ -419,9 +610,9 @@ fn validate_int_immediate(operand:&str, bits:i16, signed:bool) -> Result<i64, As
     }
 
     if immediate < 0 && !(signed && decimal) {
-        return Err(AsmValidationError(format!("Unsigned immediate operand {} cannot be negative", operand))); 
+        return Err(AsmValidationError::coded("E0103", format!("Unsigned immediate operand {} cannot be negative", operand))); 
     } else if immediate > max_immediate || (immediate < min_immediate && signed) {
-        return Err(AsmValidationError(format!("Immediate {} cannot fit into {} bits", operand, bits)));
+        return Err(AsmValidationError::coded("E0101", format!("Immediate {} cannot fit into {} bits", operand, bits)));
     }
 
     Ok(immediate)
@@ -430,31 +621,88 @@ fn validate_int_immediate(operand:&str, bits:i16, signed:bool) -> Result<i64, As
 
 /// Takes an operand from an instruction and verifies that it is a valid label operand in the form
 /// @<operand> where operand contains only alphanumeric characters and underscores, and does not
-/// start with a number. 
+/// start with a number, optionally followed by an offset such as `@table+8`.
 ///
 /// Returns an `AsmValidationError` if the label operand is invalid.
 fn validate_label_operand(line:&str, operand:&str) -> Result<(), AsmValidationError> {
     if !operand.starts_with("@") {
-        return Err(AsmValidationError(format!(
+        return Err(AsmValidationError::coded("E0402", format!(
             "{} on line {} is not a valid operand as it does not start with an '@' symbol", line, operand
         )));
     }
 
-    validate_operand_label(line, operand)?;
+    let (label, offset) = split_label_offset(operand);
+    validate_operand_label(line, label)?;
+    if offset.is_some_and(|offset| parse_label_offset(offset).is_none()) {
+        return Err(AsmValidationError::coded("E0403", format!(
+            "The offset of the label operand {} on line {} is not an integer which fits into 32 bits", operand, line
+        )));
+    }
+
+    Ok(())
+}
+
+
+/// Takes a line of assembly and its opcode, and checks that every comma separates two operands. Returns an
+/// `AsmValidationError` giving the column of the offending comma if there is a leading comma, a trailing
+/// comma, or two commas with nothing between them, as these would otherwise be silently ignored.
+fn validate_operand_separators(line:&str, opcode:&str) -> Result<(), AsmValidationError> {
+    let opcode_end_index = line.find(opcode).unwrap_or(0) + opcode.len();
+    let comment_start_index = line.find(';').unwrap_or(line.len());
+    let operands_section = &line[opcode_end_index..comment_start_index];
+    if !operands_section.contains(',') {
+        return Ok(());
+    }
+
+    let pieces:Vec<&str> = operands_section.split(',').collect();
+    let mut comma_index = opcode_end_index;
+    for (index, piece) in pieces.iter().enumerate() {
+        let column = line[..comma_index + piece.len()].chars().count() + 1;
+        if piece.trim().is_empty() {
+            if index == 0 {
+                return Err(AsmValidationError::coded("E0303", format!(
+                    "Missing operand before the comma at column {} on line {}", column, line
+                )));
+            } else if index == pieces.len() - 1 {
+                return Err(AsmValidationError::coded("E0303", format!(
+                    "Trailing comma at column {} on line {}", line[..comma_index].chars().count(), line
+                )));
+            } else {
+                return Err(AsmValidationError::coded("E0303", format!(
+                    "Missing operand between the commas at column {} on line {}", column, line
+                )));
+            }
+        }
+
+        comma_index += piece.len() + 1;
+    }
 
     Ok(())
 }
 
 
+/// Checks that the number of operands given to an instruction is one of the counts it can take, and returns
+/// an `AsmValidationError` saying how many were expected and found if not.
+fn validate_operand_count(line:&str, opcode:&str, operands:&[String], counts:&[usize]) -> Result<(), AsmValidationError> {
+    if counts.contains(&operands.len()) {
+        return Ok(());
+    }
+
+    let expected = counts.iter().map(|count| count.to_string()).collect::<Vec<String>>().join(" or ");
+    Err(AsmValidationError::coded("E0302", format!(
+        "{} takes {} operands, but {} were found on line {}", opcode, expected, operands.len(), line
+    )))
+}
+
+
 /// Takes a line of assembly and the associated opcode (which should already be validated), and checks 
 /// that the operands are valid
 fn validate_operands(line:&str, opcode:&str) -> Result<(), AsmValidationError> {
+    validate_operand_separators(line, opcode)?;
     let operands = get_operands_from_line(line, opcode);
     match opcode {
         "ADD" | "SUB" | "NAND" | "OR" => { // require 3 registers
-            if operands.len() != 3 {
-                return Err(AsmValidationError(format!("Incorrect number of operands on line {}", line)));
-            }
+            validate_operand_count(line, opcode, &operands, &[3])?;
 
             validate_register(&operands[0])?;
             validate_register(&operands[1])?;
@@ -462,9 +710,7 @@ fn validate_operands(line:&str, opcode:&str) -> Result<(), AsmValidationError> {
         },
 
         "LOAD" | "STORE" => { // requires 3 registers, optional label operand
-            if operands.len() != 3 && operands.len() != 4 {
-                return Err(AsmValidationError(format!("Incorrect number of operands on line {}", line)));
-            }
+            validate_operand_count(line, opcode, &operands, &[3, 4])?;
 
             validate_register(&operands[0])?;
             validate_register(&operands[1])?;
@@ -475,31 +721,48 @@ fn validate_operands(line:&str, opcode:&str) -> Result<(), AsmValidationError> {
             }
         },
 
-        "ADDI" | "SUBI" | "SLL" | "SRL" | "SRA" => { // require 2 registers and an immediate
-            if operands.len() != 3 {
-                return Err(AsmValidationError(format!("Incorrect number of operands on line {}", line)));
-            }
+        "ADDI" | "SUBI" => { // require 2 registers and an immediate
+            validate_operand_count(line, opcode, &operands, &[3])?;
 
             validate_register(&operands[0])?;
             validate_register(&operands[1])?;
             validate_int_immediate(&operands[2], 4, false)?;
         },
 
-        "ADDC" | "SUBC" | "CMP" | "IN" | "OUT" => { // require 2 registers
-            if operands.len() != 2 {
-                return Err(AsmValidationError(format!("Incorrect number of operands on line {}", line)));
+        "SLL" | "SRL" | "SRA" => { // require 2 registers and a register or an immediate
+            validate_operand_count(line, opcode, &operands, &[3])?;
+
+            validate_register(&operands[0])?;
+            validate_register(&operands[1])?;
+            if operands[2].starts_with('$') {
+                validate_register(&operands[2])?;
+            } else {
+                validate_int_immediate(&operands[2], 4, false)?;
             }
+        },
+
+        "ADDC" | "SUBC" | "CMP" => { // require 2 registers
+            validate_operand_count(line, opcode, &operands, &[2])?;
 
             validate_register(&operands[0])?;
             validate_register(&operands[1])?;
         },
 
+        "IN" | "OUT" => { // require a register and a 4-bit port number
+            validate_operand_count(line, opcode, &operands, &[2])?;
+
+            validate_register(&operands[0])?;
+            validate_int_immediate(&operands[1], 4, false)?;
+        },
+
         "JUMP" | "JAL" | "BEQ" | "BNE" | "BLT" | "BGT" => {
             match operands.len() {
                 1 => {
                     validate_register(&operands[0])?;
                     if operands[0] != "$sp" && operands[0] != "$fp" && operands[0] != "$ra" && operands[0] != "$pc" {
-                        return Err(AsmValidationError(format!("Incorrect number of operands on line {}", line)));
+                        return Err(AsmValidationError::coded("E0304", format!(
+                            "Missing second register on line {} - a single operand must be $sp, $fp, $ra, or $pc", line
+                        )));
                     }
                 },
 
@@ -514,16 +777,12 @@ fn validate_operands(line:&str, opcode:&str) -> Result<(), AsmValidationError> {
                     validate_label_operand(line, &operands[2])?;
                 },
 
-                _ => {
-                    return Err(AsmValidationError(format!("Incorrect number of operands on line {}", line)));
-                }
+                _ => validate_operand_count(line, opcode, &operands, &[1, 2, 3])?
             }
         }
 
         "MOVUI" | "MOVLI" => {
-            if operands.len() != 2 {
-                return Err(AsmValidationError(format!("Incorrect number of operands on line {}", line)));
-            }
+            validate_operand_count(line, opcode, &operands, &[2])?;
 
             validate_register(&operands[0])?;
             if operands[1].starts_with("@") {
@@ -534,9 +793,7 @@ fn validate_operands(line:&str, opcode:&str) -> Result<(), AsmValidationError> {
         }
         
         "syscall" => { // requires only an 8-bit immediate
-            if operands.len() != 1 {
-                return Err(AsmValidationError(format!("Incorrect number of operands on line {}", line)));
-            }
+            validate_operand_count(line, opcode, &operands, &[1])?;
 
             validate_int_immediate(&operands[0], 8, false)?;
         },
@@ -545,12 +802,12 @@ fn validate_operands(line:&str, opcode:&str) -> Result<(), AsmValidationError> {
             if operands.is_empty() {
                 return Ok(());
             } else {
-                return Err(AsmValidationError(format!("Instruction {} takes no arguments", line)));
+                return Err(AsmValidationError::coded("E0302", format!("Instruction {} takes no arguments", line)));
             }
         },
 
         _ => {
-            return Err(AsmValidationError(format!("Invalid opcode: {} on line {}", opcode, line)));
+            return Err(AsmValidationError::coded("E0301", format!("Invalid opcode: {} on line {}", opcode, line)));
         }
     }
 
@@ -562,15 +819,15 @@ fn validate_operands(line:&str, opcode:&str) -> Result<(), AsmValidationError> {
 /// The requirements for a valid label are:
 ///  - Alphanumeric characters and '_' only
 ///  - No digits 0-9 as the first character 
-fn validate_label(line:&str, label:&str) -> Result<(), AsmValidationError> {
+pub fn validate_label(line:&str, label:&str) -> Result<(), AsmValidationError> {
     if label.chars().collect::<Vec<char>>()[0].is_numeric() {
-        return Err(AsmValidationError(format!(
+        return Err(AsmValidationError::coded("E0401", format!(
             "The label {} on the line {} is not valid - labels may not start with numeric characters.", label, line)
         ));
     }
 
     if !label.chars().all(|c| c.is_alphanumeric() || c == '_') {
-        return Err(AsmValidationError(format!(
+        return Err(AsmValidationError::coded("E0401", format!(
             "The label {} on the line {} is not valid - labels may only contain alphanumeric characters or _.", label, line)
         ));
     }
@@ -591,21 +848,103 @@ fn validate_line_label(line:&str) -> Result<(), AsmValidationError> {
 }
 
 
-/// Takes a label operand and checks that it is valid; if not, it will output an `AsmValidationError`.
-fn validate_operand_label(line:&str, label:&str) -> Result<(), AsmValidationError> {
+/// Takes a label operand and checks that it is valid; if not, it will output an `AsmValidationError`. The
+/// label may be qualified by a namespace, such as `@math.sqrt`, in which case each part must be valid.
+pub fn validate_operand_label(line:&str, label:&str) -> Result<(), AsmValidationError> {
     if !label.starts_with("@") {
-        return Err(AsmValidationError(format!("Label operand {} on line {} must start with an '@' symbol", label, line)));
+        return Err(AsmValidationError::coded("E0402", format!("Label operand {} on line {} must start with an '@' symbol", label, line)));
     }
 
-    validate_label(line, &label[1..])?;
+    for part in label[1..].split('.') {
+        if part.is_empty() {
+            return Err(AsmValidationError::coded("E0401", format!("The label {} on the line {} has an empty namespace or name", label, line)));
+        }
+
+        validate_label(line, part)?;
+    }
 
     Ok(())
 } 
 
 
+/// Takes a label operand, such as `@table+8`, and returns the label operand without its offset, and the
+/// offset with its sign, such as `+8`, if it has one
+pub fn split_label_offset(operand:&str) -> (&str, Option<&str>) {
+    match operand.find(['+', '-']) {
+        Some(index) => (operand[..index].trim_end(), Some(operand[index..].trim())),
+        None => (operand, None)
+    }
+}
+
+
+/// Takes the offset of a label operand with its sign, such as `+8` or `-0x10`, and returns its value, or
+/// `None` if it is not a binary, decimal, or hexadecimal integer which fits into 32 bits
+pub fn parse_label_offset(offset:&str) -> Option<i64> {
+    let (sign, magnitude) = offset.split_at(1);
+    let magnitude = validate_int_immediate(magnitude.trim(), 32, false).ok()?;
+    Some(if sign == "-" { -magnitude } else { magnitude })
+}
+
+
+/// Takes a stream of tokens and gives a zero-destination warning for every instruction which writes its
+/// result to `$zero`, which is valid but throws the result away. This should be run before
+/// pseudo-instructions are substituted, so that only the instructions written in the source are checked.
+pub fn check_zero_destinations(tokens:&[FileTokens], warnings:&mut WarningSink) {
+    let writing_opcodes = ["ADD", "SUB", "ADDI", "SUBI", "SLL", "SRL", "SRA", "NAND", "OR", "LOAD", "IN", "MOVUI", "MOVLI"];
+    for token in tokens {
+        match token {
+            FileTokens::InstrTokens(t) if writing_opcodes.contains(&t.opcode.as_str()) && t.operand_a.as_deref() == Some("$zero") => {
+                warnings.push(AsmWarning::new(WarningKind::ZeroDestination, format!(
+                    "{}{} writes its result to $zero, so the result is thrown away", located(t.pos.as_ref()), t.opcode
+                )));
+            },
+
+            _ => ()
+        }
+    }
+}
+
+
+/// Takes a line of data which has already been validated and its position in the source, and if it is a
+/// `.half` whose value cannot be stored exactly as a 16-bit float, or a fixed-point value such as `.q8_8`
+/// which has more fractional bits than the format holds, gives a precision warning with the value which is
+/// stored instead, such as 0.0010004044 for `.half 0.001`.
+pub fn check_data_precision(line:&str, pos:Option<&SourcePos>, warnings:&mut WarningSink) {
+    let tokens:Vec<&str> = remove_label(line).split_whitespace().collect();
+    let literal = match tokens.get(1).and_then(|literal| literal.parse::<f64>().ok()) {
+        Some(literal) => literal,
+        None => return
+    };
+
+    let (stored, description) = match tokens.first() {
+        // the value is stored the same way as by the token generator, which goes through a 32-bit float
+        Some(&".half") => {
+            let stored = f16::from_f32(literal as f32);
+            (stored.to_f64(), format!("a 16-bit float, so it is stored as {}", stored))
+        },
+        Some(data_type) => match FixedPointFormat::from_data_type(data_type).map(|format| (format, format.to_raw(tokens[1], line))) {
+            Some((format, Ok(raw))) => {
+                let stored = format.from_words(&format.to_words(raw));
+                (stored, format!("a {} fixed-point number, so it is stored as {}", data_type, stored))
+            },
+            _ => return
+        },
+        None => return
+    };
+
+    if stored != literal {
+        warnings.push(AsmWarning::new(WarningKind::Precision, format!(
+            "{}{} {} cannot be stored exactly as {}", located(pos), tokens[0], tokens[1], description
+        )));
+    }
+}
+
+
 #[cfg(test)]
 mod tests {
+    use crate::process_source_into_tokens;
     use crate::validation::*;
+    use crate::warnings::{WarningFlag, WarningSettings};
 
 
     #[test]
@@ -698,6 +1037,52 @@ mod tests {
     }
 
 
+    #[test]
+    #[should_panic]
+    fn test_trailing_comma() {
+        validate_asm_line("ADD $g0, $g1,", 'c').unwrap();
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_double_comma() {
+        validate_asm_line("ADD $g0,, $g1, $g2", 'c').unwrap();
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_leading_comma() {
+        validate_asm_line("CMP , $g0, $g1", 'c').unwrap();
+    }
+
+
+    #[test]
+    fn test_suggestions() {
+        let err = validate_asm_line("ADDD $g0, $g1, $g2", 'c').unwrap_err();
+        assert!(err.0.ends_with("ADDD is not a valid opcode on line ADDD $g0, $g1, $g2 - did you mean ADD?"));
+
+        let err = validate_asm_line("ADD $g0, $g1, $g10", 'c').unwrap_err();
+        assert_eq!(err.0, "[E0201] $g10 is not a valid register - did you mean $g0?");
+
+        let err = validate_asm_line("FLY $g0", 'c').unwrap_err();
+        assert!(!err.0.contains("did you mean"));
+    }
+
+
+    #[test]
+    fn test_separator_error_columns() {
+        let err = validate_operand_separators("ADD $g0, $g1,", "ADD").unwrap_err();
+        assert!(err.0.starts_with("[E0303] Trailing comma at column 13"));
+
+        let err = validate_operand_separators("ADD $g0,, $g1, $g2", "ADD").unwrap_err();
+        assert!(err.0.starts_with("[E0303] Missing operand between the commas at column 9"));
+
+        validate_operand_separators("ADD $g0, $g1, $g2 ; a, b,", "ADD").unwrap();
+    }
+
+
     #[test]
     fn test_rrr_format_instrs() {
         validate_asm_line("my_label: ADD $g0, $zero, $g1", 'c').unwrap();
@@ -727,6 +1112,14 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_shift_instrs() {
+        validate_asm_line("SLL $g0, $g1, $g2", 'c').unwrap();
+        validate_asm_line("SRL $g2, $g3, $g4", 'c').unwrap();
+        validate_asm_line("SRA $g3, $g4, $g5", 'c').unwrap();
+    }
+
+
     #[test]
     #[should_panic]
     fn test_negative_immediate() {
@@ -748,6 +1141,25 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_int_literals() {
+        assert_eq!(parse_int_literal("0b1010_1010"), Some(0xAA));
+        assert_eq!(parse_int_literal("1_000_000"), Some(1_000_000));
+        assert_eq!(parse_int_literal("0o17"), Some(15));
+        assert_eq!(parse_int_literal("0xFF_FF"), Some(0xFFFF));
+        assert_eq!(parse_int_literal("-1_000"), Some(-1000));
+        assert_eq!(parse_int_literal("1_"), None);
+        assert_eq!(parse_int_literal("0x_FF"), None);
+        assert_eq!(parse_int_literal("0o8"), None);
+
+        assert_eq!(validate_int_immediate("0o17", 4, false).unwrap(), 15);
+        assert_eq!(validate_int_immediate("1_0", 8, true).unwrap(), 10);
+        assert_eq!(validate_int_immediate("0o777", 8, false).unwrap_err().0, "[E0101] Immediate 0o777 cannot fit into 8 bits");
+        assert_eq!(validate_int_immediate("0o19", 8, false).unwrap_err().0, "[E0102] Could not parse octal immediate 0o19");
+        validate_asm_line("mask: .section 3 [0o7, 1_000, 0b1_1]", 'd').unwrap();
+    }
+
+
     #[test]
     fn test_rro_format_instrs() {
         validate_asm_line("ADDC $g0, $g1", 'c').unwrap();
@@ -759,11 +1171,31 @@ mod tests {
         validate_asm_line("BNE $g0, $g1", 'c').unwrap();
         validate_asm_line("BLT $g0, $g1", 'c').unwrap();
         validate_asm_line("BGT $g0, $g1", 'c').unwrap();
-        validate_asm_line("IN $g0, $g1", 'c').unwrap();
+    }
+
+
+    #[test]
+    fn test_ori_format_instrs() {
+        validate_asm_line("IN $g0, 0", 'c').unwrap();
+        validate_asm_line("OUT $g0, 0xF", 'c').unwrap();
+        validate_asm_line("OUT $g5, 0b0101", 'c').unwrap();
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_ori_format_register_port() {
         validate_asm_line("OUT $g0, $g1", 'c').unwrap();
     }
 
 
+    #[test]
+    #[should_panic]
+    fn test_ori_format_port_too_large() {
+        validate_asm_line("IN $g0, 16", 'c').unwrap();
+    }
+
+
     #[test]
     fn test_orr_format_instrs_one_register() {
         validate_asm_line("JUMP $sp", 'c').unwrap();
@@ -814,6 +1246,74 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_rand_data() {
+        validate_asm_line("noise: .rand 64, seed=42", 'd').unwrap();
+        validate_asm_line("noise: .rand 0x10,seed=0xFFFFFFFF", 'd').unwrap();
+        assert!(validate_asm_line("noise: .rand 64", 'd').is_err());
+        assert!(validate_asm_line("noise: .rand 0, seed=42", 'd').is_err());
+        assert!(validate_asm_line("noise: .rand 64, seed=0x100000000", 'd').is_err());
+        assert!(validate_asm_line("noise: .rand 64, 42", 'd').is_err());
+        assert!(validate_asm_line("noise: .rand 64, seed=42", 'b').is_err());
+    }
+
+
+    #[test]
+    fn test_crc16_table_data() {
+        validate_asm_line("crc_table: .crc16_table poly=0x1021", 'd').unwrap();
+        validate_asm_line(".crc16_table poly=32773", 'd').unwrap();
+        assert!(validate_asm_line("crc_table: .crc16_table 0x1021", 'd').is_err());
+        assert!(validate_asm_line("crc_table: .crc16_table poly=0x11021", 'd').is_err());
+        assert!(validate_asm_line("crc_table: .crc16_table", 'd').is_err());
+    }
+
+
+    #[test]
+    fn test_table_data() {
+        validate_asm_line("sine: .table 256, i => sin(i / 256 * 2 * pi) * 32767", 'd').unwrap();
+        assert!(validate_asm_line("sine: .table 256, i => sin(j)", 'd').is_err());
+        assert!(validate_asm_line("sine: .table 256, i => i * 1000", 'd').is_err());
+        assert!(validate_asm_line("sine: .table 256, i => i", 't').is_err());
+    }
+
+
+    #[test]
+    fn test_int_and_long_lists() {
+        validate_asm_line("table: .int 1, 2, 3, 4", 'd').unwrap();
+        validate_asm_line("table: .long 650000000, -1", 'd').unwrap();
+        assert!(validate_asm_line("table: .int 1, 70000", 'd').is_err());
+        assert!(validate_asm_line("table: .int 1,, 2", 'd').is_err());
+        assert!(validate_asm_line("table: .int 1 2", 'd').is_err());
+        assert!(validate_asm_line("table: .long", 'd').is_err());
+    }
+
+
+    #[test]
+    fn test_byte_data() {
+        validate_asm_line("header: .byte 0x12, 0x34, 0x56", 'd').unwrap();
+        validate_asm_line("header: .byte 255, -128", 'd').unwrap();
+        assert!(validate_asm_line("header: .byte 0x100", 'd').is_err());
+        assert!(validate_asm_line("header: .byte -129", 'd').is_err());
+        assert!(validate_asm_line("header: .byte 1,, 2", 'd').is_err());
+        assert!(validate_asm_line("header: .byte", 'd').is_err());
+    }
+
+
+    #[test]
+    fn test_space_data() {
+        validate_asm_line("buffer: .space 256", 'b').unwrap();
+        validate_asm_line("buffer: .space 0x10000", 'b').unwrap();
+        assert!(validate_asm_line("buffer: .space 0", 'b').is_err());
+        validate_asm_line("buffer: .space 256", 'd').unwrap();
+        validate_asm_line("buffer: .zero 0xFFFF", 'd').unwrap();
+        validate_asm_line("buffer: .zero 16", 'b').unwrap();
+        assert!(validate_asm_line("buffer: .space 0x10000", 'd').is_err());
+        assert!(validate_asm_line("buffer: .zero 0", 'd').is_err());
+        assert!(validate_asm_line("buffer: .space 256", 't').is_err());
+        assert!(validate_asm_line("buffer: .int 256", 'b').is_err());
+    }
+
+
     #[test]
     fn test_long_data() {
         validate_asm_line("my_label: .long 40", 'd').unwrap();
@@ -915,6 +1415,11 @@ mod tests {
         validate_asm_line("my_label: .char '你'", 'd').unwrap();
         validate_asm_line("my_label: .char '\t'", 'd').unwrap();
         validate_asm_line("my_label: .char '\n'", 'd').unwrap();
+        validate_asm_line("my_label: .char '\\n'", 'd').unwrap();
+        validate_asm_line("my_label: .char '\\''", 'd').unwrap();
+        validate_asm_line("my_label: .char '\\x7F'", 'd').unwrap();
+        assert!(validate_asm_line("my_label: .char '\\q'", 'd').unwrap_err().0.contains("not a valid escape sequence"));
+        assert!(validate_asm_line("my_label: .char '\\n\\t'", 'd').is_err());
     }
 
 
@@ -969,6 +1474,26 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_strings() {
+        validate_asm_line("greeting: .asciiz \"Hello world!\"", 't').unwrap();
+        validate_asm_line("prompt: .ascii \"> \"", 't').unwrap();
+        validate_asm_line("empty: .asciiz \"\"", 't').unwrap();
+        assert!(validate_asm_line("greeting: .asciiz 13 \"Hello world!\"", 't').unwrap_err().0.starts_with("[E0503]"));
+        assert!(validate_asm_line("greeting: .asciiz 'Hello world!'", 't').unwrap_err().0.starts_with("[E0503]"));
+        assert!(validate_asm_line("greeting: .asciiz \"", 't').unwrap_err().0.starts_with("[E0503]"));
+        assert!(validate_asm_line("greeting: .asciiz \"Hello world!\"", 'd').unwrap_err().0.starts_with("[E0501]"));
+
+        validate_asm_line("quoted: .text 5 \"\\\"hi\\\"\"", 't').unwrap();
+        assert!(validate_asm_line("quoted: .text 4 \"\\\"hi\\\"\"", 't').unwrap_err().0.starts_with("[E0504]"));
+        validate_asm_line("path: .ascii \"C:\\\\dir\"", 't').unwrap();
+        assert!(validate_asm_line("path: .ascii \"C:\\dir\"", 't').unwrap_err().0.starts_with("[E0503]"));
+        let long_text = format!("long: .asciiz \"{}\"", "a".repeat(65535));
+        assert!(validate_asm_line(&long_text, 't').unwrap_err().0.starts_with("[E0504]"));
+        validate_asm_line(&long_text.replace(".asciiz", ".ascii"), 't').unwrap();
+    }
+
+
     #[test]
     fn test_valid_bytes_section() {
         validate_asm_line("my_label: .section 4 [0xFFFF, 0x1234, 0xAAAA, 0x1212]", 'd').unwrap();
@@ -1041,6 +1566,20 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_qualified_jump_label() {
+        validate_asm_line("JAL $g8, $g9, @math.sqrt", 'c').unwrap();
+        validate_asm_line("MOVLI $g0, @io.uart_port", 'c').unwrap();
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_qualified_jump_label_without_name() {
+        validate_asm_line("JAL $g8, $g9, @math.", 'c').unwrap();
+    }
+
+
     #[test]
     #[should_panic]
     fn test_movli_with_invalid_label() {
@@ -1073,4 +1612,38 @@ mod tests {
     fn test_atom_opcode() {
         validate_asm_line("my_label: ATOM", 'c').unwrap();
     }
+
+
+    #[test]
+    fn test_zero_destinations() {
+        let tokens = process_source_into_tokens("init:\n    ADD $zero, $g1, $g2\n    CMP $zero, $g1\n    HALT\n", None).unwrap();
+        let mut warnings = WarningSink::default();
+        check_zero_destinations(&tokens, &mut warnings);
+
+        let settings = WarningSettings::from_flags(&[WarningFlag::All], false);
+        let messages:Vec<&str> = warnings.enabled(&settings).iter().map(|warning| warning.message.as_str()).collect();
+        assert_eq!(messages, vec!["<source>:2:5: ADD writes its result to $zero, so the result is thrown away"]);
+    }
+
+
+    #[test]
+    fn test_data_precision() {
+        let mut warnings = WarningSink::default();
+        check_data_precision("exact: .half 5.25", None, &mut warnings);
+        check_data_precision("coeff: .half 0.001", None, &mut warnings);
+        check_data_precision("huge: .half 70000", None, &mut warnings);
+        check_data_precision("whole: .int 3", None, &mut warnings);
+        check_data_precision("gain: .q8_8 1.5", None, &mut warnings);
+        check_data_precision("gain: .q8_8 0.1", None, &mut warnings);
+        check_data_precision("pi: .q16_16 3.14159265359", None, &mut warnings);
+
+        let settings = WarningSettings::from_flags(&[WarningFlag::All], false);
+        let messages:Vec<&str> = warnings.enabled(&settings).iter().map(|warning| warning.message.as_str()).collect();
+        assert_eq!(messages, vec![
+            ".half 0.001 cannot be stored exactly as a 16-bit float, so it is stored as 0.0010004044",
+            ".half 70000 cannot be stored exactly as a 16-bit float, so it is stored as inf",
+            ".q8_8 0.1 cannot be stored exactly as a .q8_8 fixed-point number, so it is stored as 0.1015625",
+            ".q16_16 3.14159265359 cannot be stored exactly as a .q16_16 fixed-point number, so it is stored as 3.1415863037109375"
+        ]);
+    }
 }
@@ -0,0 +1,100 @@
+use crate::generate_code::OutputFormat;
+use crate::label_table::PAGE_SIZE;
+
+
+/// The version of the assembler, taken from the crate version
+pub const VERSION:&str = env!("CARGO_PKG_VERSION");
+
+/// The extensions to the base Iridium ISA which the assembler can generate instructions for
+pub const ISA_EXTENSIONS:[&str; 0] = [];
+
+/// The kinds of output the assembler can write besides the program itself, which is written in one of the
+/// `OutputFormat`s. Any new writer should be added here so that `--version` lists it.
+pub const SIDE_OUTPUTS:[&str; 12] = [
+    "split-rom", "map", "listing", "memmap", "test-vectors", "fixups", "trace-table", "coverage-map",
+    "json-diagnostics", "batch-report", "lock-file", "stats"
+];
+
+
+/// Returns the enabled ISA extensions as a comma separated list, or "none" if the base ISA is all that is
+/// supported.
+fn isa_extensions_list() -> String {
+    if ISA_EXTENSIONS.is_empty() {
+        return "none".to_owned();
+    }
+
+    ISA_EXTENSIONS.join(",")
+}
+
+
+/// Returns every kind of output the assembler can write, being each `OutputFormat` of the program followed
+/// by `SIDE_OUTPUTS`
+pub fn output_formats() -> Vec<&'static str> {
+    OutputFormat::ALL.iter().map(|format| format.name()).chain(SIDE_OUTPUTS).collect()
+}
+
+
+/// Returns the text printed by `--version`, giving the version of the assembler, the ISA extensions it
+/// supports, and the output formats it can write.
+pub fn version_report() -> String {
+    format!("iridium_assembler {}\nISA extensions: {}\nOutput formats: {}",
+        VERSION, isa_extensions_list(), output_formats().join(", "))
+}
+
+
+/// Returns the header written at the start of a program binary so that the binary can be traced back to
+/// the assembler which produced it. The header is the text `info:` followed by the version of the assembler,
+/// ending with a null byte, and is padded with a second null byte if needed so that the
+/// words after it stay aligned.
+///
+/// If the program has a bss section, its start address and size in words are added as `bss=<addr>,<size>`
+/// so that the loader knows which memory to fill with zeros, and if the sections are placed on pages of other
+/// than `PAGE_SIZE` words, the page size is added as `page=<words>` so that the loader knows where each section
+/// starts.
+pub fn output_header(bss:Option<(i64, i64)>, page_size:i64) -> Vec<u8> {
+    let bss = match bss {
+        Some((start, size)) => format!(" bss=0x{:08X},{}", start, size),
+        None => String::new()
+    };
+
+    let page = match page_size {
+        PAGE_SIZE => String::new(),
+        page_size => format!(" page={}", page_size)
+    };
+
+    let mut header = format!("info:iridium_assembler {}{}{}\0", VERSION, bss, page).into_bytes();
+
+    if header.len() % 2 != 0 {
+        header.push(0);
+    }
+
+    header
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::version::*;
+
+
+    #[test]
+    fn test_version_report() {
+        let report = version_report();
+        assert!(report.starts_with(&format!("iridium_assembler {}\n", VERSION)));
+        assert!(report.contains("ISA extensions: none"));
+        assert!(report.contains("Output formats: ird, bin, split-rom, map, listing, memmap, test-vectors, fixups, trace-table, \
+            coverage-map, json-diagnostics, batch-report, lock-file, stats"));
+    }
+
+
+    #[test]
+    fn test_output_header() {
+        let header = String::from_utf8(output_header(None, PAGE_SIZE)).unwrap();
+        assert_eq!(header.trim_end_matches('\0'), format!("info:iridium_assembler {}", VERSION));
+        assert_eq!(header.len() % 2, 0);
+        assert!(header.ends_with('\0'));
+
+        let header = String::from_utf8(output_header(Some((0x3000, 256)), 0x100)).unwrap();
+        assert_eq!(header.trim_end_matches('\0'), format!("info:iridium_assembler {} bss=0x00003000,256 page=256", VERSION));
+    }
+}
@@ -0,0 +1,223 @@
+use std::fmt;
+
+
+/// The kinds of warning the assembler can give, each of which can be turned on individually from the
+/// command line using `-W<name>`, or off using `-Wno-<name>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningKind {
+    BranchPair,
+    UnusedLabel,
+    ZeroDestination,
+    UnreachableCode,
+    Precision,
+    Deprecated,
+    Migration
+}
+
+
+impl WarningKind {
+    /// Every kind of warning, which are all turned on by `-Wall`
+    pub const ALL:[WarningKind; 7] = [
+        WarningKind::BranchPair, WarningKind::UnusedLabel, WarningKind::ZeroDestination, WarningKind::UnreachableCode,
+        WarningKind::Precision, WarningKind::Deprecated, WarningKind::Migration
+    ];
+
+
+    /// Gets the name of the warning as used on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            WarningKind::BranchPair => "branch-pair",
+            WarningKind::UnusedLabel => "unused-label",
+            WarningKind::ZeroDestination => "zero-destination",
+            WarningKind::UnreachableCode => "unreachable-code",
+            WarningKind::Precision => "precision",
+            WarningKind::Deprecated => "deprecated",
+            WarningKind::Migration => "migration"
+        }
+    }
+
+
+    /// Takes the name of a warning as used on the command line and returns the `WarningKind` it refers to,
+    /// or `None` if there is no such warning.
+    pub fn from_name(name:&str) -> Option<WarningKind> {
+        WarningKind::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+
+    /// Gets whether the warning is given without `-Wall` or `-W<name>`, which is only the case for the
+    /// warnings that are almost never intended, or that the author of a library asked for, as the others are
+    /// often written on purpose
+    pub fn enabled_by_default(&self) -> bool {
+        matches!(self, WarningKind::BranchPair | WarningKind::UnreachableCode | WarningKind::Deprecated)
+    }
+}
+
+
+/// Represents a single `-W` flag given on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningFlag {
+    /// `-Wall`, which turns on every warning
+    All,
+    /// `-W<name>`, which turns on a single warning
+    Enable(WarningKind),
+    /// `-Wno-<name>`, which turns off a single warning
+    Disable(WarningKind)
+}
+
+
+impl WarningFlag {
+    /// Takes a flag as given after `-W`, such as `all` or `no-branch-pair`, and returns the `WarningFlag` it
+    /// refers to, or `None` if it does not name a warning.
+    pub fn from_name(flag:&str) -> Option<WarningFlag> {
+        match flag.strip_prefix("no-") {
+            _ if flag == "all" => Some(WarningFlag::All),
+            Some(name) => WarningKind::from_name(name).map(WarningFlag::Disable),
+            None => WarningKind::from_name(flag).map(WarningFlag::Enable)
+        }
+    }
+}
+
+
+/// Represents which warnings are given, and whether giving any of them stops the program being assembled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarningSettings {
+    pub enabled: Vec<WarningKind>,
+    pub deny: bool
+}
+
+
+/// By default only the warnings which are `enabled_by_default` are given, and none of them are denied.
+impl Default for WarningSettings {
+    fn default() -> WarningSettings {
+        WarningSettings::from_flags(&[], false)
+    }
+}
+
+
+impl WarningSettings {
+    /// Takes the `-W` flags given on the command line and whether warnings are denied, and returns the
+    /// settings they give. The last flag naming a warning decides whether it is given, so the command line
+    /// can override the configuration file, and a warning named by any flag is not affected by `-Wall`, so
+    /// `-Wno-<name>` turns it off whether it comes before or after `-Wall`.
+    pub fn from_flags(flags:&[WarningFlag], deny:bool) -> WarningSettings {
+        let enabled = WarningKind::ALL.into_iter().filter(|kind| {
+            let named = flags.iter().rev().find_map(|flag| match flag {
+                WarningFlag::Enable(named) if named == kind => Some(true),
+                WarningFlag::Disable(named) if named == kind => Some(false),
+                _ => None
+            });
+
+            named.unwrap_or_else(|| kind.enabled_by_default() || flags.contains(&WarningFlag::All))
+        }).collect();
+
+        WarningSettings { enabled, deny }
+    }
+
+
+    /// Takes a kind of warning and returns whether it is given
+    pub fn is_enabled(&self, kind:WarningKind) -> bool {
+        self.enabled.contains(&kind)
+    }
+}
+
+
+/// Represents something in the source that is valid, but is very likely to be a mistake
+#[derive(Debug, Clone)]
+pub struct AsmWarning {
+    pub kind: WarningKind,
+    pub message: String
+}
+
+
+impl AsmWarning {
+    pub fn new(kind:WarningKind, message:String) -> AsmWarning {
+        AsmWarning {
+            kind,
+            message
+        }
+    }
+}
+
+
+/// Ensures that the `AsmWarning` type is displayed appropriately in the console, including the name of
+/// the warning so that the user knows how to turn it off.
+impl fmt::Display for AsmWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Warning [{}]: {}", self.kind.name(), self.message)
+    }
+}
+
+
+/// Collects the warnings given by each pass of the assembler, such as validation and building the label
+/// table, so that they can all be reported together and checked against the `WarningSettings` once the
+/// passes have finished, rather than each pass deciding what to print.
+#[derive(Debug, Clone, Default)]
+pub struct WarningSink {
+    warnings: Vec<AsmWarning>
+}
+
+
+impl WarningSink {
+    pub fn push(&mut self, warning:AsmWarning) {
+        self.warnings.push(warning);
+    }
+
+
+    pub fn extend<I:IntoIterator<Item = AsmWarning>>(&mut self, warnings:I) {
+        self.warnings.extend(warnings);
+    }
+
+
+    /// Takes the warning settings and returns every warning given which they turn on, in the order given.
+    pub fn enabled(&self, settings:&WarningSettings) -> Vec<&AsmWarning> {
+        self.warnings.iter().filter(|warning| settings.is_enabled(warning.kind)).collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::warnings::*;
+
+
+    #[test]
+    fn test_warning_flags() {
+        assert_eq!(WarningFlag::from_name("all"), Some(WarningFlag::All));
+        assert_eq!(WarningFlag::from_name("no-unused-label"), Some(WarningFlag::Disable(WarningKind::UnusedLabel)));
+        assert_eq!(WarningFlag::from_name("zero-destination"), Some(WarningFlag::Enable(WarningKind::ZeroDestination)));
+        assert_eq!(WarningFlag::from_name("no-such-warning"), None);
+    }
+
+
+    #[test]
+    fn test_warning_settings() {
+        assert_eq!(WarningSettings::default().enabled, vec![
+            WarningKind::BranchPair, WarningKind::UnreachableCode, WarningKind::Deprecated
+        ]);
+
+        let settings = WarningSettings::from_flags(&[WarningFlag::Disable(WarningKind::UnusedLabel), WarningFlag::All], true);
+        assert_eq!(settings.enabled, vec![
+            WarningKind::BranchPair, WarningKind::ZeroDestination, WarningKind::UnreachableCode, WarningKind::Precision,
+            WarningKind::Deprecated, WarningKind::Migration
+        ]);
+        assert!(settings.deny);
+
+        let settings = WarningSettings::from_flags(&[WarningFlag::Enable(WarningKind::UnusedLabel), WarningFlag::Disable(WarningKind::BranchPair)], false);
+        assert_eq!(settings.enabled, vec![WarningKind::UnusedLabel, WarningKind::UnreachableCode, WarningKind::Deprecated]);
+
+        let settings = WarningSettings::from_flags(&[WarningFlag::Disable(WarningKind::BranchPair), WarningFlag::Enable(WarningKind::BranchPair)], false);
+        assert!(settings.is_enabled(WarningKind::BranchPair));
+    }
+
+
+    #[test]
+    fn test_warning_sink() {
+        let mut sink = WarningSink::default();
+        sink.push(AsmWarning::new(WarningKind::BranchPair, "first".to_owned()));
+        sink.push(AsmWarning::new(WarningKind::UnusedLabel, "second".to_owned()));
+
+        let messages:Vec<&str> = sink.enabled(&WarningSettings::default()).iter().map(|warning| warning.message.as_str()).collect();
+        assert_eq!(messages, vec!["first"]);
+        assert_eq!(sink.enabled(&WarningSettings::from_flags(&[WarningFlag::All], false)).len(), 2);
+    }
+}
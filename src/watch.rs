@@ -0,0 +1,59 @@
+use std::fs;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+
+/// How often the files being watched are checked for changes
+pub const WATCH_INTERVAL:Duration = Duration::from_millis(250);
+
+
+/// Takes a list of filenames and returns the time each was last modified, or `None` for any file which
+/// cannot be read, such as while an editor is part way through saving it.
+pub fn read_modified_times(files:&[String]) -> Vec<Option<SystemTime>> {
+    files.iter()
+        .map(|file| fs::metadata(file).and_then(|metadata| metadata.modified()).ok())
+        .collect()
+}
+
+
+/// Takes a list of filenames and calls `on_change` straight away, and then again every time any of the
+/// files is modified, checking for changes every `interval`. Each call returns the files to watch from then
+/// on, so that files found while assembling, such as those named by `.include`, are watched as well. Never
+/// returns, so the program must be stopped to stop watching.
+pub fn watch_files<F:FnMut() -> Vec<String>>(files:&[String], interval:Duration, mut on_change:F) -> ! {
+    let mut files = files.to_vec();
+    let mut last_modified = read_modified_times(&files);
+    loop {
+        // a file newly watched is only compared from now on, so finding it does not assemble the program again
+        let watched = on_change();
+        if watched != files {
+            files = watched;
+            last_modified = read_modified_times(&files);
+        }
+
+        loop {
+            thread::sleep(interval);
+            let modified = read_modified_times(&files);
+            if modified != last_modified {
+                last_modified = modified;
+                break;
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::watch::read_modified_times;
+
+
+    #[test]
+    fn test_read_modified_times() {
+        let times = read_modified_times(&["test_files/test_bss.asm".to_owned(), "test_files/missing.asm".to_owned()]);
+        assert_eq!(times.len(), 2);
+        assert!(times[0].is_some());
+        assert!(times[1].is_none());
+        assert_eq!(read_modified_times(&["test_files/test_bss.asm".to_owned()])[0], times[0]);
+    }
+}
@@ -0,0 +1,2 @@
+    ADDI $g0, $zero, 1
+    BAD $g0, $g1
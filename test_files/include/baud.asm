@@ -0,0 +1 @@
+baud: .int 9600
@@ -0,0 +1 @@
+greeting: .text 6 "gr"
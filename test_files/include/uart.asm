@@ -0,0 +1,2 @@
+.include "uart_port.asm"
+    OUT $g0, UART_PORT
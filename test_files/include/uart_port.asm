@@ -0,0 +1 @@
+UART_PORT: .equ 1
@@ -0,0 +1,14 @@
+init:
+    LOAD $g0, $g8, $g9, @count
+    STORE $g0, $g8, $g9, @buffer
+    HALT
+
+data:
+    count: .int 3
+
+text:
+    name: .text 4 "bss"
+
+bss:
+    buffer: .space 0x1000
+    scratch: .space 16
@@ -0,0 +1,7 @@
+count:
+    ADDI $g1, $zero, 4
+.Lloop:
+    SUBI $g1, $g1, 1
+    CMP $g1, $zero
+    BNE $g8, $g9, @.Lloop
+    JUMP $ra
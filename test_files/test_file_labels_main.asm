@@ -0,0 +1,8 @@
+main:
+    ADDI $g0, $zero, 3
+.Lloop:
+    SUBI $g0, $g0, 1
+    CMP $g0, $zero
+    BNE $g8, $g9, @.Lloop
+    JAL $g8, $g9, @count
+    HALT
@@ -0,0 +1,16 @@
+init:
+    MOVLI $g1, 0x00
+    MOVUI $g1, 0x10
+    ADDI $g2, $zero, 3
+    JAL $g8, $g9, @sum
+    HALT
+
+.func sum
+    .bounds 4
+    LOAD $g3, $g1, $g2
+    ADD $g0, $g0, $g3
+    JUMP $ra
+.endfunc
+
+data:
+    table: .section 4 [1, 2, 3, 4]
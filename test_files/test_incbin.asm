@@ -0,0 +1,6 @@
+init:
+    HALT
+
+data:
+    .include "include/baud.asm"
+sprite: .incbin "include/sprite.bin"
@@ -0,0 +1,7 @@
+init:
+    ADDI $g0, $zero, 3
+    .include "include/uart.asm"
+    HALT
+
+data:
+    .include "include/baud.asm"
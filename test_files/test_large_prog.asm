@@ -1,5297 +0,0 @@
-start: ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-pg_end: ADDI $g0, $zero, 1
-pg_start: ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-ADDI $g0, $zero, 1
-
-data:
-    some_data: .int 10
-    some_other_data: .float 0.255
\ No newline at end of file
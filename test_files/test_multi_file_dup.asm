@@ -0,0 +1,6 @@
+helper:
+    NOP
+    JUMP $ra
+
+data:
+    value: .int 9
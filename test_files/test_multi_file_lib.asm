@@ -0,0 +1,9 @@
+double:
+    ADD $g0, $g0, $g0
+    JUMP $ra
+
+data:
+    factor: .int 2
+
+text:
+    greeting: .text 6 "hello"
@@ -0,0 +1,8 @@
+main:
+    ADDI $g0, $zero, 5
+    LOAD $g1, $g8, $g9, @value
+    JAL $g8, $g9, @double
+    HALT
+
+data:
+    value: .int 7
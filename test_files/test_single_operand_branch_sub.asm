@@ -1,6 +0,0 @@
-JUMP $ra
-BNE $sp
-BEQ $fp
-BGT $pc
-BLT $ra
-JAL $ra
\ No newline at end of file
@@ -0,0 +1,17 @@
+use std::fs;
+use std::process::Command;
+
+
+#[test]
+fn test_second_source_file_is_not_overwritten() {
+    let dir = std::env::temp_dir().join(format!("iridium_cli_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let (first, second) = (dir.join("a.asm"), dir.join("b.asm"));
+    fs::write(&first, "init:\n    HALT\n").unwrap();
+    fs::write(&second, "lib:\n    NOP\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iridium_assembler")).arg(&first).arg(&second).output().unwrap();
+    assert!(!output.status.success());
+    assert_eq!(fs::read_to_string(&second).unwrap(), "lib:\n    NOP\n");
+    fs::remove_dir_all(&dir).unwrap();
+}